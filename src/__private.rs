@@ -0,0 +1,32 @@
+use crate::value::Value;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use std::sync::Once;
+
+/// Ô nhớ cho `json_static!`: giá trị được tạo một lần ở lần gọi đầu tiên rồi
+/// dùng lại cho mọi lần gọi sau.
+pub struct StaticValue {
+    once: Once,
+    value: UnsafeCell<MaybeUninit<Value>>,
+}
+
+// `value` chỉ được ghi một lần, bên trong `call_once`, và chỉ được đọc sau khi
+// `call_once` đã trả về.
+unsafe impl Sync for StaticValue {}
+
+impl StaticValue {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        StaticValue {
+            once: Once::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    pub fn get(&'static self, init: fn() -> Value) -> &'static Value {
+        self.once.call_once(|| unsafe {
+            (*self.value.get()).as_mut_ptr().write(init());
+        });
+        unsafe { &*(*self.value.get()).as_ptr() }
+    }
+}
@@ -8,30 +8,50 @@
 //! và truyền dữ liệu giữa các hệ thống và giữa các ngôn ngữ lập trình.
 
 use crate::error::{Error, ErrorCode, Result};
+use crate::map::Map;
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use crate::number::DigitsDeserializer;
 use crate::number::Number;
-use crate::read::{self, Fused, Reference};
+use crate::read::{self, Fused, Scratch};
+use crate::value::{self, LazyValue, Value};
+#[cfg(feature = "std")]
+use crate::{io, ser};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
+use core::ops::Range;
 use core::result;
-use core::str::FromStr;
+use core::str::{self, FromStr};
 use serde::de::{self, Expected, Unexpected};
 use serde::forward_to_deserialize_any;
 
 
-pub use crate::read::{Read, SliceRead, StrRead};
+pub use crate::encoding::BytesEncoding;
+pub use crate::read::{Position, Read, Reference, SliceRead, StrRead};
+pub use crate::spanned::Spanned;
 
 #[cfg(feature = "std")]
-pub use crate::read::IoRead;
+pub use crate::read::{BufIoRead, IoRead};
 
 //////////////////////////////////////////////////////////////////////////////
 
 /// Kiểu dữ liệu phục vụ cho việc giải mã JSON -> rust
-pub struct Deserializer<R> {
+pub struct Deserializer<R, B = Vec<u8>> {
     read: R,
-    scratch: Vec<u8>,
+    scratch: B,
     remaining_depth: u8,
+    document: Option<Box<str>>,
+    bytes_encoding: BytesEncoding,
+    radix_literals: bool,
+    single_quotes: bool,
+    unquoted_keys: bool,
+    comments: bool,
+    trailing_commas: bool,
+    duplicate_keys: DuplicateKeys,
     #[cfg(feature = "float_roundtrip")]
     single_precision: bool,
     #[cfg(feature = "unbounded_depth")]
@@ -50,10 +70,48 @@ where
     ///   - Deserializer::from_slice
     ///   - Deserializer::from_reader
     pub fn new(read: R) -> Self {
+        Deserializer::with_scratch(read, Vec::new())
+    }
+
+    ///Chuyển đổi một Deserializer JSON thành một tiến trình lặp qua các giá trị của kiểu T.
+
+    pub fn into_iter<T>(self) -> StreamDeserializer<'de, R, T>
+    where
+        T: de::Deserialize<'de>,
+    {
+        // Cần lựa chọn T là gì ? 
+        let offset = self.read.byte_offset();
+        let input = self.read.input();
+        StreamDeserializer {
+            de: self,
+            offset,
+            input,
+            failed: false,
+            output: PhantomData,
+            lifetime: PhantomData,
+        }
+    }
+}
+
+impl<'de, R, B> Deserializer<R, B>
+where
+    R: read::Read<'de>,
+    B: Scratch,
+{
+    /// Giống `new` nhưng giải mã chuỗi vào bộ đệm `scratch` cho trước.
+    fn with_scratch(read: R, scratch: B) -> Self {
         Deserializer {
             read,
-            scratch: Vec::new(),
+            scratch,
             remaining_depth: 128,
+            document: None,
+            bytes_encoding: BytesEncoding::Array,
+            radix_literals: false,
+            single_quotes: false,
+            unquoted_keys: false,
+            comments: false,
+            trailing_commas: false,
+            duplicate_keys: DuplicateKeys::Allow,
         }
     }
 }
@@ -74,6 +132,33 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl<R> Deserializer<read::BufIoRead<R>>
+where
+    R: crate::io::BufRead,
+{
+    /// Tạo một deserializer đọc trực tiếp từ bộ đệm của một `io::BufRead`.
+    ///
+    /// Nhanh hơn [`from_reader`](Deserializer::from_reader) bọc quanh một
+    /// `BufReader` vì không phải đi qua từng byte bằng iterator. Giống
+    /// `from_reader`, không hỗ trợ giải mã các kiểu mượn như `&str`.
+    ///
+    /// ```
+    /// use serde_json::{Deserializer, Value};
+    /// use std::io::BufReader;
+    ///
+    /// let file = BufReader::new(&b"{\"a\": 1} {\"a\": 2}"[..]);
+    /// let values: Vec<Value> = Deserializer::from_buf_read(file)
+    ///     .into_iter()
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(values.len(), 2);
+    /// ```
+    pub fn from_buf_read(reader: R) -> Self {
+        Deserializer::new(read::BufIoRead::new(reader))
+    }
+}
+
 impl<'a> Deserializer<read::SliceRead<'a>> {
     /// Tạo ra 1 function giải mã JSON từ bytes 
     pub fn from_slice(bytes: &'a [u8]) -> Self {
@@ -88,6 +173,326 @@ impl<'a> Deserializer<read::StrRead<'a>> {
     }
 }
 
+impl Deserializer<read::StrRead<'static>> {
+    /// Tên khác của [`DeserializerBuilder::new`].
+    ///
+    /// Nằm trên một kiểu đầu vào cố định chỉ để `Deserializer::builder()` gọi
+    /// được mà không cần chú thích kiểu; builder tạo ra dùng được với mọi
+    /// nguồn đầu vào.
+    pub fn builder() -> DeserializerBuilder {
+        DeserializerBuilder::new()
+    }
+}
+
+/// Các phương ngữ JSON được đặt sẵn cho [`DeserializerBuilder::profile`].
+///
+/// Mỗi profile bật đúng một tập các cờ nới lỏng của [`Deserializer`] và tắt
+/// mọi cờ còn lại.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Profile {
+    /// JSON chuẩn theo RFC 8259; mọi cờ nới lỏng đều tắt.
+    Strict,
+    /// JSON có chú thích, như file cấu hình của VS Code: chú thích và dấu
+    /// phẩy thừa.
+    Jsonc,
+    /// Phần JSON5 mà crate hỗ trợ: chú thích, dấu phẩy thừa, chuỗi nháy đơn,
+    /// khoá không có nháy, số hệ 16 (cùng hệ 8 và 2) và escape `\xHH` (cùng
+    /// `\u{...}`).
+    Json5,
+    /// JSON trong log được xuất ra: ký tự điều khiển trực tiếp trong chuỗi và
+    /// các escape `\xHH`, `\u{...}`.
+    Logs,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile::Strict
+    }
+}
+
+/// Cách xử lý một khoá lặp lại trong cùng một object, xem
+/// [`Deserializer::set_duplicate_keys`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DuplicateKeys {
+    /// Chuyển mọi khoá cho kiểu đích. `Value` và các map giữ giá trị sau
+    /// cùng, còn struct do serde derive báo lỗi trường lặp.
+    Allow,
+    /// Trả về lỗi tại khoá lặp.
+    Reject,
+    /// Giữ giá trị đầu tiên và bỏ qua các thành viên lặp sau đó.
+    KeepFirst,
+}
+
+impl Default for DuplicateKeys {
+    fn default() -> Self {
+        DuplicateKeys::Allow
+    }
+}
+
+/// Cách xử lý chuỗi chứa UTF-8 không hợp lệ, xem
+/// [`Deserializer::set_utf8_policy`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Utf8Policy {
+    /// Trả về lỗi.
+    Strict,
+    /// Thay mỗi đoạn không hợp lệ bằng ký tự U+FFFD.
+    Replace,
+}
+
+impl Default for Utf8Policy {
+    fn default() -> Self {
+        Utf8Policy::Strict
+    }
+}
+
+/// Tập các tùy chọn phân tích dùng để tạo [`Deserializer`].
+///
+/// Được tạo bằng [`DeserializerBuilder::new`]. Mỗi tùy chọn tương ứng với một
+/// setter `set_*` của `Deserializer`. Có thể chọn cả một phương ngữ bằng
+/// [`profile`](DeserializerBuilder::profile) rồi chỉnh riêng từng cờ, sau đó
+/// chọn nguồn đầu vào bằng [`build_str`](DeserializerBuilder::build_str),
+/// [`build_slice`](DeserializerBuilder::build_slice),
+/// [`build_reader`](DeserializerBuilder::build_reader) hoặc
+/// [`build`](DeserializerBuilder::build).
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::de::{DeserializerBuilder, DuplicateKeys, Profile};
+/// use serde_json::{json, Value};
+///
+/// let builder = DeserializerBuilder::new()
+///     .profile(Profile::Jsonc)
+///     .unquoted_keys(true)
+///     .recursion_limit(16)
+///     .duplicate_keys(DuplicateKeys::Reject);
+///
+/// let mut de = builder.build_str("{port: 8080, /* tcp */}");
+/// let value = Value::deserialize(&mut de).unwrap();
+///
+/// assert_eq!(value, json!({"port": 8080}));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DeserializerBuilder {
+    recursion_limit: u8,
+    max_string_len: usize,
+    duplicate_keys: DuplicateKeys,
+    utf8_policy: Utf8Policy,
+    bytes_encoding: BytesEncoding,
+    radix_literals: bool,
+    lenient_escapes: bool,
+    control_chars_in_strings: bool,
+    single_quotes: bool,
+    unquoted_keys: bool,
+    comments: bool,
+    trailing_commas: bool,
+}
+
+impl DeserializerBuilder {
+    /// Tạo các tùy chọn mặc định của [`Deserializer::new`], phân tích JSON
+    /// chuẩn như [`Profile::Strict`].
+    pub fn new() -> Self {
+        DeserializerBuilder {
+            recursion_limit: 128,
+            max_string_len: usize::MAX,
+            duplicate_keys: DuplicateKeys::Allow,
+            utf8_policy: Utf8Policy::Strict,
+            bytes_encoding: BytesEncoding::Array,
+            radix_literals: false,
+            lenient_escapes: false,
+            control_chars_in_strings: false,
+            single_quotes: false,
+            unquoted_keys: false,
+            comments: false,
+            trailing_commas: false,
+        }
+    }
+
+    /// Đặt mọi cờ nới lỏng theo `profile`, ghi đè các cờ đã chọn trước đó.
+    /// Các tùy chọn khác được giữ nguyên.
+    pub fn profile(self, profile: Profile) -> Self {
+        let strict = DeserializerBuilder {
+            radix_literals: false,
+            lenient_escapes: false,
+            control_chars_in_strings: false,
+            single_quotes: false,
+            unquoted_keys: false,
+            comments: false,
+            trailing_commas: false,
+            ..self
+        };
+        match profile {
+            Profile::Strict => strict,
+            Profile::Jsonc => strict.comments(true).trailing_commas(true),
+            Profile::Json5 => strict
+                .comments(true)
+                .trailing_commas(true)
+                .single_quotes(true)
+                .unquoted_keys(true)
+                .radix_literals(true)
+                .lenient_escapes(true),
+            Profile::Logs => strict.control_chars_in_strings(true).lenient_escapes(true),
+        }
+    }
+
+    /// Xem [`Deserializer::set_recursion_limit`].
+    pub fn recursion_limit(mut self, limit: u8) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Xem [`Deserializer::set_max_string_len`].
+    pub fn max_string_len(mut self, max_len: usize) -> Self {
+        self.max_string_len = max_len;
+        self
+    }
+
+    /// Xem [`Deserializer::set_duplicate_keys`].
+    pub fn duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    /// Xem [`Deserializer::set_utf8_policy`].
+    pub fn utf8_policy(mut self, policy: Utf8Policy) -> Self {
+        self.utf8_policy = policy;
+        self
+    }
+
+    /// Xem [`Deserializer::set_bytes_encoding`].
+    pub fn bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = encoding;
+        self
+    }
+
+    /// Xem [`Deserializer::set_radix_literals`].
+    pub fn radix_literals(mut self, enabled: bool) -> Self {
+        self.radix_literals = enabled;
+        self
+    }
+
+    /// Xem [`Deserializer::set_lenient_escapes`].
+    pub fn lenient_escapes(mut self, enabled: bool) -> Self {
+        self.lenient_escapes = enabled;
+        self
+    }
+
+    /// Xem [`Deserializer::set_control_chars_in_strings`].
+    pub fn control_chars_in_strings(mut self, enabled: bool) -> Self {
+        self.control_chars_in_strings = enabled;
+        self
+    }
+
+    /// Xem [`Deserializer::set_single_quotes`].
+    pub fn single_quotes(mut self, enabled: bool) -> Self {
+        self.single_quotes = enabled;
+        self
+    }
+
+    /// Xem [`Deserializer::set_unquoted_keys`].
+    pub fn unquoted_keys(mut self, enabled: bool) -> Self {
+        self.unquoted_keys = enabled;
+        self
+    }
+
+    /// Xem [`Deserializer::set_comments`].
+    pub fn comments(mut self, enabled: bool) -> Self {
+        self.comments = enabled;
+        self
+    }
+
+    /// Xem [`Deserializer::set_trailing_commas`].
+    pub fn trailing_commas(mut self, enabled: bool) -> Self {
+        self.trailing_commas = enabled;
+        self
+    }
+
+    /// Tạo một deserializer đọc từ `read` với các tùy chọn này.
+    pub fn build<'de, R>(&self, read: R) -> Deserializer<R>
+    where
+        R: read::Read<'de>,
+    {
+        let mut de = Deserializer::new(read);
+        de.set_recursion_limit(self.recursion_limit);
+        de.set_max_string_len(self.max_string_len);
+        de.set_duplicate_keys(self.duplicate_keys);
+        de.set_utf8_policy(self.utf8_policy);
+        de.set_bytes_encoding(self.bytes_encoding);
+        de.set_radix_literals(self.radix_literals);
+        de.set_lenient_escapes(self.lenient_escapes);
+        de.set_control_chars_in_strings(self.control_chars_in_strings);
+        de.set_single_quotes(self.single_quotes);
+        de.set_unquoted_keys(self.unquoted_keys);
+        de.set_comments(self.comments);
+        de.set_trailing_commas(self.trailing_commas);
+        de
+    }
+
+    /// Tạo một deserializer đọc từ một chuỗi UTF-8, như
+    /// [`Deserializer::from_str`].
+    pub fn build_str<'a>(&self, s: &'a str) -> Deserializer<read::StrRead<'a>> {
+        self.build(read::StrRead::new(s))
+    }
+
+    /// Tạo một deserializer đọc từ một mảng byte, như
+    /// [`Deserializer::from_slice`].
+    pub fn build_slice<'a>(&self, bytes: &'a [u8]) -> Deserializer<read::SliceRead<'a>> {
+        self.build(read::SliceRead::new(bytes))
+    }
+
+    /// Tạo một deserializer đọc từ một `io::Read`, như
+    /// [`Deserializer::from_reader`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn build_reader<R>(&self, reader: R) -> Deserializer<read::IoRead<R>>
+    where
+        R: crate::io::Read,
+    {
+        self.build(read::IoRead::new(reader))
+    }
+
+    /// Tạo một deserializer đọc từ bộ đệm của một `io::BufRead`, như
+    /// [`Deserializer::from_buf_read`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn build_buf_read<R>(&self, reader: R) -> Deserializer<read::BufIoRead<R>>
+    where
+        R: crate::io::BufRead,
+    {
+        self.build(read::BufIoRead::new(reader))
+    }
+
+    /// Tạo một [`LineDeserializer`] giải mã mỗi dòng của `reader` với các
+    /// tùy chọn này.
+    ///
+    /// ```
+    /// use serde_json::de::{DeserializerBuilder, Profile};
+    /// use serde_json::Value;
+    ///
+    /// let log = b"{level: 'info'}\n{level: 'warn',}\n";
+    /// let mut lines = DeserializerBuilder::new()
+    ///     .profile(Profile::Json5)
+    ///     .build_lines::<_, Value>(&log[..]);
+    ///
+    /// assert_eq!(lines.next().unwrap().unwrap()["level"], "info");
+    /// assert_eq!(lines.next().unwrap().unwrap()["level"], "warn");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn build_lines<R, T>(&self, reader: R) -> LineDeserializer<R, T>
+    where
+        R: crate::io::BufRead,
+        T: de::DeserializeOwned,
+    {
+        LineDeserializer::with_builder(reader, *self)
+    }
+}
+
+impl Default for DeserializerBuilder {
+    fn default() -> Self {
+        DeserializerBuilder::new()
+    }
+}
+
 macro_rules! overflow {
     ($a:ident * 10 + $b:ident, $c:expr) => {
         match $c {
@@ -100,7 +505,11 @@ pub(crate) enum ParserNumber {
     F64(f64),
     U64(u64),
     I64(i64),
-
+    #[cfg(feature = "decimal")]
+    Decimal(rust_decimal::Decimal),
+    /// Số nguyên nằm ngoài phạm vi của u64 và i64
+    #[cfg(feature = "bigint")]
+    BigInt(num_bigint::BigInt),
 }
 
 impl ParserNumber {
@@ -114,6 +523,10 @@ impl ParserNumber {
             ParserNumber::I64(x) => visitor.visit_i64(x),
             #[cfg(feature = "arbitrary_precision")]
             ParserNumber::String(x) => visitor.visit_map(NumberDeserializer { number: x.into() }),
+            #[cfg(feature = "decimal")]
+            ParserNumber::Decimal(x) => visitor.visit_map(DigitsDeserializer::new(x)),
+            #[cfg(feature = "bigint")]
+            ParserNumber::BigInt(x) => visitor.visit_map(DigitsDeserializer::new(x)),
         }
     }
 
@@ -124,11 +537,19 @@ impl ParserNumber {
             ParserNumber::I64(x) => de::Error::invalid_type(Unexpected::Signed(x), exp),
             #[cfg(feature = "arbitrary_precision")]
             ParserNumber::String(_) => de::Error::invalid_type(Unexpected::Other("number"), exp),
+            #[cfg(feature = "decimal")]
+            ParserNumber::Decimal(x) => {
+                de::Error::invalid_type(Unexpected::Float(Number::from(x).as_f64().unwrap()), exp)
+            }
+            #[cfg(feature = "bigint")]
+            ParserNumber::BigInt(_) => {
+                de::Error::invalid_type(Unexpected::Other("big integer"), exp)
+            }
         }
     }
 }
 
-impl<'de, R: Read<'de>> Deserializer<R> {
+impl<'de, R: Read<'de>, B: Scratch> Deserializer<R, B> {
     /// Phương thức Deserializer::end nên được gọi 
     /// sau khi một giá trị đã được deserialize đầy đủ. 
     /// Điều này cho phép Deserializer kiểm tra xem luồng đầu vào 
@@ -140,20 +561,283 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    ///Chuyển đổi một Deserializer JSON thành một tiến trình lặp qua các giá trị của kiểu T.
+    /// Giống hệt [`end`](Deserializer::end): trả về lỗi nếu sau giá trị còn
+    /// bất kỳ ký tự nào khác khoảng trắng. Tên này giúp phân biệt rõ với
+    /// [`end_trailing_ok`](Deserializer::end_trailing_ok) ở nơi gọi.
+    pub fn end_strict(&mut self) -> Result<()> {
+        self.end()
+    }
 
-    pub fn into_iter<T>(self) -> StreamDeserializer<'de, R, T>
+    /// Bỏ qua khoảng trắng cuối nhưng không coi phần còn lại là lỗi. Trả về
+    /// `None` nếu đã hết đầu vào, hoặc vị trí của byte đầu tiên còn lại.
+    ///
+    /// Dành cho các bộ phân tích nhúng JSON vào một định dạng lớn hơn, nơi
+    /// phần phía sau giá trị thuộc về bộ phân tích bên ngoài.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::Value;
+    ///
+    /// let data = "{\"a\": 1}  ;rest";
+    /// let mut de = serde_json::Deserializer::from_str(data);
+    /// Value::deserialize(&mut de).unwrap();
+    ///
+    /// let rest = de.end_trailing_ok().unwrap().unwrap();
+    /// assert_eq!(&data[rest.byte_offset..], ";rest");
+    /// assert!(de.end_strict().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Chỉ trả về lỗi khi việc đọc gặp lỗi IO.
+    pub fn end_trailing_ok(&mut self) -> Result<Option<Position>> {
+        match tri!(self.parse_whitespace()) {
+            Some(_) => Ok(Some(self.peeked_start())),
+            None => Ok(None),
+        }
+    }
+
+    /// Số byte đã được tiêu thụ từ đầu vào, tức vị trí của byte tiếp theo sẽ
+    /// được đọc.
+    ///
+    /// Có thể dùng để lưu lại tiến độ, tiếp tục phân tích ở lần sau, hoặc cắt
+    /// ra đoạn văn bản gốc ứng với một giá trị vừa giải mã.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::Value;
+    ///
+    /// let data = r#"{"id": 1} [true]"#;
+    /// let mut de = serde_json::Deserializer::from_str(data);
+    ///
+    /// Value::deserialize(&mut de).unwrap();
+    /// assert_eq!(de.byte_offset(), 9);
+    /// assert_eq!(&data[..de.byte_offset()], r#"{"id": 1}"#);
+    /// ```
+    pub fn byte_offset(&self) -> usize {
+        self.read.byte_offset()
+    }
+
+    /// Bỏ qua giá trị JSON tiếp theo, kể cả mọi mảng và đối tượng lồng bên
+    /// trong, mà không dựng nó thành một kiểu Rust nào.
+    ///
+    /// Cú pháp của giá trị vẫn được kiểm tra, nhưng nhanh hơn
+    /// deserialize vào `IgnoredAny` vì không đi qua các visitor của serde và
+    /// không giải mã escape trong chuỗi. Hữu ích cho những bộ giải mã tự viết
+    /// cần bỏ qua các phần không quan tâm.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// let data = r#"{"huge": [1, [2, {"x": "y"}]]} 42"#;
+    /// let mut de = serde_json::Deserializer::from_str(data);
+    /// de.skip_value().unwrap();
+    /// assert_eq!(u32::deserialize(&mut de).unwrap(), 42);
+    /// de.end().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị tiếp theo không phải JSON hợp lệ, hoặc nếu không
+    /// còn giá trị nào.
+    pub fn skip_value(&mut self) -> Result<()> {
+        self.ignore_value()
+    }
+
+    /// Phân tích giá trị JSON tiếp theo thành một `Value`, kèm theo bảng
+    /// khoảng byte của mọi nút trong cây, tra cứu được bằng JSON Pointer.
+    ///
+    /// Khoảng byte được tính từ đầu đầu vào và không gồm khoảng trắng quanh
+    /// giá trị. Xem [`from_str_with_spans`] để có ví dụ.
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị tiếp theo không phải JSON hợp lệ, hoặc nếu không
+    /// còn giá trị nào.
+    pub fn parse_value_with_spans(&mut self) -> Result<(Value, Spans)> {
+        let mut spans = Spans::default();
+        let value = tri!(self.parse_spanned(&mut String::new(), &mut spans));
+        Ok((value, spans))
+    }
+
+    /// Bỏ qua khoảng trắng và trả về vị trí bắt đầu của giá trị JSON tiếp
+    /// theo, trước khi giá trị đó được phân tích.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::de::Position;
+    ///
+    /// let data = "[1]\n  {\"a\": 2}";
+    /// let mut de = serde_json::Deserializer::from_str(data);
+    /// de.skip_value().unwrap();
+    ///
+    /// let start = de.next_value_position().unwrap();
+    /// assert_eq!(start, Position { line: 2, column: 2, byte_offset: 6 });
+    /// assert_eq!(&data[start.byte_offset..], r#"{"a": 2}"#);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu không còn giá trị nào, hoặc nếu việc đọc gặp lỗi IO.
+    pub fn next_value_position(&mut self) -> Result<Position> {
+        match tri!(self.parse_whitespace()) {
+            Some(_) => Ok(self.peeked_start()),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+        }
+    }
+
+    /// Đọc chuỗi JSON tiếp theo, đã giải mã escape và đã kiểm tra là UTF-8
+    /// hợp lệ.
+    ///
+    /// Chuỗi được mượn thẳng từ đầu vào khi có thể, nên đây là cách an toàn
+    /// để lấy nội dung chuỗi mà không cấp phát và không cần
+    /// `str::from_utf8_unchecked`. Escape `\uXXXX` là nửa cặp surrogate đơn
+    /// lẻ bị từ chối; dùng [`next_bytes_raw`](Self::next_bytes_raw) nếu cần
+    /// giữ lại chúng.
+    ///
+    /// ```
+    /// use serde_json::de::Reference;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#" "plain" "tab\t" 1"#);
+    /// assert!(matches!(de.next_str().unwrap(), Reference::Borrowed("plain")));
+    /// assert_eq!(&*de.next_str().unwrap(), "tab\t");
+    ///
+    /// let err = de.next_str().unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid type: integer `1`, expected a string at line 1 column 18",
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị tiếp theo không phải một chuỗi hợp lệ, hoặc nếu
+    /// không còn giá trị nào.
+    pub fn next_str(&mut self) -> Result<Reference<'de, '_, str>> {
+        tri!(self.begin_string(&"a string"));
+        self.scratch.clear();
+        self.read
+            .parse_str(&mut self.scratch)
+            .map_err(|err| err.in_document(self.document.as_deref()))
+    }
+
+    /// Đọc chuỗi JSON tiếp theo dưới dạng byte đã giải mã escape, không
+    /// kiểm tra UTF-8.
+    ///
+    /// Nửa cặp surrogate đơn lẻ như `"\ud800"` được mã hóa theo WTF-8 thay vì
+    /// gây lỗi, giống như khi deserialize vào `&[u8]`. Kết quả là byte, không
+    /// phải `str`: hãy kiểm tra bằng `str::from_utf8` trước khi coi nó là văn
+    /// bản.
+    ///
+    /// ```
+    /// let mut de = serde_json::Deserializer::from_str(r#""a\ud800""#);
+    /// assert_eq!(&*de.next_bytes_raw().unwrap(), b"a\xED\xA0\x80");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị tiếp theo không phải một chuỗi, hoặc nếu không
+    /// còn giá trị nào.
+    pub fn next_bytes_raw(&mut self) -> Result<Reference<'de, '_, [u8]>> {
+        tri!(self.begin_string(&"a string"));
+        self.scratch.clear();
+        self.read
+            .parse_str_raw(&mut self.scratch)
+            .map_err(|err| err.in_document(self.document.as_deref()))
+    }
+
+    /// Phân tích giá trị tiếp theo đúng một lần vào bộ đệm, rồi gọi `f` với
+    /// một [`BufferedDeserializer`] đọc từ bộ đệm đó.
+    ///
+    /// `BufferedDeserializer` là `Copy`, nên `f` có thể thử giải mã giá trị
+    /// thành nhiều kiểu lần lượt, như derive làm với enum `untagged`, mà
+    /// không phải đọc lại đầu vào hay đi qua `Value`. Chuỗi và khóa được
+    /// mượn từ đầu vào khi có thể.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Shape {
+    ///     Point(Point),
+    ///     Path(Vec<Point>),
+    /// }
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#"[{"x": 1, "y": 2}]"#);
+    /// let shape = de
+    ///     .deserialize_buffered(|buffered| match Point::deserialize(buffered) {
+    ///         Ok(point) => Ok(Shape::Point(point)),
+    ///         Err(_) => Vec::deserialize(buffered).map(Shape::Path),
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(shape, Shape::Path(vec![Point { x: 1, y: 2 }]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị tiếp theo không phải JSON hợp lệ, nếu không
+    /// còn giá trị nào, hoặc lỗi mà `f` trả về. Lỗi của `f` không mang vị trí
+    /// riêng nên được gắn vị trí ngay sau giá trị, và giữ lại
+    /// [`Error::path`] tới chỗ hỏng bên trong giá trị.
+    ///
+    /// [`BufferedDeserializer`]: crate::value::BufferedDeserializer
+    pub fn deserialize_buffered<F, T>(&mut self, f: F) -> Result<T>
     where
-        T: de::Deserialize<'de>,
+        F: FnOnce(value::BufferedDeserializer<'_, 'de>) -> Result<T>,
     {
-        // Cần lựa chọn T là gì ? 
-        let offset = self.read.byte_offset();
-        StreamDeserializer {
-            de: self,
-            offset,
-            failed: false,
-            output: PhantomData,
-            lifetime: PhantomData,
+        let content: value::Content<'de> = tri!(de::Deserialize::deserialize(&mut *self));
+        f(value::BufferedDeserializer::new(&content)).map_err(|err| match err.path() {
+            Some(path) => {
+                let path = String::from(path);
+                self.fix_position(err).with_path(&path)
+            }
+            None => self.fix_position(err),
+        })
+    }
+
+    /// Bỏ qua khoảng trắng và dấu `"` mở đầu một chuỗi.
+    fn begin_string(&mut self, exp: &dyn Expected) -> Result<()> {
+        match tri!(self.parse_whitespace()) {
+            Some(b'"') => {
+                self.eat_quote();
+                Ok(())
+            }
+            Some(_) => Err(self.peek_invalid_type(exp)),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+        }
+    }
+
+    /// Vị trí ngay trước byte vừa được peek, vốn không phải khoảng trắng.
+    fn peeked_start(&self) -> Position {
+        // Vị trí của byte vừa peek là vị trí ngay sau byte đó. Byte này không
+        // phải '\n' nên lùi lại một cột vẫn ở cùng dòng.
+        let after = self.read.peek_position();
+        Position {
+            line: after.line,
+            column: after.column - 1,
+            byte_offset: after.byte_offset - 1,
+        }
+    }
+
+    /// Vị trí ngay sau giá trị vừa phân tích, bắt đầu tại `start`.
+    fn value_end(&self, start: Position) -> Position {
+        let byte_offset = self.read.byte_offset();
+        let position = self.read.position();
+        if position.byte_offset == byte_offset {
+            return position;
+        }
+        // Còn một byte đã peek sau giá trị, nên `position` đã vượt qua nó. Chỉ
+        // số và literal mới để lại byte như vậy, và chúng nằm trên một dòng.
+        Position {
+            line: start.line,
+            column: start.column + (byte_offset - start.byte_offset),
+            byte_offset,
         }
     }
 
@@ -201,8 +885,329 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         self.disable_recursion_limit = true;
     }
 
+    /// Gắn tên tài liệu (đường dẫn file, URL, ...) vào các lỗi phát sinh
+    /// trong quá trình phân tích, để người dùng biết tài liệu nào bị lỗi.
+    ///
+    /// Tên được hiển thị ở đầu thông điệp lỗi và có thể lấy lại bằng
+    /// [`Error::document`].
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::Value;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str("{\"port\": 80,}");
+    /// de.set_document("config.json");
+    /// let err = Value::deserialize(&mut de).unwrap_err();
+    ///
+    /// assert_eq!(err.document(), Some("config.json"));
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "config.json: trailing comma at line 1 column 13",
+    /// );
+    /// ```
+    pub fn set_document<S>(&mut self, name: S)
+    where
+        S: Into<String>,
+    {
+        self.document = Some(name.into().into_boxed_str());
+    }
+
+    /// Chọn cách đọc chuỗi byte (`deserialize_bytes`) từ một chuỗi JSON.
+    ///
+    /// Mặc định ([`BytesEncoding::Array`]) các byte của chuỗi được trả về
+    /// nguyên vẹn. Với `Base64` hoặc `Hex`, chuỗi được giải mã trước. Mảng các
+    /// số luôn được chấp nhận bất kể lựa chọn này.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::de::BytesEncoding;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#""aGVsbG8=""#);
+    /// de.set_bytes_encoding(BytesEncoding::Base64);
+    /// let bytes = serde_bytes::ByteBuf::deserialize(&mut de).unwrap();
+    /// assert_eq!(bytes, b"hello");
+    /// ```
+    pub fn set_bytes_encoding(&mut self, encoding: BytesEncoding) {
+        self.bytes_encoding = encoding;
+    }
+
+    /// Cho phép (không chuẩn JSON) số nguyên viết ở hệ 16, 8 hoặc 2 với tiền
+    /// tố `0x`, `0o`, `0b`, như thường gặp trong các file cấu hình phần cứng
+    /// viết tay. Tiền tố viết hoa (`0X`, `0O`, `0B`) cũng được chấp nhận như
+    /// JSON5. Mặc định tắt.
+    ///
+    /// Các số này được đọc như số nguyên bình thường, có thể có dấu `-` phía
+    /// trước, và phải vừa trong phạm vi u64 (hoặc i64 nếu là số âm).
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(
+    ///     r#"{"mask": 0xFF, "mode": 0o755, "flags": 0b1010, "offset": -0x10}"#,
+    /// );
+    /// de.set_radix_literals(true);
+    /// let value = Value::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(
+    ///     value,
+    ///     json!({"mask": 255, "mode": 493, "flags": 10, "offset": -16}),
+    /// );
+    /// ```
+    pub fn set_radix_literals(&mut self, enabled: bool) {
+        self.radix_literals = enabled;
+    }
+
+    /// Cho phép (không chuẩn JSON) escape `\xHH` và `\u{H..H}` trong chuỗi,
+    /// như trong JSON viết tay hoặc do một số hệ thống template sinh ra. Mặc
+    /// định tắt.
+    ///
+    /// `\x` phải theo sau bởi đúng hai chữ số hex, còn `\u{...}` chứa từ một
+    /// đến sáu chữ số hex. Cả hai được giải mã thành ký tự có mã tương ứng;
+    /// surrogate và mã lớn hơn U+10FFFF bị từ chối. Escape `\uXXXX` chuẩn vẫn
+    /// được đọc như cũ.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#""\x41\u{42} \u{1F600}""#);
+    /// de.set_lenient_escapes(true);
+    /// let s = String::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(s, "AB \u{1F600}");
+    /// ```
+    pub fn set_lenient_escapes(&mut self, enabled: bool) {
+        let mut options = self.read.options();
+        options.escapes = enabled;
+        self.read.set_options(options);
+    }
+
+    /// Cho phép (không chuẩn JSON) ký tự điều khiển như xuống dòng hay tab
+    /// nằm trực tiếp trong chuỗi thay vì báo lỗi, như trong log được xuất ra
+    /// hay JSON chuyển từ CSV. Mặc định tắt.
+    ///
+    /// Các ký tự này được giữ nguyên trong chuỗi kết quả.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str("\"line 1\nline 2\tend\"");
+    /// de.set_control_chars_in_strings(true);
+    /// let s = String::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(s, "line 1\nline 2\tend");
+    /// ```
+    pub fn set_control_chars_in_strings(&mut self, enabled: bool) {
+        let mut options = self.read.options();
+        options.control_chars = enabled;
+        self.read.set_options(options);
+    }
+
+    /// Cho phép (không chuẩn JSON) chuỗi và khoá viết trong dấu nháy đơn,
+    /// như trong cấu hình chép từ mã JavaScript. Mặc định tắt.
+    ///
+    /// Trong chuỗi nháy đơn, dấu nháy kép là ký tự bình thường còn dấu nháy
+    /// đơn phải được escape thành `\'`. Chuỗi nháy kép vẫn được đọc như cũ.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(
+    ///     r#"{'name': 'Rock \'n\' "roll"', "tags": ['a', "b"]}"#,
+    /// );
+    /// de.set_single_quotes(true);
+    /// let value = Value::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(
+    ///     value,
+    ///     json!({"name": "Rock 'n' \"roll\"", "tags": ["a", "b"]}),
+    /// );
+    /// ```
+    pub fn set_single_quotes(&mut self, enabled: bool) {
+        self.single_quotes = enabled;
+    }
+
+    /// Cho phép (không chuẩn JSON) khoá object không có dấu nháy, như
+    /// `{name: "x"}`. Mặc định tắt.
+    ///
+    /// Khoá không có nháy theo cú pháp định danh: bắt đầu bằng chữ cái, `_`,
+    /// `$` hoặc một ký tự không phải ASCII, theo sau là các ký tự đó hoặc chữ
+    /// số. Các khoá khác vẫn phải nằm trong dấu nháy.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(
+    ///     r#"{name: "ann", $id: 7, "full name": "Ann Lee", nested: {a_1: []}}"#,
+    /// );
+    /// de.set_unquoted_keys(true);
+    /// let value = Value::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(
+    ///     value,
+    ///     json!({"name": "ann", "$id": 7, "full name": "Ann Lee", "nested": {"a_1": []}}),
+    /// );
+    /// ```
+    pub fn set_unquoted_keys(&mut self, enabled: bool) {
+        self.unquoted_keys = enabled;
+    }
+
+    /// Cho phép (không chuẩn JSON) chú thích `// ...` đến hết dòng và
+    /// `/* ... */` ở mọi chỗ được phép có khoảng trắng. Mặc định tắt.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(
+    ///     "{\n  // cổng mặc định\n  \"port\": 8080 /* tcp */\n}",
+    /// );
+    /// de.set_comments(true);
+    /// let value = Value::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(value, json!({"port": 8080}));
+    /// ```
+    pub fn set_comments(&mut self, enabled: bool) {
+        self.comments = enabled;
+    }
+
+    /// Cho phép (không chuẩn JSON) một dấu phẩy thừa sau phần tử cuối của
+    /// mảng hoặc thành viên cuối của object, như `[1, 2,]`. Mặc định tắt.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#"{"a": [1, 2,], "b": 3,}"#);
+    /// de.set_trailing_commas(true);
+    /// let value = Value::deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(value, json!({"a": [1, 2], "b": 3}));
+    /// ```
+    pub fn set_trailing_commas(&mut self, enabled: bool) {
+        self.trailing_commas = enabled;
+    }
+
+    /// Đặt số lớp mảng và object lồng nhau tối đa. Mặc định là 128: đầu vào
+    /// lồng từ `limit` lớp trở lên trả về lỗi
+    /// [`Category::Limit`](crate::error::Category::Limit). Giá trị 0 được
+    /// xem như 1.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::Value;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str("[[[]]]");
+    /// de.set_recursion_limit(3);
+    /// let err = Value::deserialize(&mut de).unwrap_err();
+    /// assert_eq!(err.to_string(), "recursion limit exceeded at line 1 column 3");
+    /// ```
+    pub fn set_recursion_limit(&mut self, limit: u8) {
+        self.remaining_depth = limit.max(1);
+    }
+
+    /// Đặt độ dài tối đa tính bằng byte của một chuỗi hoặc khoá sau khi giải
+    /// mã escape. Chuỗi dài hơn trả về lỗi
+    /// [`Category::Limit`](crate::error::Category::Limit) mà không cần đọc
+    /// hết vào bộ nhớ. Chuỗi bị bỏ qua không bị giới hạn. Mặc định không
+    /// giới hạn.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#"["short", "much too long"]"#);
+    /// de.set_max_string_len(8);
+    /// let err = Vec::<String>::deserialize(&mut de).unwrap_err();
+    /// assert!(err.is_limit());
+    /// ```
+    pub fn set_max_string_len(&mut self, max_len: usize) {
+        let mut options = self.read.options();
+        options.max_string_len = max_len;
+        self.read.set_options(options);
+    }
+
+    /// Đặt cách xử lý một khoá xuất hiện nhiều lần trong cùng một object.
+    /// Mặc định ([`DuplicateKeys::Allow`]) mọi khoá được chuyển cho kiểu
+    /// đích.
+    ///
+    /// Chính sách áp dụng cho các object được giải mã qua serde, còn giá trị
+    /// bị bỏ qua như [`IgnoredAny`](serde::de::IgnoredAny) thì không.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::de::DuplicateKeys;
+    /// use serde_json::{json, Value};
+    ///
+    /// let j = r#"{"a": 1, "b": 2, "a": 3}"#;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(j);
+    /// de.set_duplicate_keys(DuplicateKeys::KeepFirst);
+    /// assert_eq!(Value::deserialize(&mut de).unwrap(), json!({"a": 1, "b": 2}));
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(j);
+    /// de.set_duplicate_keys(DuplicateKeys::Reject);
+    /// let err = Value::deserialize(&mut de).unwrap_err();
+    /// assert_eq!(err.to_string(), "duplicate key `a` at line 1 column 20");
+    /// ```
+    pub fn set_duplicate_keys(&mut self, policy: DuplicateKeys) {
+        self.duplicate_keys = policy;
+    }
+
+    /// Đặt cách xử lý chuỗi chứa UTF-8 không hợp lệ. Mặc định
+    /// ([`Utf8Policy::Strict`]) trả về lỗi.
+    ///
+    /// Chỉ có tác dụng với đầu vào là byte; `&str` luôn là UTF-8 hợp lệ.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::de::Utf8Policy;
+    ///
+    /// let mut de = serde_json::Deserializer::from_slice(b"\"caf\xE9\"");
+    /// de.set_utf8_policy(Utf8Policy::Replace);
+    /// let s = String::deserialize(&mut de).unwrap();
+    /// assert_eq!(s, "caf\u{FFFD}");
+    /// ```
+    pub fn set_utf8_policy(&mut self, policy: Utf8Policy) {
+        let mut options = self.read.options();
+        options.replace_invalid_utf8 = policy == Utf8Policy::Replace;
+        self.read.set_options(options);
+    }
+
+    /// Byte vừa peek có mở đầu một khoá object hay không.
+    fn is_key_start(&self, ch: u8) -> bool {
+        ch == b'"' || self.unquoted_keys && is_bare_key_start(ch)
+    }
+
+    /// Đọc một khoá object bắt đầu ở byte vừa peek, thoả `is_key_start`.
+    fn parse_key<'s>(&'s mut self) -> Result<Reference<'de, 's, str>> {
+        self.scratch.clear();
+        if tri!(self.peek()) == Some(b'"') {
+            self.eat_quote();
+            return self.read.parse_str(&mut self.scratch);
+        }
+        while let Some(ch) = tri!(self.peek()) {
+            if !is_bare_key_byte(ch) {
+                break;
+            }
+            self.eat_char();
+            tri!(self.scratch.push(ch));
+        }
+        if str::from_utf8(&self.scratch).is_err() {
+            return Err(self.error(ErrorCode::InvalidUnicodeCodePoint));
+        }
+        Ok(Reference::Copied(str::from_utf8(&self.scratch).unwrap()))
+    }
+
     fn peek(&mut self) -> Result<Option<u8>> {
-        self.read.peek()
+        match self.read.peek() {
+            // Chuỗi nháy đơn đi qua đúng các nhánh của chuỗi nháy kép; reader
+            // chỉ được báo khi chuỗi thực sự bắt đầu, trong `eat_quote`.
+            Ok(Some(b'\'')) if self.single_quotes => Ok(Some(b'"')),
+            Ok(ch) => Ok(ch),
+            Err(err) => Err(self.in_document(err)),
+        }
     }
 
     fn peek_or_null(&mut self) -> Result<u8> {
@@ -213,8 +1218,22 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         self.read.discard();
     }
 
+    /// Bỏ qua dấu nháy mở đầu một chuỗi vừa được peek. Nếu đó là dấu nháy
+    /// đơn, reader được báo để kết thúc chuỗi ở dấu nháy đơn.
+    fn eat_quote(&mut self) {
+        if self.single_quotes && matches!(self.read.peek(), Ok(Some(b'\''))) {
+            let mut options = self.read.options();
+            options.single_quoted = true;
+            self.read.set_options(options);
+        }
+        self.read.discard();
+    }
+
     fn next_char(&mut self) -> Result<Option<u8>> {
-        self.read.next()
+        match self.read.next() {
+            Ok(ch) => Ok(ch),
+            Err(err) => Err(self.in_document(err)),
+        }
     }
 
     fn next_char_or_null(&mut self) -> Result<u8> {
@@ -224,15 +1243,26 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     /// Error gây ra bơi 1 byte từ next_char()
     #[cold]
     fn error(&self, reason: ErrorCode) -> Error {
-        let position = self.read.position();
-        Error::syntax(reason, position.line, position.column)
+        self.in_document(Error::syntax_at(
+            reason,
+            self.read.position(),
+            self.read.input(),
+        ))
     }
 
     /// Error gây ra bởi 1 byte từ peek ()
     #[cold]
     fn peek_error(&self, reason: ErrorCode) -> Error {
-        let position = self.read.peek_position();
-        Error::syntax(reason, position.line, position.column)
+        self.in_document(Error::syntax_at(
+            reason,
+            self.read.peek_position(),
+            self.read.input(),
+        ))
+    }
+
+    #[cold]
+    fn in_document(&self, err: Error) -> Error {
+        err.in_document(self.document.as_deref())
     }
 
     /// Trả về byte đầu tiên không phải khoảng trắng 
@@ -242,10 +1272,42 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') => {
                     self.eat_char();
                 }
-                other => {
-                    return Ok(other);
+                Some(b'/') if self.comments => {
+                    self.eat_char();
+                    tri!(self.skip_comment());
+                }
+                // Không byte nào của JSON chuẩn bắt đầu bằng hai ký tự này ngoài
+                // chuỗi; chúng chỉ gặp khi đầu vào dùng một cú pháp mở rộng.
+                // Dấu nháy đơn chỉ tới đây khi `single_quotes` tắt.
+                Some(b'/') => return Err(self.peek_error(ErrorCode::CommentsNotEnabled)),
+                Some(b'\'') => return Err(self.peek_error(ErrorCode::SingleQuotesNotEnabled)),
+                other => {
+                    return Ok(other);
+                }
+            }
+        }
+    }
+
+    /// Bỏ qua một chú thích `//` hoặc `/* */` mà dấu `/` đầu đã được đọc.
+    fn skip_comment(&mut self) -> Result<()> {
+        match tri!(self.next_char()) {
+            Some(b'/') => loop {
+                match tri!(self.next_char()) {
+                    Some(b'\n') | None => return Ok(()),
+                    Some(_) => {}
+                }
+            },
+            Some(b'*') => {
+                let mut star = false;
+                loop {
+                    match tri!(self.next_char()) {
+                        Some(b'/') if star => return Ok(()),
+                        Some(ch) => star = ch == b'*',
+                        None => return Err(self.error(ErrorCode::EofWhileParsingValue)),
+                    }
                 }
             }
+            _ => Err(self.error(ErrorCode::ExpectedSomeValue)),
         }
     }
 
@@ -285,7 +1347,7 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 Err(err) => return err,
             },
             b'"' => {
-                self.eat_char();
+                self.eat_quote();
                 self.scratch.clear();
                 match self.read.parse_str(&mut self.scratch) {
                     Ok(s) => de::Error::invalid_type(Unexpected::Str(&s), exp),
@@ -350,7 +1412,7 @@ impl<'de, R: Read<'de>> Deserializer<R> {
 
     #[cold]
     fn fix_position(&self, err: Error) -> Error {
-        err.fix_position(move |code| self.error(code))
+        self.in_document(err.fix_position(move |code| self.error(code)))
     }
 
     fn parse_ident(&mut self, ident: &[u8]) -> Result<()> {
@@ -383,6 +1445,12 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 // There can be only one leading '0'.
                 match tri!(self.peek_or_null()) {
                     b'0'..=b'9' => Err(self.peek_error(ErrorCode::InvalidNumber)),
+                    b'x' | b'X' | b'o' | b'O' | b'b' | b'B' if self.radix_literals => {
+                        self.parse_radix_integer(positive)
+                    }
+                    b'x' | b'X' | b'o' | b'O' | b'b' | b'B' => {
+                        Err(self.peek_error(ErrorCode::RadixLiteralsNotEnabled))
+                    }
                     _ => self.parse_number(positive, 0),
                 }
             }
@@ -416,6 +1484,49 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
+    /// Đọc phần sau tiền tố `0x`, `0o` hoặc `0b` (hoặc dạng viết hoa) của một
+    /// số nguyên (chữ số `0` đã được đọc, ký tự tiền tố đang ở vị trí peek).
+    fn parse_radix_digits(&mut self) -> Result<u64> {
+        let radix = match tri!(self.next_char_or_null()) {
+            b'x' | b'X' => 16,
+            b'o' | b'O' => 8,
+            _ => 2,
+        };
+
+        let mut value = match (tri!(self.peek_or_null()) as char).to_digit(radix) {
+            Some(digit) => digit as u64,
+            None => match tri!(self.peek()) {
+                Some(_) => return Err(self.peek_error(ErrorCode::InvalidNumber)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+            },
+        };
+        self.eat_char();
+
+        while let Some(digit) = (tri!(self.peek_or_null()) as char).to_digit(radix) {
+            value = match value
+                .checked_mul(radix as u64)
+                .and_then(|value| value.checked_add(digit as u64))
+            {
+                Some(value) => value,
+                None => return Err(self.peek_error(ErrorCode::NumberOutOfRange)),
+            };
+            self.eat_char();
+        }
+
+        Ok(value)
+    }
+
+    fn parse_radix_integer(&mut self, positive: bool) -> Result<ParserNumber> {
+        let magnitude = tri!(self.parse_radix_digits());
+        if positive || magnitude == 0 {
+            Ok(ParserNumber::U64(magnitude))
+        } else if magnitude <= i64::MIN.unsigned_abs() {
+            Ok(ParserNumber::I64((magnitude as i64).wrapping_neg()))
+        } else {
+            Err(self.error(ErrorCode::NumberOutOfRange))
+        }
+    }
+
     fn parse_number(&mut self, positive: bool, significand: u64) -> Result<ParserNumber> {
         Ok(match tri!(self.peek_or_null()) {
             b'.' => ParserNumber::F64(tri!(self.parse_decimal(positive, significand, 0))),
@@ -598,13 +1709,14 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         /// Yêu cầu thứ hai sẽ được thực hiện trước khi truyền các phần 
         /// để giải mã cho lexical trong f64_long_from_parts.
         self.scratch.clear();
-        self.scratch
-            .extend_from_slice(itoa::Buffer::new().format(partial_significand).as_bytes());
+        tri!(self
+            .scratch
+            .extend_from_slice(itoa::Buffer::new().format(partial_significand).as_bytes()));
 
         loop {
             match tri!(self.peek_or_null()) {
                 c @ b'0'..=b'9' => {
-                    self.scratch.push(c);
+                    tri!(self.scratch.push(c));
                     self.eat_char();
                 }
                 b'.' => {
@@ -652,7 +1764,7 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     fn parse_long_decimal(&mut self, positive: bool, integer_end: usize) -> Result<f64> {
         let mut at_least_one_digit = integer_end < self.scratch.len();
         while let c @ b'0'..=b'9' = tri!(self.peek_or_null()) {
-            self.scratch.push(c);
+            tri!(self.scratch.push(c));
             self.eat_char();
             at_least_one_digit = true;
         }
@@ -734,9 +1846,11 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         let fraction_digits = -exponent as usize;
         self.scratch.clear();
         if let Some(zeros) = fraction_digits.checked_sub(significand.len() + 1) {
-            self.scratch.extend(iter::repeat(b'0').take(zeros + 1));
+            for _ in 0..=zeros {
+                tri!(self.scratch.push(b'0'));
+            }
         }
-        self.scratch.extend_from_slice(significand.as_bytes());
+        tri!(self.scratch.extend_from_slice(significand.as_bytes()));
         let integer_end = self.scratch.len() - fraction_digits;
         self.parse_long_decimal(positive, integer_end)
     }
@@ -840,11 +1954,53 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(not(feature = "arbitrary_precision"))]
+    #[cfg(not(any(
+        feature = "arbitrary_precision",
+        feature = "decimal",
+        feature = "bigint"
+    )))]
     fn parse_any_number(&mut self, positive: bool) -> Result<ParserNumber> {
         self.parse_integer(positive)
     }
 
+    /// Số nguyên vừa u64/i64 được giữ nguyên. Với feature `bigint`, số nguyên
+    /// lớn hơn được giữ dưới dạng `BigInt`; với feature `decimal`, số có phần
+    /// thập phân hoặc phần mũ được giữ chính xác dưới dạng `Decimal` nếu có
+    /// thể. Các số còn lại được làm tròn về f64 như bình thường.
+    #[cfg(all(
+        any(feature = "decimal", feature = "bigint"),
+        not(feature = "arbitrary_precision")
+    ))]
+    fn parse_any_number(&mut self, positive: bool) -> Result<ParserNumber> {
+        let mut buf = String::with_capacity(16);
+        if !positive {
+            buf.push('-');
+        }
+        tri!(self.scan_integer(&mut buf));
+        if let Ok(unsigned) = buf.parse() {
+            return Ok(ParserNumber::U64(unsigned));
+        }
+        if let Ok(signed @ i64::MIN..=-1) = buf.parse::<i64>() {
+            return Ok(ParserNumber::I64(signed));
+        }
+        #[cfg(feature = "bigint")]
+        match buf.parse::<num_bigint::BigInt>() {
+            // "-0" không phải số nguyên lớn, để nó thành -0.0 như trước.
+            Ok(big) if big.sign() != num_bigint::Sign::NoSign => {
+                return Ok(ParserNumber::BigInt(big));
+            }
+            _ => {}
+        }
+        #[cfg(feature = "decimal")]
+        if let Some(decimal) = crate::number::parse_decimal(&buf) {
+            return Ok(ParserNumber::Decimal(decimal));
+        }
+        match buf.parse::<f64>() {
+            Ok(f) if f.is_finite() => Ok(ParserNumber::F64(f)),
+            _ => Err(self.error(ErrorCode::NumberOutOfRange)),
+        }
+    }
+
     #[cfg(feature = "arbitrary_precision")]
     fn parse_any_number(&mut self, positive: bool) -> Result<ParserNumber> {
         let mut buf = String::with_capacity(16);
@@ -864,7 +2020,12 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         Ok(ParserNumber::String(buf))
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    #[cfg(any(
+        feature = "std",
+        feature = "arbitrary_precision",
+        feature = "decimal",
+        feature = "bigint"
+    ))]
     fn scan_or_eof(&mut self, buf: &mut String) -> Result<u8> {
         match tri!(self.next_char()) {
             Some(b) => {
@@ -875,13 +2036,34 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    #[cfg(any(
+        feature = "std",
+        feature = "arbitrary_precision",
+        feature = "decimal",
+        feature = "bigint"
+    ))]
     fn scan_integer(&mut self, buf: &mut String) -> Result<()> {
         match tri!(self.scan_or_eof(buf)) {
             b'0' => {
                 // There can be only one leading '0'.
                 match tri!(self.peek_or_null()) {
                     b'0'..=b'9' => Err(self.peek_error(ErrorCode::InvalidNumber)),
+                    b'x' | b'X' | b'o' | b'O' | b'b' | b'B' if self.radix_literals => {
+                        let negative = buf.starts_with('-');
+                        buf.clear();
+                        let magnitude = tri!(self.parse_radix_digits());
+                        if negative && magnitude != 0 {
+                            if magnitude > i64::MIN.unsigned_abs() {
+                                return Err(self.error(ErrorCode::NumberOutOfRange));
+                            }
+                            buf.push('-');
+                        }
+                        buf.push_str(&magnitude.to_string());
+                        Ok(())
+                    }
+                    b'x' | b'X' | b'o' | b'O' | b'b' | b'B' => {
+                        Err(self.peek_error(ErrorCode::RadixLiteralsNotEnabled))
+                    }
                     _ => self.scan_number(buf),
                 }
             }
@@ -900,7 +2082,12 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    #[cfg(any(
+        feature = "std",
+        feature = "arbitrary_precision",
+        feature = "decimal",
+        feature = "bigint"
+    ))]
     fn scan_number(&mut self, buf: &mut String) -> Result<()> {
         match tri!(self.peek_or_null()) {
             b'.' => self.scan_decimal(buf),
@@ -909,7 +2096,12 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    #[cfg(any(
+        feature = "std",
+        feature = "arbitrary_precision",
+        feature = "decimal",
+        feature = "bigint"
+    ))]
     fn scan_decimal(&mut self, buf: &mut String) -> Result<()> {
         self.eat_char();
         buf.push('.');
@@ -934,7 +2126,12 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    #[cfg(any(
+        feature = "std",
+        feature = "arbitrary_precision",
+        feature = "decimal",
+        feature = "bigint"
+    ))]
     fn scan_exponent(&mut self, e: char, buf: &mut String) -> Result<()> {
         self.eat_char();
         buf.push(e);
@@ -987,6 +2184,10 @@ impl<'de, R: Read<'de>> Deserializer<R> {
             Some(b',') => {
                 self.eat_char();
                 match self.parse_whitespace() {
+                    Ok(Some(b']')) if self.trailing_commas => {
+                        self.eat_char();
+                        Ok(())
+                    }
                     Ok(Some(b']')) => Err(self.peek_error(ErrorCode::TrailingComma)),
                     _ => Err(self.peek_error(ErrorCode::TrailingCharacters)),
                 }
@@ -1002,6 +2203,16 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 self.eat_char();
                 Ok(())
             }
+            Some(b',') if self.trailing_commas => {
+                self.eat_char();
+                match tri!(self.parse_whitespace()) {
+                    Some(b'}') => {
+                        self.eat_char();
+                        Ok(())
+                    }
+                    _ => Err(self.peek_error(ErrorCode::TrailingCharacters)),
+                }
+            }
             Some(b',') => Err(self.peek_error(ErrorCode::TrailingComma)),
             Some(_) => Err(self.peek_error(ErrorCode::TrailingCharacters)),
             None => Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
@@ -1046,12 +2257,17 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                     None
                 }
                 b'"' => {
-                    self.eat_char();
-                    tri!(self.read.ignore_str());
+                    self.eat_quote();
+                    tri!(self
+                        .read
+                        .ignore_str()
+                        .map_err(|err| err.in_document(self.document.as_deref())));
                     None
                 }
                 frame @ b'[' | frame @ b'{' => {
-                    self.scratch.extend(enclosing.take());
+                    if let Some(enclosing) = enclosing.take() {
+                        tri!(self.scratch.push(enclosing));
+                    }
                     self.eat_char();
                     Some(frame)
                 }
@@ -1073,6 +2289,19 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 match tri!(self.parse_whitespace()) {
                     Some(b',') if accept_comma => {
                         self.eat_char();
+                        if self.trailing_commas {
+                            match tri!(self.parse_whitespace()) {
+                                Some(b']') if frame == b'[' => {
+                                    accept_comma = false;
+                                    continue;
+                                }
+                                Some(b'}') if frame == b'{' => {
+                                    accept_comma = false;
+                                    continue;
+                                }
+                                _ => {}
+                            }
+                        }
                         break;
                     }
                     Some(b']') if frame == b'[' => {}
@@ -1107,11 +2336,25 @@ impl<'de, R: Read<'de>> Deserializer<R> {
 
             if frame == b'{' {
                 match tri!(self.parse_whitespace()) {
-                    Some(b'"') => self.eat_char(),
+                    Some(b'"') => {
+                        self.eat_quote();
+                        tri!(self
+                            .read
+                            .ignore_str()
+                            .map_err(|err| err.in_document(self.document.as_deref())));
+                    }
+                    Some(ch) if self.is_key_start(ch) => {
+                        // Khoá không có nháy; scratch đang giữ các khung lồng nhau.
+                        while let Some(ch) = tri!(self.peek()) {
+                            if !is_bare_key_byte(ch) {
+                                break;
+                            }
+                            self.eat_char();
+                        }
+                    }
                     Some(_) => return Err(self.peek_error(ErrorCode::KeyMustBeAString)),
                     None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
                 }
-                tri!(self.read.ignore_str());
                 match tri!(self.parse_whitespace()) {
                     Some(b':') => self.eat_char(),
                     Some(_) => return Err(self.peek_error(ErrorCode::ExpectedColon)),
@@ -1127,8 +2370,15 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         match tri!(self.next_char_or_null()) {
             b'0' => {
                 // There can be only one leading '0'.
-                if let b'0'..=b'9' = tri!(self.peek_or_null()) {
-                    return Err(self.peek_error(ErrorCode::InvalidNumber));
+                match tri!(self.peek_or_null()) {
+                    b'0'..=b'9' => return Err(self.peek_error(ErrorCode::InvalidNumber)),
+                    b'x' | b'X' | b'o' | b'O' | b'b' | b'B' if self.radix_literals => {
+                        return self.parse_radix_digits().map(drop);
+                    }
+                    b'x' | b'X' | b'o' | b'O' | b'b' | b'B' => {
+                        return Err(self.peek_error(ErrorCode::RadixLiteralsNotEnabled));
+                    }
+                    _ => {}
                 }
             }
             b'1'..=b'9' => {
@@ -1202,6 +2452,26 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     }
 }
 
+/// Phân tích một số theo đúng ngữ pháp số của JSON, không cho phép khoảng
+/// trắng hay ký tự thừa.
+///
+/// Chữ số được giữ chính xác tới mức mà các feature đang bật cho phép: với
+/// `decimal`, `"1.50"` vẫn hiển thị là `1.50`; với `bigint`, số nguyên 40 chữ
+/// số không bị làm tròn. Số nằm ngoài phạm vi của f64 như `1e400` trả về lỗi.
+///
+/// ```
+/// use serde_json::Number;
+///
+/// let n: Number = "-12".parse().unwrap();
+/// assert_eq!(n, Number::from(-12));
+///
+/// let f: Number = "2.5e-3".parse().unwrap();
+/// assert_eq!(f.as_f64(), Some(0.0025));
+///
+/// assert!("01".parse::<Number>().is_err());
+/// assert!(" 1".parse::<Number>().is_err());
+/// assert!("1e400".parse::<Number>().is_err());
+/// ```
 impl FromStr for Number {
     type Err = Error;
 
@@ -1265,33 +2535,529 @@ macro_rules! if_checking_recursion_limit {
     };
 }
 
-#[cfg(feature = "unbounded_depth")]
-macro_rules! if_checking_recursion_limit {
-    ($this:ident $($body:tt)*) => {
-        if !$this.disable_recursion_limit {
-            $this $($body)*
+#[cfg(feature = "unbounded_depth")]
+macro_rules! if_checking_recursion_limit {
+    ($this:ident $($body:tt)*) => {
+        if !$this.disable_recursion_limit {
+            $this $($body)*
+        }
+    };
+}
+
+macro_rules! check_recursion {
+    ($this:ident $($body:tt)*) => {
+        if_checking_recursion_limit! {
+            $this.remaining_depth -= 1;
+            if $this.remaining_depth == 0 {
+                return Err($this.peek_error(ErrorCode::RecursionLimitExceeded));
+            }
+        }
+
+        $this $($body)*
+
+        if_checking_recursion_limit! {
+            $this.remaining_depth += 1;
+        }
+    };
+}
+
+impl<'de, R: Read<'de>, B: Scratch> Deserializer<R, B> {
+    /// Phân tích giá trị tiếp theo thành một `LazyValue`: mảng và đối tượng
+    /// nằm sâu từ `depth` lớp trở xuống chỉ được kiểm tra cú pháp và giữ lại
+    /// dưới dạng văn bản gốc.
+    pub(crate) fn parse_lazy(&mut self, depth: usize) -> Result<LazyValue<'de>> {
+        let peek = match tri!(self.parse_whitespace()) {
+            Some(b) => b,
+            None => {
+                return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
+            }
+        };
+
+        match peek {
+            b'[' | b'{' if depth == 0 => {
+                let start = self.read.byte_offset();
+                tri!(self.ignore_value());
+                let raw = match self.read.input() {
+                    Some(input) => &input[start..self.read.byte_offset()],
+                    None => {
+                        return Err(de::Error::custom(
+                            "lazy parsing needs the whole input in memory",
+                        ))
+                    }
+                };
+                match str::from_utf8(raw) {
+                    Ok(raw) => Ok(LazyValue::Raw(raw)),
+                    Err(_) => Err(self.error(ErrorCode::InvalidUnicodeCodePoint)),
+                }
+            }
+            b'[' => {
+                let mut elements = Vec::new();
+                check_recursion! {
+                    self.eat_char();
+                    let ret = self.parse_lazy_elements(depth - 1, &mut elements);
+                }
+                tri!(ret);
+                tri!(self.end_seq());
+                Ok(LazyValue::Array(elements))
+            }
+            b'{' => {
+                let mut members = Vec::new();
+                check_recursion! {
+                    self.eat_char();
+                    let ret = self.parse_lazy_members(depth - 1, &mut members);
+                }
+                tri!(ret);
+                tri!(self.end_map());
+                Ok(LazyValue::Object(members))
+            }
+            _ => Ok(LazyValue::Scalar(tri!(de::Deserialize::deserialize(
+                &mut *self
+            )))),
+        }
+    }
+
+    fn parse_lazy_elements(
+        &mut self,
+        depth: usize,
+        elements: &mut Vec<LazyValue<'de>>,
+    ) -> Result<()> {
+        loop {
+            match tri!(self.parse_whitespace()) {
+                Some(b']') => return Ok(()),
+                Some(b',') if !elements.is_empty() => {
+                    self.eat_char();
+                    if let Some(b']') = tri!(self.parse_whitespace()) {
+                        if self.trailing_commas {
+                            continue;
+                        }
+                        return Err(self.peek_error(ErrorCode::TrailingComma));
+                    }
+                }
+                Some(_) if elements.is_empty() => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedListCommaOrEnd)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingList)),
+            }
+            elements.push(tri!(self.parse_lazy(depth)));
+        }
+    }
+
+    fn parse_lazy_members(
+        &mut self,
+        depth: usize,
+        members: &mut Vec<(String, LazyValue<'de>)>,
+    ) -> Result<()> {
+        loop {
+            match tri!(self.parse_whitespace()) {
+                Some(b'}') => return Ok(()),
+                Some(b',') if !members.is_empty() => {
+                    self.eat_char();
+                    if let Some(b'}') = tri!(self.parse_whitespace()) {
+                        if self.trailing_commas {
+                            continue;
+                        }
+                        return Err(self.peek_error(ErrorCode::TrailingComma));
+                    }
+                }
+                Some(_) if members.is_empty() => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedObjectCommaOrEnd)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
+            }
+            match tri!(self.parse_whitespace()) {
+                Some(ch) if self.is_key_start(ch) => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::KeyMustBeAString)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+            }
+            let key = String::from(&*tri!(self.parse_key()));
+            tri!(self.parse_object_colon());
+            let value = tri!(self.parse_lazy(depth));
+            members.push((key, value));
+        }
+    }
+
+    /// Phân tích giá trị tiếp theo thành `Value` và ghi khoảng byte của nó,
+    /// cùng mọi nút con, vào `spans` dưới `pointer`.
+    fn parse_spanned(&mut self, pointer: &mut String, spans: &mut Spans) -> Result<Value> {
+        let peek = match tri!(self.parse_whitespace()) {
+            Some(b) => b,
+            None => {
+                return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
+            }
+        };
+
+        let start = self.read.byte_offset();
+        let index = spans.push(pointer, start);
+        let len = pointer.len();
+        let value = match peek {
+            b'[' => {
+                let mut list = Vec::new();
+                check_recursion! {
+                    self.eat_char();
+                    let ret = self.parse_spanned_elements(pointer, spans, &mut list);
+                }
+                tri!(ret);
+                tri!(self.end_seq());
+                Value::Array(list)
+            }
+            b'{' => {
+                let mut map = Map::new();
+                check_recursion! {
+                    self.eat_char();
+                    let ret = self.parse_spanned_members(pointer, spans, &mut map);
+                }
+                tri!(ret);
+                tri!(self.end_map());
+                Value::Object(map)
+            }
+            _ => tri!(de::Deserialize::deserialize(&mut *self)),
+        };
+        pointer.truncate(len);
+        spans.entries[index].1.end = self.read.byte_offset();
+        Ok(value)
+    }
+
+    fn parse_spanned_elements(
+        &mut self,
+        pointer: &mut String,
+        spans: &mut Spans,
+        list: &mut Vec<Value>,
+    ) -> Result<()> {
+        let len = pointer.len();
+        loop {
+            match tri!(self.parse_whitespace()) {
+                Some(b']') => return Ok(()),
+                Some(b',') if !list.is_empty() => {
+                    self.eat_char();
+                    if let Some(b']') = tri!(self.parse_whitespace()) {
+                        if self.trailing_commas {
+                            continue;
+                        }
+                        return Err(self.peek_error(ErrorCode::TrailingComma));
+                    }
+                }
+                Some(_) if list.is_empty() => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedListCommaOrEnd)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingList)),
+            }
+            pointer.push('/');
+            pointer.push_str(itoa::Buffer::new().format(list.len()));
+            let element = tri!(self.parse_spanned(pointer, spans));
+            pointer.truncate(len);
+            list.push(element);
+        }
+    }
+
+    fn parse_spanned_members(
+        &mut self,
+        pointer: &mut String,
+        spans: &mut Spans,
+        map: &mut Map<String, Value>,
+    ) -> Result<()> {
+        let len = pointer.len();
+        let mut first = true;
+        loop {
+            match tri!(self.parse_whitespace()) {
+                Some(b'}') => return Ok(()),
+                Some(b',') if !first => {
+                    self.eat_char();
+                    if let Some(b'}') = tri!(self.parse_whitespace()) {
+                        if self.trailing_commas {
+                            continue;
+                        }
+                        return Err(self.peek_error(ErrorCode::TrailingComma));
+                    }
+                }
+                Some(_) if first => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedObjectCommaOrEnd)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
+            }
+            first = false;
+            match tri!(self.parse_whitespace()) {
+                Some(ch) if self.is_key_start(ch) => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::KeyMustBeAString)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+            }
+            let key = String::from(&*tri!(self.parse_key()));
+            tri!(self.parse_object_colon());
+            pointer.push('/');
+            pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            // Với khoá lặp, giá trị sau thay thế giá trị trước như trong
+            // `Value`, nên khoảng byte của giá trị trước cũng bị bỏ.
+            if map.contains_key(&key) {
+                spans.remove_subtree(pointer);
+            }
+            let value = tri!(self.parse_spanned(pointer, spans));
+            pointer.truncate(len);
+            map.insert(key, value);
+        }
+    }
+
+    /// Chỉ giải mã những phần của giá trị tiếp theo nằm trên các đường dẫn
+    /// trong `node`, và bỏ qua phần còn lại bằng `ignore_value`.
+    fn parse_projected(&mut self, node: &Projection, out: &mut [Option<Value>]) -> Result<()> {
+        if !node.ends.is_empty() {
+            let value: Value = tri!(de::Deserialize::deserialize(&mut *self));
+            node.fill_from(&value, out);
+            return Ok(());
+        }
+        if node.children.is_empty() {
+            return self.ignore_value();
+        }
+
+        let peek = match tri!(self.parse_whitespace()) {
+            Some(b) => b,
+            None => {
+                return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
+            }
+        };
+
+        match peek {
+            b'[' => {
+                self.eat_char();
+                let mut index = 0;
+                loop {
+                    match tri!(self.parse_whitespace()) {
+                        Some(b']') => break,
+                        Some(b',') if index > 0 => {
+                            self.eat_char();
+                            if let Some(b']') = tri!(self.parse_whitespace()) {
+                                if self.trailing_commas {
+                                    continue;
+                                }
+                                return Err(self.peek_error(ErrorCode::TrailingComma));
+                            }
+                        }
+                        Some(_) if index == 0 => {}
+                        Some(_) => return Err(self.peek_error(ErrorCode::ExpectedListCommaOrEnd)),
+                        None => return Err(self.peek_error(ErrorCode::EofWhileParsingList)),
+                    }
+                    match node.child_at(index) {
+                        Some(child) => tri!(self.parse_projected(child, out)),
+                        None => tri!(self.ignore_value()),
+                    }
+                    index += 1;
+                }
+                self.end_seq()
+            }
+            b'{' => {
+                self.eat_char();
+                let mut first = true;
+                loop {
+                    match tri!(self.parse_whitespace()) {
+                        Some(b'}') => break,
+                        Some(b',') if !first => {
+                            self.eat_char();
+                            if let Some(b'}') = tri!(self.parse_whitespace()) {
+                                if self.trailing_commas {
+                                    continue;
+                                }
+                                return Err(self.peek_error(ErrorCode::TrailingComma));
+                            }
+                        }
+                        Some(_) if first => {}
+                        Some(_) => return Err(self.peek_error(ErrorCode::ExpectedObjectCommaOrEnd)),
+                        None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
+                    }
+                    first = false;
+                    match tri!(self.parse_whitespace()) {
+                        Some(ch) if self.is_key_start(ch) => {}
+                        Some(_) => return Err(self.peek_error(ErrorCode::KeyMustBeAString)),
+                        None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+                    }
+                    let child = {
+                        let key = tri!(self.parse_key());
+                        node.child(&key)
+                    };
+                    tri!(self.parse_object_colon());
+                    match child {
+                        Some(child) => tri!(self.parse_projected(child, out)),
+                        None => tri!(self.ignore_value()),
+                    }
+                }
+                self.end_map()
+            }
+            _ => self.ignore_value(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, R: Read<'de>, B: Scratch> Deserializer<R, B> {
+    /// Chép mọi giá trị JSON còn lại trong đầu vào ra `writer` ở dạng gọn
+    /// nhất, bỏ khoảng trắng không có nghĩa mà không dựng giá trị nào thành
+    /// `Value`.
+    ///
+    /// Các giá trị nối tiếp nhau được ngăn cách bằng một dòng mới. Số được
+    /// chép nguyên văn nên không mất độ chính xác; chuỗi và khoá được giải mã
+    /// rồi mã hóa lại như [`to_writer`](crate::to_writer), nên các escape
+    /// không cần thiết như `\u0041` được viết lại thành `A`. Các tùy chọn
+    /// cú pháp mở rộng đã bật (chú thích, dấu phẩy thừa, nháy đơn, khoá không
+    /// nháy, ...) được chấp nhận ở đầu vào, còn đầu ra luôn là JSON chuẩn.
+    ///
+    /// ```
+    /// let input = "{\n  // cổng mặc định\n  \"port\": 8080,\n  \"hosts\": [\"a\", \"b\",],\n}\n";
+    /// let mut de = serde_json::Deserializer::from_str(input);
+    /// de.set_comments(true);
+    /// de.set_trailing_commas(true);
+    ///
+    /// let mut out = Vec::new();
+    /// de.minify_to(&mut out).unwrap();
+    /// assert_eq!(out, br#"{"port":8080,"hosts":["a","b"]}"#);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu đầu vào không phải JSON hợp lệ hoặc nếu việc đọc hay
+    /// ghi gặp lỗi IO. Phần đã ghi ra `writer` trước khi gặp lỗi được giữ
+    /// nguyên.
+    pub fn minify_to<W>(&mut self, mut writer: W) -> Result<()>
+    where
+        W: io::Write,
+    {
+        let mut first = true;
+        while tri!(self.parse_whitespace()).is_some() {
+            if !first {
+                tri!(writer.write_all(b"\n").map_err(Error::io));
+            }
+            first = false;
+            tri!(self.minify_value(&mut writer));
+        }
+        Ok(())
+    }
+
+    fn minify_value<W>(&mut self, writer: &mut W) -> Result<()>
+    where
+        W: io::Write,
+    {
+        let peek = match tri!(self.parse_whitespace()) {
+            Some(b) => b,
+            None => {
+                return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
+            }
+        };
+
+        match peek {
+            b'n' | b't' | b'f' => {
+                let ident: &[u8] = match peek {
+                    b'n' => b"null",
+                    b't' => b"true",
+                    _ => b"false",
+                };
+                self.eat_char();
+                tri!(self.parse_ident(&ident[1..]));
+                writer.write_all(ident).map_err(Error::io)
+            }
+            b'-' | b'0'..=b'9' => {
+                let mut buf = String::new();
+                if peek == b'-' {
+                    self.eat_char();
+                    buf.push('-');
+                }
+                tri!(self.scan_integer(&mut buf));
+                writer.write_all(buf.as_bytes()).map_err(Error::io)
+            }
+            b'"' => {
+                let value = tri!(self.parse_key());
+                write_escaped_str(writer, &value)
+            }
+            b'[' => {
+                tri!(writer.write_all(b"[").map_err(Error::io));
+                check_recursion! {
+                    self.eat_char();
+                    let ret = self.minify_elements(writer);
+                }
+                tri!(ret);
+                tri!(self.end_seq());
+                writer.write_all(b"]").map_err(Error::io)
+            }
+            b'{' => {
+                tri!(writer.write_all(b"{").map_err(Error::io));
+                check_recursion! {
+                    self.eat_char();
+                    let ret = self.minify_members(writer);
+                }
+                tri!(ret);
+                tri!(self.end_map());
+                writer.write_all(b"}").map_err(Error::io)
+            }
+            _ => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
         }
-    };
-}
+    }
 
-macro_rules! check_recursion {
-    ($this:ident $($body:tt)*) => {
-        if_checking_recursion_limit! {
-            $this.remaining_depth -= 1;
-            if $this.remaining_depth == 0 {
-                return Err($this.peek_error(ErrorCode::RecursionLimitExceeded));
+    fn minify_elements<W>(&mut self, writer: &mut W) -> Result<()>
+    where
+        W: io::Write,
+    {
+        let mut first = true;
+        loop {
+            match tri!(self.parse_whitespace()) {
+                Some(b']') => return Ok(()),
+                Some(b',') if !first => {
+                    self.eat_char();
+                    if let Some(b']') = tri!(self.parse_whitespace()) {
+                        if self.trailing_commas {
+                            continue;
+                        }
+                        return Err(self.peek_error(ErrorCode::TrailingComma));
+                    }
+                    tri!(writer.write_all(b",").map_err(Error::io));
+                }
+                Some(_) if first => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedListCommaOrEnd)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingList)),
             }
+            first = false;
+            tri!(self.minify_value(writer));
         }
+    }
 
-        $this $($body)*
-
-        if_checking_recursion_limit! {
-            $this.remaining_depth += 1;
+    fn minify_members<W>(&mut self, writer: &mut W) -> Result<()>
+    where
+        W: io::Write,
+    {
+        let mut first = true;
+        loop {
+            match tri!(self.parse_whitespace()) {
+                Some(b'}') => return Ok(()),
+                Some(b',') if !first => {
+                    self.eat_char();
+                    if let Some(b'}') = tri!(self.parse_whitespace()) {
+                        if self.trailing_commas {
+                            continue;
+                        }
+                        return Err(self.peek_error(ErrorCode::TrailingComma));
+                    }
+                    tri!(writer.write_all(b",").map_err(Error::io));
+                }
+                Some(_) if first => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedObjectCommaOrEnd)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
+            }
+            first = false;
+            match tri!(self.parse_whitespace()) {
+                Some(ch) if self.is_key_start(ch) => {}
+                Some(_) => return Err(self.peek_error(ErrorCode::KeyMustBeAString)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+            }
+            {
+                let key = tri!(self.parse_key());
+                tri!(write_escaped_str(writer, &key));
+            }
+            tri!(self.parse_object_colon());
+            tri!(writer.write_all(b":").map_err(Error::io));
+            tri!(self.minify_value(writer));
         }
-    };
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_escaped_str<W>(writer: &mut W, value: &str) -> Result<()>
+where
+    W: io::Write,
+{
+    ser::format_escaped_str(writer, &mut ser::CompactFormatter, value, false).map_err(Error::io)
 }
 
-impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
+impl<'de, 'a, R: Read<'de>, B: Scratch> de::Deserializer<'de> for &'a mut Deserializer<R, B> {
     type Error = Error;
 
     #[inline]
@@ -1328,9 +3094,13 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
             }
             b'0'..=b'9' => tri!(self.parse_any_number(true)).visit(visitor),
             b'"' => {
-                self.eat_char();
+                self.eat_quote();
                 self.scratch.clear();
-                match tri!(self.read.parse_str(&mut self.scratch)) {
+                match tri!(self
+                    .read
+                    .parse_str(&mut self.scratch)
+                    .map_err(|err| err.in_document(self.document.as_deref())))
+                {
                     Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
                     Reference::Copied(s) => visitor.visit_str(s),
                 }
@@ -1507,9 +3277,13 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 
         let value = match peek {
             b'"' => {
-                self.eat_char();
+                self.eat_quote();
                 self.scratch.clear();
-                match tri!(self.read.parse_str(&mut self.scratch)) {
+                match tri!(self
+                    .read
+                    .parse_str(&mut self.scratch)
+                    .map_err(|err| err.in_document(self.document.as_deref())))
+                {
                     Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
                     Reference::Copied(s) => visitor.visit_str(s),
                 }
@@ -1578,14 +3352,34 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
         };
 
         let value = match peek {
-            b'"' => {
-                self.eat_char();
+            b'"' if self.bytes_encoding == BytesEncoding::Array => {
+                self.eat_quote();
                 self.scratch.clear();
-                match tri!(self.read.parse_str_raw(&mut self.scratch)) {
+                match tri!(self
+                    .read
+                    .parse_str_raw(&mut self.scratch)
+                    .map_err(|err| err.in_document(self.document.as_deref())))
+                {
                     Reference::Borrowed(b) => visitor.visit_borrowed_bytes(b),
                     Reference::Copied(b) => visitor.visit_bytes(b),
                 }
             }
+            b'"' => {
+                self.eat_quote();
+                self.scratch.clear();
+                let encoding = self.bytes_encoding;
+                let s = tri!(self
+                    .read
+                    .parse_str(&mut self.scratch)
+                    .map_err(|err| err.in_document(self.document.as_deref())));
+                match encoding.decode(s.as_bytes()) {
+                    Some(bytes) => visitor.visit_byte_buf(bytes),
+                    None => Err(de::Error::invalid_value(
+                        Unexpected::Str(&s),
+                        &encoding.expecting(),
+                    )),
+                }
+            }
             b'[' => self.deserialize_seq(visitor),
             _ => Err(self.peek_invalid_type(&visitor)),
         };
@@ -1755,13 +3549,23 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
+        if name == crate::spanned::NAME {
+            let start = tri!(self.next_value_position());
+            return visitor.visit_map(SpannedAccess {
+                de: self,
+                start,
+                end: None,
+                state: 0,
+            });
+        }
+
         let peek = match tri!(self.parse_whitespace()) {
             Some(b) => b,
             None => {
@@ -1851,18 +3655,18 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
     }
 }
 
-struct SeqAccess<'a, R: 'a> {
-    de: &'a mut Deserializer<R>,
+struct SeqAccess<'a, R: 'a, B: 'a> {
+    de: &'a mut Deserializer<R, B>,
     first: bool,
 }
 
-impl<'a, R: 'a> SeqAccess<'a, R> {
-    fn new(de: &'a mut Deserializer<R>) -> Self {
+impl<'a, R: 'a, B: 'a> SeqAccess<'a, R, B> {
+    fn new(de: &'a mut Deserializer<R, B>) -> Self {
         SeqAccess { de, first: true }
     }
 }
 
-impl<'de, 'a, R: Read<'de> + 'a> de::SeqAccess<'de> for SeqAccess<'a, R> {
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::SeqAccess<'de> for SeqAccess<'a, R, B> {
     type Error = Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
@@ -1891,6 +3695,7 @@ impl<'de, 'a, R: Read<'de> + 'a> de::SeqAccess<'de> for SeqAccess<'a, R> {
         };
 
         match peek {
+            Some(b']') if self.de.trailing_commas => Ok(None),
             Some(b']') => Err(self.de.peek_error(ErrorCode::TrailingComma)),
             Some(_) => Ok(Some(tri!(seed.deserialize(&mut *self.de)))),
             None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
@@ -1898,50 +3703,82 @@ impl<'de, 'a, R: Read<'de> + 'a> de::SeqAccess<'de> for SeqAccess<'a, R> {
     }
 }
 
-struct MapAccess<'a, R: 'a> {
-    de: &'a mut Deserializer<R>,
+struct MapAccess<'a, R: 'a, B: 'a> {
+    de: &'a mut Deserializer<R, B>,
     first: bool,
+    /// Các khoá đã gặp, chỉ được ghi khi chính sách khoá lặp khác `Allow`.
+    seen: BTreeSet<String>,
 }
 
-impl<'a, R: 'a> MapAccess<'a, R> {
-    fn new(de: &'a mut Deserializer<R>) -> Self {
-        MapAccess { de, first: true }
+impl<'a, R: 'a, B: 'a> MapAccess<'a, R, B> {
+    fn new(de: &'a mut Deserializer<R, B>) -> Self {
+        MapAccess {
+            de,
+            first: true,
+            seen: BTreeSet::new(),
+        }
     }
 }
 
-impl<'de, 'a, R: Read<'de> + 'a> de::MapAccess<'de> for MapAccess<'a, R> {
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::MapAccess<'de> for MapAccess<'a, R, B> {
     type Error = Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
     where
         K: de::DeserializeSeed<'de>,
     {
-        let peek = match tri!(self.de.parse_whitespace()) {
-            Some(b'}') => {
-                return Ok(None);
-            }
-            Some(b',') if !self.first => {
-                self.de.eat_char();
-                tri!(self.de.parse_whitespace())
-            }
-            Some(b) => {
-                if self.first {
-                    self.first = false;
-                    Some(b)
-                } else {
-                    return Err(self.de.peek_error(ErrorCode::ExpectedObjectCommaOrEnd));
+        loop {
+            let peek = match tri!(self.de.parse_whitespace()) {
+                Some(b'}') => {
+                    return Ok(None);
+                }
+                Some(b',') if !self.first => {
+                    self.de.eat_char();
+                    tri!(self.de.parse_whitespace())
+                }
+                Some(b) => {
+                    if self.first {
+                        self.first = false;
+                        Some(b)
+                    } else {
+                        return Err(self.de.peek_error(ErrorCode::ExpectedObjectCommaOrEnd));
+                    }
+                }
+                None => {
+                    return Err(self.de.peek_error(ErrorCode::EofWhileParsingObject));
                 }
+            };
+
+            match peek {
+                Some(b'}') if self.de.trailing_commas => return Ok(None),
+                Some(b'}') => return Err(self.de.peek_error(ErrorCode::TrailingComma)),
+                Some(ch) if self.de.is_key_start(ch) => {}
+                Some(_) => return Err(self.de.peek_error(ErrorCode::KeyMustBeAString)),
+                None => return Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
             }
-            None => {
-                return Err(self.de.peek_error(ErrorCode::EofWhileParsingObject));
+
+            if self.de.duplicate_keys == DuplicateKeys::Allow {
+                return seed.deserialize(MapKey { de: &mut *self.de }).map(Some);
             }
-        };
 
-        match peek {
-            Some(b'"') => seed.deserialize(MapKey { de: &mut *self.de }).map(Some),
-            Some(b'}') => Err(self.de.peek_error(ErrorCode::TrailingComma)),
-            Some(_) => Err(self.de.peek_error(ErrorCode::KeyMustBeAString)),
-            None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
+            let key = match tri!(self.de.parse_key()) {
+                Reference::Borrowed(key) => Cow::Borrowed(key),
+                Reference::Copied(key) => Cow::Owned(String::from(key)),
+            };
+            if self.seen.contains(&*key) {
+                if self.de.duplicate_keys == DuplicateKeys::Reject {
+                    let message = alloc::format!("duplicate key `{}`", key);
+                    return Err(self.de.error(ErrorCode::Message(message.into_boxed_str())));
+                }
+                // KeepFirst: bỏ qua cả thành viên lặp.
+                tri!(self.de.parse_object_colon());
+                tri!(self.de.ignore_value());
+                continue;
+            }
+            self.seen.insert(String::from(&*key));
+            return seed
+                .deserialize(value::MapKeyDeserializer { key })
+                .map(Some);
         }
     }
 
@@ -1955,17 +3792,17 @@ impl<'de, 'a, R: Read<'de> + 'a> de::MapAccess<'de> for MapAccess<'a, R> {
     }
 }
 
-struct VariantAccess<'a, R: 'a> {
-    de: &'a mut Deserializer<R>,
+struct VariantAccess<'a, R: 'a, B: 'a> {
+    de: &'a mut Deserializer<R, B>,
 }
 
-impl<'a, R: 'a> VariantAccess<'a, R> {
-    fn new(de: &'a mut Deserializer<R>) -> Self {
+impl<'a, R: 'a, B: 'a> VariantAccess<'a, R, B> {
+    fn new(de: &'a mut Deserializer<R, B>) -> Self {
         VariantAccess { de }
     }
 }
 
-impl<'de, 'a, R: Read<'de> + 'a> de::EnumAccess<'de> for VariantAccess<'a, R> {
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::EnumAccess<'de> for VariantAccess<'a, R, B> {
     type Error = Error;
     type Variant = Self;
 
@@ -1979,7 +3816,9 @@ impl<'de, 'a, R: Read<'de> + 'a> de::EnumAccess<'de> for VariantAccess<'a, R> {
     }
 }
 
-impl<'de, 'a, R: Read<'de> + 'a> de::VariantAccess<'de> for VariantAccess<'a, R> {
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::VariantAccess<'de>
+    for VariantAccess<'a, R, B>
+{
     type Error = Error;
 
     fn unit_variant(self) -> Result<()> {
@@ -2008,17 +3847,19 @@ impl<'de, 'a, R: Read<'de> + 'a> de::VariantAccess<'de> for VariantAccess<'a, R>
     }
 }
 
-struct UnitVariantAccess<'a, R: 'a> {
-    de: &'a mut Deserializer<R>,
+struct UnitVariantAccess<'a, R: 'a, B: 'a> {
+    de: &'a mut Deserializer<R, B>,
 }
 
-impl<'a, R: 'a> UnitVariantAccess<'a, R> {
-    fn new(de: &'a mut Deserializer<R>) -> Self {
+impl<'a, R: 'a, B: 'a> UnitVariantAccess<'a, R, B> {
+    fn new(de: &'a mut Deserializer<R, B>) -> Self {
         UnitVariantAccess { de }
     }
 }
 
-impl<'de, 'a, R: Read<'de> + 'a> de::EnumAccess<'de> for UnitVariantAccess<'a, R> {
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::EnumAccess<'de>
+    for UnitVariantAccess<'a, R, B>
+{
     type Error = Error;
     type Variant = Self;
 
@@ -2031,7 +3872,9 @@ impl<'de, 'a, R: Read<'de> + 'a> de::EnumAccess<'de> for UnitVariantAccess<'a, R
     }
 }
 
-impl<'de, 'a, R: Read<'de> + 'a> de::VariantAccess<'de> for UnitVariantAccess<'a, R> {
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::VariantAccess<'de>
+    for UnitVariantAccess<'a, R, B>
+{
     type Error = Error;
 
     fn unit_variant(self) -> Result<()> {
@@ -2069,10 +3912,73 @@ impl<'de, 'a, R: Read<'de> + 'a> de::VariantAccess<'de> for UnitVariantAccess<'a
     }
 }
 
-/// Chỉ deserialize từ điều này sau khi dò được một byte '"'! Nếu không, 
-/// nó có thể deserialize được JSON không hợp lệ thành công.
-struct MapKey<'a, R: 'a> {
-    de: &'a mut Deserializer<R>,
+/// Đưa một giá trị vào visitor của [`Spanned`] dưới dạng map gồm giá trị và
+/// vị trí của nó.
+struct SpannedAccess<'a, R: 'a, B: 'a> {
+    de: &'a mut Deserializer<R, B>,
+    start: Position,
+    end: Option<Position>,
+    state: u8,
+}
+
+impl<'de, 'a, R: Read<'de> + 'a, B: Scratch + 'a> de::MapAccess<'de> for SpannedAccess<'a, R, B> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let key = match self.state {
+            0 => crate::spanned::VALUE,
+            1 => crate::spanned::SPAN,
+            _ => return Ok(None),
+        };
+        seed.deserialize(de::IntoDeserializer::<Error>::into_deserializer(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.state += 1;
+        match self.end {
+            None => {
+                let value = tri!(seed.deserialize(&mut *self.de));
+                self.end = Some(self.de.value_end(self.start));
+                Ok(value)
+            }
+            Some(end) => {
+                let start = self.start;
+                let span = [
+                    start.line,
+                    start.column,
+                    start.byte_offset,
+                    end.line,
+                    end.column,
+                    end.byte_offset,
+                ];
+                seed.deserialize(de::value::SeqDeserializer::new(span.into_iter()))
+            }
+        }
+    }
+}
+
+/// Byte có thể mở đầu một khoá không có nháy: chữ cái, `_`, `$` hoặc byte
+/// của một ký tự không phải ASCII.
+fn is_bare_key_start(ch: u8) -> bool {
+    ch.is_ascii_alphabetic() || ch == b'_' || ch == b'$' || ch >= 0x80
+}
+
+fn is_bare_key_byte(ch: u8) -> bool {
+    is_bare_key_start(ch) || ch.is_ascii_digit()
+}
+
+/// Chỉ deserialize từ điều này sau khi dò được một byte thoả
+/// `Deserializer::is_key_start`! Nếu không, nó có thể deserialize được JSON
+/// không hợp lệ thành công.
+struct MapKey<'a, R: 'a, B: 'a> {
+    de: &'a mut Deserializer<R, B>,
 }
 
 macro_rules! deserialize_integer_key {
@@ -2081,9 +3987,7 @@ macro_rules! deserialize_integer_key {
         where
             V: de::Visitor<'de>,
         {
-            self.de.eat_char();
-            self.de.scratch.clear();
-            let string = tri!(self.de.read.parse_str(&mut self.de.scratch));
+            let string = tri!(self.de.parse_key());
             match (string.parse(), string) {
                 (Ok(integer), _) => visitor.$visit(integer),
                 (Err(_), Reference::Borrowed(s)) => visitor.visit_borrowed_str(s),
@@ -2093,9 +3997,10 @@ macro_rules! deserialize_integer_key {
     };
 }
 
-impl<'de, 'a, R> de::Deserializer<'de> for MapKey<'a, R>
+impl<'de, 'a, R, B> de::Deserializer<'de> for MapKey<'a, R, B>
 where
     R: Read<'de>,
+    B: Scratch,
 {
     type Error = Error;
 
@@ -2104,9 +4009,7 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.de.eat_char();
-        self.de.scratch.clear();
-        match tri!(self.de.read.parse_str(&mut self.de.scratch)) {
+        match tri!(self.de.parse_key()) {
             Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
             Reference::Copied(s) => visitor.visit_str(s),
         }
@@ -2123,6 +4026,18 @@ where
     deserialize_integer_key!(deserialize_u64 => visit_u64);
     deserialize_integer_key!(deserialize_u128 => visit_u128);
 
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match tri!(self.de.parse_key()) {
+            Reference::Borrowed("true") | Reference::Copied("true") => visitor.visit_bool(true),
+            Reference::Borrowed("false") | Reference::Copied("false") => visitor.visit_bool(false),
+            Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Reference::Copied(s) => visitor.visit_str(s),
+        }
+    }
+
     #[inline]
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
     where
@@ -2158,6 +4073,11 @@ where
     where
         V: de::Visitor<'de>,
     {
+        if tri!(self.de.peek()) != Some(b'"') {
+            // Khoá không có nháy chỉ có thể là tên của một biến thể đơn vị.
+            let key = tri!(self.de.parse_key());
+            return visitor.visit_enum(de::IntoDeserializer::<Error>::into_deserializer(&*key));
+        }
         self.de.deserialize_enum(name, variants, visitor)
     }
 
@@ -2178,8 +4098,8 @@ where
     }
 
     forward_to_deserialize_any! {
-        bool f32 f64 char str string unit unit_struct seq tuple tuple_struct map
-        struct identifier ignored_any
+        f32 f64 char str string unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
     }
 }
 
@@ -2211,6 +4131,7 @@ where
 pub struct StreamDeserializer<'de, R, T> {
     de: Deserializer<R>,
     offset: usize,
+    input: Option<&'de [u8]>,
     failed: bool,
     output: PhantomData<T>,
     lifetime: PhantomData<&'de ()>,
@@ -2231,9 +4152,11 @@ where
     ///   - Deserializer::from_reader(...).into_iter()
     pub fn new(read: R) -> Self {
         let offset = read.byte_offset();
+        let input = read.input();
         StreamDeserializer {
             de: Deserializer::new(read),
             offset,
+            input,
             failed: false,
             output: PhantomData,
             lifetime: PhantomData,
@@ -2275,18 +4198,43 @@ where
         self.offset
     }
 
+    /// Phần đầu vào bắt đầu từ [`byte_offset`](StreamDeserializer::byte_offset),
+    /// tức là những gì chưa được giải mã thành công. Có thể lưu lại phần này
+    /// (hoặc chỉ lưu `byte_offset`) để tiếp tục đọc sau khi khởi động lại.
+    ///
+    /// Trả về `None` nếu đầu vào là một `io::Read`, vì khi đó dữ liệu không
+    /// nằm sẵn trong bộ nhớ.
+    ///
+    /// ```
+    /// use serde_json::{Deserializer, Value};
+    ///
+    /// let data = br#"{"seq": 1} {"seq": 2} {"seq": 3}"#;
+    /// let mut stream = Deserializer::from_slice(data).into_iter::<Value>();
+    /// stream.next().unwrap().unwrap();
+    ///
+    /// let checkpoint = stream.remaining_input().unwrap();
+    /// assert_eq!(checkpoint, br#" {"seq": 2} {"seq": 3}"#);
+    ///
+    /// // Sau khi khởi động lại, tiếp tục từ checkpoint.
+    /// let rest: Vec<Value> = Deserializer::from_slice(checkpoint)
+    ///     .into_iter()
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(rest.len(), 2);
+    /// ```
+    pub fn remaining_input(&self) -> Option<&'de [u8]> {
+        self.input.map(|input| &input[self.offset..])
+    }
+
     fn peek_end_of_value(&mut self) -> Result<()> {
         match tri!(self.de.peek()) {
             Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') | Some(b'"') | Some(b'[')
             | Some(b']') | Some(b'{') | Some(b'}') | Some(b',') | Some(b':') | None => Ok(()),
-            Some(_) => {
-                let position = self.de.read.peek_position();
-                Err(Error::syntax(
-                    ErrorCode::TrailingCharacters,
-                    position.line,
-                    position.column,
-                ))
-            }
+            Some(_) => Err(Error::syntax_at(
+                ErrorCode::TrailingCharacters,
+                self.de.read.peek_position(),
+                self.de.read.input(),
+            )),
         }
     }
 }
@@ -2299,6 +4247,50 @@ where
     type Item = Result<T>;
 
     fn next(&mut self) -> Option<Result<T>> {
+        self.next_with_range()
+            .map(|result| result.map(|(value, _range)| value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if R::should_early_return_if_failed && self.failed {
+            return (0, Some(0));
+        }
+        // Mỗi giá trị chiếm ít nhất một byte.
+        let remaining = self
+            .input
+            .map(|input| input.len() - self.de.read.byte_offset());
+        (0, remaining)
+    }
+}
+
+impl<'de, R, T> StreamDeserializer<'de, R, T>
+where
+    R: Read<'de>,
+    T: de::Deserialize<'de>,
+{
+    /// Giống `next()` nhưng trả về kèm theo khoảng byte của giá trị trong đầu
+    /// vào, để có thể chuyển tiếp nguyên văn bản gốc sau khi đã lọc theo các
+    /// trường đã giải mã.
+    ///
+    /// Khoảng byte không bao gồm khoảng trắng xung quanh giá trị.
+    ///
+    /// ```
+    /// use serde_json::{Deserializer, Value};
+    ///
+    /// let data = r#"{"level": "info"}  {"level": "error", "msg": "boom"}"#;
+    /// let mut stream = Deserializer::from_str(data).into_iter::<Value>();
+    ///
+    /// let mut errors = Vec::new();
+    /// while let Some(result) = stream.next_with_range() {
+    ///     let (value, range) = result.unwrap();
+    ///     if value["level"] == "error" {
+    ///         errors.push(&data[range]);
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(errors, [r#"{"level": "error", "msg": "boom"}"#]);
+    /// ```
+    pub fn next_with_range(&mut self) -> Option<Result<(T, Range<usize>)>> {
         if R::should_early_return_if_failed && self.failed {
             return None;
         }
@@ -2320,35 +4312,170 @@ where
                     _ => false,
                 };
                 self.offset = self.de.read.byte_offset();
+                let start = self.offset;
                 let result = de::Deserialize::deserialize(&mut self.de);
 
-                Some(match result {
-                    Ok(value) => {
-                        self.offset = self.de.read.byte_offset();
-                        if self_delineated_value {
-                            Ok(value)
-                        } else {
-                            self.peek_end_of_value().map(|_| value)
-                        }
-                    }
-                    Err(e) => {
-                        self.de.read.set_failed(&mut self.failed);
-                        Err(e)
-                    }
-                })
-            }
-            Err(e) => {
-                self.de.read.set_failed(&mut self.failed);
-                Some(Err(e))
+                Some(match result {
+                    Ok(value) => {
+                        self.offset = self.de.read.byte_offset();
+                        let value = (value, start..self.offset);
+                        if self_delineated_value {
+                            Ok(value)
+                        } else {
+                            self.peek_end_of_value().map(|_| value)
+                        }
+                    }
+                    Err(e) => {
+                        self.de.read.set_failed(&mut self.failed);
+                        Err(e)
+                    }
+                })
+            }
+            Err(e) => {
+                self.de.read.set_failed(&mut self.failed);
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<'de, R, T> FusedIterator for StreamDeserializer<'de, R, T>
+where
+    R: Read<'de> + Fused,
+    T: de::Deserialize<'de>,
+{
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+/// Đọc một luồng JSON mà mỗi dòng chứa đúng một giá trị (NDJSON / JSON Lines).
+///
+/// Mỗi dòng được đọc vào một bộ đệm rồi giải mã như một slice, nên tốc độ gần
+/// với [`from_slice`] dù đầu vào là một luồng. Dòng trống được bỏ qua. Một giá
+/// trị trải trên nhiều dòng, hoặc nhiều giá trị trên cùng một dòng, là lỗi.
+///
+/// Lỗi ở một dòng không làm dừng iterator: lần gọi `next` sau sẽ đọc dòng kế
+/// tiếp. Vị trí trong lỗi được tính theo toàn bộ luồng. Chỉ lỗi IO mới kết
+/// thúc iterator.
+///
+/// Mỗi dòng được phân tích như JSON chuẩn; dùng
+/// [`DeserializerBuilder::build_lines`] để áp dụng các tùy chọn khác.
+///
+/// ```
+/// use serde_json::de::LineDeserializer;
+/// use serde_json::Value;
+///
+/// let log = b"{\"level\": \"info\"}\n\n{\"level\": \"warn\"} 1\n{\"level\": \"error\"}\n";
+/// let mut lines = LineDeserializer::<_, Value>::new(&log[..]);
+///
+/// assert_eq!(lines.next().unwrap().unwrap()["level"], "info");
+/// let err = lines.next().unwrap().unwrap_err();
+/// assert_eq!(err.line(), 3);
+/// assert_eq!(lines.next().unwrap().unwrap()["level"], "error");
+/// assert!(lines.next().is_none());
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct LineDeserializer<R, T> {
+    reader: R,
+    builder: DeserializerBuilder,
+    document: Option<Box<str>>,
+    buf: Vec<u8>,
+    line: usize,
+    offset: usize,
+    failed: bool,
+    output: PhantomData<T>,
+}
+
+#[cfg(feature = "std")]
+impl<R, T> LineDeserializer<R, T>
+where
+    R: crate::io::BufRead,
+    T: de::DeserializeOwned,
+{
+    /// Tạo một iterator đọc từng dòng của `reader`.
+    pub fn new(reader: R) -> Self {
+        DeserializerBuilder::new().build_lines(reader)
+    }
+
+    /// Giống [`Deserializer::set_document`]: gắn tên tài liệu vào lỗi của
+    /// mọi dòng.
+    pub fn set_document<S>(&mut self, name: S)
+    where
+        S: Into<String>,
+    {
+        self.document = Some(name.into().into_boxed_str());
+    }
+
+    fn with_builder(reader: R, builder: DeserializerBuilder) -> Self {
+        LineDeserializer {
+            reader,
+            builder,
+            document: None,
+            buf: Vec::new(),
+            line: 0,
+            offset: 0,
+            failed: false,
+            output: PhantomData,
+        }
+    }
+
+    /// Số byte đã được đọc, tính đến hết dòng gần nhất. Có thể dùng để tiếp
+    /// tục đọc từ đúng vị trí này sau khi khởi động lại.
+    pub fn byte_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, T> Iterator for LineDeserializer<R, T>
+where
+    R: crate::io::BufRead,
+    T: de::DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        while !self.failed {
+            self.buf.clear();
+            let n = match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => return None,
+                Ok(n) => n,
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(Error::io(err)));
+                }
+            };
+            let (line, start) = (self.line, self.offset);
+            self.line += 1;
+            self.offset += n;
+
+            let text = match self.buf.strip_suffix(b"\n") {
+                Some(text) => text,
+                None => &self.buf,
+            };
+            if text.iter().all(|b| matches!(b, b' ' | b'\t' | b'\r')) {
+                continue;
             }
+            let mut de = self.builder.build_slice(text);
+            let result = de::Deserialize::deserialize(&mut de).and_then(|value| {
+                tri!(de.end());
+                Ok(value)
+            });
+            return Some(result.map_err(|err| {
+                err.shift_position(line, start)
+                    .in_document(self.document.as_deref())
+            }));
         }
+        None
     }
 }
 
-impl<'de, R, T> FusedIterator for StreamDeserializer<'de, R, T>
+#[cfg(feature = "std")]
+impl<R, T> FusedIterator for LineDeserializer<R, T>
 where
-    R: Read<'de> + Fused,
-    T: de::Deserialize<'de>,
+    R: crate::io::BufRead,
+    T: de::DeserializeOwned,
 {
 }
 
@@ -2358,9 +4485,17 @@ fn from_trait<'de, R, T>(read: R) -> Result<T>
 where
     R: Read<'de>,
     T: de::Deserialize<'de>,
+{
+    from_seed(read, PhantomData)
+}
+
+fn from_seed<'de, R, S>(read: R, seed: S) -> Result<S::Value>
+where
+    R: Read<'de>,
+    S: de::DeserializeSeed<'de>,
 {
     let mut de = Deserializer::new(read);
-    let value = tri!(de::Deserialize::deserialize(&mut de));
+    let value = tri!(seed.deserialize(&mut de));
 
     // Make sure the whole stream has been consumed.
     tri!(de.end());
@@ -2480,6 +4615,165 @@ where
     from_trait(read::IoRead::new(rdr))
 }
 
+/// Giống [`from_reader`] nhưng việc giải mã được điều khiển bởi một
+/// [`DeserializeSeed`](de::DeserializeSeed) thay vì `T: Deserialize`.
+///
+/// Dùng khi bộ giải mã cần trạng thái bên ngoài (schema registry, interner,
+/// arena, ...) mà vẫn muốn đọc trực tiếp từ luồng IO.
+///
+/// # Example
+///
+/// ```
+/// use serde::de::{DeserializeSeed, Deserializer, SeqAccess, Visitor};
+/// use std::collections::HashSet;
+/// use std::fmt;
+///
+/// // Gom mọi chuỗi trong một mảng vào một interner dùng chung.
+/// struct Intern<'a>(&'a mut HashSet<String>);
+///
+/// impl<'de, 'a> DeserializeSeed<'de> for Intern<'a> {
+///     type Value = usize;
+///
+///     fn deserialize<D>(self, deserializer: D) -> Result<usize, D::Error>
+///     where
+///         D: Deserializer<'de>,
+///     {
+///         deserializer.deserialize_seq(self)
+///     }
+/// }
+///
+/// impl<'de, 'a> Visitor<'de> for Intern<'a> {
+///     type Value = usize;
+///
+///     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+///         formatter.write_str("an array of strings")
+///     }
+///
+///     fn visit_seq<A>(self, mut seq: A) -> Result<usize, A::Error>
+///     where
+///         A: SeqAccess<'de>,
+///     {
+///         let mut count = 0;
+///         while let Some(s) = seq.next_element::<String>()? {
+///             self.0.insert(s);
+///             count += 1;
+///         }
+///         Ok(count)
+///     }
+/// }
+///
+/// let mut interner = HashSet::new();
+/// let reader = br#"["a", "b", "a"]"#.as_ref();
+/// let count = serde_json::from_reader_seed(reader, Intern(&mut interner)).unwrap();
+///
+/// assert_eq!(count, 3);
+/// assert_eq!(interner.len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// Giống như [`from_reader`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn from_reader_seed<'de, R, S>(rdr: R, seed: S) -> Result<S::Value>
+where
+    R: crate::io::Read,
+    S: de::DeserializeSeed<'de>,
+{
+    from_seed(read::IoRead::new(rdr), seed)
+}
+
+/// Giống [`from_reader`] nhưng đọc trực tiếp từ bộ đệm của một `io::BufRead`,
+/// xem [`Deserializer::from_buf_read`].
+///
+/// ```
+/// use std::io::BufReader;
+///
+/// let reader = BufReader::new(&b"[1, 2, 3]"[..]);
+/// let v: Vec<u8> = serde_json::from_buf_read(reader).unwrap();
+/// assert_eq!(v, [1, 2, 3]);
+/// ```
+///
+/// # Errors
+///
+/// Giống như [`from_reader`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn from_buf_read<R, T>(rdr: R) -> Result<T>
+where
+    R: crate::io::BufRead,
+    T: de::DeserializeOwned,
+{
+    from_trait(read::BufIoRead::new(rdr))
+}
+
+/// Chép các giá trị JSON từ `reader` ra `writer` ở dạng gọn nhất, không
+/// dựng chúng thành `Value`, xem [`Deserializer::minify_to`].
+///
+/// Dùng cho proxy hoặc công cụ build cần thu nhỏ những văn bản lớn mà chỉ
+/// giữ một lượng bộ nhớ nhỏ. `reader` được đọc qua một bộ đệm riêng, còn
+/// `writer` nên được bọc trong `BufWriter` nếu mỗi lần ghi là tốn kém. Để
+/// chấp nhận chú thích hoặc các cú pháp mở rộng khác, tạo một
+/// [`Deserializer`] với các tùy chọn đó rồi gọi `minify_to`.
+///
+/// ```
+/// let input = b"[ 1.50 , {\"a\" : \"\\u00e9\"} ]\n{ }";
+/// let mut out = Vec::new();
+/// serde_json::minify_stream(&input[..], &mut out).unwrap();
+/// assert_eq!(out, "[1.50,{\"a\":\"é\"}]\n{}".as_bytes());
+/// ```
+///
+/// # Errors
+///
+/// Trả về lỗi nếu đầu vào không phải JSON hợp lệ hoặc nếu việc đọc hay ghi
+/// gặp lỗi IO.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn minify_stream<R, W>(reader: R, writer: W) -> Result<()>
+where
+    R: crate::io::Read,
+    W: crate::io::Write,
+{
+    let mut de = Deserializer::from_buf_read(std::io::BufReader::new(reader));
+    de.minify_to(writer)
+}
+
+/// Ánh xạ tệp tại `path` vào bộ nhớ rồi giải mã nó như [`from_slice`], để
+/// đọc các tệp rất lớn với tốc độ của slice mà không phải chép cả tệp vào
+/// bộ nhớ hay tự viết mã `unsafe` để mmap.
+///
+/// Tệp không được bị sửa đổi hoặc cắt ngắn bởi tiến trình khác trong lúc
+/// đang giải mã; điều đó là hành vi không xác định với mọi cách dùng mmap.
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::env::temp_dir().join("serde_json_from_path_mmap.json");
+/// std::fs::write(&path, br#"{"ids": [1, 2, 3]}"#)?;
+///
+/// let v: serde_json::Value = serde_json::from_path_mmap(&path)?;
+/// assert_eq!(v["ids"][2], 3);
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Giống [`from_slice`], cộng với lỗi IO khi mở hoặc ánh xạ tệp.
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub fn from_path_mmap<T, P>(path: P) -> Result<T>
+where
+    T: de::DeserializeOwned,
+    P: AsRef<std::path::Path>,
+{
+    let file = tri!(std::fs::File::open(path).map_err(Error::io));
+    // An toàn miễn là tệp không bị thay đổi trong lúc đang được ánh xạ, như đã
+    // ghi trong tài liệu ở trên.
+    let map = tri!(unsafe { memmap2::Mmap::map(&file) }.map_err(Error::io));
+    from_slice(&map)
+}
+
 /// Deserialize an instance of type `T` from bytes of JSON text.
 ///
 /// # Example
@@ -2521,6 +4815,453 @@ where
     from_trait(read::SliceRead::new(v))
 }
 
+/// Giống [`from_slice`] nhưng trả về kèm theo khoảng byte của giá trị trong
+/// `v`, không tính khoảng trắng ở đầu và cuối.
+///
+/// ```
+/// let j = b"  [1, 2]\n";
+/// let (v, range) = serde_json::from_slice_with_range::<Vec<u8>>(j).unwrap();
+///
+/// assert_eq!(v, [1, 2]);
+/// assert_eq!(&j[range], b"[1, 2]");
+/// ```
+///
+/// # Errors
+///
+/// Giống như [`from_slice`].
+pub fn from_slice_with_range<'a, T>(v: &'a [u8]) -> Result<(T, Range<usize>)>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut de = Deserializer::new(read::SliceRead::new(v));
+    tri!(de.parse_whitespace());
+    let start = de.byte_offset();
+    let value = tri!(de::Deserialize::deserialize(&mut de));
+    let end = de.byte_offset();
+
+    // Make sure the whole stream has been consumed.
+    tri!(de.end());
+    Ok((value, start..end))
+}
+
+/// Giống [`from_slice`] nhưng dùng `scratch` làm bộ đệm để giải mã các chuỗi
+/// có escape, thay vì cấp phát một bộ đệm trên heap.
+///
+/// Bộ đệm có kích thước cố định do người gọi cấp, ví dụ một mảng trên stack
+/// hoặc một vùng `static`, nên phần giải mã chuỗi không bao giờ cấp phát và
+/// lượng bộ nhớ dùng trên các thiết bị nhúng có heap nhỏ trở nên cố định.
+/// Bộ đệm cũng được dùng cho các số thực quá dài và khoá không có nháy. Crate
+/// vẫn cần `alloc`: lỗi và các kiểu sở hữu như `String` vẫn cấp phát.
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Reading<'a> {
+///     sensor: &'a str,
+///     value: f32,
+/// }
+///
+/// let mut scratch = [0; 256];
+/// for msg in [&br#"{"sensor": "t0", "value": 21.5}"#[..], br#"{"sensor": "t1", "value": -3}"#] {
+///     let reading: Reading = serde_json::from_slice_in(msg, &mut scratch).unwrap();
+///     assert!(reading.sensor.starts_with('t'));
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Giống như [`from_slice`]. Ngoài ra trả về lỗi thuộc
+/// [`Category::Limit`](crate::error::Category::Limit) nếu một chuỗi cần giải
+/// mã không vừa trong `scratch`.
+pub fn from_slice_in<'a, T>(v: &'a [u8], scratch: &mut [u8]) -> Result<T>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut de =
+        Deserializer::with_scratch(read::SliceRead::new(v), read::FixedScratch::new(scratch));
+    match de::Deserialize::deserialize(&mut de) {
+        Ok(value) => de.end().map(|()| value),
+        Err(err) => Err(de.fix_position(err)),
+    }
+}
+
+/// Chỉ giải mã những giá trị nằm tại các JSON Pointer trong `pointers`, bỏ
+/// qua mọi phần khác của văn bản mà không dựng chúng thành `Value`.
+///
+/// Kết quả có cùng thứ tự với `pointers`; phần tử là `None` nếu đường dẫn
+/// không tồn tại (hoặc không phải một JSON Pointer hợp lệ). Dùng để lấy vài
+/// trường từ những văn bản rất lớn với tốc độ gần bằng việc chỉ kiểm tra cú
+/// pháp. Toàn bộ `v` vẫn được kiểm tra là JSON hợp lệ.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let j = br#"{"id": 7, "meta": {"tags": ["a", "b"], "big": [1, 2, 3]}, "rest": {}}"#;
+/// let found = serde_json::from_slice_projected(j, &["/id", "/meta/tags/1", "/nope"]).unwrap();
+/// assert_eq!(found, [Some(json!(7)), Some(json!("b")), None]);
+/// ```
+///
+/// # Errors
+///
+/// Trả về lỗi nếu `v` không phải là một văn bản JSON hợp lệ.
+pub fn from_slice_projected<S>(v: &[u8], pointers: &[S]) -> Result<Vec<Option<Value>>>
+where
+    S: AsRef<str>,
+{
+    let mut root = Projection::default();
+    for (i, pointer) in pointers.iter().enumerate() {
+        root.insert(pointer.as_ref(), i);
+    }
+    let mut out = Vec::new();
+    out.resize(pointers.len(), None);
+
+    let mut de = Deserializer::new(read::SliceRead::new(v));
+    tri!(de.parse_projected(&root, &mut out));
+    tri!(de.end());
+    Ok(out)
+}
+
+/// Cây các đường dẫn cần lấy trong [`from_slice_projected`]. `ends` là chỉ số
+/// của những pointer kết thúc tại nút này.
+#[derive(Default)]
+struct Projection {
+    ends: Vec<usize>,
+    children: Vec<(String, Projection)>,
+}
+
+impl Projection {
+    fn insert(&mut self, pointer: &str, index: usize) {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return;
+        }
+        let mut node = self;
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            let pos = match node.children.iter().position(|(t, _)| *t == token) {
+                Some(pos) => pos,
+                None => {
+                    node.children.push((token, Projection::default()));
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[pos].1;
+        }
+        node.ends.push(index);
+    }
+
+    fn child(&self, key: &str) -> Option<&Projection> {
+        self.children
+            .iter()
+            .find(|(token, _)| token == key)
+            .map(|(_, child)| child)
+    }
+
+    fn child_at(&self, index: usize) -> Option<&Projection> {
+        self.children
+            .iter()
+            .find(|(token, _)| value::parse_index(token) == Some(index))
+            .map(|(_, child)| child)
+    }
+
+    fn fill_from(&self, value: &Value, out: &mut [Option<Value>]) {
+        for &i in &self.ends {
+            out[i] = Some(value.clone());
+        }
+        for (token, child) in &self.children {
+            let next = match value {
+                Value::Object(map) => map.get(token),
+                Value::Array(list) => value::parse_index(token).and_then(|i| list.get(i)),
+                _ => None,
+            };
+            if let Some(next) = next {
+                child.fill_from(next, out);
+            }
+        }
+    }
+}
+
+/// Giống [`from_slice`] với `T = Value`, nhưng trả về kèm bảng khoảng byte
+/// của mọi nút trong cây. Xem [`from_str_with_spans`].
+///
+/// # Errors
+///
+/// Trả về lỗi nếu `v` không phải là một văn bản JSON hợp lệ.
+pub fn from_slice_with_spans(v: &[u8]) -> Result<(Value, Spans)> {
+    let mut de = Deserializer::new(read::SliceRead::new(v));
+    let value = tri!(de.parse_value_with_spans());
+    tri!(de.end());
+    Ok(value)
+}
+
+/// Giống [`from_str`] với `T = Value`, nhưng trả về kèm bảng khoảng byte của
+/// mọi nút trong cây, để các công cụ như linter hay trình soạn thảo chỉ được
+/// về đúng vị trí trong văn bản nguồn.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let j = r#"{"name": "web", "ports": [80, 443]}"#;
+/// let (value, spans) = serde_json::from_str_with_spans(j).unwrap();
+///
+/// assert_eq!(value["ports"][1], json!(443));
+/// assert_eq!(spans.get("/ports/1"), Some(30..33));
+/// assert_eq!(&j[spans.get("/ports").unwrap()], "[80, 443]");
+/// assert_eq!(spans.get(""), Some(0..j.len()));
+/// ```
+///
+/// # Errors
+///
+/// Trả về lỗi nếu `s` không phải là một văn bản JSON hợp lệ.
+pub fn from_str_with_spans(s: &str) -> Result<(Value, Spans)> {
+    let mut de = Deserializer::new(read::StrRead::new(s));
+    let value = tri!(de.parse_value_with_spans());
+    tri!(de.end());
+    Ok(value)
+}
+
+/// Bảng khoảng byte của các nút trong một `Value`, theo JSON Pointer của
+/// từng nút.
+///
+/// Được trả về bởi [`from_str_with_spans`], [`from_slice_with_spans`] và
+/// [`Deserializer::parse_value_with_spans`]. Các nút được giữ theo thứ tự
+/// xuất hiện trong văn bản, mỗi nút đứng trước các nút con của nó.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Spans {
+    entries: Vec<(String, Range<usize>)>,
+    index: BTreeMap<String, usize>,
+}
+
+impl Spans {
+    /// Khoảng byte của nút tại `pointer`, nếu có nút đó.
+    pub fn get(&self, pointer: &str) -> Option<Range<usize>> {
+        self.index.get(pointer).map(|&i| self.entries[i].1.clone())
+    }
+
+    /// Số nút trong bảng.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Bảng có rỗng không.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Duyệt các cặp pointer và khoảng byte theo thứ tự trong văn bản.
+    ///
+    /// ```
+    /// let (_, spans) = serde_json::from_str_with_spans(r#"{"a": [true]}"#).unwrap();
+    /// let pointers: Vec<&str> = spans.iter().map(|(pointer, _)| pointer).collect();
+    /// assert_eq!(pointers, ["", "/a", "/a/0"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Range<usize>)> {
+        self.entries
+            .iter()
+            .map(|(pointer, range)| (pointer.as_str(), range.clone()))
+    }
+
+    /// Cập nhật `value` và bảng này sau một lần sửa văn bản, chỉ phân tích
+    /// lại nút nhỏ nhất chứa chỗ sửa thay vì cả tài liệu.
+    ///
+    /// `text` là toàn bộ văn bản sau khi sửa, `edited` là khoảng byte trong
+    /// văn bản cũ đã bị thay thế, và `inserted` là độ dài tính bằng byte của
+    /// đoạn văn bản thay vào. `value` và bảng này phải là kết quả phân tích
+    /// văn bản cũ, chẳng hạn từ [`from_str_with_spans`] hoặc một lần gọi
+    /// `reparse` trước đó.
+    ///
+    /// Nếu đoạn mới của nút đó không còn là đúng một giá trị JSON, chẳng hạn
+    /// khi chỗ sửa thêm một phần tử mới, nút cha được thử thay thế, cho đến
+    /// cả tài liệu. Trả về JSON Pointer của nút đã được phân tích lại; mọi
+    /// nút nằm ngoài nút đó giữ nguyên giá trị, chỉ có khoảng byte bị dịch.
+    /// Giới hạn đệ quy được tính như khi phân tích cả tài liệu, kể cả các
+    /// mảng và object bao quanh nút.
+    ///
+    /// ```
+    /// use serde_json::json;
+    ///
+    /// let old = r#"{"name": "web", "ports": [80, 443]}"#;
+    /// let (mut value, mut spans) = serde_json::from_str_with_spans(old).unwrap();
+    ///
+    /// // Sửa `80` thành `8080`: chỉ phần tử đó được phân tích lại.
+    /// let new = r#"{"name": "web", "ports": [8080, 443]}"#;
+    /// let pointer = spans.reparse(&mut value, new, 26..28, 4).unwrap();
+    /// assert_eq!(pointer, "/ports/0");
+    /// assert_eq!(value, json!({"name": "web", "ports": [8080, 443]}));
+    /// assert_eq!(&new[spans.get("/ports/1").unwrap()], "443");
+    ///
+    /// // Thêm một phần tử: `443` không còn là một giá trị đơn nên cả mảng
+    /// // được phân tích lại.
+    /// let newer = r#"{"name": "web", "ports": [8080, 443, 9000]}"#;
+    /// let pointer = spans.reparse(&mut value, newer, 35..35, 6).unwrap();
+    /// assert_eq!(pointer, "/ports");
+    /// assert_eq!(value["ports"], json!([8080, 443, 9000]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu `text` không phải là một văn bản JSON hợp lệ. Khi đó
+    /// `value` và bảng này không bị thay đổi.
+    pub fn reparse(
+        &mut self,
+        value: &mut Value,
+        text: &str,
+        edited: Range<usize>,
+        inserted: usize,
+    ) -> Result<String> {
+        let containing = self.containing(&edited);
+        for (n, &i) in containing.iter().enumerate().rev() {
+            let (pointer, range) = &self.entries[i];
+            let end = range.end - edited.len() + inserted;
+            let source = match text.as_bytes().get(range.start..end) {
+                Some(source) => source,
+                None => continue,
+            };
+            // Nút nằm dưới `depth` mảng hoặc object, nên chỉ còn phần độ sâu
+            // mà lần phân tích cả tài liệu dành cho nó.
+            let depth = pointer.matches('/').count();
+            let mut de = Deserializer::new(read::SliceRead::new(source));
+            de.remaining_depth = (128 - depth.min(128)) as u8;
+            let (node, spans) = match de.parse_value_with_spans() {
+                Ok(parsed) if de.end().is_ok() => parsed,
+                _ => continue,
+            };
+            let pointer = pointer.clone();
+            let start = range.start;
+            match value.pointer_mut(&pointer) {
+                Some(target) => *target = node,
+                None => continue,
+            }
+            self.splice(i, &containing[..n], start, spans, &edited, inserted);
+            return Ok(pointer);
+        }
+
+        let (node, spans) = tri!(from_str_with_spans(text));
+        *value = node;
+        *self = spans;
+        Ok(String::new())
+    }
+
+    /// Chỉ số của các nút chứa trọn `edited`, từ gốc xuống nút sâu nhất.
+    fn containing(&self, edited: &Range<usize>) -> Vec<usize> {
+        // Các nút được xếp theo vị trí bắt đầu, nên nút cuối cùng bắt đầu
+        // không sau chỗ sửa là nút chứa sâu nhất hoặc một nút con của nó. Các
+        // nút chứa chỗ sửa vì thế đều là tổ tiên của nút đó.
+        let last = match self
+            .entries
+            .partition_point(|(_, range)| range.start <= edited.start)
+            .checked_sub(1)
+        {
+            Some(last) => last,
+            None => return Vec::new(),
+        };
+        let mut containing = Vec::new();
+        let mut pointer = self.entries[last].0.as_str();
+        loop {
+            if let Some(&i) = self.index.get(pointer) {
+                let range = &self.entries[i].1;
+                if range.start <= edited.start && edited.end <= range.end {
+                    containing.push(i);
+                }
+            }
+            match pointer.rfind('/') {
+                Some(slash) => pointer = &pointer[..slash],
+                None => break,
+            }
+        }
+        containing.reverse();
+        containing
+    }
+
+    /// Thay nút thứ `i` và các nút con của nó bằng `spans`, vốn được tính
+    /// từ `start`, rồi dịch khoảng byte của các nút đứng sau và của
+    /// `ancestors`, các nút chứa nút thứ `i`.
+    ///
+    /// Chỉ các nút trong cây con bị thay mới được ghi lại vào bảng tra cứu;
+    /// các nút khác chỉ bị dịch vị trí.
+    fn splice(
+        &mut self,
+        i: usize,
+        ancestors: &[usize],
+        start: usize,
+        spans: Spans,
+        edited: &Range<usize>,
+        inserted: usize,
+    ) {
+        let shift = |offset: usize| {
+            if offset >= edited.end {
+                offset - edited.len() + inserted
+            } else {
+                offset
+            }
+        };
+        let pointer = self.entries[i].0.clone();
+        let after = self.entries[i..]
+            .iter()
+            .position(|(p, _)| !is_within(p, &pointer))
+            .map_or(self.entries.len(), |n| i + n);
+
+        for &ancestor in ancestors {
+            let range = &mut self.entries[ancestor].1;
+            range.end = shift(range.end);
+        }
+        for (_, range) in &mut self.entries[after..] {
+            *range = shift(range.start)..shift(range.end);
+        }
+
+        let replaced: Vec<(String, Range<usize>)> = spans
+            .entries
+            .into_iter()
+            .map(|(p, range)| {
+                let p = alloc::format!("{}{}", pointer, p);
+                (p, start + range.start..start + range.end)
+            })
+            .collect();
+        let added = replaced.len();
+        let removed: Vec<_> = self.entries.splice(i..after, replaced).collect();
+        for (p, _) in &removed {
+            self.index.remove(p);
+        }
+        if added != removed.len() {
+            for position in self.index.values_mut() {
+                if *position >= after {
+                    *position = *position + added - removed.len();
+                }
+            }
+        }
+        for (n, (p, _)) in self.entries[i..i + added].iter().enumerate() {
+            self.index.insert(p.clone(), i + n);
+        }
+    }
+
+    /// Thêm một nút bắt đầu tại `start`; phần cuối được điền sau.
+    fn push(&mut self, pointer: &str, start: usize) -> usize {
+        let i = self.entries.len();
+        self.entries.push((String::from(pointer), start..start));
+        self.index.insert(String::from(pointer), i);
+        i
+    }
+
+    /// Bỏ nút tại `pointer` cùng mọi nút con của nó.
+    fn remove_subtree(&mut self, pointer: &str) {
+        self.entries.retain(|(p, _)| !is_within(p, pointer));
+        self.reindex();
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, (p, _)) in self.entries.iter().enumerate() {
+            self.index.insert(p.clone(), i);
+        }
+    }
+}
+
+/// `pointer` có phải là `ancestor` hoặc trỏ vào bên trong nó không.
+fn is_within(pointer: &str, ancestor: &str) -> bool {
+    pointer.starts_with(ancestor)
+        && (pointer.len() == ancestor.len() || pointer.as_bytes()[ancestor.len()] == b'/')
+}
+
 /// Deserialize an instance of type `T` from a string of JSON text.
 ///
 /// # Example
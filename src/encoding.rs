@@ -0,0 +1,144 @@
+//! Mã hóa chuỗi byte thành chuỗi JSON dạng base64 hoặc hex và ngược lại.
+
+use alloc::vec::Vec;
+
+/// Cách biểu diễn chuỗi byte (`serialize_bytes`) trong JSON.
+///
+/// Mặc định là [`Array`](BytesEncoding::Array): mỗi byte là một số trong một
+/// mảng JSON, ví dụ `[104,105]`. Hai lựa chọn còn lại ghi chuỗi byte thành một
+/// chuỗi JSON ngắn hơn nhiều.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BytesEncoding {
+    /// Mảng các số, ví dụ `[104,105]`.
+    Array,
+    /// Chuỗi base64 chuẩn (RFC 4648) có padding, ví dụ `"aGk="`.
+    Base64,
+    /// Chuỗi hex chữ thường, ví dụ `"6869"`.
+    Hex,
+}
+
+impl Default for BytesEncoding {
+    fn default() -> Self {
+        BytesEncoding::Array
+    }
+}
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+pub(crate) fn encode_base64(bytes: &[u8], out: &mut Vec<u8>) {
+    out.reserve((bytes.len() + 2) / 3 * 4);
+    let mut chunks = bytes.chunks_exact(3);
+    for chunk in &mut chunks {
+        let n = (chunk[0] as u32) << 16 | (chunk[1] as u32) << 8 | chunk[2] as u32;
+        out.push(BASE64_CHARS[(n >> 18) as usize & 63]);
+        out.push(BASE64_CHARS[(n >> 12) as usize & 63]);
+        out.push(BASE64_CHARS[(n >> 6) as usize & 63]);
+        out.push(BASE64_CHARS[n as usize & 63]);
+    }
+    match *chunks.remainder() {
+        [a] => {
+            let n = (a as u32) << 16;
+            out.push(BASE64_CHARS[(n >> 18) as usize & 63]);
+            out.push(BASE64_CHARS[(n >> 12) as usize & 63]);
+            out.extend_from_slice(b"==");
+        }
+        [a, b] => {
+            let n = (a as u32) << 16 | (b as u32) << 8;
+            out.push(BASE64_CHARS[(n >> 18) as usize & 63]);
+            out.push(BASE64_CHARS[(n >> 12) as usize & 63]);
+            out.push(BASE64_CHARS[(n >> 6) as usize & 63]);
+            out.push(b'=');
+        }
+        _ => {}
+    }
+}
+
+fn decode_base64_char(c: u8) -> Option<u32> {
+    let n = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+    Some(n as u32)
+}
+
+/// Giải mã base64 chuẩn. Padding ở cuối là không bắt buộc.
+pub(crate) fn decode_base64(s: &[u8]) -> Option<Vec<u8>> {
+    let s = match s {
+        [rest @ .., b'=', b'='] if rest.len() % 4 == 2 => rest,
+        [rest @ .., b'='] if rest.len() % 4 == 3 => rest,
+        _ => s,
+    };
+    if s.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(s.len() / 4 * 3 + 2);
+    for chunk in s.chunks(4) {
+        let mut n = 0;
+        for (i, &c) in chunk.iter().enumerate() {
+            n |= decode_base64_char(c)? << (18 - 6 * i);
+        }
+        out.push((n >> 16) as u8);
+        if chunk.len() > 2 {
+            out.push((n >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            out.push(n as u8);
+        }
+    }
+    Some(out)
+}
+
+pub(crate) fn encode_hex(bytes: &[u8], out: &mut Vec<u8>) {
+    out.reserve(bytes.len() * 2);
+    for byte in bytes {
+        out.push(HEX_CHARS[(byte >> 4) as usize]);
+        out.push(HEX_CHARS[(byte & 0xF) as usize]);
+    }
+}
+
+/// Giải mã hex, chấp nhận cả chữ hoa và chữ thường.
+pub(crate) fn decode_hex(s: &[u8]) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(s.len() / 2);
+    for pair in s.chunks(2) {
+        let hi = (pair[0] as char).to_digit(16)?;
+        let lo = (pair[1] as char).to_digit(16)?;
+        out.push((hi << 4 | lo) as u8);
+    }
+    Some(out)
+}
+
+impl BytesEncoding {
+    /// Ghi `bytes` đã được mã hóa vào `out`. Không dùng cho `Array`.
+    pub(crate) fn encode(self, bytes: &[u8], out: &mut Vec<u8>) {
+        match self {
+            BytesEncoding::Array => unreachable!(),
+            BytesEncoding::Base64 => encode_base64(bytes, out),
+            BytesEncoding::Hex => encode_hex(bytes, out),
+        }
+    }
+
+    pub(crate) fn decode(self, s: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            BytesEncoding::Array => unreachable!(),
+            BytesEncoding::Base64 => decode_base64(s),
+            BytesEncoding::Hex => decode_hex(s),
+        }
+    }
+
+    pub(crate) fn expecting(self) -> &'static str {
+        match self {
+            BytesEncoding::Array => "an array of bytes",
+            BytesEncoding::Base64 => "a base64 string",
+            BytesEncoding::Hex => "a hex string",
+        }
+    }
+}
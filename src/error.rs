@@ -1,9 +1,12 @@
 //! Khi mà serializing hoặc deserializing JSON xuất hiện lỗi 
 
 use crate::io;
+use crate::read::Position;
 use alloc::boxed::Box;
 use alloc::string::{String, ToString};
 use core::fmt::{self, Debug, Display};
+use core::mem;
+use core::ops::Range;
 use core::result;
 use core::str::FromStr;
 use serde::{de, ser};
@@ -26,6 +29,17 @@ pub enum Category {
 
     /// Lỗi do kết thúc dữ liệu đầu vào sớm 
     Eof,
+
+    /// Dữ liệu vượt quá một giới hạn tài nguyên, ví dụ độ sâu lồng nhau
+    /// tối đa. Dữ liệu có thể hợp lệ nhưng quá lớn hoặc có dấu hiệu độc hại.
+    ///
+    /// Lỗi vượt độ sâu đệ quy trước đây thuộc [`Category::Syntax`]; nay nó
+    /// thuộc loại này nên `is_syntax()` trả về `false` cho lỗi đó.
+    Limit,
+
+    /// Dữ liệu dùng một cú pháp mở rộng (chú thích, chuỗi nháy đơn, số
+    /// `0x`, escape `\x`) mà tuỳ chọn tương ứng của deserializer đang tắt.
+    Unsupported,
 }
 /// type Error đại diện cho tất cả những lỗi có thể xảy ra khi 
 /// serializing hoặc deserializing Json data
@@ -55,15 +69,126 @@ impl Error {
         self.err.column
     }
 
+    /// Giống [`column`](Error::column) nhưng đếm theo ký tự Unicode thay vì
+    /// theo byte, như cách trình soạn thảo hiển thị cột. `input` phải là văn
+    /// bản đã được phân tích.
+    ///
+    /// Ký tự tab nhảy tới điểm dừng tab kế tiếp, cách nhau `tab_width` cột;
+    /// với `tab_width` là 1, tab được đếm như mọi ký tự khác. Trả về 0 nếu lỗi
+    /// không có vị trí.
+    ///
+    /// ```
+    /// let input = "{\"tên\":\t\t]";
+    /// let err = serde_json::from_str::<serde_json::Value>(input).unwrap_err();
+    /// assert_eq!(err.column(), 11);
+    /// assert_eq!(err.char_column(input, 1), 10);
+    /// assert_eq!(err.char_column(input, 4), 13);
+    /// ```
+    pub fn char_column(&self, input: &str, tab_width: usize) -> usize {
+        if self.err.line == 0 {
+            return 0;
+        }
+        let bytes = input.as_bytes();
+        let mut line_start = 0;
+        for _ in 1..self.err.line {
+            match bytes[line_start..].iter().position(|&b| b == b'\n') {
+                Some(i) => line_start += i + 1,
+                None => break,
+            }
+        }
+        let end = (line_start + self.err.column).min(bytes.len());
+
+        let tab_width = tab_width.max(1);
+        let mut column = 0;
+        for &b in &bytes[line_start..end] {
+            match b {
+                b'\t' => column = (column / tab_width + 1) * tab_width,
+                // Byte tiếp nối của một ký tự UTF-8 nhiều byte.
+                0x80..=0xBF => {}
+                _ => column += 1,
+            }
+        }
+        column
+    }
+
+    /// Khoảng byte trong dữ liệu đầu vào chứa ký tự gây ra lỗi.
+    ///
+    /// Chỉ có với lỗi được phát hiện trong lúc phân tích cú pháp; trả về
+    /// `None` với lỗi IO, lỗi khi serialize và lỗi tạo bởi `Error::custom`.
+    /// Dùng để hiển thị lỗi bằng các thư viện như codespan hoặc ariadne mà
+    /// không phải tính lại vị trí từ dòng và cột.
+    ///
+    /// Khi đầu vào là `&str` hoặc `&[u8]`, khoảng bao trọn ký tự UTF-8 gây
+    /// lỗi nên có thể dùng để cắt chuỗi đầu vào. Với lỗi
+    /// [`Category::Eof`] khoảng là rỗng và nằm ở cuối đầu vào.
+    ///
+    /// ```
+    /// let err = serde_json::from_str::<Vec<u32>>("[1, x]").unwrap_err();
+    /// assert_eq!(err.span(), Some(4..5));
+    ///
+    /// let err = serde_json::from_str::<Vec<u32>>("[1, é]").unwrap_err();
+    /// assert_eq!(err.span(), Some(4..6));
+    ///
+    /// let err = serde_json::from_str::<Vec<u32>>("[1, 2").unwrap_err();
+    /// assert_eq!(err.span(), Some(5..5));
+    /// ```
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.err.span.clone()
+    }
+
+    /// Tên của tài liệu chứa lỗi, nếu được đặt bằng
+    /// [`Deserializer::set_document`](crate::Deserializer::set_document).
+    pub fn document(&self) -> Option<&str> {
+        self.err.document.as_deref()
+    }
+
+    /// JSON Pointer tới giá trị gây ra lỗi, với lỗi xảy ra khi giải mã từ một
+    /// [`Value`](crate::Value) bằng [`from_value`](crate::from_value) hoặc
+    /// `T::deserialize(&value)`.
+    ///
+    /// Trả về `None` nếu lỗi nằm ở chính giá trị gốc hoặc không đến từ
+    /// `Value`.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Item {
+    ///     price: f64,
+    /// }
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Order {
+    ///     items: Vec<Item>,
+    /// }
+    ///
+    /// let value = json!({"items": [{"price": 1.5}, {"price": "free"}]});
+    /// let err = serde_json::from_value::<Order>(value).unwrap_err();
+    /// assert_eq!(err.path(), Some("/items/1/price"));
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid type: string \"free\", expected f64 at /items/1/price",
+    /// );
+    /// ```
+    pub fn path(&self) -> Option<&str> {
+        self.err.path.as_deref()
+    }
+
     /// Categorizes the cause of this error.
     ///
     /// Cate::IO - lỗi truy nhập
     /// Cate::Syntax -  Lỗi cú pháp
     /// Cate::Data - lỗi dữ liệu đưa vào
     /// Cate::EOF - kết thúc bất ngờ của dữ liệu đưa vào
+    /// Cate::Limit - vượt quá giới hạn tài nguyên
+    /// Cate::Unsupported - cú pháp mở rộng chưa được bật
     pub fn classify(&self) -> Category {
         match self.err.code {
-            ErrorCode::Message(_) => Category::Data,
+            ErrorCode::Message(_)
+            | ErrorCode::MissingField(_)
+            | ErrorCode::FloatMustBeFinite
+            | ErrorCode::ExpectedObject => Category::Data,
             ErrorCode::Io(_) => Category::Io,
             ErrorCode::EofWhileParsingList
             | ErrorCode::EofWhileParsingObject
@@ -83,8 +208,15 @@ impl Error {
             | ErrorCode::LoneLeadingSurrogateInHexEscape
             | ErrorCode::TrailingComma
             | ErrorCode::TrailingCharacters
-            | ErrorCode::UnexpectedEndOfHexEscape
-            | ErrorCode::RecursionLimitExceeded => Category::Syntax,
+            | ErrorCode::UnexpectedEndOfHexEscape => Category::Syntax,
+            ErrorCode::RecursionLimitExceeded
+            | ErrorCode::StringTooLong
+            | ErrorCode::KeyTooLong
+            | ErrorCode::ScratchTooSmall => Category::Limit,
+            ErrorCode::CommentsNotEnabled
+            | ErrorCode::SingleQuotesNotEnabled
+            | ErrorCode::RadixLiteralsNotEnabled
+            | ErrorCode::EscapesNotEnabled => Category::Unsupported,
         }
     }
 
@@ -94,6 +226,9 @@ impl Error {
     }
 
     /// check lỗi có phải syntax
+    ///
+    /// Lỗi vượt độ sâu đệ quy không còn là lỗi cú pháp mà thuộc
+    /// [`Category::Limit`]; dùng [`Error::is_limit`] để nhận ra nó.
     pub fn is_syntax(&self) -> bool {
         self.classify() == Category::Syntax
     }
@@ -107,6 +242,16 @@ impl Error {
     pub fn is_eof(&self) -> bool {
         self.classify() == Category::Eof
     }
+
+    /// dữ liệu vượt quá giới hạn tài nguyên, ví dụ lồng nhau quá sâu
+    pub fn is_limit(&self) -> bool {
+        self.classify() == Category::Limit
+    }
+
+    /// dữ liệu dùng một cú pháp mở rộng chưa được bật
+    pub fn is_unsupported(&self) -> bool {
+        self.classify() == Category::Unsupported
+    }
 }
 
 
@@ -116,8 +261,9 @@ impl Error {
 impl From<Error> for io::Error {
     /// Ví dụ cách chuyển đổi một serde_json::Error thành một io::Error.
     ///
-    /// Lỗi cú pháp và dữ liệu JSON được chuyển thành lỗi IO InvalidData.
+    /// Lỗi cú pháp, dữ liệu và giới hạn JSON được chuyển thành lỗi IO InvalidData.
     /// Lỗi EOF được chuyển thành lỗi IO UnexpectedEof.
+    /// Lỗi cú pháp mở rộng chưa bật được chuyển thành lỗi IO Unsupported.
     ///
     /// ```
     /// use std::io;
@@ -134,7 +280,11 @@ impl From<Error> for io::Error {
     ///             Category::Io => {
     ///                 MyError::Io(err.into())
     ///             }
-    ///             Category::Syntax | Category::Data | Category::Eof => {
+    ///             Category::Syntax
+    ///             | Category::Data
+    ///             | Category::Eof
+    ///             | Category::Limit
+    ///             | Category::Unsupported => {
     ///                 MyError::Json(err)
     ///             }
     ///         }
@@ -147,8 +297,11 @@ impl From<Error> for io::Error {
         } else {
             match j.classify() {
                 Category::Io => unreachable!(),
-                Category::Syntax | Category::Data => io::Error::new(io::ErrorKind::InvalidData, j),
+                Category::Syntax | Category::Data | Category::Limit => {
+                    io::Error::new(io::ErrorKind::InvalidData, j)
+                }
                 Category::Eof => io::Error::new(io::ErrorKind::UnexpectedEof, j),
+                Category::Unsupported => io::Error::new(io::ErrorKind::Unsupported, j),
             }
         }
     }
@@ -158,6 +311,11 @@ struct ErrorImpl {
     code: ErrorCode,
     line: usize,
     column: usize,
+    // Khoảng byte của ký tự gây lỗi, nếu biết.
+    span: Option<Range<usize>>,
+    document: Option<Box<str>>,
+    // JSON Pointer tới giá trị gây lỗi khi giải mã từ một `Value`.
+    path: Option<Box<str>>,
 }
 
 pub(crate) enum ErrorCode {
@@ -226,16 +384,78 @@ pub(crate) enum ErrorCode {
 
     /// Lồng ghép các mảng, và json quá 128 lớp 
     RecursionLimitExceeded,
+
+    /// Chuỗi dài hơn giới hạn đặt bằng `Serializer::set_max_string_len` hoặc
+    /// `Deserializer::set_max_string_len`.
+    StringTooLong,
+
+    /// Khóa dài hơn giới hạn đặt bằng `Serializer::set_max_key_len`.
+    KeyTooLong,
+
+    /// Bộ đệm truyền cho `from_slice_in` không đủ chỗ cho một chuỗi cần giải mã.
+    ScratchTooSmall,
+
+    /// Gặp chú thích khi `Deserializer::set_comments` đang tắt.
+    CommentsNotEnabled,
+
+    /// Gặp chuỗi nháy đơn khi `Deserializer::set_single_quotes` đang tắt.
+    SingleQuotesNotEnabled,
+
+    /// Gặp số `0x`, `0o` hoặc `0b` khi `Deserializer::set_radix_literals`
+    /// đang tắt.
+    RadixLiteralsNotEnabled,
+
+    /// Gặp escape `\x` khi `Deserializer::set_lenient_escapes` đang tắt.
+    EscapesNotEnabled,
+
+    /// Gặp NaN hoặc vô cực khi chính sách là `NonFinitePolicy::Error`.
+    FloatMustBeFinite,
+
+    /// Struct thiếu một trường bắt buộc.
+    MissingField(&'static str),
+
+    /// Giá trị ghi vào một `Map` có sẵn không phải map hay struct.
+    ExpectedObject,
 }
 
 impl Error {
     #[cold]
     pub(crate) fn syntax(code: ErrorCode, line: usize, column: usize) -> Self {
         Error {
-            err: Box::new(ErrorImpl { code, line, column }),
+            err: Box::new(ErrorImpl {
+                code,
+                line,
+                column,
+                span: None,
+                document: None,
+                path: None,
+            }),
         }
     }
 
+    /// Lỗi cú pháp tại `position`. `input` là toàn bộ đầu vào nếu nó nằm sẵn
+    /// trong bộ nhớ, dùng để mở rộng khoảng lỗi ra cả ký tự UTF-8.
+    #[cold]
+    pub(crate) fn syntax_at(code: ErrorCode, position: Position, input: Option<&[u8]>) -> Self {
+        let mut err = Error {
+            err: Box::new(ErrorImpl {
+                code,
+                line: position.line,
+                column: position.column,
+                span: None,
+                document: None,
+                path: None,
+            }),
+        };
+        let end = position.byte_offset;
+        err.err.span = Some(if err.classify() == Category::Eof {
+            end..end
+        } else {
+            char_span(input.unwrap_or_default(), end)
+        });
+        err
+    }
+
     // Not public API. Should be pub(crate).
     //
     // Update `eager_json` crate when this function changes.
@@ -247,10 +467,63 @@ impl Error {
                 code: ErrorCode::Io(error),
                 line: 0,
                 column: 0,
+                span: None,
+                document: None,
+                path: None,
             }),
         }
     }
 
+    #[cold]
+    pub(crate) fn in_document(mut self, document: Option<&str>) -> Self {
+        if let (None, Some(name)) = (&self.err.document, document) {
+            self.err.document = Some(Box::from(name));
+        }
+        self
+    }
+
+    /// Thêm `token` vào đầu đường dẫn của lỗi khi lỗi đi ngược lên qua một
+    /// mảng hoặc object trong lúc giải mã từ `Value`.
+    #[cold]
+    pub(crate) fn prepend_path(mut self, token: &str) -> Self {
+        let token = token.replace('~', "~0").replace('/', "~1");
+        let rest = self.err.path.as_deref().unwrap_or("");
+        self.err.path = Some(alloc::format!("/{}{}", token, rest).into_boxed_str());
+        self
+    }
+
+    /// Thay đường dẫn của lỗi bằng `path`; chuỗi rỗng xóa đường dẫn.
+    #[cold]
+    pub(crate) fn with_path(mut self, path: &str) -> Self {
+        self.err.path = if path.is_empty() {
+            None
+        } else {
+            Some(Box::from(path))
+        };
+        self
+    }
+
+    /// Trường bắt buộc bị thiếu, nếu đây là lỗi thiếu trường.
+    pub(crate) fn missing_field(&self) -> Option<&'static str> {
+        match self.err.code {
+            ErrorCode::MissingField(field) => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Dời vị trí của lỗi khi đầu vào được giải mã là một phần của một văn
+    /// bản lớn hơn, bắt đầu sau `lines` dòng và `offset` byte.
+    #[cold]
+    pub(crate) fn shift_position(mut self, lines: usize, offset: usize) -> Self {
+        if self.err.line != 0 {
+            self.err.line += lines;
+        }
+        if let Some(span) = &mut self.err.span {
+            *span = span.start + offset..span.end + offset;
+        }
+        self
+    }
+
     #[cold]
     pub(crate) fn fix_position<F>(self, f: F) -> Self
     where
@@ -264,6 +537,81 @@ impl Error {
     }
 }
 
+impl Clone for ErrorCode {
+    fn clone(&self) -> Self {
+        match self {
+            ErrorCode::Message(msg) => ErrorCode::Message(msg.clone()),
+            // io::Error không clone được, chỉ giữ lại kind và thông điệp.
+            ErrorCode::Io(err) => ErrorCode::Io(clone_io_error(err)),
+            ErrorCode::EofWhileParsingList => ErrorCode::EofWhileParsingList,
+            ErrorCode::EofWhileParsingObject => ErrorCode::EofWhileParsingObject,
+            ErrorCode::EofWhileParsingString => ErrorCode::EofWhileParsingString,
+            ErrorCode::EofWhileParsingValue => ErrorCode::EofWhileParsingValue,
+            ErrorCode::ExpectedColon => ErrorCode::ExpectedColon,
+            ErrorCode::ExpectedListCommaOrEnd => ErrorCode::ExpectedListCommaOrEnd,
+            ErrorCode::ExpectedObjectCommaOrEnd => ErrorCode::ExpectedObjectCommaOrEnd,
+            ErrorCode::ExpectedSomeIdent => ErrorCode::ExpectedSomeIdent,
+            ErrorCode::ExpectedSomeValue => ErrorCode::ExpectedSomeValue,
+            ErrorCode::InvalidEscape => ErrorCode::InvalidEscape,
+            ErrorCode::InvalidNumber => ErrorCode::InvalidNumber,
+            ErrorCode::NumberOutOfRange => ErrorCode::NumberOutOfRange,
+            ErrorCode::InvalidUnicodeCodePoint => ErrorCode::InvalidUnicodeCodePoint,
+            ErrorCode::ControlCharacterWhileParsingString => {
+                ErrorCode::ControlCharacterWhileParsingString
+            }
+            ErrorCode::KeyMustBeAString => ErrorCode::KeyMustBeAString,
+            ErrorCode::LoneLeadingSurrogateInHexEscape => {
+                ErrorCode::LoneLeadingSurrogateInHexEscape
+            }
+            ErrorCode::TrailingComma => ErrorCode::TrailingComma,
+            ErrorCode::TrailingCharacters => ErrorCode::TrailingCharacters,
+            ErrorCode::UnexpectedEndOfHexEscape => ErrorCode::UnexpectedEndOfHexEscape,
+            ErrorCode::RecursionLimitExceeded => ErrorCode::RecursionLimitExceeded,
+            ErrorCode::StringTooLong => ErrorCode::StringTooLong,
+            ErrorCode::KeyTooLong => ErrorCode::KeyTooLong,
+            ErrorCode::ScratchTooSmall => ErrorCode::ScratchTooSmall,
+            ErrorCode::CommentsNotEnabled => ErrorCode::CommentsNotEnabled,
+            ErrorCode::SingleQuotesNotEnabled => ErrorCode::SingleQuotesNotEnabled,
+            ErrorCode::RadixLiteralsNotEnabled => ErrorCode::RadixLiteralsNotEnabled,
+            ErrorCode::EscapesNotEnabled => ErrorCode::EscapesNotEnabled,
+            ErrorCode::FloatMustBeFinite => ErrorCode::FloatMustBeFinite,
+            ErrorCode::MissingField(field) => ErrorCode::MissingField(field),
+            ErrorCode::ExpectedObject => ErrorCode::ExpectedObject,
+        }
+    }
+}
+
+impl PartialEq for ErrorCode {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ErrorCode::Message(a), ErrorCode::Message(b)) => a == b,
+            (ErrorCode::Io(a), ErrorCode::Io(b)) => io_error_eq(a, b),
+            (ErrorCode::MissingField(a), ErrorCode::MissingField(b)) => a == b,
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn clone_io_error(err: &io::Error) -> io::Error {
+    io::Error::new(err.kind(), err.to_string())
+}
+
+#[cfg(not(feature = "std"))]
+fn clone_io_error(err: &io::Error) -> io::Error {
+    err.clone()
+}
+
+#[cfg(feature = "std")]
+fn io_error_eq(a: &io::Error, b: &io::Error) -> bool {
+    a.kind() == b.kind() && a.to_string() == b.to_string()
+}
+
+#[cfg(not(feature = "std"))]
+fn io_error_eq(a: &io::Error, b: &io::Error) -> bool {
+    a == b
+}
+
 impl Display for ErrorCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -293,6 +641,18 @@ impl Display for ErrorCode {
             ErrorCode::TrailingCharacters => f.write_str("trailing characters"),
             ErrorCode::UnexpectedEndOfHexEscape => f.write_str("unexpected end of hex escape"),
             ErrorCode::RecursionLimitExceeded => f.write_str("recursion limit exceeded"),
+            ErrorCode::StringTooLong => f.write_str("string length limit exceeded"),
+            ErrorCode::KeyTooLong => f.write_str("key length limit exceeded"),
+            ErrorCode::ScratchTooSmall => f.write_str("scratch buffer too small"),
+            ErrorCode::CommentsNotEnabled => f.write_str("comments are not enabled"),
+            ErrorCode::SingleQuotesNotEnabled => {
+                f.write_str("single-quoted strings are not enabled")
+            }
+            ErrorCode::RadixLiteralsNotEnabled => f.write_str("radix literals are not enabled"),
+            ErrorCode::EscapesNotEnabled => f.write_str("non-standard escapes are not enabled"),
+            ErrorCode::FloatMustBeFinite => f.write_str("float must be finite"),
+            ErrorCode::MissingField(field) => write!(f, "missing field `{}`", field),
+            ErrorCode::ExpectedObject => f.write_str("value must serialize to a map or struct"),
         }
     }
 }
@@ -315,8 +675,14 @@ impl Display for Error {
 
 impl Display for ErrorImpl {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(document) = &self.document {
+            tri!(write!(f, "{}: ", document));
+        }
         if self.line == 0 {
-            Display::fmt(&self.code, f)
+            match &self.path {
+                Some(path) => write!(f, "{} at {}", self.code, path),
+                None => Display::fmt(&self.code, f),
+            }
         } else {
             write!(
                 f,
@@ -327,17 +693,54 @@ impl Display for ErrorImpl {
     }
 }
 
+/// Bản sao của một lỗi IO chỉ giữ lại `ErrorKind` và thông điệp của lỗi gốc,
+/// vì `io::Error` không thực hiện `Clone`.
+impl Clone for Error {
+    fn clone(&self) -> Self {
+        Error {
+            err: Box::new(ErrorImpl {
+                code: self.err.code.clone(),
+                line: self.err.line,
+                column: self.err.column,
+                span: self.err.span.clone(),
+                document: self.err.document.clone(),
+                path: self.err.path.clone(),
+            }),
+        }
+    }
+}
+
+/// Hai lỗi bằng nhau khi có cùng loại lỗi, thông điệp và vị trí.
+/// Lỗi IO được so sánh theo `ErrorKind` và thông điệp.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.err.code == other.err.code
+            && self.err.line == other.err.line
+            && self.err.column == other.err.column
+            && self.err.span == other.err.span
+            && self.err.document == other.err.document
+            && self.err.path == other.err.path
+    }
+}
+
 // Xóa đi 2 lớp bọc bên ngoài biểu diễn gỡ lỗi
 // Đây là biểu diễn cho người dùng xem, là kết quả của unwrap 
 impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
+        tri!(write!(
             f,
-            "Error({:?}, line: {}, column: {})",
+            "Error({:?}, line: {}, column: {}",
             self.err.code.to_string(),
             self.err.line,
             self.err.column
-        )
+        ));
+        if let Some(document) = &self.err.document {
+            tri!(write!(f, ", document: {:?}", document));
+        }
+        if let Some(path) = &self.err.path {
+            tri!(write!(f, ", path: {:?}", path));
+        }
+        f.write_str(")")
     }
 }
 
@@ -355,6 +758,11 @@ impl de::Error for Error {
             Error::custom(format_args!("invalid type: {}, expected {}", unexp, exp))
         }
     }
+
+    #[cold]
+    fn missing_field(field: &'static str) -> Self {
+        Error::syntax(ErrorCode::MissingField(field), 0, 0)
+    }
 }
 
 impl ser::Error for Error {
@@ -364,6 +772,32 @@ impl ser::Error for Error {
     }
 }
 
+#[cfg(feature = "diagnostic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostic")))]
+impl miette::Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self.classify() {
+            Category::Io => "serde_json::io",
+            Category::Syntax => "serde_json::syntax",
+            Category::Data => "serde_json::data",
+            Category::Eof => "serde_json::eof",
+            Category::Limit => "serde_json::limit",
+            Category::Unsupported => "serde_json::unsupported",
+        };
+        Some(Box::new(code))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let span = self.span()?;
+        let text = match &self.err.document {
+            Some(document) => format!("{}: {}", document, self.err.code),
+            None => self.err.code.to_string(),
+        };
+        let label = miette::LabeledSpan::new(Some(text), span.start, span.end - span.start);
+        Some(Box::new(core::iter::once(label)))
+    }
+}
+
 //Phân tích thông điệp lỗi của chúng ta dạng "{} tại dòng {} cột {}" 
 //để giải quyết việc erased-serde chuyển lại lỗi qua de::Error::custom.
 fn make_error(mut msg: String) -> Error {
@@ -373,10 +807,28 @@ fn make_error(mut msg: String) -> Error {
             code: ErrorCode::Message(msg.into_boxed_str()),
             line,
             column,
+            span: None,
+            document: None,
+            path: None,
         }),
     }
 }
 
+/// Khoảng byte của ký tự chứa byte ngay trước `end`. Khi không có `input`,
+/// khoảng chỉ gồm đúng byte đó.
+fn char_span(input: &[u8], end: usize) -> Range<usize> {
+    let is_continuation = |i: usize| matches!(input.get(i), Some(0x80..=0xBF));
+    let mut start = end.saturating_sub(1);
+    while start > 0 && is_continuation(start) {
+        start -= 1;
+    }
+    let mut end = end;
+    while is_continuation(end) {
+        end += 1;
+    }
+    start..end
+}
+
 fn parse_line_col(msg: &mut String) -> Option<(usize, usize)> {
     let start_of_suffix = match msg.rfind(" at line ") {
         Some(index) => index,
@@ -0,0 +1,600 @@
+//! Các adapter dùng với `#[serde(with = "...")]` cho những cách mã hóa trường
+//! hay gặp trong JSON.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Event {
+//!     #[serde(with = "serde_json::helpers::u64_as_string")]
+//!     id: u64,
+//!     #[serde(with = "serde_json::helpers::base64")]
+//!     payload: Vec<u8>,
+//!     #[serde(with = "serde_json::helpers::empty_string_as_none")]
+//!     note: Option<String>,
+//! }
+//!
+//! let event = Event {
+//!     id: u64::MAX,
+//!     payload: b"hi".to_vec(),
+//!     note: None,
+//! };
+//! let json = serde_json::to_string(&event).unwrap();
+//! assert_eq!(json, r#"{"id":"18446744073709551615","payload":"aGk=","note":""}"#);
+//! assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+//! ```
+
+use crate::encoding;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::{self, FromStr};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Ghi một số dưới dạng chuỗi, và đọc được cả chuỗi lẫn số.
+struct NumberOrString<T>(PhantomData<T>);
+
+impl<'de> Visitor<'de> for NumberOrString<u64> {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an unsigned integer or a string containing one")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<u64, E>
+    where
+        E: de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<u64, E>
+    where
+        E: de::Error,
+    {
+        u64::try_from(v).map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<u64, E>
+    where
+        E: de::Error,
+    {
+        u64::from_str(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+/// Một số `u64` được ghi dưới dạng chuỗi, ví dụ `"18446744073709551615"`.
+///
+/// JavaScript chỉ biểu diễn chính xác số nguyên tới 2^53, nên ID 64-bit
+/// thường được truyền dưới dạng chuỗi. Khi đọc, cả chuỗi và số đều được chấp
+/// nhận.
+pub mod u64_as_string {
+    use super::*;
+
+    /// Serialize một `u64` thành chuỗi.
+    pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserialize một `u64` từ chuỗi hoặc số.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NumberOrString::<u64>(PhantomData))
+    }
+}
+
+impl<'de> Visitor<'de> for NumberOrString<f64> {
+    type Value = f64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or a string containing one")
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<f64, E>
+    where
+        E: de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<f64, E>
+    where
+        E: de::Error,
+    {
+        Ok(v as f64)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<f64, E>
+    where
+        E: de::Error,
+    {
+        Ok(v as f64)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<f64, E>
+    where
+        E: de::Error,
+    {
+        f64::from_str(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+/// Một số `f64` được ghi dưới dạng chuỗi, ví dụ `"0.1"`.
+///
+/// Cách ghi này cũng biểu diễn được `NaN` và vô cực (`"NaN"`, `"inf"`,
+/// `"-inf"`), vốn không có trong JSON. Khi đọc, cả chuỗi và số đều được chấp
+/// nhận.
+pub mod f64_as_string {
+    use super::*;
+
+    /// Serialize một `f64` thành chuỗi.
+    pub fn serialize<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if value.is_finite() {
+            let mut buffer = ryu::Buffer::new();
+            serializer.serialize_str(buffer.format_finite(*value))
+        } else {
+            serializer.collect_str(value)
+        }
+    }
+
+    /// Deserialize một `f64` từ chuỗi hoặc số.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NumberOrString::<f64>(PhantomData))
+    }
+}
+
+/// Một chuỗi byte được ghi dưới dạng chuỗi base64 chuẩn có padding.
+///
+/// Dùng được với mọi kiểu `AsRef<[u8]>` khi serialize và mọi kiểu
+/// `From<Vec<u8>>` khi deserialize. Khi đọc, padding là không bắt buộc.
+pub mod base64 {
+    use super::*;
+
+    /// Serialize chuỗi byte thành chuỗi base64.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + AsRef<[u8]>,
+        S: Serializer,
+    {
+        let mut encoded = Vec::new();
+        encoding::encode_base64(value.as_ref(), &mut encoded);
+        // Ký tự base64 luôn là ASCII.
+        serializer.serialize_str(unsafe { str::from_utf8_unchecked(&encoded) })
+    }
+
+    /// Deserialize chuỗi byte từ chuỗi base64.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        struct Base64Visitor;
+
+        impl<'de> Visitor<'de> for Base64Visitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a base64 string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+            where
+                E: de::Error,
+            {
+                encoding::decode_base64(v.as_bytes())
+                    .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(Base64Visitor).map(T::from)
+    }
+}
+
+/// Một `SystemTime` được ghi dưới dạng số mili giây kể từ Unix epoch.
+///
+/// Thời điểm trước epoch được ghi thành số âm.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod timestamp_millis {
+    use super::*;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// Serialize một `SystemTime` thành số mili giây kể từ Unix epoch.
+    pub fn serialize<S>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let millis = match value.duration_since(UNIX_EPOCH) {
+            Ok(after) => i64::try_from(after.as_millis()),
+            Err(before) => i64::try_from(before.duration().as_millis()).map(|millis| -millis),
+        };
+        match millis {
+            Ok(millis) => serializer.serialize_i64(millis),
+            Err(_) => Err(serde::ser::Error::custom("timestamp out of range")),
+        }
+    }
+
+    /// Deserialize một `SystemTime` từ số mili giây kể từ Unix epoch.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SystemTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let millis: i64 = tri!(de::Deserialize::deserialize(deserializer));
+        let offset = Duration::from_millis(millis.unsigned_abs());
+        let time = if millis >= 0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        };
+        time.ok_or_else(|| de::Error::custom("timestamp out of range"))
+    }
+}
+
+/// Một `chrono::DateTime<Utc>` được ghi dưới dạng chuỗi RFC 3339, ví dụ
+/// `"2023-04-05T06:07:08Z"`.
+///
+/// Khi đọc, chấp nhận mọi múi giờ và chuyển về UTC.
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+pub mod rfc3339 {
+    use super::*;
+    use chrono::{DateTime, SecondsFormat, Utc};
+
+    /// Serialize một `DateTime<Utc>` thành chuỗi RFC 3339.
+    pub fn serialize<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+
+    /// Deserialize một `DateTime<Utc>` từ chuỗi RFC 3339.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Rfc3339Visitor;
+
+        impl<'de> Visitor<'de> for Rfc3339Visitor {
+            type Value = DateTime<Utc>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an RFC 3339 timestamp")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<DateTime<Utc>, E>
+            where
+                E: de::Error,
+            {
+                match DateTime::parse_from_rfc3339(v) {
+                    Ok(datetime) => Ok(datetime.with_timezone(&Utc)),
+                    Err(_) => Err(E::invalid_value(Unexpected::Str(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(Rfc3339Visitor)
+    }
+}
+
+/// Một `Option<String>` trong đó `None` được ghi thành chuỗi rỗng.
+///
+/// Khi đọc, cả chuỗi rỗng và `null` đều cho ra `None`.
+pub mod empty_string_as_none {
+    use super::*;
+
+    /// Serialize `None` thành `""` và `Some(s)` thành `s`.
+    pub fn serialize<S>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(value.as_deref().unwrap_or(""))
+    }
+
+    /// Deserialize `""` hoặc `null` thành `None`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value: Option<String> = tri!(de::Deserialize::deserialize(deserializer));
+        Ok(value.filter(|s| !s.is_empty()))
+    }
+}
+
+/// Một map có khóa `Cow<'de, str>`, ví dụ `BTreeMap<Cow<'de, str>, T>`, trong
+/// đó khóa được mượn thẳng từ input thay vì cấp phát một `String` cho mỗi mục.
+///
+/// Với `from_str` và `from_slice`, khóa `&'de str` vốn đã được mượn, nhưng
+/// deserialize sẽ lỗi nếu một khóa có escape như `"a\nb"`. Còn `Cow<str>` thì
+/// serde luôn cấp phát. Adapter này mượn mọi khóa không có escape và chỉ cấp
+/// phát cho những khóa có escape. Khi đọc từ `io::Read`, mọi khóa đều được
+/// cấp phát.
+///
+/// ```
+/// use serde::Deserialize;
+/// use std::borrow::Cow;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Deserialize)]
+/// struct Counts<'a> {
+///     #[serde(borrow, with = "serde_json::helpers::borrowed_keys")]
+///     by_name: BTreeMap<Cow<'a, str>, u32>,
+/// }
+///
+/// let input = r#"{"by_name": {"alice": 1, "b\u00f6b": 2}}"#;
+/// let counts: Counts = serde_json::from_str(input).unwrap();
+/// assert!(matches!(counts.by_name.keys().next(), Some(Cow::Borrowed("alice"))));
+/// assert_eq!(counts.by_name["böb"], 2);
+/// ```
+pub mod borrowed_keys {
+    use super::*;
+
+    /// Serialize map như bình thường.
+    pub fn serialize<M, S>(value: &M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        M: Serialize,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    /// Deserialize một map, mượn khóa từ input khi có thể.
+    pub fn deserialize<'de, D, M, V>(deserializer: D) -> Result<M, D::Error>
+    where
+        D: Deserializer<'de>,
+        M: Default + Extend<(Cow<'de, str>, V)>,
+        V: de::Deserialize<'de>,
+    {
+        struct MapVisitor<M, V>(PhantomData<(M, V)>);
+
+        impl<'de, M, V> Visitor<'de> for MapVisitor<M, V>
+        where
+            M: Default + Extend<(Cow<'de, str>, V)>,
+            V: de::Deserialize<'de>,
+        {
+            type Value = M;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<M, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut map = M::default();
+                while let Some(key) = tri!(access.next_key_seed(CowKey)) {
+                    let value = tri!(access.next_value());
+                    map.extend(Some((key, value)));
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+
+    struct CowKey;
+
+    impl<'de> DeserializeSeed<'de> for CowKey {
+        type Value = Cow<'de, str>;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Cow<'de, str>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for CowKey {
+        type Value = Cow<'de, str>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string key")
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Cow<'de, str>, E>
+        where
+            E: de::Error,
+        {
+            Ok(Cow::Borrowed(v))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Cow<'de, str>, E>
+        where
+            E: de::Error,
+        {
+            Ok(Cow::Owned(String::from(v)))
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Cow<'de, str>, E>
+        where
+            E: de::Error,
+        {
+            Ok(Cow::Owned(v))
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+/// Một trường có thể vắng mặt, là `null`, hoặc có giá trị — ba trạng thái mà
+/// request PATCH cần phân biệt ("không đổi", "xóa đi", "đặt giá trị mới").
+///
+/// Dùng cùng `#[serde(default)]` để một trường vắng mặt trở thành
+/// [`Missing`](OptionalField::Missing), và
+/// `skip_serializing_if = "OptionalField::is_missing"` để không ghi ra trường
+/// vắng mặt. Dễ đọc hơn `Option<Option<T>>` và không cần adapter riêng.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use serde_json::helpers::OptionalField;
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct UserPatch {
+///     #[serde(default, skip_serializing_if = "OptionalField::is_missing")]
+///     name: OptionalField<String>,
+///     #[serde(default, skip_serializing_if = "OptionalField::is_missing")]
+///     email: OptionalField<String>,
+///     #[serde(default, skip_serializing_if = "OptionalField::is_missing")]
+///     age: OptionalField<u8>,
+/// }
+///
+/// let patch: UserPatch = serde_json::from_str(r#"{"name": "ann", "email": null}"#).unwrap();
+/// assert_eq!(patch.name, OptionalField::Value("ann".to_owned()));
+/// assert_eq!(patch.email, OptionalField::Null);
+/// assert_eq!(patch.age, OptionalField::Missing);
+///
+/// let mut email = Some("old@example.com".to_owned());
+/// patch.email.clone().apply_to(&mut email);
+/// assert_eq!(email, None);
+///
+/// let json = serde_json::to_string(&patch).unwrap();
+/// assert_eq!(json, r#"{"name":"ann","email":null}"#);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OptionalField<T> {
+    /// Trường không có trong input.
+    Missing,
+    /// Trường có mặt với giá trị `null`.
+    Null,
+    /// Trường có mặt với một giá trị.
+    Value(T),
+}
+
+impl<T> OptionalField<T> {
+    /// Trả về true nếu trường vắng mặt.
+    pub fn is_missing(&self) -> bool {
+        matches!(self, OptionalField::Missing)
+    }
+
+    /// Trả về true nếu trường có mặt với giá trị `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, OptionalField::Null)
+    }
+
+    /// Trả về true nếu trường có mặt, kể cả khi là `null`.
+    pub fn is_present(&self) -> bool {
+        !self.is_missing()
+    }
+
+    /// Trả về giá trị nếu có.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            OptionalField::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Chuyển `&OptionalField<T>` thành `OptionalField<&T>`.
+    pub fn as_ref(&self) -> OptionalField<&T> {
+        match self {
+            OptionalField::Missing => OptionalField::Missing,
+            OptionalField::Null => OptionalField::Null,
+            OptionalField::Value(value) => OptionalField::Value(value),
+        }
+    }
+
+    /// Biến đổi giá trị bằng `f` nếu có, giữ nguyên `Missing` và `Null`.
+    pub fn map<U, F>(self, f: F) -> OptionalField<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            OptionalField::Missing => OptionalField::Missing,
+            OptionalField::Null => OptionalField::Null,
+            OptionalField::Value(value) => OptionalField::Value(f(value)),
+        }
+    }
+
+    /// Áp dụng thay đổi vào `target` theo kiểu PATCH: `Missing` giữ nguyên,
+    /// `Null` đặt thành `None`, `Value(v)` đặt thành `Some(v)`.
+    pub fn apply_to(self, target: &mut Option<T>) {
+        match self {
+            OptionalField::Missing => {}
+            OptionalField::Null => *target = None,
+            OptionalField::Value(value) => *target = Some(value),
+        }
+    }
+
+    /// Chuyển thành `Option<Option<T>>`: `None` là vắng mặt, `Some(None)` là
+    /// `null`.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            OptionalField::Missing => None,
+            OptionalField::Null => Some(None),
+            OptionalField::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T> Default for OptionalField<T> {
+    fn default() -> Self {
+        OptionalField::Missing
+    }
+}
+
+impl<T> From<Option<Option<T>>> for OptionalField<T> {
+    fn from(value: Option<Option<T>>) -> Self {
+        match value {
+            None => OptionalField::Missing,
+            Some(None) => OptionalField::Null,
+            Some(Some(value)) => OptionalField::Value(value),
+        }
+    }
+}
+
+impl<T> From<OptionalField<T>> for Option<Option<T>> {
+    fn from(value: OptionalField<T>) -> Self {
+        value.into_option()
+    }
+}
+
+impl<T> Serialize for OptionalField<T>
+where
+    T: Serialize,
+{
+    /// `Missing` và `Null` đều được ghi thành `null`; bỏ qua trường vắng mặt
+    /// bằng `skip_serializing_if`.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            OptionalField::Missing | OptionalField::Null => serializer.serialize_none(),
+            OptionalField::Value(value) => serializer.serialize_some(value),
+        }
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for OptionalField<T>
+where
+    T: de::Deserialize<'de>,
+{
+    /// Chỉ được gọi khi trường có mặt, nên kết quả là `Null` hoặc `Value`.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value: Option<T> = tri!(de::Deserialize::deserialize(deserializer));
+        Ok(match value {
+            None => OptionalField::Null,
+            Some(value) => OptionalField::Value(value),
+        })
+    }
+}
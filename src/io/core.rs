@@ -5,39 +5,70 @@ use alloc::vec::Vec;
 use core::fmt::{self, Display};
 use core::result;
 
+/// Loại của một [`Error`], tương ứng với các biến thể cùng tên của
+/// `std::io::ErrorKind`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ErrorKind {
+    /// Bộ ghi không nhận thêm được byte nào, ví dụ bộ đệm cố định đã đầy.
+    WriteZero,
+    /// Mọi lỗi khác.
     Other,
 }
 
-// Các lỗi IO không bao giờ xảy ra trong chế độ no std
-pub struct Error;
+// Trong chế độ no std, lỗi IO chỉ đến từ các bộ ghi có thể thất bại
+// (như bộ đệm cố định bị tràn), nên chỉ cần giữ loại lỗi và một thông điệp tĩnh.
+///
+/// Bộ ghi tự viết tạo lỗi bằng [`Error::new`], giống như với `std::io::Error`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: &'static str,
+}
 
 impl Display for Error {
-    fn fmt(&self, _formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        unreachable!() // panic 
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.message)
     }
 }
 
 impl Error {
-    pub(crate) fn new(_kind: ErrorKind, _error: &'static str) -> Error {
-        Error
+    /// Tạo một lỗi với loại `kind` và thông điệp `message`.
+    pub fn new(kind: ErrorKind, message: &'static str) -> Error {
+        Error { kind, message }
+    }
+
+    /// Loại của lỗi.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
     }
 }
 
+/// Kết quả của các thao tác IO.
 pub type Result<T> = result::Result<T, Error>;
 
+/// Đích ghi byte, thay cho `std::io::Write` khi không có std.
 pub trait Write {
+    /// Ghi một phần của `buf`, trả về số byte đã ghi.
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
-    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
-        // Tất cả phần thực thi write trong chế độ no-std
-        // luôn viết toàn bộ vào bộ đệm
-        let result = self.write(buf);
-        debug_assert!(result.is_ok());
-        debug_assert_eq!(result.unwrap_or(0), buf.len());
+    /// Ghi toàn bộ `buf`, trả lỗi [`ErrorKind::WriteZero`] nếu bộ ghi không
+    /// nhận thêm byte nào.
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match tri!(self.write(buf)) {
+                0 => {
+                    return Err(Error::new(
+                        ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ));
+                }
+                n => buf = &buf[n..],
+            }
+        }
         Ok(())
     }
 
+    /// Đẩy mọi byte còn trong bộ đệm ra đích.
     fn flush(&mut self) -> Result<()>;
 }
 
@@ -1,5 +1,10 @@
 //! Một lib nhỏ gọn, thân thiện với no_std xung quanh std::io.
 //! Trường hợp có sử dụng std sẽ export std::io
+//!
+//! Khi không có std, đây là nơi lấy trait [`Write`] và kiểu [`Error`] để tự
+//! viết một bộ ghi, ví dụ một bộ đệm cố định có thể bị đầy. Với std, các
+//! tên này chính là của `std::io`, nên cùng một đoạn code chạy được ở cả
+//! hai chế độ.
 pub use self::imp::{Error, ErrorKind, Result, Write};
 
 #[cfg(not(feature = "std"))]
@@ -10,4 +15,4 @@ mod imp;
 use std::io as imp;
 
 #[cfg(feature = "std")]
-pub use std::io::{Bytes, Read};
+pub use std::io::{BufRead, Bytes, Read};
@@ -0,0 +1,1160 @@
+//! Bộ đánh giá cho một tập con thực dụng của [JMESPath] trên [`Value`].
+//!
+//! Hỗ trợ: định danh (kể cả định danh trong dấu nháy kép), chỉ số và slice
+//! (`[0]`, `[-1]`, `[::2]`), các phép chiếu (`[*]`, `*`, `[]`, `[?cond]`),
+//! so sánh (`==`, `!=`, `<`, `<=`, `>`, `>=`), `&&`, `||`, `!`, pipe `|`,
+//! multiselect (`[a, b]`, `{x: a, y: b}`), `@`, literal (`` `json` `` và
+//! `'raw'`) và các hàm:
+//!
+//! `abs`, `avg`, `contains`, `ends_with`, `join`, `keys`, `length`, `map`,
+//! `max`, `max_by`, `min`, `min_by`, `not_null`, `reverse`, `sort`,
+//! `sort_by`, `starts_with`, `sum`, `to_number`, `to_string`, `type`,
+//! `values`.
+//!
+//! ```
+//! use serde_json::json;
+//!
+//! let data = json!({
+//!     "reservations": [
+//!         {"instances": [{"id": "a", "state": "running"}, {"id": "b", "state": "stopped"}]},
+//!         {"instances": [{"id": "c", "state": "running"}]}
+//!     ]
+//! });
+//!
+//! let running = data
+//!     .search("reservations[].instances[] | [?state == 'running'].id")
+//!     .unwrap();
+//! assert_eq!(running, json!(["a", "c"]));
+//!
+//! let count = data.search("length(reservations[].instances[])").unwrap();
+//! assert_eq!(count, json!(3));
+//! ```
+//!
+//! [JMESPath]: https://jmespath.org/specification.html
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::map::Map;
+use crate::value::{Number, Value};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::str::FromStr;
+
+/// Một biểu thức JMESPath đã được phân tích, có thể dùng lại nhiều lần.
+///
+/// ```
+/// use serde_json::jmespath::Expression;
+/// use serde_json::json;
+///
+/// let expr = Expression::compile("people[?age > `30`].name | sort(@)").unwrap();
+///
+/// let data = json!({"people": [
+///     {"name": "Sam", "age": 45},
+///     {"name": "Alex", "age": 20},
+///     {"name": "Bo", "age": 31}
+/// ]});
+/// assert_eq!(expr.search(&data).unwrap(), json!(["Bo", "Sam"]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Expression {
+    ast: Ast,
+}
+
+impl Expression {
+    /// Phân tích biểu thức. Lỗi cú pháp cho biết cột (tính từ 1) nơi phát
+    /// hiện lỗi.
+    pub fn compile(expression: &str) -> Result<Expression> {
+        let tokens = tri!(lex(expression));
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            remaining_depth: MAX_DEPTH,
+        };
+        let ast = tri!(parser.expression(0));
+        match parser.peek() {
+            Token::Eof => Ok(Expression { ast }),
+            _ => Err(parser.unexpected()),
+        }
+    }
+
+    /// Đánh giá biểu thức trên `data`.
+    ///
+    /// Lỗi chỉ xảy ra khi gọi hàm với sai số lượng hoặc sai kiểu tham số.
+    pub fn search(&self, data: &Value) -> Result<Value> {
+        eval(&self.ast, data)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Expression> {
+        Expression::compile(s)
+    }
+}
+
+impl Value {
+    /// Phân tích rồi đánh giá một biểu thức [JMESPath](crate::jmespath) trên
+    /// giá trị này.
+    ///
+    /// ```
+    /// use serde_json::json;
+    ///
+    /// let data = json!({"a": {"b": [1, 2, 3]}});
+    /// assert_eq!(data.search("a.b[-1]").unwrap(), json!(3));
+    /// assert_eq!(data.search("a.missing").unwrap(), json!(null));
+    /// assert!(data.search("a.[").is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "jmespath")))]
+    pub fn search(&self, expression: &str) -> Result<Value> {
+        tri!(Expression::compile(expression)).search(self)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    QuotedIdentifier(String),
+    Number(i64),
+    Literal(Value),
+    Dot,
+    Star,
+    Flatten,
+    Filter,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    Comma,
+    Colon,
+    Pipe,
+    Or,
+    And,
+    Not,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Current,
+    Expref,
+    Eof,
+}
+
+#[cold]
+fn syntax_error(message: String, offset: usize) -> Error {
+    Error::syntax(ErrorCode::Message(message.into_boxed_str()), 1, offset + 1)
+}
+
+#[cold]
+fn runtime_error(message: String) -> Error {
+    Error::syntax(ErrorCode::Message(message.into_boxed_str()), 0, 0)
+}
+
+fn lex(input: &str) -> Result<Vec<(usize, Token)>> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let next = bytes.get(i + 1).copied();
+        let token = match bytes[i] {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                tokens.push((start, Token::Identifier(input[start..i].to_owned())));
+                continue;
+            }
+            b'-' | b'0'..=b'9' => {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                match input[start..i].parse() {
+                    Ok(n) => tokens.push((start, Token::Number(n))),
+                    Err(_) => return Err(syntax_error("invalid number".to_owned(), start)),
+                }
+                continue;
+            }
+            b'"' => {
+                let end = tri!(closing(bytes, start, b'"'));
+                i = end + 1;
+                match crate::from_str(&input[start..i]) {
+                    Ok(s) => Token::QuotedIdentifier(s),
+                    Err(_) => {
+                        return Err(syntax_error("invalid quoted identifier".to_owned(), start));
+                    }
+                }
+            }
+            b'\'' => {
+                let end = tri!(closing(bytes, start, b'\''));
+                i = end + 1;
+                Token::Literal(Value::String(unescape_raw(&input[start + 1..end])))
+            }
+            b'`' => {
+                let end = tri!(closing(bytes, start, b'`'));
+                i = end + 1;
+                let json = input[start + 1..end].replace("\\`", "`");
+                match crate::from_str(&json) {
+                    Ok(value) => Token::Literal(value),
+                    Err(_) => return Err(syntax_error("invalid JSON literal".to_owned(), start)),
+                }
+            }
+            b'[' => match next {
+                Some(b']') => {
+                    i += 2;
+                    Token::Flatten
+                }
+                Some(b'?') => {
+                    i += 2;
+                    Token::Filter
+                }
+                _ => {
+                    i += 1;
+                    Token::LBracket
+                }
+            },
+            b'|' | b'&' | b'!' | b'<' | b'>' | b'=' => {
+                let (token, len) = match (bytes[i], next) {
+                    (b'|', Some(b'|')) => (Token::Or, 2),
+                    (b'|', _) => (Token::Pipe, 1),
+                    (b'&', Some(b'&')) => (Token::And, 2),
+                    (b'&', _) => (Token::Expref, 1),
+                    (b'!', Some(b'=')) => (Token::Ne, 2),
+                    (b'!', _) => (Token::Not, 1),
+                    (b'<', Some(b'=')) => (Token::Le, 2),
+                    (b'<', _) => (Token::Lt, 1),
+                    (b'>', Some(b'=')) => (Token::Ge, 2),
+                    (b'>', _) => (Token::Gt, 1),
+                    (b'=', Some(b'=')) => (Token::Eq, 2),
+                    _ => return Err(syntax_error("expected `==`".to_owned(), start)),
+                };
+                i += len;
+                token
+            }
+            b => {
+                i += 1;
+                match b {
+                    b'.' => Token::Dot,
+                    b'*' => Token::Star,
+                    b'@' => Token::Current,
+                    b']' => Token::RBracket,
+                    b'{' => Token::LBrace,
+                    b'}' => Token::RBrace,
+                    b'(' => Token::LParen,
+                    b')' => Token::RParen,
+                    b',' => Token::Comma,
+                    b':' => Token::Colon,
+                    _ => return Err(syntax_error("unexpected character".to_owned(), start)),
+                }
+            }
+        };
+        tokens.push((start, token));
+    }
+
+    tokens.push((input.len(), Token::Eof));
+    Ok(tokens)
+}
+
+/// Vị trí của dấu đóng `delimiter` không bị escape, bắt đầu tìm sau `start`.
+fn closing(bytes: &[u8], start: usize, delimiter: u8) -> Result<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == delimiter => return Ok(i),
+            _ => i += 1,
+        }
+    }
+    Err(syntax_error(
+        format!("unterminated `{}`", delimiter as char),
+        start,
+    ))
+}
+
+/// Trong raw string chỉ `\'` và `\\` là escape; các `\` khác giữ nguyên.
+fn unescape_raw(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some(next @ '\'') | Some(next @ '\\') => out.push(next),
+                Some(next) => {
+                    out.push('\\');
+                    out.push(next);
+                }
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, Copy, Debug)]
+enum Comparator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug)]
+enum Ast {
+    Identity,
+    Field(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Literal(Value),
+    Subexpr(Box<Ast>, Box<Ast>),
+    /// Chiếu `rhs` lên từng phần tử của mảng `lhs`.
+    Projection(Box<Ast>, Box<Ast>),
+    /// Chiếu `rhs` lên từng giá trị của object `lhs`.
+    ValueProjection(Box<Ast>, Box<Ast>),
+    /// Chiếu `rhs` lên các phần tử của mảng `lhs` thỏa mãn điều kiện.
+    FilterProjection(Box<Ast>, Box<Ast>, Box<Ast>),
+    Flatten(Box<Ast>),
+    Pipe(Box<Ast>, Box<Ast>),
+    Or(Box<Ast>, Box<Ast>),
+    And(Box<Ast>, Box<Ast>),
+    Not(Box<Ast>),
+    Compare(Comparator, Box<Ast>, Box<Ast>),
+    MultiList(Vec<Ast>),
+    MultiHash(Vec<(String, Ast)>),
+    Function(String, Vec<Ast>),
+    Expref(Box<Ast>),
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    // Số lớp lồng nhau còn được phép, như `remaining_depth` của
+    // `Deserializer`. Biểu thức thường đến từ người dùng, nên không giới hạn
+    // thì `((((...` hay `!!!!...` đủ sâu sẽ làm tràn stack.
+    remaining_depth: u8,
+}
+
+// Độ sâu tối đa của cây cú pháp, tính cả chuỗi toán tử nối tiếp như `a.b.c`.
+const MAX_DEPTH: u8 = 128;
+
+// Phép chiếu dừng lại ở các token có binding power nhỏ hơn giá trị này.
+const PROJECTION_STOP: u8 = 10;
+
+fn binding_power(token: &Token) -> u8 {
+    match token {
+        Token::Pipe => 1,
+        Token::Or => 2,
+        Token::And => 3,
+        Token::Eq | Token::Ne | Token::Lt | Token::Le | Token::Gt | Token::Ge => 5,
+        Token::Flatten => 9,
+        Token::Star => 20,
+        Token::Filter => 21,
+        Token::Dot => 40,
+        Token::Not => 45,
+        Token::LBrace => 50,
+        Token::LBracket => 55,
+        Token::LParen => 60,
+        _ => 0,
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn peek_at(&self, n: usize) -> &Token {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + n).min(last)].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].1.clone();
+        if self.pos + 1 < self.tokens.len() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        if *self.peek() == expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    #[cold]
+    fn unexpected(&self) -> Error {
+        let (offset, token) = &self.tokens[self.pos];
+        let message = match token {
+            Token::Eof => "unexpected end of expression".to_owned(),
+            _ => "unexpected token".to_owned(),
+        };
+        syntax_error(message, *offset)
+    }
+
+    fn expression(&mut self, rbp: u8) -> Result<Ast> {
+        let depth = self.remaining_depth;
+        let result = self.expression_nested(rbp);
+        self.remaining_depth = depth;
+        result
+    }
+
+    fn expression_nested(&mut self, rbp: u8) -> Result<Ast> {
+        tri!(self.descend());
+        let mut left = tri!(self.nud());
+        while rbp < binding_power(self.peek()) {
+            // Mỗi toán tử nối tiếp bọc `left` thêm một lớp.
+            tri!(self.descend());
+            left = tri!(self.led(left));
+        }
+        Ok(left)
+    }
+
+    fn descend(&mut self) -> Result<()> {
+        match self.remaining_depth.checked_sub(1) {
+            Some(depth) => {
+                self.remaining_depth = depth;
+                Ok(())
+            }
+            None => Err(syntax_error(
+                "expression nested too deeply".to_owned(),
+                self.tokens[self.pos].0,
+            )),
+        }
+    }
+
+    fn nud(&mut self) -> Result<Ast> {
+        let position = self.pos;
+        Ok(match self.advance() {
+            Token::Literal(value) => Ast::Literal(value),
+            Token::Identifier(name) => Ast::Field(name),
+            Token::QuotedIdentifier(name) => {
+                if *self.peek() == Token::LParen {
+                    return Err(self.unexpected());
+                }
+                Ast::Field(name)
+            }
+            Token::Star => {
+                let rhs = if *self.peek() == Token::RBracket {
+                    Ast::Identity
+                } else {
+                    tri!(self.projection_rhs(binding_power(&Token::Star)))
+                };
+                Ast::ValueProjection(Box::new(Ast::Identity), Box::new(rhs))
+            }
+            Token::Filter => tri!(self.filter(Ast::Identity)),
+            Token::LBrace => tri!(self.multi_select_hash()),
+            Token::LParen => {
+                let inner = tri!(self.expression(0));
+                tri!(self.expect(Token::RParen));
+                inner
+            }
+            Token::Flatten => {
+                let lhs = Ast::Flatten(Box::new(Ast::Identity));
+                let rhs = tri!(self.projection_rhs(binding_power(&Token::Flatten)));
+                Ast::Projection(Box::new(lhs), Box::new(rhs))
+            }
+            Token::Not => Ast::Not(Box::new(tri!(self.expression(binding_power(&Token::Not))))),
+            Token::LBracket => match (self.peek(), self.peek_at(1)) {
+                (Token::Number(_), _) | (Token::Colon, _) => {
+                    let index = tri!(self.index_expression());
+                    tri!(self.project_if_slice(Ast::Identity, index))
+                }
+                (Token::Star, Token::RBracket) => {
+                    self.advance();
+                    self.advance();
+                    let rhs = tri!(self.projection_rhs(binding_power(&Token::Star)));
+                    Ast::Projection(Box::new(Ast::Identity), Box::new(rhs))
+                }
+                _ => tri!(self.multi_select_list()),
+            },
+            Token::Current => Ast::Identity,
+            Token::Expref => Ast::Expref(Box::new(tri!(self.expression(0)))),
+            _ => {
+                self.pos = position;
+                return Err(self.unexpected());
+            }
+        })
+    }
+
+    fn led(&mut self, left: Ast) -> Result<Ast> {
+        let position = self.pos;
+        let token = self.advance();
+        let bp = binding_power(&token);
+        Ok(match token {
+            Token::Dot => {
+                if *self.peek() == Token::Star {
+                    self.advance();
+                    let rhs = tri!(self.projection_rhs(bp));
+                    Ast::ValueProjection(Box::new(left), Box::new(rhs))
+                } else {
+                    let rhs = tri!(self.dot_rhs(bp));
+                    Ast::Subexpr(Box::new(left), Box::new(rhs))
+                }
+            }
+            Token::Pipe => Ast::Pipe(Box::new(left), Box::new(tri!(self.expression(bp)))),
+            Token::Or => Ast::Or(Box::new(left), Box::new(tri!(self.expression(bp)))),
+            Token::And => Ast::And(Box::new(left), Box::new(tri!(self.expression(bp)))),
+            Token::LParen => {
+                let name = match left {
+                    Ast::Field(name) => name,
+                    _ => {
+                        self.pos = position;
+                        return Err(self.unexpected());
+                    }
+                };
+                let mut args = Vec::new();
+                while *self.peek() != Token::RParen {
+                    args.push(tri!(self.expression(0)));
+                    if *self.peek() == Token::Comma {
+                        self.advance();
+                    }
+                }
+                tri!(self.expect(Token::RParen));
+                Ast::Function(name, args)
+            }
+            Token::Filter => tri!(self.filter(left)),
+            Token::Eq | Token::Ne | Token::Lt | Token::Le | Token::Gt | Token::Ge => {
+                let comparator = match token {
+                    Token::Eq => Comparator::Eq,
+                    Token::Ne => Comparator::Ne,
+                    Token::Lt => Comparator::Lt,
+                    Token::Le => Comparator::Le,
+                    Token::Gt => Comparator::Gt,
+                    _ => Comparator::Ge,
+                };
+                let rhs = tri!(self.expression(bp));
+                Ast::Compare(comparator, Box::new(left), Box::new(rhs))
+            }
+            Token::Flatten => {
+                let lhs = Ast::Flatten(Box::new(left));
+                let rhs = tri!(self.projection_rhs(bp));
+                Ast::Projection(Box::new(lhs), Box::new(rhs))
+            }
+            Token::LBracket => match self.peek() {
+                Token::Number(_) | Token::Colon => {
+                    let index = tri!(self.index_expression());
+                    tri!(self.project_if_slice(left, index))
+                }
+                _ => {
+                    tri!(self.expect(Token::Star));
+                    tri!(self.expect(Token::RBracket));
+                    let rhs = tri!(self.projection_rhs(binding_power(&Token::Star)));
+                    Ast::Projection(Box::new(left), Box::new(rhs))
+                }
+            },
+            _ => {
+                self.pos = position;
+                return Err(self.unexpected());
+            }
+        })
+    }
+
+    fn filter(&mut self, left: Ast) -> Result<Ast> {
+        let condition = tri!(self.expression(0));
+        tri!(self.expect(Token::RBracket));
+        let rhs = if *self.peek() == Token::Flatten {
+            Ast::Identity
+        } else {
+            tri!(self.projection_rhs(binding_power(&Token::Filter)))
+        };
+        Ok(Ast::FilterProjection(
+            Box::new(left),
+            Box::new(rhs),
+            Box::new(condition),
+        ))
+    }
+
+    /// Phần sau `[` của một chỉ số hoặc slice.
+    fn index_expression(&mut self) -> Result<Ast> {
+        if *self.peek() != Token::Colon && *self.peek_at(1) != Token::Colon {
+            let index = match self.advance() {
+                Token::Number(n) => n,
+                _ => unreachable!(),
+            };
+            tri!(self.expect(Token::RBracket));
+            return Ok(Ast::Index(index));
+        }
+
+        let mut parts = [None; 3];
+        let mut current = 0;
+        loop {
+            match self.peek() {
+                Token::RBracket => break,
+                Token::Colon if current < 2 => current += 1,
+                Token::Number(n) if parts[current].is_none() => parts[current] = Some(*n),
+                _ => return Err(self.unexpected()),
+            }
+            self.advance();
+        }
+        if parts[2] == Some(0) {
+            return Err(self.unexpected());
+        }
+        self.advance();
+        Ok(Ast::Slice(parts[0], parts[1], parts[2]))
+    }
+
+    fn project_if_slice(&mut self, left: Ast, index: Ast) -> Result<Ast> {
+        let is_slice = matches!(index, Ast::Slice(..));
+        let indexed = Ast::Subexpr(Box::new(left), Box::new(index));
+        if is_slice {
+            let rhs = tri!(self.projection_rhs(binding_power(&Token::Star)));
+            Ok(Ast::Projection(Box::new(indexed), Box::new(rhs)))
+        } else {
+            Ok(indexed)
+        }
+    }
+
+    fn projection_rhs(&mut self, bp: u8) -> Result<Ast> {
+        match self.peek() {
+            token if binding_power(token) < PROJECTION_STOP => Ok(Ast::Identity),
+            Token::LBracket | Token::Filter => self.expression(bp),
+            Token::Dot => {
+                self.advance();
+                self.dot_rhs(bp)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn dot_rhs(&mut self, bp: u8) -> Result<Ast> {
+        match self.peek() {
+            Token::Identifier(_) | Token::QuotedIdentifier(_) | Token::Star => self.expression(bp),
+            Token::LBracket => {
+                self.advance();
+                self.multi_select_list()
+            }
+            Token::LBrace => {
+                self.advance();
+                self.multi_select_hash()
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn multi_select_list(&mut self) -> Result<Ast> {
+        let mut items = Vec::new();
+        loop {
+            items.push(tri!(self.expression(0)));
+            if *self.peek() == Token::RBracket {
+                self.advance();
+                return Ok(Ast::MultiList(items));
+            }
+            tri!(self.expect(Token::Comma));
+        }
+    }
+
+    fn multi_select_hash(&mut self) -> Result<Ast> {
+        let mut entries = Vec::new();
+        loop {
+            let key = match self.peek() {
+                Token::Identifier(key) | Token::QuotedIdentifier(key) => key.clone(),
+                _ => return Err(self.unexpected()),
+            };
+            self.advance();
+            tri!(self.expect(Token::Colon));
+            entries.push((key, tri!(self.expression(0))));
+            if *self.peek() == Token::RBrace {
+                self.advance();
+                return Ok(Ast::MultiHash(entries));
+            }
+            tri!(self.expect(Token::Comma));
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => false,
+        Value::String(s) => !s.is_empty(),
+        Value::Array(list) => !list.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+        Value::Bool(true) | Value::Number(_) => true,
+    }
+}
+
+fn project<'a, I>(items: I, rhs: &Ast) -> Result<Value>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let mut out = Vec::new();
+    for item in items {
+        let value = tri!(eval(rhs, item));
+        if !value.is_null() {
+            out.push(value);
+        }
+    }
+    Ok(Value::Array(out))
+}
+
+fn eval(ast: &Ast, value: &Value) -> Result<Value> {
+    Ok(match ast {
+        Ast::Identity => value.clone(),
+        Ast::Field(name) => match value {
+            Value::Object(map) => map.get(name).cloned().unwrap_or(Value::Null),
+            _ => Value::Null,
+        },
+        Ast::Index(index) => match value {
+            Value::Array(list) => {
+                let index = if *index < 0 {
+                    list.len() as i64 + index
+                } else {
+                    *index
+                };
+                match usize::try_from(index) {
+                    Ok(index) => list.get(index).cloned().unwrap_or(Value::Null),
+                    Err(_) => Value::Null,
+                }
+            }
+            _ => Value::Null,
+        },
+        Ast::Slice(start, stop, step) => match value {
+            Value::Array(list) => Value::Array(slice(list, *start, *stop, *step)),
+            _ => Value::Null,
+        },
+        Ast::Literal(literal) => literal.clone(),
+        Ast::Subexpr(lhs, rhs) | Ast::Pipe(lhs, rhs) => tri!(eval(rhs, &tri!(eval(lhs, value)))),
+        Ast::Projection(lhs, rhs) => match tri!(eval(lhs, value)) {
+            Value::Array(list) => tri!(project(&list, rhs)),
+            _ => Value::Null,
+        },
+        Ast::ValueProjection(lhs, rhs) => match tri!(eval(lhs, value)) {
+            Value::Object(map) => tri!(project(map.values(), rhs)),
+            _ => Value::Null,
+        },
+        Ast::FilterProjection(lhs, rhs, condition) => match tri!(eval(lhs, value)) {
+            Value::Array(list) => {
+                let mut matching = Vec::new();
+                for item in &list {
+                    if is_truthy(&tri!(eval(condition, item))) {
+                        matching.push(item);
+                    }
+                }
+                tri!(project(matching, rhs))
+            }
+            _ => Value::Null,
+        },
+        Ast::Flatten(inner) => match tri!(eval(inner, value)) {
+            Value::Array(list) => {
+                let mut out = Vec::with_capacity(list.len());
+                for item in list {
+                    match item {
+                        Value::Array(nested) => out.extend(nested),
+                        item => out.push(item),
+                    }
+                }
+                Value::Array(out)
+            }
+            _ => Value::Null,
+        },
+        Ast::Or(lhs, rhs) => {
+            let left = tri!(eval(lhs, value));
+            if is_truthy(&left) {
+                left
+            } else {
+                tri!(eval(rhs, value))
+            }
+        }
+        Ast::And(lhs, rhs) => {
+            let left = tri!(eval(lhs, value));
+            if is_truthy(&left) {
+                tri!(eval(rhs, value))
+            } else {
+                left
+            }
+        }
+        Ast::Not(inner) => Value::Bool(!is_truthy(&tri!(eval(inner, value)))),
+        Ast::Compare(comparator, lhs, rhs) => {
+            let left = tri!(eval(lhs, value));
+            let right = tri!(eval(rhs, value));
+            compare(*comparator, &left, &right)
+        }
+        Ast::MultiList(items) => {
+            if value.is_null() {
+                return Ok(Value::Null);
+            }
+            let mut out = Vec::with_capacity(items.len());
+            for item in items {
+                out.push(tri!(eval(item, value)));
+            }
+            Value::Array(out)
+        }
+        Ast::MultiHash(entries) => {
+            if value.is_null() {
+                return Ok(Value::Null);
+            }
+            let mut out = Map::new();
+            for (key, item) in entries {
+                out.insert(key.clone(), tri!(eval(item, value)));
+            }
+            Value::Object(out)
+        }
+        Ast::Function(name, args) => tri!(call(name, args, value)),
+        Ast::Expref(_) => {
+            return Err(runtime_error(
+                "expression reference used outside of a function".to_owned(),
+            ));
+        }
+    })
+}
+
+fn slice(list: &[Value], start: Option<i64>, stop: Option<i64>, step: Option<i64>) -> Vec<Value> {
+    let len = list.len() as i64;
+    let step = step.unwrap_or(1);
+    let clamp = |i: i64| {
+        if i < 0 {
+            (i + len).max(if step < 0 { -1 } else { 0 })
+        } else {
+            i.min(if step < 0 { len - 1 } else { len })
+        }
+    };
+    let mut i = match start {
+        Some(start) => clamp(start),
+        None if step < 0 => len - 1,
+        None => 0,
+    };
+    let stop = match stop {
+        Some(stop) => clamp(stop),
+        None if step < 0 => -1,
+        None => len,
+    };
+
+    let mut out = Vec::new();
+    while (step > 0 && i < stop) || (step < 0 && i > stop) {
+        out.push(list[i as usize].clone());
+        i += step;
+    }
+    out
+}
+
+fn compare(comparator: Comparator, left: &Value, right: &Value) -> Value {
+    let ordering = match comparator {
+        Comparator::Eq => return Value::Bool(json_equal(left, right)),
+        Comparator::Ne => return Value::Bool(!json_equal(left, right)),
+        _ => match (left, right) {
+            (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+            _ => None,
+        },
+    };
+    match ordering {
+        Some(ordering) => Value::Bool(match comparator {
+            Comparator::Lt => ordering == Ordering::Less,
+            Comparator::Le => ordering != Ordering::Greater,
+            Comparator::Gt => ordering == Ordering::Greater,
+            _ => ordering != Ordering::Less,
+        }),
+        None => Value::Null,
+    }
+}
+
+// Bằng nhau theo JMESPath: số được so theo giá trị, nên `1` bằng `1.0`, kể
+// cả khi nằm trong mảng hay object.
+fn json_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.numeric_cmp(b) == Some(Ordering::Equal),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).map_or(false, |b| json_equal(a, b)))
+        }
+        _ => left == right,
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cold]
+fn invalid_type(function: &str, position: usize, expected: &str, found: &Value) -> Error {
+    runtime_error(format!(
+        "invalid type for argument {} of {}(): expected {}, found {}",
+        position + 1,
+        function,
+        expected,
+        type_name(found),
+    ))
+}
+
+/// Tham số của một lời gọi hàm: các giá trị đã đánh giá, hoặc biểu thức gốc
+/// với các hàm nhận `&expr`.
+struct Args<'a> {
+    function: &'a str,
+    asts: &'a [Ast],
+    values: Vec<Value>,
+}
+
+impl<'a> Args<'a> {
+    fn value(&self, i: usize) -> &Value {
+        &self.values[i]
+    }
+
+    fn number(&self, i: usize) -> Result<&Number> {
+        match &self.values[i] {
+            Value::Number(n) => Ok(n),
+            other => Err(invalid_type(self.function, i, "number", other)),
+        }
+    }
+
+    fn string(&self, i: usize) -> Result<&str> {
+        match &self.values[i] {
+            Value::String(s) => Ok(s),
+            other => Err(invalid_type(self.function, i, "string", other)),
+        }
+    }
+
+    fn array(&self, i: usize) -> Result<&Vec<Value>> {
+        match &self.values[i] {
+            Value::Array(list) => Ok(list),
+            other => Err(invalid_type(self.function, i, "array", other)),
+        }
+    }
+
+    fn object(&self, i: usize) -> Result<&Map<String, Value>> {
+        match &self.values[i] {
+            Value::Object(map) => Ok(map),
+            other => Err(invalid_type(self.function, i, "object", other)),
+        }
+    }
+
+    fn expref(&self, i: usize) -> Result<&'a Ast> {
+        match &self.asts[i] {
+            Ast::Expref(ast) => Ok(ast),
+            _ => Err(invalid_type(
+                self.function,
+                i,
+                "expression",
+                &self.values[i],
+            )),
+        }
+    }
+
+    /// Khóa sắp xếp của từng phần tử: tất cả phải cùng là số hoặc cùng là
+    /// chuỗi.
+    fn sort_keys(&self, i: usize, keys: Vec<Value>) -> Result<Vec<Value>> {
+        let all_numbers = keys.iter().all(Value::is_number);
+        let all_strings = keys.iter().all(Value::is_string);
+        match keys.iter().find(|key| !key.is_number() && !key.is_string()) {
+            Some(bad) => Err(invalid_type(self.function, i, "number or string", bad)),
+            None if all_numbers || all_strings => Ok(keys),
+            None => Err(runtime_error(format!(
+                "{}() requires all numbers or all strings",
+                self.function,
+            ))),
+        }
+    }
+}
+
+fn total_cmp(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+fn call(name: &str, asts: &[Ast], current: &Value) -> Result<Value> {
+    let arity = match name {
+        "abs" | "avg" | "keys" | "length" | "max" | "min" | "reverse" | "sort" | "sum"
+        | "to_number" | "to_string" | "type" | "values" => 1,
+        "contains" | "ends_with" | "join" | "map" | "max_by" | "min_by" | "sort_by"
+        | "starts_with" => 2,
+        "not_null" if !asts.is_empty() => asts.len(),
+        "not_null" => 1,
+        _ => return Err(runtime_error(format!("unknown function: {}()", name))),
+    };
+    if asts.len() != arity {
+        return Err(runtime_error(format!(
+            "{}() takes {} argument(s), found {}",
+            name,
+            arity,
+            asts.len(),
+        )));
+    }
+
+    let mut values = Vec::with_capacity(asts.len());
+    for ast in asts {
+        values.push(match ast {
+            Ast::Expref(_) => Value::Null,
+            ast => tri!(eval(ast, current)),
+        });
+    }
+    let args = Args {
+        function: name,
+        asts,
+        values,
+    };
+
+    Ok(match name {
+        "abs" => {
+            let n = tri!(args.number(0));
+            Value::Number(if let Some(i) = n.as_i64() {
+                Number::from(i.unsigned_abs())
+            } else if n.is_u64() {
+                n.clone()
+            } else {
+                Number::from_f64(n.as_f64().unwrap_or(0.0).abs()).unwrap_or_else(|| n.clone())
+            })
+        }
+        "avg" | "sum" => {
+            let list = tri!(args.array(0));
+            let mut sum = Number::from(0u8);
+            for (i, item) in list.iter().enumerate() {
+                let n = match item {
+                    Value::Number(n) => n,
+                    other => return Err(invalid_type(name, 0, "array of numbers", other)),
+                };
+                sum = match sum.checked_add(n) {
+                    Some(sum) => sum,
+                    None => {
+                        let f = sum.as_f64().unwrap_or(0.0) + n.as_f64().unwrap_or(0.0);
+                        tri!(Number::from_f64(f).ok_or_else(|| {
+                            runtime_error(format!("{}() overflowed at element {}", name, i))
+                        }))
+                    }
+                };
+            }
+            if name == "sum" {
+                Value::Number(sum)
+            } else if list.is_empty() {
+                Value::Null
+            } else {
+                let avg = sum.as_f64().unwrap_or(0.0) / list.len() as f64;
+                Number::from_f64(avg).map_or(Value::Null, Value::Number)
+            }
+        }
+        "contains" => Value::Bool(match args.value(0) {
+            Value::Array(list) => list.contains(args.value(1)),
+            Value::String(s) => match args.value(1) {
+                Value::String(needle) => s.contains(needle.as_str()),
+                _ => false,
+            },
+            other => return Err(invalid_type(name, 0, "array or string", other)),
+        }),
+        "ends_with" => Value::Bool(tri!(args.string(0)).ends_with(tri!(args.string(1)))),
+        "starts_with" => Value::Bool(tri!(args.string(0)).starts_with(tri!(args.string(1)))),
+        "join" => {
+            let separator = tri!(args.string(0));
+            let mut parts = Vec::new();
+            for item in tri!(args.array(1)) {
+                match item {
+                    Value::String(s) => parts.push(s.as_str()),
+                    other => return Err(invalid_type(name, 1, "array of strings", other)),
+                }
+            }
+            Value::String(parts.join(separator))
+        }
+        "keys" => tri!(args.object(0))
+            .keys()
+            .cloned()
+            .map(Value::String)
+            .collect(),
+        "values" => tri!(args.object(0)).values().cloned().collect(),
+        "length" => Value::from(match args.value(0) {
+            Value::String(s) => s.chars().count(),
+            Value::Array(list) => list.len(),
+            Value::Object(map) => map.len(),
+            other => return Err(invalid_type(name, 0, "string, array or object", other)),
+        }),
+        "map" => {
+            let ast = tri!(args.expref(0));
+            let mut out = Vec::new();
+            for item in tri!(args.array(1)) {
+                out.push(tri!(eval(ast, item)));
+            }
+            Value::Array(out)
+        }
+        "max" | "min" | "sort" => {
+            let list = tri!(args.sort_keys(0, tri!(args.array(0)).clone()));
+            let mut list = list;
+            list.sort_by(total_cmp);
+            match name {
+                "sort" => Value::Array(list),
+                "max" => list.pop().unwrap_or(Value::Null),
+                _ => list.into_iter().next().unwrap_or(Value::Null),
+            }
+        }
+        "max_by" | "min_by" | "sort_by" => {
+            let list = tri!(args.array(0));
+            let ast = tri!(args.expref(1));
+            let mut keys = Vec::with_capacity(list.len());
+            for item in list {
+                keys.push(tri!(eval(ast, item)));
+            }
+            let keys = tri!(args.sort_keys(1, keys));
+            let mut pairs: Vec<(&Value, &Value)> = keys.iter().zip(list).collect();
+            pairs.sort_by(|a, b| total_cmp(a.0, b.0));
+            match name {
+                "sort_by" => pairs.into_iter().map(|(_, item)| item.clone()).collect(),
+                "max_by" => pairs
+                    .last()
+                    .map_or(Value::Null, |(_, item)| (*item).clone()),
+                _ => pairs
+                    .first()
+                    .map_or(Value::Null, |(_, item)| (*item).clone()),
+            }
+        }
+        "not_null" => args
+            .values
+            .iter()
+            .find(|value| !value.is_null())
+            .cloned()
+            .unwrap_or(Value::Null),
+        "reverse" => match args.value(0) {
+            Value::String(s) => Value::String(s.chars().rev().collect()),
+            Value::Array(list) => list.iter().rev().cloned().collect(),
+            other => return Err(invalid_type(name, 0, "string or array", other)),
+        },
+        "to_number" => match args.value(0) {
+            Value::Number(n) => Value::Number(n.clone()),
+            Value::String(s) => s.parse().map_or(Value::Null, Value::Number),
+            _ => Value::Null,
+        },
+        "to_string" => match args.value(0) {
+            Value::String(s) => Value::String(s.clone()),
+            other => Value::String(other.to_string()),
+        },
+        "type" => Value::String(type_name(args.value(0)).to_owned()),
+        _ => unreachable!(),
+    })
+}
@@ -192,18 +192,32 @@ extern crate alloc;
 
 #[cfg(feature = "std")]
 #[doc(inline)]
-pub use crate::de::from_reader;
+pub use crate::de::{from_buf_read, from_reader, from_reader_seed, minify_stream};
+#[cfg(feature = "mmap")]
 #[doc(inline)]
-pub use crate::de::{from_slice, from_str, Deserializer, StreamDeserializer};
+pub use crate::de::from_path_mmap;
+#[doc(inline)]
+pub use crate::de::{
+    from_slice, from_slice_in, from_slice_projected, from_slice_with_range, from_slice_with_spans,
+    from_str, from_str_with_spans, Deserializer, StreamDeserializer,
+};
 #[doc(inline)]
 pub use crate::error::{Error, Result};
 #[doc(inline)]
-pub use crate::ser::{to_string, to_string_pretty, to_vec, to_vec_pretty};
+pub use crate::ser::{
+    patch_str, to_log_string, to_string, to_string_pretty, to_string_pretty_with_indent,
+    to_string_truncated, to_string_with_capacity, to_vec, to_vec_pretty, to_vec_pretty_with_indent,
+    to_vec_with_capacity, LogOptions,
+};
 #[cfg(feature = "std")]
 #[doc(inline)]
-pub use crate::ser::{to_writer, to_writer_pretty, Serializer};
+pub use crate::ser::{
+    to_writer, to_writer_buffered, to_writer_pretty, to_writer_pretty_buffered, Serializer,
+};
+#[doc(inline)]
+pub use crate::spanned::Spanned;
 #[doc(inline)]
-pub use crate::value::{from_value, to_value, Map, Number, Value};
+pub use crate::value::{from_value, from_value_ref, to_value, FromJsonNumber, Map, Number, Value};
 
 // We only use our own error type; no need for From conversions provided by the
 // standard library's try! macro. This reduces lines of LLVM IR by 4%.
@@ -221,16 +235,30 @@ mod macros;
 
 pub mod de;
 pub mod error;
+pub mod helpers;
+#[cfg(feature = "jmespath")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jmespath")))]
+pub mod jmespath;
+pub mod io;
 pub mod map;
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub mod parallel;
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub mod ser;
 pub mod value;
 
 
-mod io;
+mod encoding;
 #[cfg(feature = "std")]
 mod iter;
 mod number;
 mod read;
+mod spanned;
+
+// Not public API. Used by the `json_static!` macro.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub mod __private;
 
@@ -37,6 +37,19 @@
 /// });
 /// ```
 ///
+/// Khóa viết dạng `@tên` dùng chính tên đó làm chuỗi, đỡ phải viết dấu nháy.
+/// Một identifier không có `@` vẫn là một biểu thức như trước: giá trị của
+/// biến đó được dùng làm khóa.
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// let role = "admin";
+/// let field = "role";
+/// let value = json!({ @id: 1, @name: "ann", field: role });
+/// assert_eq!(value, json!({ "id": 1, "name": "ann", "role": "admin" }));
+/// ```
+///
 /// ```
 /// # use serde_json::json;
 /// #
@@ -60,6 +73,14 @@ macro_rules! json_internal_vec {
     };
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! json_internal_stringify {
+    ($key:ident) => {
+        stringify!($key)
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! json_unexpected {
@@ -71,6 +92,80 @@ macro_rules! json_unexpected {
 macro_rules! json_expect_expr_comma {
     ($e:expr , $($tt:tt)*) => {};
 }
+/// Tạo một `Value::Array` từ một iterator, mỗi phần tử được chuyển sang
+/// `Value` bằng `to_value` (panic nếu thất bại, giống `json!`).
+///
+/// ```
+/// # use serde_json::{json, json_array};
+/// #
+/// let names = vec!["a", "b"];
+/// assert_eq!(json_array!(names.iter()), json!(["a", "b"]));
+/// assert_eq!(json_array!((1..4).map(|i| i * i)), json!([1, 4, 9]));
+/// ```
+///
+/// Bên trong `json!`, một mảng cũng có thể được viết dưới dạng
+/// `[for pattern in iter => phần tử]`, với phần tử là bất kỳ thứ gì mà `json!`
+/// chấp nhận:
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// let users = vec![("ann", 31), ("bob", 27)];
+/// let value = json!({
+///     "count": users.len(),
+///     "users": [for (name, age) in &users => {"name": name, "age": age}],
+/// });
+/// assert_eq!(value["users"][1], json!({"name": "bob", "age": 27}));
+/// ```
+#[macro_export]
+macro_rules! json_array {
+    ($iter:expr $(,)?) => {
+        $crate::Value::Array(::core::iter::Iterator::collect(
+            ::core::iter::Iterator::map(::core::iter::IntoIterator::into_iter($iter), |item| {
+                $crate::to_value(item).unwrap()
+            }),
+        ))
+    };
+}
+
+/// Giống `json!`, nhưng trả về một `&'static Value` chỉ được tạo một lần, ở
+/// lần đầu tiên biểu thức được thực thi. Dùng cho các văn bản JSON lớn được
+/// nhúng sẵn trong chương trình (schema, dữ liệu mẫu, ...) để không phải dựng
+/// lại chúng mỗi lần gọi.
+///
+/// Vì giá trị được dùng chung cho mọi lần gọi, nội dung không được tham chiếu
+/// đến biến cục bộ; chỉ hằng số và `static` là được phép.
+///
+/// ```
+/// use serde_json::{json, json_static, Value};
+///
+/// fn schema() -> &'static Value {
+///     json_static!({
+///         "type": "object",
+///         "required": ["id"],
+///         "properties": {"id": {"type": "integer"}}
+///     })
+/// }
+///
+/// assert_eq!(schema()["required"], json!(["id"]));
+/// assert!(std::ptr::eq(schema(), schema()));
+/// ```
+///
+/// ```compile_fail
+/// # use serde_json::json_static;
+/// let id = 1;
+/// let v = json_static!({"id": id});
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[macro_export]
+macro_rules! json_static {
+    ($($json:tt)+) => {{
+        static VALUE: $crate::__private::StaticValue = $crate::__private::StaticValue::new();
+        VALUE.get(|| $crate::json!($($json)+))
+    }};
+}
+
 ///
 #[macro_export(local_inner_macros)]
 macro_rules! json {
@@ -103,6 +198,16 @@ macro_rules! json_internal {
         $crate::Value::Array(json_internal_vec![])
     };
 
+    // Mảng được tạo từ một iterator: [for x in iter => body]
+    ([for $pat:pat in $iter:expr => $($body:tt)+]) => {
+        $crate::Value::Array(
+            ::core::iter::Iterator::collect(::core::iter::Iterator::map(
+                ::core::iter::IntoIterator::into_iter($iter),
+                |$pat| json_internal!($($body)+),
+            ))
+        )
+    };
+
     ([ $($tt:tt)+ ]) => {
         $crate::Value::Array(json_internal!(@array [] $($tt)+))
     };
@@ -276,6 +381,11 @@ macro_rules! json_internal {
         json_unexpected!($comma);
     };
 
+    // Khóa dạng `@tên`, dùng chính tên đó làm khóa
+    (@object $object:ident () (@ $key:ident : $($rest:tt)*) $copy:tt) => {
+        json_internal!(@object $object (json_internal_stringify!($key)) (: $($rest)*) (: $($rest)*));
+    };
+
     // Khóa được đặt trong ngoặc đơn hoàn toàn 
     (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
         json_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
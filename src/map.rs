@@ -4,16 +4,21 @@
 //! preserve_order để sử dụng indexmap 
 //! Nhưng mà thư viện nhỏ này không hỗ trợ preserve_order @@
 //!
+//! Thay vào đó, có thể chọn lúc chạy một map giữ thứ tự chèn bằng
+//! [`Map::new_ordered`], bất kể các feature được bật.
+//!
 //! [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
 //! [`IndexMap`]: https://docs.rs/indexmap/*/indexmap/map/struct.IndexMap.html
 
 use crate::value::Value;
 use alloc::string::String;
+use alloc::vec::{self, Vec};
 use core::borrow::Borrow;
 use core::fmt::{self, Debug};
 use core::hash::Hash;
 use core::iter::{FromIterator, FusedIterator};
 use core::ops;
+use core::slice;
 use serde::de;
 
 #[cfg(not(feature = "preserve_order"))]
@@ -26,37 +31,123 @@ pub struct Map<K, V> {
     map: MapImpl<K, V>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type MapImpl<K, V> = BTreeMap<K, V>;
+enum MapImpl<K, V> {
+    Sorted(BTreeMap<K, V>),
+    Ordered(OrderedMap<K, V>),
+}
+
+/// Map giữ thứ tự chèn: các cặp nằm trong `entries` theo thứ tự, còn `index`
+/// ánh xạ mỗi khóa tới vị trí của nó để tra cứu nhanh.
+#[derive(Clone)]
+struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+    index: BTreeMap<K, usize>,
+}
+
+impl OrderedMap<String, Value> {
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.index.get(key).copied()
+    }
+
+    fn push(&mut self, key: String, value: Value) {
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+    }
 
+    fn remove_at(&mut self, index: usize) -> (String, Value) {
+        let entry = self.entries.remove(index);
+        self.index.remove(&entry.0);
+        for (k, _) in &self.entries[index..] {
+            if let Some(i) = self.index.get_mut(k) {
+                *i -= 1;
+            }
+        }
+        entry
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, (k, _)) in self.entries.iter().enumerate() {
+            self.index.insert(k.clone(), i);
+        }
+    }
+}
 
 impl Map<String, Value> {
     /// Tạo ra 1 map trống 
     #[inline]
     pub fn new() -> Self {
         Map {
-            map: MapImpl::new(),
+            map: MapImpl::Sorted(BTreeMap::new()),
         }
     }
 
     /// Tạo ra một map trống, với dung lượng ban đầu 
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
-        Map {
-            #[cfg(not(feature = "preserve_order"))]
-            map: {
-                // does not support with_capacity
-                let _ = capacity;
-                BTreeMap::new()
-            },
+        // does not support with_capacity
+        let _ = capacity;
+        Map::new()
+    }
 
+    /// Tạo ra một map trống luôn duyệt các khóa theo thứ tự tăng dần, giống
+    /// [`Map::new`]. Dùng tên này để nói rõ thứ tự ở nơi gọi.
+    #[inline]
+    pub fn new_sorted() -> Self {
+        Map::new()
+    }
+
+    /// Tạo ra một map trống giữ thứ tự chèn: các hàm duyệt và việc mã hóa trả
+    /// về các khóa theo thứ tự chúng được thêm vào lần đầu. Chèn lại một khóa
+    /// đã có không đổi vị trí của nó; xóa một khóa dịch các khóa phía sau lên.
+    ///
+    /// Khác với feature `preserve_order`, lựa chọn này được đưa ra lúc chạy,
+    /// nên một thư viện có thể bảo đảm thứ tự output mà không phụ thuộc vào
+    /// feature mà người dùng của nó bật. Hai map bằng nhau nếu có cùng các
+    /// cặp khóa - giá trị, bất kể thứ tự.
+    ///
+    /// ```
+    /// use serde_json::{json, Map, Value};
+    ///
+    /// let mut map = Map::new_ordered();
+    /// map.insert("zeta".to_owned(), json!(1));
+    /// map.insert("alpha".to_owned(), json!(2));
+    ///
+    /// assert!(map.preserves_order());
+    /// assert_eq!(Value::Object(map).to_string(), r#"{"zeta":1,"alpha":2}"#);
+    /// ```
+    #[inline]
+    pub fn new_ordered() -> Self {
+        Map {
+            map: MapImpl::Ordered(OrderedMap {
+                entries: Vec::new(),
+                index: BTreeMap::new(),
+            }),
         }
     }
 
+    /// Trả về true nếu map được tạo bằng [`Map::new_ordered`] và giữ thứ tự
+    /// chèn, false nếu map duyệt các khóa theo thứ tự tăng dần.
+    #[inline]
+    pub fn preserves_order(&self) -> bool {
+        matches!(self.map, MapImpl::Ordered(_))
+    }
+
     /// Xóa bỏ mọi cặp key:value trong map 
     #[inline]
     pub fn clear(&mut self) {
-        self.map.clear();
+        match &mut self.map {
+            MapImpl::Sorted(map) => map.clear(),
+            MapImpl::Ordered(map) => {
+                map.entries.clear();
+                map.index.clear();
+            }
+        }
     }
 
     /// Trả về tham chiếu đến giá trị tương ứng với khóa 
@@ -67,7 +158,10 @@ impl Map<String, Value> {
         String: Borrow<Q>,
         Q: ?Sized + Ord + Eq + Hash,
     {
-        self.map.get(key)
+        match &self.map {
+            MapImpl::Sorted(map) => map.get(key),
+            MapImpl::Ordered(map) => map.find(key).map(|i| &map.entries[i].1),
+        }
     }
 
 
@@ -78,7 +172,10 @@ impl Map<String, Value> {
         String: Borrow<Q>,
         Q: ?Sized + Ord + Eq + Hash,
     {
-        self.map.contains_key(key)
+        match &self.map {
+            MapImpl::Sorted(map) => map.contains_key(key),
+            MapImpl::Ordered(map) => map.index.contains_key(key),
+        }
     }
 
     /// Trả về một tham chiếu có thể thay đổi đến giá trị tương ứng với khóa.
@@ -88,7 +185,13 @@ impl Map<String, Value> {
         String: Borrow<Q>,
         Q: ?Sized + Ord + Eq + Hash,
     {
-        self.map.get_mut(key)
+        match &mut self.map {
+            MapImpl::Sorted(map) => map.get_mut(key),
+            MapImpl::Ordered(map) => match map.find(key) {
+                Some(i) => Some(&mut map.entries[i].1),
+                None => None,
+            },
+        }
     }
 
     /// Trả về cặp key - value khớp với key đưa ra 
@@ -100,7 +203,13 @@ impl Map<String, Value> {
         String: Borrow<Q>,
         Q: ?Sized + Ord + Eq + Hash,
     {
-        self.map.get_key_value(key)
+        match &self.map {
+            MapImpl::Sorted(map) => map.get_key_value(key),
+            MapImpl::Ordered(map) => map.find(key).map(|i| {
+                let (k, v) = &map.entries[i];
+                (k, v)
+            }),
+        }
     }
 
     /// Thêm 1 cặp key - value vào map 
@@ -108,7 +217,16 @@ impl Map<String, Value> {
     /// Nếu map tồn tại key đó, giá trị của key được update và giá trị cũ được trả về 
     #[inline]
     pub fn insert(&mut self, k: String, v: Value) -> Option<Value> {
-        self.map.insert(k, v)
+        match &mut self.map {
+            MapImpl::Sorted(map) => map.insert(k, v),
+            MapImpl::Ordered(map) => match map.index.get(&k) {
+                Some(&i) => Some(core::mem::replace(&mut map.entries[i].1, v)),
+                None => {
+                    map.push(k, v);
+                    None
+                }
+            },
+        }
     }
 
     /// Xóa một khóa trong bảng, trả về giá trị của khóa đó,
@@ -120,10 +238,7 @@ impl Map<String, Value> {
         String: Borrow<Q>,
         Q: ?Sized + Ord + Eq + Hash,
     {
-        #[cfg(feature = "preserve_order")]
-        return self.map.swap_remove(key);
-        #[cfg(not(feature = "preserve_order"))]
-        return self.map.remove(key);
+        self.remove_entry(key).map(|(_, value)| value)
     }
 
     /// Xóa key trong map, trả về key - value nếu key 
@@ -134,16 +249,25 @@ impl Map<String, Value> {
         String: Borrow<Q>,
         Q: ?Sized + Ord + Eq + Hash,
     {
-        #[cfg(any(feature = "preserve_order", not(no_btreemap_remove_entry)))]
-        return self.map.remove_entry(key);
-        
+        match &mut self.map {
+            MapImpl::Sorted(map) => map.remove_entry(key),
+            MapImpl::Ordered(map) => map.find(key).map(|i| map.remove_at(i)),
+        }
     }
 
     /// Chuyển tất cả các phần tử từ other -> seft 
     #[inline]
     pub fn append(&mut self, other: &mut Self) {
-        #[cfg(not(feature = "preserve_order"))]
-        self.map.append(&mut other.map);
+        if let (MapImpl::Sorted(map), MapImpl::Sorted(other)) = (&mut self.map, &mut other.map) {
+            return map.append(other);
+        }
+        let empty = if other.preserves_order() {
+            Map::new_ordered()
+        } else {
+            Map::new()
+        };
+        let other = core::mem::replace(other, empty);
+        self.extend(other);
     }
 
     /// Lấy ra mục tương ứng với khóa trên bảng, thao tác tại chỗ 
@@ -151,32 +275,52 @@ impl Map<String, Value> {
     where
         S: Into<String>,
     {
-        #[cfg(not(feature = "preserve_order"))]
         use alloc::collections::btree_map::Entry as EntryImpl;
 
-        match self.map.entry(key.into()) {
-            EntryImpl::Vacant(vacant) => Entry::Vacant(VacantEntry { vacant }),
-            EntryImpl::Occupied(occupied) => Entry::Occupied(OccupiedEntry { occupied }),
+        let key = key.into();
+        match &mut self.map {
+            MapImpl::Sorted(map) => match map.entry(key) {
+                EntryImpl::Vacant(vacant) => Entry::Vacant(VacantEntry {
+                    vacant: VacantEntryImpl::Sorted(vacant),
+                }),
+                EntryImpl::Occupied(occupied) => Entry::Occupied(OccupiedEntry {
+                    occupied: OccupiedEntryImpl::Sorted(occupied),
+                }),
+            },
+            MapImpl::Ordered(map) => match map.index.get(&key) {
+                Some(&index) => Entry::Occupied(OccupiedEntry {
+                    occupied: OccupiedEntryImpl::Ordered { map, index },
+                }),
+                None => Entry::Vacant(VacantEntry {
+                    vacant: VacantEntryImpl::Ordered { map, key },
+                }),
+            },
         }
     }
 
     /// Trả về số lượng các phần tử trong map
     #[inline]
     pub fn len(&self) -> usize {
-        self.map.len()
+        match &self.map {
+            MapImpl::Sorted(map) => map.len(),
+            MapImpl::Ordered(map) => map.entries.len(),
+        }
     }
 
     /// Trả về true, nếu bảng đã cho là trống rỗng 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        self.len() == 0
     }
 
     /// Trả về con trỏ, trên các mục của bảng đã cho 
     #[inline]
     pub fn iter(&self) -> Iter {
         Iter {
-            iter: self.map.iter(),
+            iter: match &self.map {
+                MapImpl::Sorted(map) => IterImpl::Sorted(map.iter()),
+                MapImpl::Ordered(map) => IterImpl::Ordered(map.entries.iter()),
+            },
         }
     }
 
@@ -184,7 +328,10 @@ impl Map<String, Value> {
     #[inline]
     pub fn iter_mut(&mut self) -> IterMut {
         IterMut {
-            iter: self.map.iter_mut(),
+            iter: match &mut self.map {
+                MapImpl::Sorted(map) => IterMutImpl::Sorted(map.iter_mut()),
+                MapImpl::Ordered(map) => IterMutImpl::Ordered(map.entries.iter_mut()),
+            },
         }
     }
 
@@ -192,7 +339,10 @@ impl Map<String, Value> {
     #[inline]
     pub fn keys(&self) -> Keys {
         Keys {
-            iter: self.map.keys(),
+            iter: match &self.map {
+                MapImpl::Sorted(map) => KeysImpl::Sorted(map.keys()),
+                MapImpl::Ordered(map) => KeysImpl::Ordered(map.entries.iter()),
+            },
         }
     }
 
@@ -200,7 +350,10 @@ impl Map<String, Value> {
     #[inline]
     pub fn values(&self) -> Values {
         Values {
-            iter: self.map.values(),
+            iter: match &self.map {
+                MapImpl::Sorted(map) => ValuesImpl::Sorted(map.values()),
+                MapImpl::Ordered(map) => ValuesImpl::Ordered(map.entries.iter()),
+            },
         }
     }
 
@@ -208,7 +361,10 @@ impl Map<String, Value> {
     #[inline]
     pub fn values_mut(&mut self) -> ValuesMut {
         ValuesMut {
-            iter: self.map.values_mut(),
+            iter: match &mut self.map {
+                MapImpl::Sorted(map) => ValuesMutImpl::Sorted(map.values_mut()),
+                MapImpl::Ordered(map) => ValuesMutImpl::Ordered(map.entries.iter_mut()),
+            },
         }
     }
 
@@ -218,11 +374,23 @@ impl Map<String, Value> {
     /// returns `false`.
     #[cfg(not(no_btreemap_retain))]
     #[inline]
-    pub fn retain<F>(&mut self, f: F)
+    pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&String, &mut Value) -> bool,
     {
-        self.map.retain(f);
+        match &mut self.map {
+            MapImpl::Sorted(map) => map.retain(f),
+            MapImpl::Ordered(map) => {
+                let mut kept = Vec::with_capacity(map.entries.len());
+                for (k, mut v) in map.entries.drain(..) {
+                    if f(&k, &mut v) {
+                        kept.push((k, v));
+                    }
+                }
+                map.entries = kept;
+                map.reindex();
+            }
+        }
     }
 }
 
@@ -230,9 +398,7 @@ impl Map<String, Value> {
 impl Default for Map<String, Value> {
     #[inline]
     fn default() -> Self {
-        Map {
-            map: MapImpl::new(),
-        }
+        Map::new()
     }
 }
 
@@ -240,20 +406,32 @@ impl Clone for Map<String, Value> {
     #[inline]
     fn clone(&self) -> Self {
         Map {
-            map: self.map.clone(),
+            map: match &self.map {
+                MapImpl::Sorted(map) => MapImpl::Sorted(map.clone()),
+                MapImpl::Ordered(map) => MapImpl::Ordered(map.clone()),
+            },
         }
     }
 
     #[inline]
     fn clone_from(&mut self, source: &Self) {
-        self.map.clone_from(&source.map);
+        match (&mut self.map, &source.map) {
+            (MapImpl::Sorted(map), MapImpl::Sorted(source)) => map.clone_from(source),
+            _ => *self = source.clone(),
+        }
     }
 }
 
 impl PartialEq for Map<String, Value> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.map.eq(&other.map)
+        if let (MapImpl::Sorted(a), MapImpl::Sorted(b)) = (&self.map, &other.map) {
+            return a == b;
+        }
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key).map_or(false, |v| *value == *v))
     }
 }
 
@@ -283,7 +461,7 @@ where
     type Output = Value;
 
     fn index(&self, index: &Q) -> &Value {
-        self.map.index(index)
+        self.get(index).expect("no entry found for key")
     }
 }
 
@@ -304,14 +482,14 @@ where
     Q: ?Sized + Ord + Eq + Hash,
 {
     fn index_mut(&mut self, index: &Q) -> &mut Value {
-        self.map.get_mut(index).expect("no entry found for key")
+        self.get_mut(index).expect("no entry found for key")
     }
 }
 
 impl Debug for Map<String, Value> {
     #[inline]
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.map.fmt(formatter)
+        formatter.debug_map().entries(self.iter()).finish()
     }
 }
 
@@ -380,7 +558,7 @@ impl FromIterator<(String, Value)> for Map<String, Value> {
         T: IntoIterator<Item = (String, Value)>,
     {
         Map {
-            map: FromIterator::from_iter(iter),
+            map: MapImpl::Sorted(FromIterator::from_iter(iter)),
         }
     }
 }
@@ -390,7 +568,14 @@ impl Extend<(String, Value)> for Map<String, Value> {
     where
         T: IntoIterator<Item = (String, Value)>,
     {
-        self.map.extend(iter);
+        match &mut self.map {
+            MapImpl::Sorted(map) => map.extend(iter),
+            MapImpl::Ordered(_) => {
+                for (key, value) in iter {
+                    self.insert(key, value);
+                }
+            }
+        }
     }
 }
 
@@ -426,6 +611,58 @@ macro_rules! delegate_iterator {
     }
 }
 
+// Iterator của từng backend: với map giữ thứ tự, phần tử của Vec được ánh xạ
+// về cùng kiểu Item với iterator của BTreeMap.
+macro_rules! iterator_impl {
+    ($name:ident $(<$lt:lifetime>)? {
+        Sorted($sorted:ty),
+        Ordered($ordered:ty, |$p:pat_param| $map:expr),
+    } => $item:ty) => {
+        enum $name $(<$lt>)? {
+            Sorted($sorted),
+            Ordered($ordered),
+        }
+
+        impl $(<$lt>)? Iterator for $name $(<$lt>)? {
+            type Item = $item;
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                match self {
+                    $name::Sorted(iter) => iter.next(),
+                    $name::Ordered(iter) => iter.next().map(|$p| $map),
+                }
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match self {
+                    $name::Sorted(iter) => iter.size_hint(),
+                    $name::Ordered(iter) => iter.size_hint(),
+                }
+            }
+        }
+
+        impl $(<$lt>)? DoubleEndedIterator for $name $(<$lt>)? {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match self {
+                    $name::Sorted(iter) => iter.next_back(),
+                    $name::Ordered(iter) => iter.next_back().map(|$p| $map),
+                }
+            }
+        }
+
+        impl $(<$lt>)? ExactSizeIterator for $name $(<$lt>)? {
+            #[inline]
+            fn len(&self) -> usize {
+                match self {
+                    $name::Sorted(iter) => iter.len(),
+                    $name::Ordered(iter) => iter.len(),
+                }
+            }
+        }
+    };
+}
+
 //////////////////////////////////////////////////////////////////////////////
 
 /// Một cảnh nhìn vào một mục duy nhất trong một bảng đồ, 
@@ -453,11 +690,21 @@ pub struct OccupiedEntry<'a> {
     occupied: OccupiedEntryImpl<'a>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type VacantEntryImpl<'a> = btree_map::VacantEntry<'a, String, Value>;
+enum VacantEntryImpl<'a> {
+    Sorted(btree_map::VacantEntry<'a, String, Value>),
+    Ordered {
+        map: &'a mut OrderedMap<String, Value>,
+        key: String,
+    },
+}
 
-#[cfg(not(feature = "preserve_order"))]
-type OccupiedEntryImpl<'a> = btree_map::OccupiedEntry<'a, String, Value>;
+enum OccupiedEntryImpl<'a> {
+    Sorted(btree_map::OccupiedEntry<'a, String, Value>),
+    Ordered {
+        map: &'a mut OrderedMap<String, Value>,
+        index: usize,
+    },
+}
 
 impl<'a> Entry<'a> {
     /// Trả về 1 tham chiếu đến khóa của mục này 
@@ -579,7 +826,10 @@ impl<'a> VacantEntry<'a> {
     /// ```
     #[inline]
     pub fn key(&self) -> &String {
-        self.vacant.key()
+        match &self.vacant {
+            VacantEntryImpl::Sorted(vacant) => vacant.key(),
+            VacantEntryImpl::Ordered { key, .. } => key,
+        }
     }
 
     ///
@@ -603,7 +853,13 @@ impl<'a> VacantEntry<'a> {
     /// ```
     #[inline]
     pub fn insert(self, value: Value) -> &'a mut Value {
-        self.vacant.insert(value)
+        match self.vacant {
+            VacantEntryImpl::Sorted(vacant) => vacant.insert(value),
+            VacantEntryImpl::Ordered { map, key } => {
+                map.push(key, value);
+                &mut map.entries.last_mut().unwrap().1
+            }
+        }
     }
 }
 
@@ -629,7 +885,10 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn key(&self) -> &String {
-        self.occupied.key()
+        match &self.occupied {
+            OccupiedEntryImpl::Sorted(occupied) => occupied.key(),
+            OccupiedEntryImpl::Ordered { map, index } => &map.entries[*index].0,
+        }
     }
 
     /// Lấy 1 tham chiếu đến giá trị của mục 
@@ -653,7 +912,10 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn get(&self) -> &Value {
-        self.occupied.get()
+        match &self.occupied {
+            OccupiedEntryImpl::Sorted(occupied) => occupied.get(),
+            OccupiedEntryImpl::Ordered { map, index } => &map.entries[*index].1,
+        }
     }
 
     /// Lấy 1 tham chiếu có thể thay đổi tới giá trị của 1 mục 
@@ -679,7 +941,10 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn get_mut(&mut self) -> &mut Value {
-        self.occupied.get_mut()
+        match &mut self.occupied {
+            OccupiedEntryImpl::Sorted(occupied) => occupied.get_mut(),
+            OccupiedEntryImpl::Ordered { map, index } => &mut map.entries[*index].1,
+        }
     }
 
     /// Chuyển một mục thành 1 tham chiếu có thể thay đổi
@@ -707,7 +972,10 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn into_mut(self) -> &'a mut Value {
-        self.occupied.into_mut()
+        match self.occupied {
+            OccupiedEntryImpl::Sorted(occupied) => occupied.into_mut(),
+            OccupiedEntryImpl::Ordered { map, index } => &mut map.entries[index].1,
+        }
     }
 
     /// Sets the value of the entry with the `OccupiedEntry`'s key, and returns
@@ -733,7 +1001,7 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn insert(&mut self, value: Value) -> Value {
-        self.occupied.insert(value)
+        core::mem::replace(self.get_mut(), value)
     }
 
     /// Takes the value of the entry out of the map, and returns it.
@@ -757,10 +1025,10 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn remove(self) -> Value {
-        #[cfg(feature = "preserve_order")]
-        return self.occupied.swap_remove();
-        #[cfg(not(feature = "preserve_order"))]
-        return self.occupied.remove();
+        match self.occupied {
+            OccupiedEntryImpl::Sorted(occupied) => occupied.remove(),
+            OccupiedEntryImpl::Ordered { map, index } => map.remove_at(index).1,
+        }
     }
 }
 
@@ -771,9 +1039,7 @@ impl<'a> IntoIterator for &'a Map<String, Value> {
     type IntoIter = Iter<'a>;
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        Iter {
-            iter: self.map.iter(),
-        }
+        self.iter()
     }
 }
 
@@ -782,10 +1048,10 @@ pub struct Iter<'a> {
     iter: IterImpl<'a>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type IterImpl<'a> = btree_map::Iter<'a, String, Value>;
-#[cfg(feature = "preserve_order")]
-type IterImpl<'a> = indexmap::map::Iter<'a, String, Value>;
+iterator_impl!(IterImpl<'a> {
+    Sorted(btree_map::Iter<'a, String, Value>),
+    Ordered(slice::Iter<'a, (String, Value)>, |(k, v)| (k, v)),
+} => (&'a String, &'a Value));
 
 delegate_iterator!((Iter<'a>) => (&'a String, &'a Value));
 
@@ -796,9 +1062,7 @@ impl<'a> IntoIterator for &'a mut Map<String, Value> {
     type IntoIter = IterMut<'a>;
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        IterMut {
-            iter: self.map.iter_mut(),
-        }
+        self.iter_mut()
     }
 }
 
@@ -807,10 +1071,10 @@ pub struct IterMut<'a> {
     iter: IterMutImpl<'a>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type IterMutImpl<'a> = btree_map::IterMut<'a, String, Value>;
-#[cfg(feature = "preserve_order")]
-type IterMutImpl<'a> = indexmap::map::IterMut<'a, String, Value>;
+iterator_impl!(IterMutImpl<'a> {
+    Sorted(btree_map::IterMut<'a, String, Value>),
+    Ordered(slice::IterMut<'a, (String, Value)>, |(k, v)| (&*k, v)),
+} => (&'a String, &'a mut Value));
 
 delegate_iterator!((IterMut<'a>) => (&'a String, &'a mut Value));
 
@@ -822,7 +1086,10 @@ impl IntoIterator for Map<String, Value> {
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
-            iter: self.map.into_iter(),
+            iter: match self.map {
+                MapImpl::Sorted(map) => IntoIterImpl::Sorted(map.into_iter()),
+                MapImpl::Ordered(map) => IntoIterImpl::Ordered(map.entries.into_iter()),
+            },
         }
     }
 }
@@ -832,10 +1099,10 @@ pub struct IntoIter {
     iter: IntoIterImpl,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type IntoIterImpl = btree_map::IntoIter<String, Value>;
-#[cfg(feature = "preserve_order")]
-type IntoIterImpl = indexmap::map::IntoIter<String, Value>;
+iterator_impl!(IntoIterImpl {
+    Sorted(btree_map::IntoIter<String, Value>),
+    Ordered(vec::IntoIter<(String, Value)>, |entry| entry),
+} => (String, Value));
 
 delegate_iterator!((IntoIter) => (String, Value));
 
@@ -846,10 +1113,10 @@ pub struct Keys<'a> {
     iter: KeysImpl<'a>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type KeysImpl<'a> = btree_map::Keys<'a, String, Value>;
-#[cfg(feature = "preserve_order")]
-type KeysImpl<'a> = indexmap::map::Keys<'a, String, Value>;
+iterator_impl!(KeysImpl<'a> {
+    Sorted(btree_map::Keys<'a, String, Value>),
+    Ordered(slice::Iter<'a, (String, Value)>, |(k, _)| k),
+} => &'a String);
 
 delegate_iterator!((Keys<'a>) => &'a String);
 
@@ -860,10 +1127,10 @@ pub struct Values<'a> {
     iter: ValuesImpl<'a>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type ValuesImpl<'a> = btree_map::Values<'a, String, Value>;
-#[cfg(feature = "preserve_order")]
-type ValuesImpl<'a> = indexmap::map::Values<'a, String, Value>;
+iterator_impl!(ValuesImpl<'a> {
+    Sorted(btree_map::Values<'a, String, Value>),
+    Ordered(slice::Iter<'a, (String, Value)>, |(_, v)| v),
+} => &'a Value);
 
 delegate_iterator!((Values<'a>) => &'a Value);
 
@@ -874,8 +1141,9 @@ pub struct ValuesMut<'a> {
     iter: ValuesMutImpl<'a>,
 }
 
-#[cfg(not(feature = "preserve_order"))]
-type ValuesMutImpl<'a> = btree_map::ValuesMut<'a, String, Value>;
-
+iterator_impl!(ValuesMutImpl<'a> {
+    Sorted(btree_map::ValuesMut<'a, String, Value>),
+    Ordered(slice::IterMut<'a, (String, Value)>, |(_, v)| v),
+} => &'a mut Value);
 
 delegate_iterator!((ValuesMut<'a>) => &'a mut Value);
@@ -1,34 +1,94 @@
 use crate::de::ParserNumber;
 use crate::error::Error;
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
 use core::fmt::{self, Debug, Display};
 #[cfg(not(feature = "arbitrary_precision"))]
 use core::hash::{Hash, Hasher};
+use core::ops::{Add, Div, Mul, Sub};
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use serde::de::IntoDeserializer;
 use serde::de::{self, Unexpected, Visitor};
 use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer};
 
 
 /// Đại diện cho một số trong json
 /// Dù là số nguyên hay là dấu phẩy động 
+///
+/// # Phép toán
+///
+/// `Number` hỗ trợ `+`, `-`, `*`, `/` và các phiên bản `checked_*`, theo quy
+/// tắc nâng kiểu sau:
+///
+/// - Hai số nguyên cho ra số nguyên nếu kết quả chính xác nằm trong phạm vi
+///   của u64 hoặc i64 (hoặc bất kỳ số nguyên nào với feature `bigint`). Phép
+///   chia chỉ cho ra số nguyên khi chia hết.
+/// - Với feature `decimal`, nếu không có số hạng nào là f64 thì kết quả được
+///   tính bằng `Decimal`.
+/// - Các trường hợp còn lại được tính bằng f64.
+///
+/// Toán tử panic nếu kết quả không phải là số JSON (chia cho 0, vô cực);
+/// `checked_*` trả về None trong trường hợp đó, và cả khi số nguyên bị tràn
+/// thay vì tính lại bằng f64.
+///
+/// ```
+/// # use serde_json::Number;
+/// #
+/// let total = Number::from(2) + Number::from(3);
+/// assert_eq!(total, Number::from(5));
+///
+/// let avg = &total / &Number::from(2);
+/// assert_eq!(avg.as_f64(), Some(2.5));
+/// ```
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Number {
     n: N,
 }
 
-#[derive(Copy, Clone)]
+#[cfg_attr(not(feature = "bigint"), derive(Copy))]
+#[derive(Clone)]
 enum N {
     PosInt(u64),
     /// Luôn nhỏ hơn 0
     NegInt(i64),
     /// Luôn lớn hơn 0 
     Float(f64),
+    /// Số thập phân chính xác, giữ nguyên các chữ số như trong JSON
+    #[cfg(feature = "decimal")]
+    Decimal(Decimal),
+    /// Luôn nằm ngoài phạm vi của u64 và i64
+    #[cfg(feature = "bigint")]
+    BigInt(BigInt),
 }
 
+/// Tên "ma thuật" dùng để chuyển một số chính xác (`Decimal` hoặc `BigInt`)
+/// qua data model của serde: khi serialize là tên của một newtype struct chứa
+/// chuỗi chữ số, khi deserialize là khóa duy nhất của một map có giá trị là
+/// chuỗi chữ số.
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+pub(crate) const DIGITS_TOKEN: &str = "$serde_json::private::Digits";
+
 impl PartialEq for N {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (N::PosInt(a), N::PosInt(b)) => a == b,
             (N::NegInt(a), N::NegInt(b)) => a == b,
             (N::Float(a), N::Float(b)) => a == b,
+            #[cfg(feature = "decimal")]
+            (N::Decimal(a), N::Decimal(b)) => a == b,
+            // So sánh theo giá trị thập phân của biểu diễn ngắn nhất của f64,
+            // để `1.0` đọc từ JSON vẫn bằng `1.0f64`.
+            #[cfg(feature = "decimal")]
+            (N::Decimal(a), N::Float(b)) | (N::Float(b), N::Decimal(a)) => {
+                float_to_decimal(*b) == Some(*a)
+            }
+            #[cfg(feature = "bigint")]
+            (N::BigInt(a), N::BigInt(b)) => a == b,
             _ => false,
         }
     }
@@ -53,6 +113,11 @@ impl Hash for N {
                     f.to_bits().hash(h);
                 }
             }
+            // Phải khớp với mã băm của số f64 bằng nó.
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => N::Float(decimal_to_f64(d)).hash(h),
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => b.hash(h),
         }
     }
 }
@@ -81,6 +146,10 @@ impl Number {
             N::PosInt(v) => v <= i64::max_value() as u64,
             N::NegInt(_) => true,
             N::Float(_) => false,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => false,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => false,
         }
     }
 
@@ -105,6 +174,10 @@ impl Number {
         match self.n {
             N::PosInt(_) => true,
             N::NegInt(_) | N::Float(_) => false,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => false,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => false,
         }
     }
 
@@ -125,7 +198,44 @@ impl Number {
     pub fn is_f64(&self) -> bool {
         match self.n {
             N::Float(_) => true,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => true,
             N::PosInt(_) | N::NegInt(_) => false,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => false,
+        }
+    }
+
+    /// Trả về true nếu số được ghi dưới dạng số thực, tức là có dấu chấm thập
+    /// phân hoặc phần mũ như `1.0` hay `1e3`, và false với số nguyên như `1`.
+    ///
+    /// Số đọc từ JSON giữ nguyên dạng này khi được ghi lại, kể cả với feature
+    /// `decimal`: `1.0` vẫn là `1.0` và `1e3` thành `1000.0`, không bao giờ
+    /// thành số nguyên. Nhờ vậy các công cụ cần phân biệt `integer` với
+    /// `number` (như trình kiểm tra JSON Schema) không bị sai khi đọc lại.
+    ///
+    /// ```
+    /// # use serde_json::{json, Number};
+    /// #
+    /// let v = json!([1, 1.0, -2.5e3]);
+    ///
+    /// assert!(!v[0].as_number().unwrap().is_decimal_literal());
+    /// assert!(v[1].as_number().unwrap().is_decimal_literal());
+    /// assert!(v[2].as_number().unwrap().is_decimal_literal());
+    ///
+    /// let n: Number = serde_json::from_str("1.0").unwrap();
+    /// assert!(n.is_decimal_literal());
+    /// assert_eq!(n.to_string(), "1.0");
+    /// ```
+    #[inline]
+    pub fn is_decimal_literal(&self) -> bool {
+        match self.n {
+            N::Float(_) => true,
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => d.scale() > 0,
+            N::PosInt(_) | N::NegInt(_) => false,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => false,
         }
     }
 
@@ -155,6 +265,10 @@ impl Number {
             }
             N::NegInt(n) => Some(n),
             N::Float(_) => None,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => None,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => None,
         }
     }
 
@@ -175,6 +289,10 @@ impl Number {
         match self.n {
             N::PosInt(n) => Some(n),
             N::NegInt(_) | N::Float(_) => None,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => None,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => None,
         }
     }
     /// Chuyển dữ liệu từ json -> rust 
@@ -194,9 +312,108 @@ impl Number {
             N::PosInt(n) => Some(n as f64),
             N::NegInt(n) => Some(n as f64),
             N::Float(n) => Some(n),
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => Some(decimal_to_f64(d)),
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => Some(bigint_to_f64(b)),
+        }
+    }
+
+    /// Trả về true nếu số là một số nguyên theo nghĩa toán học, kể cả khi nó
+    /// được biểu diễn bằng f64 như `2.0` hay `1e20`.
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// assert!(Number::from(-3).is_integer());
+    /// assert!(Number::from_f64(2.0).unwrap().is_integer());
+    /// assert!(!Number::from_f64(2.5).unwrap().is_integer());
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        match self.n {
+            N::PosInt(_) | N::NegInt(_) => true,
+            N::Float(f) => is_integral(f),
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => d.fract().is_zero(),
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => true,
+        }
+    }
+
+    /// Trả về true nếu giá trị của số biểu diễn được chính xác bằng `T`.
+    ///
+    /// Khác với [`to_num`](Number::to_num), số nguyên được biểu diễn bằng f64
+    /// như `2.0` cũng được coi là vừa với các kiểu số nguyên.
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// let n = Number::from_f64(255.0).unwrap();
+    /// assert!(n.fits::<u8>());
+    /// assert!(!n.fits::<i8>());
+    /// assert_eq!(n.to_num::<u8>(), None);
+    ///
+    /// assert!(!Number::from(-1).fits::<u64>());
+    /// assert!(!Number::from_f64(0.5).unwrap().fits::<i32>());
+    /// ```
+    pub fn fits<T>(&self) -> bool
+    where
+        T: FromJsonNumber,
+    {
+        match self.integral_i128() {
+            Some(i) => T::from_json_integer(i).is_some(),
+            None => T::from_json_number(self).is_some(),
         }
     }
 
+    /// Giá trị dưới dạng i128 nếu số là số nguyên theo nghĩa toán học và nằm
+    /// trong phạm vi của i128.
+    fn integral_i128(&self) -> Option<i128> {
+        match self.n {
+            N::PosInt(u) => Some(u as i128),
+            N::NegInt(i) => Some(i as i128),
+            N::Float(f) => {
+                const LIMIT: f64 = 170141183460469231731687303715884105728.0;
+                if is_integral(f) && -LIMIT <= f && f < LIMIT {
+                    Some(f as i128)
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => {
+                if d.fract().is_zero() {
+                    i128::try_from(d).ok()
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => i128::try_from(b).ok(),
+        }
+    }
+
+    /// Chuyển số sang kiểu `T`, kiểm tra phạm vi theo quy tắc của
+    /// [`FromJsonNumber`].
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// let n = Number::from(300);
+    /// assert_eq!(n.to_num::<u16>(), Some(300));
+    /// assert_eq!(n.to_num::<u8>(), None);
+    /// assert_eq!(n.to_num::<f32>(), Some(300.0));
+    ///
+    /// let f = Number::from_f64(2.0).unwrap();
+    /// assert_eq!(f.to_num::<i32>(), None);
+    /// ```
+    pub fn to_num<T>(&self) -> Option<T>
+    where
+        T: FromJsonNumber,
+    {
+        T::from_json_number(self)
+    }
+
     /// Chuyển đổi một f64 có hạn hết thành một Number. 
     /// Các giá trị vô cực hoặc NaN không phải là số JSON.
     ///
@@ -224,6 +441,69 @@ impl Number {
         }
     }
 
+    /// Trả về giá trị chính xác của số dưới dạng `Decimal`.
+    ///
+    /// Số nguyên luôn chuyển được. Số f64 được chuyển từ biểu diễn ngắn nhất
+    /// của nó, nên `0.1f64` cho ra đúng `0.1`. Trả về None nếu số nằm ngoài
+    /// phạm vi của `Decimal`.
+    ///
+    /// ```
+    /// use rust_decimal::Decimal;
+    /// use serde_json::Number;
+    ///
+    /// let price: Number = serde_json::from_str("0.10").unwrap();
+    /// assert_eq!(price.as_decimal().unwrap().to_string(), "0.10");
+    ///
+    /// let qty = Number::from(3);
+    /// assert_eq!(qty.as_decimal(), Some(Decimal::from(3)));
+    ///
+    /// let ratio = Number::from_f64(0.1).unwrap();
+    /// assert_eq!(ratio.as_decimal().unwrap().to_string(), "0.1");
+    /// ```
+    #[cfg(feature = "decimal")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "decimal")))]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self.n {
+            N::PosInt(u) => Some(Decimal::from(u)),
+            N::NegInt(i) => Some(Decimal::from(i)),
+            N::Float(f) => float_to_decimal(f),
+            N::Decimal(d) => Some(d),
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => parse_decimal(&b.to_string()),
+        }
+    }
+
+    /// Trả về giá trị của số dưới dạng `BigInt` nếu nó là số nguyên, kể cả
+    /// khi nó nằm ngoài phạm vi của u64 và i64. Số f64 trả về None.
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    /// use serde_json::Number;
+    ///
+    /// let big: Number = serde_json::from_str("-123456789012345678901234567890").unwrap();
+    /// assert!(!big.is_i64());
+    /// assert_eq!(big.to_string(), "-123456789012345678901234567890");
+    /// assert_eq!(
+    ///     big.as_bigint(),
+    ///     "-123456789012345678901234567890".parse::<BigInt>().ok(),
+    /// );
+    ///
+    /// assert_eq!(Number::from(7).as_bigint(), Some(BigInt::from(7)));
+    /// assert_eq!(Number::from_f64(7.5).unwrap().as_bigint(), None);
+    /// ```
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+    pub fn as_bigint(&self) -> Option<BigInt> {
+        match self.n {
+            N::PosInt(u) => Some(BigInt::from(u)),
+            N::NegInt(i) => Some(BigInt::from(i)),
+            N::Float(_) => None,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => None,
+            N::BigInt(ref b) => Some(b.clone()),
+        }
+    }
+
 }
 
 impl Display for Number {
@@ -232,6 +512,10 @@ impl Display for Number {
             N::PosInt(u) => formatter.write_str(itoa::Buffer::new().format(u)),
             N::NegInt(i) => formatter.write_str(itoa::Buffer::new().format(i)),
             N::Float(f) => formatter.write_str(ryu::Buffer::new().format_finite(f)),
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => Display::fmt(&d, formatter),
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => Display::fmt(b, formatter),
         }
     }
 
@@ -253,6 +537,10 @@ impl Serialize for Number {
             N::PosInt(u) => serializer.serialize_u64(u),
             N::NegInt(i) => serializer.serialize_i64(i),
             N::Float(f) => serializer.serialize_f64(f),
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => serializer.serialize_newtype_struct(DIGITS_TOKEN, &d.to_string()),
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => serializer.serialize_newtype_struct(DIGITS_TOKEN, &b.to_string()),
         }
     }
 
@@ -291,6 +579,19 @@ impl<'de> Deserialize<'de> for Number {
                 Number::from_f64(value).ok_or_else(|| de::Error::custom("not a JSON number"))
             }
 
+            #[cfg(any(feature = "decimal", feature = "bigint"))]
+            fn visit_map<V>(self, mut visitor: V) -> Result<Number, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let key: Option<String> = tri!(visitor.next_key());
+                if key.as_deref() != Some(DIGITS_TOKEN) {
+                    return Err(de::Error::invalid_type(Unexpected::Map, &self));
+                }
+                let digits: String = tri!(visitor.next_value());
+                number_from_digits(&digits)
+            }
+
             #[cfg(feature = "arbitrary_precision")]
             #[inline]
             fn visit_map<V>(self, mut visitor: V) -> Result<Number, V::Error>
@@ -323,6 +624,10 @@ macro_rules! deserialize_any {
                 N::PosInt(u) => visitor.visit_u64(u),
                 N::NegInt(i) => visitor.visit_i64(i),
                 N::Float(f) => visitor.visit_f64(f),
+                #[cfg(feature = "decimal")]
+                N::Decimal(d) => visitor.visit_map(DigitsDeserializer::new(d)),
+                #[cfg(feature = "bigint")]
+                N::BigInt(ref b) => visitor.visit_map(DigitsDeserializer::new(b)),
             }
         }
 
@@ -358,10 +663,24 @@ macro_rules! deserialize_any {
 
 macro_rules! deserialize_number {
     ($deserialize:ident => $visit:ident) => {
+        deserialize_number!($deserialize => $visit, visit_bigint);
+    };
+
+    ($deserialize:ident => $visit:ident, $visit_bigint:ident) => {
         fn $deserialize<V>(self, visitor: V) -> Result<V::Value, Error>
         where
             V: Visitor<'de>,
         {
+            // Kiểu số cụ thể không nhận map, nên số chính xác được đưa ra
+            // dưới dạng số nguyên 128 bit hoặc f64 thay vì qua DIGITS_TOKEN.
+            #[cfg(feature = "decimal")]
+            if let N::Decimal(d) = self.n {
+                return visitor.visit_f64(decimal_to_f64(d));
+            }
+            #[cfg(feature = "bigint")]
+            if let N::BigInt(ref b) = self.n {
+                return $visit_bigint(b, visitor);
+            }
             self.deserialize_any(visitor)
         }
 
@@ -390,8 +709,8 @@ impl<'de> Deserializer<'de> for Number {
     deserialize_number!(deserialize_u32 => visit_u32);
     deserialize_number!(deserialize_u64 => visit_u64);
     deserialize_number!(deserialize_u128 => visit_u128);
-    deserialize_number!(deserialize_f32 => visit_f32);
-    deserialize_number!(deserialize_f64 => visit_f64);
+    deserialize_number!(deserialize_f32 => visit_f32, visit_bigint_as_f64);
+    deserialize_number!(deserialize_f64 => visit_f64, visit_bigint_as_f64);
 
     forward_to_deserialize_any! {
         bool char str string bytes byte_buf option unit unit_struct
@@ -415,8 +734,8 @@ impl<'de, 'a> Deserializer<'de> for &'a Number {
     deserialize_number!(deserialize_u32 => visit_u32);
     deserialize_number!(deserialize_u64 => visit_u64);
     deserialize_number!(deserialize_u128 => visit_u128);
-    deserialize_number!(deserialize_f32 => visit_f32);
-    deserialize_number!(deserialize_f64 => visit_f64);
+    deserialize_number!(deserialize_f32 => visit_f32, visit_bigint_as_f64);
+    deserialize_number!(deserialize_f64 => visit_f64, visit_bigint_as_f64);
 
     forward_to_deserialize_any! {
         bool char str string bytes byte_buf option unit unit_struct
@@ -444,6 +763,10 @@ impl From<ParserNumber> for Number {
                     N::NegInt(i)
                 }
             }
+            #[cfg(feature = "decimal")]
+            ParserNumber::Decimal(d) => N::Decimal(d),
+            #[cfg(feature = "bigint")]
+            ParserNumber::BigInt(b) => N::BigInt(b),
         };
         Number { n }
     }
@@ -504,6 +827,74 @@ macro_rules! impl_from_signed {
 impl_from_unsigned!(u8, u16, u32, u64, usize);
 impl_from_signed!(i8, i16, i32, i64, isize);
 
+/// Kiểu Rust có thể lấy ra từ một [`Number`] bằng [`Number::to_num`] hoặc
+/// [`Value::to_num`](crate::Value::to_num).
+///
+/// Các kiểu số nguyên chỉ nhận số nguyên nằm trong phạm vi của chúng; số có
+/// phần thập phân như `2.0` không được chấp nhận. `f64` nhận mọi số, còn `f32`
+/// nhận mọi số có độ lớn không vượt quá `f32::MAX` (sau khi làm tròn).
+pub trait FromJsonNumber: Sized {
+    /// Chuyển `number` sang `Self`, hoặc trả về None nếu không được phép.
+    fn from_json_number(number: &Number) -> Option<Self>;
+
+    /// Chuyển một số nguyên sang `Self`; được [`Number::fits`] dùng cho số
+    /// nguyên được biểu diễn bằng f64.
+    ///
+    /// Mặc định gọi `from_json_number` với số nguyên đó nếu nó nằm trong phạm
+    /// vi của u64 hoặc i64.
+    fn from_json_integer(value: i128) -> Option<Self> {
+        let number = if let Ok(u) = u64::try_from(value) {
+            Number::from(u)
+        } else {
+            Number::from(i64::try_from(value).ok()?)
+        };
+        Self::from_json_number(&number)
+    }
+}
+
+macro_rules! impl_from_json_number_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FromJsonNumber for $ty {
+                fn from_json_number(number: &Number) -> Option<Self> {
+                    match number.n {
+                        N::PosInt(u) => <$ty>::try_from(u).ok(),
+                        N::NegInt(i) => <$ty>::try_from(i).ok(),
+                        N::Float(_) => None,
+                        #[cfg(feature = "decimal")]
+                        N::Decimal(_) => None,
+                        #[cfg(feature = "bigint")]
+                        N::BigInt(ref b) => <$ty>::try_from(b).ok(),
+                    }
+                }
+
+                fn from_json_integer(value: i128) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_json_number_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FromJsonNumber for f64 {
+    fn from_json_number(number: &Number) -> Option<Self> {
+        number.as_f64()
+    }
+}
+
+impl FromJsonNumber for f32 {
+    fn from_json_number(number: &Number) -> Option<Self> {
+        let f = number.as_f64()? as f32;
+        if f.is_finite() {
+            Some(f)
+        } else {
+            None
+        }
+    }
+}
+
 
 impl Number {
     #[cfg(not(feature = "arbitrary_precision"))]
@@ -513,7 +904,518 @@ impl Number {
             N::PosInt(u) => Unexpected::Unsigned(u),
             N::NegInt(i) => Unexpected::Signed(i),
             N::Float(f) => Unexpected::Float(f),
+            #[cfg(feature = "decimal")]
+            N::Decimal(d) => Unexpected::Float(decimal_to_f64(d)),
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => Unexpected::Other("big integer"),
+        }
+    }
+
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Lý do một phép toán trên số nguyên không cho ra số nguyên.
+enum Inexact {
+    // Số nguyên với feature `bigint` không bao giờ tràn.
+    #[cfg_attr(feature = "bigint", allow(dead_code))]
+    Overflow,
+    Fraction,
+    DivideByZero,
+}
+
+impl Number {
+    /// Cộng hai số theo quy tắc nâng kiểu của [`Number`].
+    ///
+    /// Trả về None nếu phép cộng hai số nguyên bị tràn, hoặc nếu kết quả f64
+    /// không hữu hạn.
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// let a = Number::from(u64::MAX);
+    /// assert_eq!(a.checked_add(&Number::from(-1)), Some(Number::from(u64::MAX - 1)));
+    ///
+    /// let half = Number::from_f64(0.5).unwrap();
+    /// assert_eq!(Number::from(1).checked_add(&half), Number::from_f64(1.5));
+    ///
+    /// let max = Number::from_f64(f64::MAX).unwrap();
+    /// assert_eq!(max.checked_add(&max), None);
+    /// ```
+    pub fn checked_add(&self, rhs: &Number) -> Option<Number> {
+        self.arith(rhs, Op::Add, true)
+    }
+
+    /// Trừ hai số theo quy tắc nâng kiểu của [`Number`].
+    ///
+    /// Trả về None nếu phép trừ hai số nguyên bị tràn, hoặc nếu kết quả f64
+    /// không hữu hạn.
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// let a = Number::from(3);
+    /// assert_eq!(a.checked_sub(&Number::from(5)), Some(Number::from(-2)));
+    /// assert_eq!(a.checked_sub(&Number::from_f64(0.5).unwrap()), Number::from_f64(2.5));
+    /// ```
+    pub fn checked_sub(&self, rhs: &Number) -> Option<Number> {
+        self.arith(rhs, Op::Sub, true)
+    }
+
+    /// Nhân hai số theo quy tắc nâng kiểu của [`Number`].
+    ///
+    /// Trả về None nếu phép nhân hai số nguyên bị tràn, hoặc nếu kết quả f64
+    /// không hữu hạn.
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// let a = Number::from(-4);
+    /// assert_eq!(a.checked_mul(&Number::from(5)), Some(Number::from(-20)));
+    ///
+    /// let max = Number::from_f64(f64::MAX).unwrap();
+    /// assert_eq!(max.checked_mul(&a), None);
+    /// ```
+    pub fn checked_mul(&self, rhs: &Number) -> Option<Number> {
+        self.arith(rhs, Op::Mul, true)
+    }
+
+    /// Chia hai số theo quy tắc nâng kiểu của [`Number`]. Hai số nguyên chia
+    /// hết cho ra số nguyên, nếu không thì kết quả là f64.
+    ///
+    /// Trả về None khi chia cho 0, hoặc nếu kết quả f64 không hữu hạn.
+    ///
+    /// ```
+    /// # use serde_json::Number;
+    /// #
+    /// let a = Number::from(7);
+    /// assert_eq!(a.checked_div(&Number::from(7)), Some(Number::from(1)));
+    /// assert_eq!(a.checked_div(&Number::from(2)), Number::from_f64(3.5));
+    /// assert_eq!(a.checked_div(&Number::from(0)), None);
+    /// ```
+    pub fn checked_div(&self, rhs: &Number) -> Option<Number> {
+        self.arith(rhs, Op::Div, true)
+    }
+
+    /// Khi `checked` là false, số nguyên bị tràn được tính lại bằng f64 thay
+    /// vì trả về None.
+    fn arith(&self, rhs: &Number, op: Op, checked: bool) -> Option<Number> {
+        if self.is_integer_repr() && rhs.is_integer_repr() {
+            match integer_arith(self, rhs, op) {
+                Ok(n) => return Some(n),
+                Err(Inexact::DivideByZero) => return None,
+                Err(Inexact::Overflow) if checked => return None,
+                Err(Inexact::Overflow) | Err(Inexact::Fraction) => {}
+            }
+        }
+
+        #[cfg(feature = "decimal")]
+        if !self.is_f64_repr() && !rhs.is_f64_repr() {
+            if let (Some(a), Some(b)) = (self.as_decimal(), rhs.as_decimal()) {
+                let d = match op {
+                    Op::Add => a.checked_add(b),
+                    Op::Sub => a.checked_sub(b),
+                    Op::Mul => a.checked_mul(b),
+                    Op::Div => a.checked_div(b),
+                };
+                match d {
+                    Some(d) => return Some(Number::from(d)),
+                    None if checked => return None,
+                    None => {}
+                }
+            }
+        }
+
+        let (a, b) = (self.as_f64()?, rhs.as_f64()?);
+        Number::from_f64(match op {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div => a / b,
+        })
+    }
+
+    /// Số nguyên, kể cả `BigInt`.
+    fn is_integer_repr(&self) -> bool {
+        match self.n {
+            N::PosInt(_) | N::NegInt(_) => true,
+            N::Float(_) => false,
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => false,
+            #[cfg(feature = "bigint")]
+            N::BigInt(_) => true,
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    fn is_f64_repr(&self) -> bool {
+        matches!(self.n, N::Float(_))
+    }
+
+    fn as_i128(&self) -> Option<i128> {
+        match self.n {
+            N::PosInt(u) => Some(u as i128),
+            N::NegInt(i) => Some(i as i128),
+            _ => None,
         }
     }
 
+    /// So sánh giá trị của hai số, bỏ qua cách biểu diễn.
+    pub(crate) fn numeric_cmp(&self, other: &Number) -> Option<Ordering> {
+        if let (Some(a), Some(b)) = (self.as_i128(), other.as_i128()) {
+            return Some(a.cmp(&b));
+        }
+        match (&self.n, &other.n) {
+            (N::Float(a), N::Float(b)) => return a.partial_cmp(b),
+            (N::Float(f), _) => {
+                if let Some(i) = other.as_i128() {
+                    return Some(cmp_int_float(i, *f).reverse());
+                }
+            }
+            (_, N::Float(f)) => {
+                if let Some(i) = self.as_i128() {
+                    return Some(cmp_int_float(i, *f));
+                }
+            }
+            _ => {}
+        }
+
+        #[cfg(feature = "decimal")]
+        if let (Some(a), Some(b)) = (self.as_decimal(), other.as_decimal()) {
+            return Some(a.cmp(&b));
+        }
+        #[cfg(feature = "bigint")]
+        if let (Some(a), Some(b)) = (self.as_bigint(), other.as_bigint()) {
+            return Some(a.cmp(&b));
+        }
+        self.as_f64()?.partial_cmp(&other.as_f64()?)
+    }
+
+    /// Thứ tự toàn phần dùng cho [`Value::total_cmp`](crate::Value::total_cmp):
+    /// so sánh theo giá trị, nếu bằng nhau thì số nguyên đứng trước số thực
+    /// (`1 < 1.0`), để chỉ những số `==` nhau mới được coi là bằng nhau.
+    pub(crate) fn total_cmp(&self, other: &Number) -> Ordering {
+        self.numeric_cmp(other)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.is_integer_repr().cmp(&self.is_integer_repr()))
+    }
+
+    /// Băm dùng cho [`Value::content_hash`](crate::Value::content_hash).
+    /// Khác với `Hash`, mỗi loại biểu diễn có một tag riêng để `0` và `0.0`,
+    /// hay `-1` và `u64::MAX`, không cho cùng một chuỗi byte.
+    pub(crate) fn content_hash<H: Hasher>(&self, state: &mut H) {
+        match self.n {
+            N::PosInt(i) => {
+                state.write_u8(0);
+                state.write_u64(i);
+            }
+            N::NegInt(i) => {
+                state.write_u8(1);
+                state.write_i64(i);
+            }
+            N::Float(_) => {
+                state.write_u8(2);
+                self.n.hash(state);
+            }
+            // Phải khớp với số f64 bằng nó.
+            #[cfg(feature = "decimal")]
+            N::Decimal(_) => {
+                state.write_u8(2);
+                self.n.hash(state);
+            }
+            #[cfg(feature = "bigint")]
+            N::BigInt(ref b) => {
+                state.write_u8(3);
+                b.hash(state);
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "bigint"))]
+fn integer_arith(a: &Number, b: &Number, op: Op) -> Result<Number, Inexact> {
+    // Cả hai đều là u64 hoặc i64 nên cộng và trừ không thể tràn i128.
+    let (a, b) = (a.as_i128().unwrap(), b.as_i128().unwrap());
+    let result = match op {
+        Op::Add => a + b,
+        Op::Sub => a - b,
+        Op::Mul => tri!(a.checked_mul(b).ok_or(Inexact::Overflow)),
+        Op::Div => {
+            if b == 0 {
+                return Err(Inexact::DivideByZero);
+            }
+            if a % b != 0 {
+                return Err(Inexact::Fraction);
+            }
+            a / b
+        }
+    };
+    if let Ok(u) = u64::try_from(result) {
+        Ok(Number::from(u))
+    } else if let Ok(i) = i64::try_from(result) {
+        Ok(Number::from(i))
+    } else {
+        Err(Inexact::Overflow)
+    }
+}
+
+#[cfg(feature = "bigint")]
+fn integer_arith(a: &Number, b: &Number, op: Op) -> Result<Number, Inexact> {
+    let (a, b) = (a.as_bigint().unwrap(), b.as_bigint().unwrap());
+    let result = match op {
+        Op::Add => a + b,
+        Op::Sub => a - b,
+        Op::Mul => a * b,
+        Op::Div => {
+            if b.sign() == num_bigint::Sign::NoSign {
+                return Err(Inexact::DivideByZero);
+            }
+            if (&a % &b).sign() != num_bigint::Sign::NoSign {
+                return Err(Inexact::Fraction);
+            }
+            a / b
+        }
+    };
+    Ok(Number::from(result))
+}
+
+pub(crate) fn is_integral(f: f64) -> bool {
+    // Mọi f64 có độ lớn từ 2^52 trở lên đều là số nguyên.
+    const LIMIT: f64 = 4503599627370496.0;
+    f <= -LIMIT || LIMIT <= f || (f as i64) as f64 == f
+}
+
+/// So sánh chính xác một số nguyên với một số f64 hữu hạn.
+fn cmp_int_float(i: i128, f: f64) -> Ordering {
+    // Mọi số nguyên ở đây đều nằm trong (-2^64, 2^64).
+    const LIMIT: f64 = 18446744073709551616.0;
+    if f >= LIMIT {
+        return Ordering::Less;
+    }
+    if f <= -LIMIT {
+        return Ordering::Greater;
+    }
+    // Phép ép kiểu cắt bỏ phần thập phân; f64::trunc không có trong no_std.
+    let trunc = f as i128;
+    match i.cmp(&trunc) {
+        Ordering::Equal => 0.0.partial_cmp(&(f - trunc as f64)).unwrap(),
+        ord => ord,
+    }
+}
+
+/// Hai số bằng nhau về giá trị nhưng khác cách biểu diễn (ví dụ `1` và `1.0`)
+/// không bằng nhau theo `==`, nên chúng cũng không so sánh được với nhau.
+///
+/// ```
+/// # use serde_json::Number;
+/// #
+/// let one = Number::from(1);
+/// let half = Number::from_f64(0.5).unwrap();
+/// assert!(half < one);
+/// assert!(Number::from(u64::MAX) > Number::from(-1));
+/// assert_eq!(one.partial_cmp(&Number::from_f64(1.0).unwrap()), None);
+/// ```
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        match self.numeric_cmp(other) {
+            Some(Ordering::Equal) if self != other => None,
+            ord => ord,
+        }
+    }
+}
+
+macro_rules! impl_arith {
+    ($($trait:ident $method:ident $op:ident,)*) => {
+        $(
+            /// Theo quy tắc nâng kiểu của [`Number`].
+            ///
+            /// # Panics
+            ///
+            /// Panic khi chia cho 0 hoặc khi kết quả không hữu hạn.
+            impl<'a> $trait<&'a Number> for &'a Number {
+                type Output = Number;
+
+                fn $method(self, rhs: &'a Number) -> Number {
+                    match self.arith(rhs, Op::$op, false) {
+                        Some(n) => n,
+                        None => panic!("result of {} is not a JSON number", stringify!($method)),
+                    }
+                }
+            }
+
+            impl $trait for Number {
+                type Output = Number;
+
+                fn $method(self, rhs: Number) -> Number {
+                    (&self).$method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_arith! {
+    Add add Add,
+    Sub sub Sub,
+    Mul mul Mul,
+    Div div Div,
+}
+
+#[cfg(feature = "decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "decimal")))]
+impl From<Decimal> for Number {
+    #[inline]
+    fn from(d: Decimal) -> Self {
+        Number { n: N::Decimal(d) }
+    }
+}
+
+/// Phân tích các chữ số JSON, có thể có phần mũ, thành `Decimal` mà không làm
+/// tròn. Trả về None nếu không biểu diễn được chính xác.
+///
+/// Nếu `digits` có dấu chấm hoặc phần mũ, kết quả có ít nhất một chữ số sau
+/// dấu chấm, để một số như `1e3` được ghi lại thành `1000.0` chứ không thành
+/// số nguyên `1000`. Số nguyên viết không có dấu chấm vẫn là số nguyên.
+#[cfg(feature = "decimal")]
+pub(crate) fn parse_decimal(digits: &str) -> Option<Decimal> {
+    let scientific = digits.contains(|c| c == 'e' || c == 'E');
+    let mut decimal = if scientific {
+        Decimal::from_scientific(digits).ok()?
+    } else {
+        Decimal::from_str_exact(digits).ok()?
+    };
+    if decimal.scale() == 0 && (scientific || digits.contains('.')) {
+        decimal.rescale(1);
+        // Không đổi được scale nếu mantissa đã dùng hết 96 bit.
+        if decimal.scale() != 1 {
+            return None;
+        }
+    }
+    // Decimal bỏ dấu của số 0 khi phân tích; giữ lại để `-0` vẫn là `-0`.
+    if decimal.is_zero() && digits.starts_with('-') {
+        decimal.set_sign_negative(true);
+    }
+    Some(decimal)
+}
+
+#[cfg(feature = "bigint")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+impl From<BigInt> for Number {
+    /// Số nằm trong phạm vi của u64 hoặc i64 được lưu như số nguyên thường.
+    fn from(b: BigInt) -> Self {
+        let n = if let Ok(u) = u64::try_from(&b) {
+            N::PosInt(u)
+        } else if let Ok(i) = i64::try_from(&b) {
+            N::NegInt(i)
+        } else {
+            N::BigInt(b)
+        };
+        Number { n }
+    }
+}
+
+/// Dựng lại một `Number` từ chuỗi chữ số được chuyển qua `DIGITS_TOKEN`.
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+pub(crate) fn number_from_digits<E>(digits: &str) -> Result<Number, E>
+where
+    E: de::Error,
+{
+    #[cfg(feature = "bigint")]
+    match digits.parse::<BigInt>() {
+        // BigInt không có -0; để Decimal giữ nó nếu được.
+        Ok(b) if b.sign() == num_bigint::Sign::NoSign && digits.starts_with('-') => {}
+        Ok(b) => return Ok(Number::from(b)),
+        Err(_) => {}
+    }
+    #[cfg(feature = "decimal")]
+    if let Some(d) = parse_decimal(digits) {
+        return Ok(Number::from(d));
+    }
+    Err(de::Error::custom("invalid number"))
+}
+
+#[cfg(feature = "decimal")]
+fn float_to_decimal(f: f64) -> Option<Decimal> {
+    parse_decimal(ryu::Buffer::new().format_finite(f))
+}
+
+/// Giá trị f64 gần nhất với `d`.
+#[cfg(feature = "decimal")]
+fn decimal_to_f64(d: Decimal) -> f64 {
+    // Chuỗi chữ số của Decimal luôn là một số f64 hợp lệ.
+    d.to_string().parse().unwrap()
+}
+
+/// Giá trị f64 gần nhất với `b`.
+#[cfg(feature = "bigint")]
+fn bigint_to_f64(b: &BigInt) -> f64 {
+    // Có thể ra vô cực với số quá lớn, giống như khi đọc JSON thành f64.
+    b.to_string().parse().unwrap()
+}
+
+#[cfg(feature = "bigint")]
+fn visit_bigint<'de, V>(b: &BigInt, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    if let Ok(i) = i128::try_from(b) {
+        visitor.visit_i128(i)
+    } else if let Ok(u) = u128::try_from(b) {
+        visitor.visit_u128(u)
+    } else {
+        visitor.visit_f64(bigint_to_f64(b))
+    }
+}
+
+#[cfg(feature = "bigint")]
+fn visit_bigint_as_f64<'de, V>(b: &BigInt, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    visitor.visit_f64(bigint_to_f64(b))
+}
+
+/// Đưa một số chính xác vào visitor dưới dạng map `{DIGITS_TOKEN: "chữ số"}`.
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+pub(crate) struct DigitsDeserializer {
+    digits: Option<String>,
+}
+
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+impl DigitsDeserializer {
+    pub(crate) fn new<T: Display>(value: T) -> Self {
+        DigitsDeserializer {
+            digits: Some(value.to_string()),
+        }
+    }
+}
+
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+impl<'de> de::MapAccess<'de> for DigitsDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if self.digits.is_none() {
+            return Ok(None);
+        }
+        let key = DIGITS_TOKEN.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let digits = self.digits.take().unwrap();
+        seed.deserialize(digits.into_deserializer())
+    }
 }
@@ -0,0 +1,248 @@
+//! Giải mã và mã hóa song song trên thread pool của rayon.
+//!
+//! Dành cho các tác vụ ETL xử lý những lô lớn NDJSON (mỗi dòng một giá trị
+//! JSON) hoặc xuất những mảng rất lớn, khi làm việc trên một luồng là nút
+//! thắt. Các phần tử được xử lý độc lập với nhau trên các luồng của rayon,
+//! nhưng kết quả luôn giữ đúng thứ tự của đầu vào.
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Row {
+//!     id: u32,
+//! }
+//!
+//! let input = b"{\"id\": 1}\n{\"id\": 2}\n\n{\"id\": 3}\n";
+//! let rows: Vec<Row> = serde_json::parallel::from_ndjson_slice(input)
+//!     .into_iter()
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! assert_eq!(rows.iter().map(|row| row.id).collect::<Vec<_>>(), [1, 2, 3]);
+//! ```
+
+use crate::de::from_slice;
+use crate::error::{Error, Result};
+use crate::io;
+use crate::ser::to_writer;
+use alloc::vec::{self, Vec};
+use core::marker::PhantomData;
+use rayon::prelude::*;
+use serde::de::{Deserialize, DeserializeOwned};
+use serde::ser::Serialize;
+
+/// Giải mã song song mọi dòng của `input`, mỗi dòng một giá trị. Dòng trống
+/// được bỏ qua.
+///
+/// Trả về kết quả của từng dòng theo thứ tự; lỗi ở một dòng không ảnh hưởng
+/// đến các dòng khác. Vị trí trong lỗi được tính theo toàn bộ `input`.
+pub fn from_ndjson_slice<'a, T>(input: &'a [u8]) -> Vec<Result<T>>
+where
+    T: Deserialize<'a> + Send,
+{
+    parse_lines(input, 0, 0)
+}
+
+/// Đọc NDJSON từ một `io::BufRead` theo từng lô dòng, giải mã mỗi lô song
+/// song và trả về các giá trị theo đúng thứ tự.
+///
+/// Giống [`LineDeserializer`](crate::de::LineDeserializer), lỗi ở một dòng
+/// không làm dừng iterator; chỉ lỗi IO mới kết thúc nó.
+///
+/// ```
+/// use serde_json::parallel::ParLineDeserializer;
+/// use serde_json::Value;
+///
+/// let input = (0..1000).map(|i| format!("{{\"i\": {}}}\n", i)).collect::<String>();
+/// let values = ParLineDeserializer::<_, Value>::new(input.as_bytes()).batch_lines(64);
+///
+/// for (i, value) in values.enumerate() {
+///     assert_eq!(value.unwrap()["i"], i);
+/// }
+/// ```
+pub struct ParLineDeserializer<R, T> {
+    reader: R,
+    batch_lines: usize,
+    buf: Vec<u8>,
+    line: usize,
+    offset: usize,
+    pending: vec::IntoIter<Result<T>>,
+    error: Option<Error>,
+    failed: bool,
+    output: PhantomData<T>,
+}
+
+impl<R, T> ParLineDeserializer<R, T>
+where
+    R: io::BufRead,
+    T: DeserializeOwned + Send,
+{
+    /// Tạo một iterator đọc `reader` theo lô 1024 dòng.
+    pub fn new(reader: R) -> Self {
+        ParLineDeserializer {
+            reader,
+            batch_lines: 1024,
+            buf: Vec::new(),
+            line: 0,
+            offset: 0,
+            pending: Vec::new().into_iter(),
+            error: None,
+            failed: false,
+            output: PhantomData,
+        }
+    }
+
+    /// Số dòng được đọc và giải mã cùng lúc. Lô lớn hơn tận dụng các luồng
+    /// tốt hơn nhưng giữ nhiều dữ liệu trong bộ nhớ hơn.
+    pub fn batch_lines(mut self, lines: usize) -> Self {
+        self.batch_lines = lines.max(1);
+        self
+    }
+
+    fn read_batch(&mut self) {
+        self.buf.clear();
+        let (first_line, first_offset) = (self.line, self.offset);
+        for _ in 0..self.batch_lines {
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => {
+                    self.failed = true;
+                    break;
+                }
+                Ok(n) => {
+                    self.line += 1;
+                    self.offset += n;
+                }
+                Err(err) => {
+                    self.error = Some(Error::io(err));
+                    self.failed = true;
+                    break;
+                }
+            }
+        }
+        self.pending = parse_lines(&self.buf, first_line, first_offset).into_iter();
+    }
+}
+
+impl<R, T> Iterator for ParLineDeserializer<R, T>
+where
+    R: io::BufRead,
+    T: DeserializeOwned + Send,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        loop {
+            if let Some(result) = self.pending.next() {
+                return Some(result);
+            }
+            if self.failed {
+                return self.error.take().map(Err);
+            }
+            self.read_batch();
+        }
+    }
+}
+
+/// Giải mã song song các dòng không trống của `input`, vốn bắt đầu sau
+/// `first_line` dòng và `first_offset` byte của toàn bộ luồng.
+fn parse_lines<'a, T>(input: &'a [u8], first_line: usize, first_offset: usize) -> Vec<Result<T>>
+where
+    T: Deserialize<'a> + Send,
+{
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for (i, text) in input.split(|&b| b == b'\n').enumerate() {
+        if !text.iter().all(|b| matches!(b, b' ' | b'\t' | b'\r')) {
+            lines.push((first_line + i, first_offset + offset, text));
+        }
+        offset += text.len() + 1;
+    }
+
+    lines
+        .into_par_iter()
+        .map(|(line, offset, text)| {
+            from_slice(text).map_err(|err| err.shift_position(line, offset))
+        })
+        .collect()
+}
+
+/// Mã hóa `items` thành một mảng JSON dạng gọn, trong đó các phần tử được mã
+/// hóa song song vào bộ đệm riêng của từng luồng rồi ghép lại theo thứ tự.
+///
+/// Kết quả giống hệt `serde_json::to_vec(items)`. Với một `Value::Array`, hãy
+/// truyền vào slice các phần tử của nó.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let rows = json!([{"id": 1}, {"id": 2}, null]);
+/// let bytes = serde_json::parallel::to_vec_par(rows.as_array().unwrap()).unwrap();
+/// assert_eq!(bytes, serde_json::to_vec(&rows).unwrap());
+/// ```
+///
+/// # Lỗi
+///
+/// Trả về lỗi nếu một phần tử bất kỳ không mã hóa được, ví dụ khi phần tử đó
+/// là map có khóa không phải chuỗi.
+pub fn to_vec_par<T>(items: &[T]) -> Result<Vec<u8>>
+where
+    T: Serialize + Sync,
+{
+    let chunks = tri!(serialize_chunks(items));
+    let len = chunks.iter().map(Vec::len).sum::<usize>() + chunks.len() + 1;
+    let mut out = Vec::with_capacity(len);
+    out.push(b'[');
+    for (i, chunk) in chunks.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        out.extend_from_slice(chunk);
+    }
+    out.push(b']');
+    Ok(out)
+}
+
+/// Mã hóa `items` song song như [`to_vec_par`] rồi ghi mảng JSON vào
+/// `writer`.
+///
+/// # Lỗi
+///
+/// Trả về lỗi nếu một phần tử bất kỳ không mã hóa được hoặc nếu `writer` trả
+/// về lỗi.
+pub fn to_writer_par<W, T>(mut writer: W, items: &[T]) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize + Sync,
+{
+    let chunks = tri!(serialize_chunks(items));
+    tri!(writer.write_all(b"[").map_err(Error::io));
+    for (i, chunk) in chunks.iter().enumerate() {
+        if i > 0 {
+            tri!(writer.write_all(b",").map_err(Error::io));
+        }
+        tri!(writer.write_all(chunk).map_err(Error::io));
+    }
+    writer.write_all(b"]").map_err(Error::io)
+}
+
+/// Chia `items` thành vài khối cho mỗi luồng và mã hóa mỗi khối thành các
+/// phần tử cách nhau bởi dấu phẩy.
+fn serialize_chunks<T>(items: &[T]) -> Result<Vec<Vec<u8>>>
+where
+    T: Serialize + Sync,
+{
+    let chunk_len = (items.len() / (rayon::current_num_threads() * 4)).max(1);
+    items
+        .par_chunks(chunk_len)
+        .map(|chunk| {
+            let mut buf = Vec::new();
+            for (i, item) in chunk.iter().enumerate() {
+                if i > 0 {
+                    buf.push(b',');
+                }
+                tri!(to_writer(&mut buf, item));
+            }
+            Ok(buf)
+        })
+        .collect()
+}
@@ -1,5 +1,5 @@
 use crate::error::{Error, ErrorCode, Result};
-use alloc::vec::Vec;
+use alloc::string::String;
 use core::char;
 use core::cmp;
 use core::ops::Deref;
@@ -8,6 +8,8 @@ use core::str;
 use crate::io;
 use crate::iter::LineColIterator;
 
+pub(crate) use self::private::{FixedScratch, Scratch};
+
 /// Phân tích, tái tổ hợp đầu vào 
 /// Không được thực hiện cho các loại ngoài serde_json 
 pub trait Read<'de>: private::Sealed {
@@ -40,15 +42,18 @@ pub trait Read<'de>: private::Sealed {
     /// phân tích chuỗi json nằm trong cho đến khi dấu ngoặc kép tiếp theo được sử dụng
     ///  " adsadsa " -> hàm trả về các byte gốc trong chuỗi
     #[doc(hidden)]
-    fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>>;
+    fn parse_str<'s, S: Scratch>(
+        &'s mut self,
+        scratch: &'s mut S,
+    ) -> Result<Reference<'de, 's, str>>;
 
     /// byte trước đó là giấu {}
     /// phân tích chuỗi json nằm trong cho đến khi dấu ngoặc kép tiếp theo được sử dụng
     ///  " adsadsa " -> hàm trả về các byte gốc trong chuỗi
     #[doc(hidden)]
-    fn parse_str_raw<'s>(
+    fn parse_str_raw<'s, S: Scratch>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
     ) -> Result<Reference<'de, 's, [u8]>>;
 
     /// Tương tự parse_str, nhưng bỏ qua dữ liệu, không trả về 
@@ -70,18 +75,53 @@ pub trait Read<'de>: private::Sealed {
     /// bằng cách thiết lập cờ hoặc bằng cách cắt đứt dữ liệu đầu vào.
     #[doc(hidden)]
     fn set_failed(&mut self, failed: &mut bool);
+
+    /// Toàn bộ đầu vào, nếu đầu vào là một slice nằm sẵn trong bộ nhớ.
+    /// Phải được gọi trước `set_failed`, vì `set_failed` có thể cắt slice.
+    #[doc(hidden)]
+    fn input(&self) -> Option<&'de [u8]> {
+        None
+    }
+
+    /// Các tùy chọn đọc chuỗi mà reader đang dùng.
+    #[doc(hidden)]
+    fn options(&self) -> private::Options;
+
+    #[doc(hidden)]
+    fn set_options(&mut self, options: private::Options);
 }
 
+/// Một vị trí nằm giữa hai byte của đầu vào, tính từ đầu tài liệu.
+///
+/// `line` bắt đầu từ 1. `column` là số byte đứng trước vị trí này trên cùng
+/// dòng, còn `byte_offset` là số byte đứng trước nó trong toàn bộ đầu vào. Vì
+/// vậy vị trí ngay trước một byte có `column` bằng cột (tính từ 0) của byte
+/// đó, và `&input[position.byte_offset..]` bắt đầu đúng tại byte đó.
+///
+/// Xem [`Deserializer::next_value_position`](crate::Deserializer::next_value_position).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Position {
+    /// Số thứ tự của dòng, bắt đầu từ 1.
     pub line: usize,
+    /// Số byte đứng trước vị trí này trên cùng dòng.
     pub column: usize,
+    /// Số byte đứng trước vị trí này tính từ đầu đầu vào.
+    pub byte_offset: usize,
 }
 
+/// Một chuỗi đọc từ đầu vào, hoặc mượn thẳng từ đầu vào, hoặc được chép
+/// vào bộ đệm tạm của `Deserializer` khi phải giải mã escape hay khi đầu vào
+/// là một luồng IO.
+///
+/// Xem [`Deserializer::next_str`](crate::Deserializer::next_str).
+#[derive(Debug)]
 pub enum Reference<'b, 'c, T>
 where
     T: ?Sized + 'static,
 {
+    /// Mượn từ đầu vào, sống lâu bằng đầu vào.
     Borrowed(&'b T),
+    /// Nằm trong bộ đệm tạm, chỉ dùng được tới lần đọc tiếp theo.
     Copied(&'c T),
 }
 
@@ -109,6 +149,27 @@ where
     iter: LineColIterator<io::Bytes<R>>,
     /// Lưu trữ tạm thời byte đã xem trước đó 
     ch: Option<u8>,
+    options: private::Options,
+}
+
+/// Nguồn đưa JSON vào được đọc từ một `io::BufRead`.
+///
+/// Khác với [`IoRead`], các byte được lấy trực tiếp từ bộ đệm của reader qua
+/// `fill_buf` thay vì qua một iterator từng byte, và nội dung chuỗi được chép
+/// theo từng đoạn.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct BufIoRead<R>
+where
+    R: io::BufRead,
+{
+    reader: R,
+    /// Lưu trữ tạm thời byte đã xem trước đó
+    ch: Option<u8>,
+    line: usize,
+    col: usize,
+    start_of_line: usize,
+    options: private::Options,
 }
 
 /// Nguồn đầu vào Json từ 1 mảng byte 
@@ -117,6 +178,7 @@ pub struct SliceRead<'a> {
     slice: &'a [u8],
     //Chỉ số của byte tiếp theo sẽ được trả về bởi next() hoặc peek().
     index: usize,
+    options: private::Options,
 }
 
 /// Nguồn đầu vào JSON đọc từ một chuỗi UTF-8.
@@ -130,7 +192,128 @@ pub struct StrRead<'a> {
 // Để ngăn chặn người dùng từ triển khai trait Read
 ///ta có thể sử dụng cách đóng gói (sealing) trait bằng cách sử dụng từ khóa sealed
 mod private {
+    use crate::error::{Error, ErrorCode, Result};
+    use alloc::vec::Vec;
+    use core::ops::Deref;
+
     pub trait Sealed {}
+
+    /// Bộ đệm tạm mà reader ghi các chuỗi cần giải mã vào.
+    pub trait Scratch: Deref<Target = [u8]> {
+        fn clear(&mut self);
+        fn push(&mut self, byte: u8) -> Result<()>;
+        fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<()>;
+        fn pop(&mut self) -> Option<u8>;
+    }
+
+    impl Scratch for Vec<u8> {
+        fn clear(&mut self) {
+            Vec::clear(self);
+        }
+
+        fn push(&mut self, byte: u8) -> Result<()> {
+            Vec::push(self, byte);
+            Ok(())
+        }
+
+        fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<()> {
+            Vec::extend_from_slice(self, bytes);
+            Ok(())
+        }
+
+        fn pop(&mut self) -> Option<u8> {
+            Vec::pop(self)
+        }
+    }
+
+    /// Bộ đệm tạm có dung lượng cố định do người gọi cấp. Ghi vượt quá
+    /// dung lượng trả về lỗi `ErrorCode::ScratchTooSmall` thay vì cấp phát.
+    pub struct FixedScratch<'b> {
+        buf: &'b mut [u8],
+        len: usize,
+    }
+
+    impl<'b> FixedScratch<'b> {
+        pub fn new(buf: &'b mut [u8]) -> Self {
+            FixedScratch { buf, len: 0 }
+        }
+    }
+
+    impl<'b> Deref for FixedScratch<'b> {
+        type Target = [u8];
+
+        fn deref(&self) -> &[u8] {
+            &self.buf[..self.len]
+        }
+    }
+
+    impl<'b> Scratch for FixedScratch<'b> {
+        fn clear(&mut self) {
+            self.len = 0;
+        }
+
+        fn push(&mut self, byte: u8) -> Result<()> {
+            self.extend_from_slice(&[byte])
+        }
+
+        fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<()> {
+            let end = self.len + bytes.len();
+            match self.buf.get_mut(self.len..end) {
+                Some(dst) => {
+                    dst.copy_from_slice(bytes);
+                    self.len = end;
+                    Ok(())
+                }
+                // Vị trí được `Deserializer` điền sau.
+                None => Err(Error::syntax(ErrorCode::ScratchTooSmall, 0, 0)),
+            }
+        }
+
+        fn pop(&mut self) -> Option<u8> {
+            self.len = self.len.checked_sub(1)?;
+            Some(self.buf[self.len])
+        }
+    }
+
+    /// Các tùy chọn đọc chuỗi được đặt trên `Deserializer`.
+    #[derive(Clone, Copy)]
+    pub struct Options {
+        /// Chấp nhận escape `\xHH` và `\u{H..H}`.
+        pub escapes: bool,
+        /// Chấp nhận ký tự điều khiển nằm trực tiếp trong chuỗi.
+        pub control_chars: bool,
+        /// Chuỗi sắp đọc được mở bằng dấu nháy đơn. `Deserializer` bật cờ
+        /// này khi bỏ qua dấu nháy đơn mở chuỗi và reader xoá nó khi bắt đầu
+        /// đọc chuỗi.
+        pub single_quoted: bool,
+        /// Độ dài tối đa tính bằng byte của một chuỗi sau khi giải mã.
+        pub max_string_len: usize,
+        /// Thay các đoạn UTF-8 không hợp lệ bằng U+FFFD thay vì báo lỗi.
+        pub replace_invalid_utf8: bool,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                escapes: false,
+                control_chars: false,
+                single_quoted: false,
+                max_string_len: usize::MAX,
+                replace_invalid_utf8: false,
+            }
+        }
+    }
+
+    impl Options {
+        /// Dấu nháy kết thúc chuỗi sắp đọc.
+        pub fn take_quote(&mut self) -> u8 {
+            if core::mem::replace(&mut self.single_quoted, false) {
+                b'\''
+            } else {
+                b'"'
+            }
+        }
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -145,6 +328,7 @@ where
         IoRead {
             iter: LineColIterator::new(reader.bytes()),
             ch: None,
+            options: private::Options::default(),
         }
     }
 }
@@ -157,9 +341,9 @@ impl<R> IoRead<R>
 where
     R: io::Read,
 {
-    fn parse_str_bytes<'s, T, F>(
+    fn parse_str_bytes<'s, S: Scratch, T, F>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
         validate: bool,
         result: F,
     ) -> Result<T>
@@ -167,24 +351,33 @@ where
         T: 's,
         F: FnOnce(&'s Self, &'s [u8]) -> Result<T>,
     {
+        let quote = self.options.take_quote();
+        let escape = escape_table(quote);
         loop {
             let ch = tri!(next_or_eof(self));
-            if !ESCAPE[ch as usize] {
-                scratch.push(ch);
+            if !escape[ch as usize] {
+                tri!(scratch.push(ch));
+                if scratch.len() > self.options.max_string_len {
+                    return error(self, ErrorCode::StringTooLong);
+                }
                 continue;
             }
             match ch {
-                b'"' => {
+                _ if ch == quote => {
+                    tri!(finish_scratch(self, validate, scratch));
                     return result(self, scratch);
                 }
                 b'\\' => {
-                    tri!(parse_escape(self, validate, scratch));
+                    tri!(parse_escape(self, quote, validate, scratch));
+                    if scratch.len() > self.options.max_string_len {
+                        return error(self, ErrorCode::StringTooLong);
+                    }
                 }
                 _ => {
-                    if validate {
+                    if validate && !self.options.control_chars {
                         return error(self, ErrorCode::ControlCharacterWhileParsingString);
                     }
-                    scratch.push(ch);
+                    tri!(scratch.push(ch));
                 }
             }
         }
@@ -240,6 +433,7 @@ where
         Position {
             line: self.iter.line(),
             column: self.iter.col(),
+            byte_offset: self.iter.byte_offset(),
         }
     }
 
@@ -255,34 +449,44 @@ where
         }
     }
 
-    fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>> {
+    fn parse_str<'s, S: Scratch>(
+
+        &'s mut self,
+
+        scratch: &'s mut S,
+
+    ) -> Result<Reference<'de, 's, str>> {
         self.parse_str_bytes(scratch, true, as_str)
             .map(Reference::Copied)
     }
 
-    fn parse_str_raw<'s>(
+    fn parse_str_raw<'s, S: Scratch>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
     ) -> Result<Reference<'de, 's, [u8]>> {
         self.parse_str_bytes(scratch, false, |_, bytes| Ok(bytes))
             .map(Reference::Copied)
     }
 
     fn ignore_str(&mut self) -> Result<()> {
+        let quote = self.options.take_quote();
+        let escape = escape_table(quote);
         loop {
             let ch = tri!(next_or_eof(self));
-            if !ESCAPE[ch as usize] {
+            if !escape[ch as usize] {
                 continue;
             }
             match ch {
-                b'"' => {
+                _ if ch == quote => {
                     return Ok(());
                 }
                 b'\\' => {
-                    tri!(ignore_escape(self));
+                    tri!(ignore_escape(self, quote));
                 }
                 _ => {
-                    return error(self, ErrorCode::ControlCharacterWhileParsingString);
+                    if !self.options.control_chars {
+                        return error(self, ErrorCode::ControlCharacterWhileParsingString);
+                    }
                 }
             }
         }
@@ -309,6 +513,242 @@ where
     fn set_failed(&mut self, failed: &mut bool) {
         *failed = true;
     }
+
+    fn options(&self) -> private::Options {
+        self.options
+    }
+
+    fn set_options(&mut self, options: private::Options) {
+        self.options = options;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(feature = "std")]
+impl<R> BufIoRead<R>
+where
+    R: io::BufRead,
+{
+    /// Tạo một nguồn JSON đọc từ bộ đệm của một `io::BufRead`.
+    pub fn new(reader: R) -> Self {
+        BufIoRead {
+            reader,
+            ch: None,
+            line: 1,
+            col: 0,
+            start_of_line: 0,
+            options: private::Options::default(),
+        }
+    }
+
+    /// Lấy byte tiếp theo từ bộ đệm của reader và cập nhật vị trí.
+    fn next_from_reader(&mut self) -> Result<Option<u8>> {
+        let ch = match tri!(self.reader.fill_buf().map_err(Error::io)).first() {
+            Some(&ch) => ch,
+            None => return Ok(None),
+        };
+        self.reader.consume(1);
+        if ch == b'\n' {
+            self.start_of_line += self.col + 1;
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
+        }
+        Ok(Some(ch))
+    }
+
+    fn parse_str_bytes<'s, S: Scratch, T, F>(
+        &'s mut self,
+        scratch: &'s mut S,
+        validate: bool,
+        result: F,
+    ) -> Result<T>
+    where
+        T: 's,
+        F: FnOnce(&'s Self, &'s [u8]) -> Result<T>,
+    {
+        let quote = self.options.take_quote();
+        let escape = escape_table(quote);
+        loop {
+            // Chép một lần cả đoạn không cần xử lý. Chuỗi hợp lệ không chứa
+            // ký tự xuống dòng nên chỉ cần cập nhật cột.
+            if self.ch.is_none() {
+                let buf = tri!(self.reader.fill_buf().map_err(Error::io));
+                let n = buf
+                    .iter()
+                    .position(|&ch| escape[ch as usize])
+                    .unwrap_or(buf.len());
+                tri!(scratch.extend_from_slice(&buf[..n]));
+                self.reader.consume(n);
+                self.col += n;
+                if scratch.len() > self.options.max_string_len {
+                    return error(self, ErrorCode::StringTooLong);
+                }
+            }
+
+            let ch = tri!(next_or_eof(self));
+            if !escape[ch as usize] {
+                tri!(scratch.push(ch));
+                continue;
+            }
+            match ch {
+                _ if ch == quote => {
+                    tri!(finish_scratch(self, validate, scratch));
+                    return result(self, scratch);
+                }
+                b'\\' => {
+                    tri!(parse_escape(self, quote, validate, scratch));
+                    if scratch.len() > self.options.max_string_len {
+                        return error(self, ErrorCode::StringTooLong);
+                    }
+                }
+                _ => {
+                    if validate && !self.options.control_chars {
+                        return error(self, ErrorCode::ControlCharacterWhileParsingString);
+                    }
+                    tri!(scratch.push(ch));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> private::Sealed for BufIoRead<R> where R: io::BufRead {}
+
+#[cfg(feature = "std")]
+impl<'de, R> Read<'de> for BufIoRead<R>
+where
+    R: io::BufRead,
+{
+    #[inline]
+    fn next(&mut self) -> Result<Option<u8>> {
+        match self.ch.take() {
+            Some(ch) => Ok(Some(ch)),
+            None => self.next_from_reader(),
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Result<Option<u8>> {
+        match self.ch {
+            Some(ch) => Ok(Some(ch)),
+            None => {
+                self.ch = tri!(self.next_from_reader());
+                Ok(self.ch)
+            }
+        }
+    }
+
+    #[inline]
+    fn discard(&mut self) {
+        self.ch = None;
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.col,
+            byte_offset: self.start_of_line + self.col,
+        }
+    }
+
+    fn peek_position(&self) -> Position {
+        // Giống IoRead, byte được peek đã được tính vào vị trí.
+        self.position()
+    }
+
+    fn byte_offset(&self) -> usize {
+        let offset = self.start_of_line + self.col;
+        match self.ch {
+            Some(_) => offset - 1,
+            None => offset,
+        }
+    }
+
+    fn parse_str<'s, S: Scratch>(
+
+        &'s mut self,
+
+        scratch: &'s mut S,
+
+    ) -> Result<Reference<'de, 's, str>> {
+        self.parse_str_bytes(scratch, true, as_str)
+            .map(Reference::Copied)
+    }
+
+    fn parse_str_raw<'s, S: Scratch>(
+        &'s mut self,
+        scratch: &'s mut S,
+    ) -> Result<Reference<'de, 's, [u8]>> {
+        self.parse_str_bytes(scratch, false, |_, bytes| Ok(bytes))
+            .map(Reference::Copied)
+    }
+
+    fn ignore_str(&mut self) -> Result<()> {
+        let quote = self.options.take_quote();
+        let escape = escape_table(quote);
+        loop {
+            if self.ch.is_none() {
+                let buf = tri!(self.reader.fill_buf().map_err(Error::io));
+                let n = buf
+                    .iter()
+                    .position(|&ch| escape[ch as usize])
+                    .unwrap_or(buf.len());
+                self.reader.consume(n);
+                self.col += n;
+            }
+
+            let ch = tri!(next_or_eof(self));
+            if !escape[ch as usize] {
+                continue;
+            }
+            match ch {
+                _ if ch == quote => {
+                    return Ok(());
+                }
+                b'\\' => {
+                    tri!(ignore_escape(self, quote));
+                }
+                _ => {
+                    if !self.options.control_chars {
+                        return error(self, ErrorCode::ControlCharacterWhileParsingString);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode_hex_escape(&mut self) -> Result<u16> {
+        let mut n = 0;
+        for _ in 0..4 {
+            match decode_hex_val(tri!(next_or_eof(self))) {
+                None => return error(self, ErrorCode::InvalidEscape),
+                Some(val) => {
+                    n = (n << 4) + val;
+                }
+            }
+        }
+        Ok(n)
+    }
+
+    const should_early_return_if_failed: bool = true;
+
+    #[inline]
+    #[cold]
+    fn set_failed(&mut self, failed: &mut bool) {
+        *failed = true;
+    }
+
+    fn options(&self) -> private::Options {
+        self.options
+    }
+
+    fn set_options(&mut self, options: private::Options) {
+        self.options = options;
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -319,12 +759,16 @@ impl<'a> SliceRead<'a> {
         SliceRead {
             slice,
             index: 0,
-
+            options: private::Options::default(),
         }
     }
 
     fn position_of_index(&self, i: usize) -> Position {
-        let mut position = Position { line: 1, column: 0 };
+        let mut position = Position {
+            line: 1,
+            column: 0,
+            byte_offset: i,
+        };
         for ch in &self.slice[..i] {
             match *ch {
                 b'\n' => {
@@ -340,9 +784,9 @@ impl<'a> SliceRead<'a> {
     }
 
     
-    fn parse_str_bytes<'s, T, F>(
+    fn parse_str_bytes<'s, S: Scratch, T, F>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
         validate: bool,
         result: F,
     ) -> Result<Reference<'a, 's, T>>
@@ -352,36 +796,41 @@ impl<'a> SliceRead<'a> {
     {
         // Chỉ số của byte đầu tiên chưa được sao chép vào không gian tạm thời 
         let mut start = self.index;
+        let quote = self.options.take_quote();
+        let escape = escape_table(quote);
 
         loop {
-            while self.index < self.slice.len() && !ESCAPE[self.slice[self.index] as usize] {
+            while self.index < self.slice.len() && !escape[self.slice[self.index] as usize] {
                 self.index += 1;
             }
             if self.index == self.slice.len() {
                 return error(self, ErrorCode::EofWhileParsingString);
             }
             match self.slice[self.index] {
-                b'"' => {
-                    if scratch.is_empty() {
+                ch if ch == quote => {
+                    let rest = &self.slice[start..self.index];
+                    self.index += 1;
+                    if scratch.is_empty() && !needs_replacement(self.options, validate, rest) {
+                        if rest.len() > self.options.max_string_len {
+                            return error(self, ErrorCode::StringTooLong);
+                        }
                         // Trả về 1 slice of Json mà không cần sử dụng sao chép
-                        let borrowed = &self.slice[start..self.index];
-                        self.index += 1;
-                        return result(self, borrowed).map(Reference::Borrowed);
+                        return result(self, rest).map(Reference::Borrowed);
                     } else {
-                        scratch.extend_from_slice(&self.slice[start..self.index]);
-                        self.index += 1;
+                        tri!(scratch.extend_from_slice(rest));
+                        tri!(finish_scratch(self, validate, scratch));
                         return result(self, scratch).map(Reference::Copied);
                     }
                 }
                 b'\\' => {
-                    scratch.extend_from_slice(&self.slice[start..self.index]);
+                    tri!(scratch.extend_from_slice(&self.slice[start..self.index]));
                     self.index += 1;
-                    tri!(parse_escape(self, validate, scratch));
+                    tri!(parse_escape(self, quote, validate, scratch));
                     start = self.index;
                 }
                 _ => {
                     self.index += 1;
-                    if validate {
+                    if validate && !self.options.control_chars {
                         return error(self, ErrorCode::ControlCharacterWhileParsingString);
                     }
                 }
@@ -434,36 +883,47 @@ impl<'a> Read<'a> for SliceRead<'a> {
         self.index
     }
 
-    fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
+    fn parse_str<'s, S: Scratch>(
+
+        &'s mut self,
+
+        scratch: &'s mut S,
+
+    ) -> Result<Reference<'a, 's, str>> {
         self.parse_str_bytes(scratch, true, as_str)
     }
 
-    fn parse_str_raw<'s>(
+    fn parse_str_raw<'s, S: Scratch>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
     ) -> Result<Reference<'a, 's, [u8]>> {
         self.parse_str_bytes(scratch, false, |_, bytes| Ok(bytes))
     }
 
     fn ignore_str(&mut self) -> Result<()> {
+        let quote = self.options.take_quote();
+        let escape = escape_table(quote);
         loop {
-            while self.index < self.slice.len() && !ESCAPE[self.slice[self.index] as usize] {
+            while self.index < self.slice.len() && !escape[self.slice[self.index] as usize] {
                 self.index += 1;
             }
             if self.index == self.slice.len() {
                 return error(self, ErrorCode::EofWhileParsingString);
             }
             match self.slice[self.index] {
-                b'"' => {
+                ch if ch == quote => {
                     self.index += 1;
                     return Ok(());
                 }
                 b'\\' => {
                     self.index += 1;
-                    tri!(ignore_escape(self));
+                    tri!(ignore_escape(self, quote));
                 }
                 _ => {
-                    return error(self, ErrorCode::ControlCharacterWhileParsingString);
+                    if !self.options.control_chars {
+                        return error(self, ErrorCode::ControlCharacterWhileParsingString);
+                    }
+                    self.index += 1;
                 }
             }
         }
@@ -497,6 +957,18 @@ impl<'a> Read<'a> for SliceRead<'a> {
     fn set_failed(&mut self, _failed: &mut bool) {
         self.slice = &self.slice[..self.index];
     }
+
+    fn input(&self) -> Option<&'a [u8]> {
+        Some(self.slice)
+    }
+
+    fn options(&self) -> private::Options {
+        self.options
+    }
+
+    fn set_options(&mut self, options: private::Options) {
+        self.options = options;
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -541,7 +1013,13 @@ impl<'a> Read<'a> for StrRead<'a> {
         self.delegate.byte_offset()
     }
 
-    fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
+    fn parse_str<'s, S: Scratch>(
+
+        &'s mut self,
+
+        scratch: &'s mut S,
+
+    ) -> Result<Reference<'a, 's, str>> {
         self.delegate.parse_str_bytes(scratch, true, |_, bytes| {
             //Tạo nguồn đầu vào JSON để đọc từ chuỗi UTF-8. 
             //Yêu cầu đầu vào phải là &str với bảo đảm UTF-8 và 
@@ -550,9 +1028,9 @@ impl<'a> Read<'a> for StrRead<'a> {
         })
     }
 
-    fn parse_str_raw<'s>(
+    fn parse_str_raw<'s, S: Scratch>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
     ) -> Result<Reference<'a, 's, [u8]>> {
         self.delegate.parse_str_raw(scratch)
     }
@@ -572,6 +1050,18 @@ impl<'a> Read<'a> for StrRead<'a> {
     fn set_failed(&mut self, failed: &mut bool) {
         self.delegate.set_failed(failed);
     }
+
+    fn input(&self) -> Option<&'a [u8]> {
+        self.delegate.input()
+    }
+
+    fn options(&self) -> private::Options {
+        self.delegate.options()
+    }
+
+    fn set_options(&mut self, options: private::Options) {
+        self.delegate.set_options(options);
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -606,13 +1096,19 @@ where
         R::byte_offset(self)
     }
 
-    fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>> {
+    fn parse_str<'s, S: Scratch>(
+
+        &'s mut self,
+
+        scratch: &'s mut S,
+
+    ) -> Result<Reference<'de, 's, str>> {
         R::parse_str(self, scratch)
     }
 
-    fn parse_str_raw<'s>(
+    fn parse_str_raw<'s, S: Scratch>(
         &'s mut self,
-        scratch: &'s mut Vec<u8>,
+        scratch: &'s mut S,
     ) -> Result<Reference<'de, 's, [u8]>> {
         R::parse_str_raw(self, scratch)
     }
@@ -630,6 +1126,18 @@ where
     fn set_failed(&mut self, failed: &mut bool) {
         R::set_failed(self, failed);
     }
+
+    fn input(&self) -> Option<&'de [u8]> {
+        R::input(self)
+    }
+
+    fn options(&self) -> private::Options {
+        R::options(self)
+    }
+
+    fn set_options(&mut self, options: private::Options) {
+        R::set_options(self, options);
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -666,6 +1174,24 @@ static ESCAPE: [bool; 256] = {
     ]
 };
 
+// Như `ESCAPE`, nhưng cho chuỗi mở bằng dấu nháy đơn: chuỗi kết thúc ở dấu
+// nháy đơn và dấu nháy kép chỉ là một ký tự bình thường.
+static ESCAPE_SINGLE_QUOTED: [bool; 256] = {
+    let mut table = ESCAPE;
+    table[b'"' as usize] = false;
+    table[b'\'' as usize] = true;
+    table
+};
+
+/// Bảng các byte làm dừng vòng quét nhanh của một chuỗi kết thúc ở `quote`.
+fn escape_table(quote: u8) -> &'static [bool; 256] {
+    if quote == b'\'' {
+        &ESCAPE_SINGLE_QUOTED
+    } else {
+        &ESCAPE
+    }
+}
+
 fn next_or_eof<'de, R>(read: &mut R) -> Result<u8>
 where
     R: ?Sized + Read<'de>,
@@ -690,8 +1216,30 @@ fn error<'de, R, T>(read: &R, reason: ErrorCode) -> Result<T>
 where
     R: ?Sized + Read<'de>,
 {
-    let position = read.position();
-    Err(Error::syntax(reason, position.line, position.column))
+    Err(Error::syntax_at(reason, read.position(), read.input()))
+}
+
+/// Kiểm tra giới hạn độ dài và áp dụng chính sách UTF-8 lên một chuỗi đã đọc
+/// xong vào `scratch`.
+fn finish_scratch<'de, R, S>(read: &R, validate: bool, scratch: &mut S) -> Result<()>
+where
+    R: ?Sized + Read<'de>,
+    S: Scratch,
+{
+    let options = read.options();
+    if scratch.len() > options.max_string_len {
+        return error(read, ErrorCode::StringTooLong);
+    }
+    if needs_replacement(options, validate, scratch) {
+        let replaced = String::from_utf8_lossy(scratch).into_owned();
+        scratch.clear();
+        tri!(scratch.extend_from_slice(replaced.as_bytes()));
+    }
+    Ok(())
+}
+
+fn needs_replacement(options: private::Options, validate: bool, bytes: &[u8]) -> bool {
+    validate && options.replace_invalid_utf8 && str::from_utf8(bytes).is_err()
 }
 
 fn as_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
@@ -700,29 +1248,41 @@ fn as_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
 
 /// Phân tích một chuỗi escape JSON và gắn nó vào không gian tạm (scratch)
 /// Giả sử byte trước đó đã được đọc là một dấu backslash..
-fn parse_escape<'de, R: Read<'de>>(
+fn parse_escape<'de, R: Read<'de>, S: Scratch>(
     read: &mut R,
+    quote: u8,
     validate: bool,
-    scratch: &mut Vec<u8>,
+    scratch: &mut S,
 ) -> Result<()> {
     let ch = tri!(next_or_eof(read));
 
     match ch {
-        b'"' => scratch.push(b'"'),
-        b'\\' => scratch.push(b'\\'),
-        b'/' => scratch.push(b'/'),
-        b'b' => scratch.push(b'\x08'),
-        b'f' => scratch.push(b'\x0c'),
-        b'n' => scratch.push(b'\n'),
-        b'r' => scratch.push(b'\r'),
-        b't' => scratch.push(b'\t'),
+        b'"' => tri!(scratch.push(b'"')),
+        b'\'' if quote == b'\'' => tri!(scratch.push(b'\'')),
+        b'\\' => tri!(scratch.push(b'\\')),
+        b'/' => tri!(scratch.push(b'/')),
+        b'b' => tri!(scratch.push(b'\x08')),
+        b'f' => tri!(scratch.push(b'\x0c')),
+        b'n' => tri!(scratch.push(b'\n')),
+        b'r' => tri!(scratch.push(b'\r')),
+        b't' => tri!(scratch.push(b'\t')),
+        b'x' if read.options().escapes => {
+            let c = tri!(decode_lenient_escape(read, false));
+            tri!(scratch.extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes()));
+        }
+        b'u' if read.options().escapes && tri!(peek_or_eof(read)) == b'{' => {
+            read.discard();
+            let c = tri!(decode_lenient_escape(read, true));
+            tri!(scratch.extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes()));
+        }
+        b'x' => return error(read, ErrorCode::EscapesNotEnabled),
         b'u' => {
-            fn encode_surrogate(scratch: &mut Vec<u8>, n: u16) {
+            fn encode_surrogate<S: Scratch>(scratch: &mut S, n: u16) -> Result<()> {
                 scratch.extend_from_slice(&[
                     (n >> 12 & 0b0000_1111) as u8 | 0b1110_0000,
                     (n >> 6 & 0b0011_1111) as u8 | 0b1000_0000,
                     (n & 0b0011_1111) as u8 | 0b1000_0000,
-                ]);
+                ])
             }
 
             let c = match tri!(read.decode_hex_escape()) {
@@ -730,8 +1290,7 @@ fn parse_escape<'de, R: Read<'de>>(
                     return if validate {
                         error(read, ErrorCode::LoneLeadingSurrogateInHexEscape)
                     } else {
-                        encode_surrogate(scratch, n);
-                        Ok(())
+                        encode_surrogate(scratch, n)
                     };
                 }
 
@@ -744,8 +1303,7 @@ fn parse_escape<'de, R: Read<'de>>(
                             read.discard();
                             error(read, ErrorCode::UnexpectedEndOfHexEscape)
                         } else {
-                            encode_surrogate(scratch, n1);
-                            Ok(())
+                            encode_surrogate(scratch, n1)
                         };
                     }
 
@@ -756,10 +1314,10 @@ fn parse_escape<'de, R: Read<'de>>(
                             read.discard();
                             error(read, ErrorCode::UnexpectedEndOfHexEscape)
                         } else {
-                            encode_surrogate(scratch, n1);
+                            tri!(encode_surrogate(scratch, n1));
                             // Vì ký tự \ trước byte này bắt đầu một chuỗi escape, 
                             // do đó chúng ta cần phải phân tích nó ngay bây giờ
-                            parse_escape(read, validate, scratch)
+                            parse_escape(read, quote, validate, scratch)
                         };
                     }
 
@@ -783,7 +1341,7 @@ fn parse_escape<'de, R: Read<'de>>(
                 n => char::from_u32(n as u32).unwrap(),
             };
 
-            scratch.extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes());
+            tri!(scratch.extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes()));
         }
         _ => {
             return error(read, ErrorCode::InvalidEscape);
@@ -795,7 +1353,7 @@ fn parse_escape<'de, R: Read<'de>>(
 
 /// Phân tích một chuỗi kí tự đặc biệt trong JSON và loại bỏ giá trị. 
 /// Giả sử ký tự trước đó đã đọc là một dấu gạch chéo ngược.
-fn ignore_escape<'de, R>(read: &mut R) -> Result<()>
+fn ignore_escape<'de, R>(read: &mut R, quote: u8) -> Result<()>
 where
     R: ?Sized + Read<'de>,
 {
@@ -803,6 +1361,15 @@ where
 
     match ch {
         b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => {}
+        b'\'' if quote == b'\'' => {}
+        b'x' if read.options().escapes => {
+            tri!(decode_lenient_escape(read, false));
+        }
+        b'u' if read.options().escapes && tri!(peek_or_eof(read)) == b'{' => {
+            read.discard();
+            tri!(decode_lenient_escape(read, true));
+        }
+        b'x' => return error(read, ErrorCode::EscapesNotEnabled),
         b'u' => {
             // Không quan tâm chuỗi có chắc chắn còn hợp lệ không 
             // không biết chuỗi ptich được thành 1 chuôi hoặc 1 bộ đệm byte
@@ -817,6 +1384,37 @@ where
     Ok(())
 }
 
+/// Đọc phần còn lại của escape không chuẩn `\xHH` (hai chữ số hex) hoặc
+/// `\u{H..H}` (một đến sáu chữ số hex, khi `braced`, sau dấu `{`) và trả về
+/// ký tự tương ứng. Surrogate và giá trị lớn hơn U+10FFFF bị từ chối.
+fn decode_lenient_escape<'de, R>(read: &mut R, braced: bool) -> Result<char>
+where
+    R: ?Sized + Read<'de>,
+{
+    let mut n: u32 = 0;
+    let mut digits = 0;
+    loop {
+        let ch = tri!(next_or_eof(read));
+        if braced && ch == b'}' && digits > 0 {
+            break;
+        }
+        match decode_hex_val(ch) {
+            Some(val) if digits < 6 => {
+                n = (n << 4) + val as u32;
+                digits += 1;
+            }
+            _ => return error(read, ErrorCode::InvalidEscape),
+        }
+        if !braced && digits == 2 {
+            break;
+        }
+    }
+    match char::from_u32(n) {
+        Some(c) => Ok(c),
+        None => error(read, ErrorCode::InvalidUnicodeCodePoint),
+    }
+}
+
 static HEX: [u8; 256] = {
     const __: u8 = 255; // not a hex digit
     [
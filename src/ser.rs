@@ -2,10 +2,17 @@
 
 use crate::error::{Error, ErrorCode, Result};
 use crate::io;
+use crate::value::{F32Policy, NonFinitePolicy, Value};
+
+pub use crate::encoding::BytesEncoding;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt::{self, Display};
 use core::num::FpCategory;
+use core::ops::Range;
+use core::str;
 use serde::ser::{self, Impossible, Serialize};
 
 /// 1 Struct phục vụ cho việc mã hõa dữ liệu trong rust -> json data
@@ -13,6 +20,81 @@ use serde::ser::{self, Impossible, Serialize};
 pub struct Serializer<W, F = CompactFormatter> {
     writer: W,
     formatter: F,
+    // Số mảng/đối tượng đã mở nhưng chưa đóng. Khác 0 sau khi serialize xong
+    // nghĩa là output đang dở dang.
+    depth: usize,
+    max_depth: usize,
+    reject_duplicate_keys: bool,
+    bytes_encoding: BytesEncoding,
+    separator: ValueSeparator,
+    // Đã có giá trị nào được ghi bằng `write_value` chưa.
+    wrote_value: bool,
+    max_string_len: usize,
+    max_key_len: usize,
+    length_overflow: LengthOverflow,
+    f32_policy: F32Policy,
+    non_finite_policy: NonFinitePolicy,
+    escape_solidus: bool,
+    non_string_keys: NonStringKeys,
+}
+
+/// Cách phân tách các giá trị cấp cao nhất được ghi liên tiếp bằng
+/// [`Serializer::write_value`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ValueSeparator {
+    /// Không ghi gì giữa các giá trị. Hai số liền nhau như `1` và `2` sẽ
+    /// thành `12`, nên chỉ nên dùng khi mọi giá trị là mảng, đối tượng hoặc
+    /// chuỗi.
+    Nothing,
+    /// Ghi `\n` sau mỗi giá trị (NDJSON / JSON Lines).
+    Newline,
+    /// Ghi byte record separator `0x1E` trước và `\n` sau mỗi giá trị
+    /// (JSON text sequences, RFC 7464).
+    RecordSeparator,
+}
+
+impl Default for ValueSeparator {
+    fn default() -> Self {
+        ValueSeparator::Nothing
+    }
+}
+
+/// Cách xử lý một chuỗi hoặc khóa dài hơn giới hạn đặt bằng
+/// [`Serializer::set_max_string_len`] hoặc [`Serializer::set_max_key_len`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LengthOverflow {
+    /// Trả về lỗi [`Category::Limit`](crate::error::Category::Limit).
+    Error,
+    /// Cắt bớt phần cuối tại ranh giới ký tự cho vừa giới hạn.
+    Truncate,
+}
+
+impl Default for LengthOverflow {
+    fn default() -> Self {
+        LengthOverflow::Error
+    }
+}
+
+/// Cách ghi khóa của map không phải là chuỗi, ví dụ khóa của một
+/// `BTreeMap<u64, T>`. Xem [`Serializer::set_non_string_keys`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NonStringKeys {
+    /// Khóa số nguyên được ghi trong dấu nháy như `{"1":true}`, khóa `bool`
+    /// như `{"true":1}`, khóa `char` như chuỗi một ký tự. Đây là JSON chuẩn.
+    Quote,
+    /// Khóa số nguyên được ghi không có dấu nháy như `{1:true}`. Đây không
+    /// phải JSON chuẩn, chỉ dùng cho những công cụ nhập liệu đòi hỏi dạng
+    /// này. Khóa `bool` và `char` vẫn được ghi như chuỗi.
+    Unquoted,
+    /// Mọi khóa không phải chuỗi, kể cả số nguyên, `bool` và `char`, đều là
+    /// lỗi.
+    Reject,
+}
+
+impl Default for NonStringKeys {
+    fn default() -> Self {
+        NonStringKeys::Quote
+    }
 }
 
 impl<W> Serializer<W>
@@ -47,7 +129,23 @@ where
     /// 1 writer chỉ định
     #[inline]
     pub fn with_formatter(writer: W, formatter: F) -> Self {
-        Serializer { writer, formatter }
+        Serializer {
+            writer,
+            formatter,
+            depth: 0,
+            max_depth: usize::MAX,
+            reject_duplicate_keys: false,
+            bytes_encoding: BytesEncoding::Array,
+            separator: ValueSeparator::Nothing,
+            wrote_value: false,
+            max_string_len: usize::MAX,
+            max_key_len: usize::MAX,
+            length_overflow: LengthOverflow::Error,
+            f32_policy: F32Policy::Native,
+            non_finite_policy: NonFinitePolicy::Null,
+            escape_solidus: false,
+            non_string_keys: NonStringKeys::Quote,
+        }
     }
 
     /// Trả về giá trị của writer từ `Serializer`
@@ -55,6 +153,505 @@ where
     pub fn into_inner(self) -> W {
         self.writer
     }
+
+    /// Chọn cách ghi chuỗi byte (`serialize_bytes`), ví dụ các trường dùng
+    /// `serde_bytes`. Mặc định là một mảng các số.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::ser::BytesEncoding;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_bytes_encoding(BytesEncoding::Base64);
+    /// serde_bytes::Bytes::new(b"hello").serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), br#""aGVsbG8=""#);
+    /// ```
+    pub fn set_bytes_encoding(&mut self, encoding: BytesEncoding) {
+        self.bytes_encoding = encoding;
+    }
+
+    /// Giới hạn số lớp mảng/đối tượng lồng nhau. Khi vượt quá, serialize trả
+    /// về lỗi [`Category::Limit`](crate::error::Category::Limit) thay vì tiếp
+    /// tục đệ quy, nên có thể serialize an toàn một `Value` nhận từ nơi khác
+    /// mà không lo tràn stack. Mặc định không giới hạn.
+    ///
+    /// Một biến thể enum được ghi thành `{"Variant": ...}` nên chiếm thêm một
+    /// lớp.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::json;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_max_depth(2);
+    /// json!([[1, 2], {"a": 3}]).serialize(&mut ser).unwrap();
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_max_depth(2);
+    /// let err = json!([[[1]]]).serialize(&mut ser).unwrap_err();
+    /// assert!(err.is_limit());
+    /// ```
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Trả về lỗi khi một đối tượng được ghi hai khóa giống nhau, thay vì
+    /// tạo ra JSON có khóa trùng mà nhiều parser khác sẽ âm thầm xử lý theo
+    /// cách riêng. Điều này có thể xảy ra với `collect_map` trên một
+    /// `Vec<(K, V)>` hoặc với các impl `Serialize` tự viết. Mặc định tắt.
+    ///
+    /// Hai khóa được coi là trùng nếu chúng được ghi ra giống hệt nhau, nên
+    /// khóa số `1` và khóa chuỗi `"1"` là trùng.
+    ///
+    /// ```
+    /// use serde::Serializer as _;
+    ///
+    /// let pairs = vec![("id", 1), ("name", 2), ("id", 3)];
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_reject_duplicate_keys(true);
+    /// let err = ser.collect_map(pairs).unwrap_err();
+    /// assert_eq!(err.to_string(), r#"duplicate key "id""#);
+    /// ```
+    pub fn set_reject_duplicate_keys(&mut self, reject: bool) {
+        self.reject_duplicate_keys = reject;
+    }
+
+    /// Chọn cách phân tách các giá trị ghi bằng
+    /// [`write_value`](Serializer::write_value). Mặc định là
+    /// [`ValueSeparator::Nothing`].
+    pub fn set_value_separator(&mut self, separator: ValueSeparator) {
+        self.separator = separator;
+    }
+
+    /// Giới hạn độ dài, tính bằng byte UTF-8 trước khi escape, của mỗi chuỗi
+    /// được ghi ra, trừ khóa của đối tượng. Chuỗi dài hơn được xử lý theo
+    /// [`set_length_overflow`](Serializer::set_length_overflow). Mặc định
+    /// không giới hạn.
+    ///
+    /// Dùng khi hệ thống phía sau từ chối những trường vượt quá một kích
+    /// thước cố định, để lỗi xảy ra ngay khi mã hóa.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::json;
+    /// use serde_json::ser::LengthOverflow;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_max_string_len(5);
+    /// let err = json!({"note": "too long"}).serialize(&mut ser).unwrap_err();
+    /// assert!(err.is_limit());
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_max_string_len(5);
+    /// ser.set_length_overflow(LengthOverflow::Truncate);
+    /// json!({"note": "too long"}).serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), br#"{"note":"too l"}"#);
+    /// ```
+    pub fn set_max_string_len(&mut self, max_len: usize) {
+        self.max_string_len = max_len;
+    }
+
+    /// Giới hạn độ dài, tính bằng byte UTF-8 trước khi escape, của mỗi khóa
+    /// đối tượng, kể cả tên trường của struct và tên biến thể enum. Mặc định
+    /// không giới hạn.
+    pub fn set_max_key_len(&mut self, max_len: usize) {
+        self.max_key_len = max_len;
+    }
+
+    /// Chọn trả về lỗi hay cắt bớt khi một chuỗi hoặc khóa vượt quá giới hạn.
+    /// Mặc định là [`LengthOverflow::Error`].
+    pub fn set_length_overflow(&mut self, overflow: LengthOverflow) {
+        self.length_overflow = overflow;
+    }
+
+    /// Chọn cách ghi một số `f32`. Mặc định là [`F32Policy::Native`]: ghi số
+    /// thập phân ngắn nhất của `f32`, ví dụ `0.1`. Dùng cùng chính sách với
+    /// [`to_value_with`](crate::value::to_value_with) để output của hai cách
+    /// mã hóa giống nhau.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::value::F32Policy;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_f32_policy(F32Policy::Widen);
+    /// 0.1f32.serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), b"0.10000000149011612");
+    /// ```
+    pub fn set_f32_policy(&mut self, policy: F32Policy) {
+        self.f32_policy = policy;
+    }
+
+    /// Chọn cách xử lý NaN và vô cực. Mặc định là [`NonFinitePolicy::Null`]:
+    /// ghi `null`, giống [`to_value`](crate::to_value).
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::value::NonFinitePolicy;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_non_finite_policy(NonFinitePolicy::StringTokens);
+    /// [f32::INFINITY, 2.0].serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), br#"["Infinity",2.0]"#);
+    /// ```
+    pub fn set_non_finite_policy(&mut self, policy: NonFinitePolicy) {
+        self.non_finite_policy = policy;
+    }
+
+    /// Escape dấu `/` trong chuỗi và khóa thành `\/`, để chuỗi như
+    /// `"</script>"` có thể nhúng thẳng vào HTML hoặc đưa cho những bộ đọc cũ
+    /// đòi hỏi điều đó. Mặc định `/` được ghi nguyên.
+    ///
+    /// Giống các ký tự khác cần escape, dấu `/` được ghi qua
+    /// [`Formatter::write_char_escape`] với [`CharEscape::Solidus`], nên một
+    /// formatter tự viết có thể chọn cách viết khác như `\u002f`.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::json;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_escape_solidus(true);
+    /// json!({"a/b": "</script>"}).serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), br#"{"a\/b":"<\/script>"}"#);
+    /// ```
+    pub fn set_escape_solidus(&mut self, escape: bool) {
+        self.escape_solidus = escape;
+    }
+
+    /// Chọn cách ghi khóa của map không phải là chuỗi. Mặc định là
+    /// [`NonStringKeys::Quote`].
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::ser::NonStringKeys;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map: BTreeMap<u64, bool> = [(1, true), (20, false)].into_iter().collect();
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_non_string_keys(NonStringKeys::Unquoted);
+    /// map.serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), b"{1:true,20:false}");
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_non_string_keys(NonStringKeys::Reject);
+    /// let err = map.serialize(&mut ser).unwrap_err();
+    /// assert_eq!(err.to_string(), "key must be a string");
+    /// ```
+    pub fn set_non_string_keys(&mut self, keys: NonStringKeys) {
+        self.non_string_keys = keys;
+    }
+
+    /// Ghi thêm một giá trị cấp cao nhất vào writer, kèm theo dấu phân tách
+    /// đã chọn bằng [`set_value_separator`](Serializer::set_value_separator).
+    /// Nhờ vậy một serializer có thể ghi cả một luồng giá trị thay vì phải tạo
+    /// serializer mới cho mỗi giá trị. Formatter được gọi qua
+    /// [`Formatter::begin_value`] và [`Formatter::end_value`] quanh mỗi giá
+    /// trị.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use serde_json::ser::ValueSeparator;
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// ser.set_value_separator(ValueSeparator::Newline);
+    /// ser.write_value(&json!({"id": 1})).unwrap();
+    /// ser.write_value(&2).unwrap();
+    /// assert_eq!(ser.finish().unwrap(), b"{\"id\":1}\n2\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị không mã hóa được, hoặc nếu giá trị trước đó
+    /// chỉ được ghi một phần (xem [`is_complete`](Serializer::is_complete)),
+    /// vì khi đó luồng output đã hỏng.
+    pub fn write_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        if !self.is_complete() {
+            return Err(ser::Error::custom(
+                "cannot write a value after an incomplete value",
+            ));
+        }
+        if self.separator == ValueSeparator::RecordSeparator {
+            tri!(self.writer.write_all(b"\x1E").map_err(Error::io));
+        }
+        tri!(self
+            .formatter
+            .begin_value(&mut self.writer, !self.wrote_value)
+            .map_err(Error::io));
+        self.wrote_value = true;
+        tri!(value.serialize(&mut *self));
+        tri!(self
+            .formatter
+            .end_value(&mut self.writer)
+            .map_err(Error::io));
+        match self.separator {
+            ValueSeparator::Nothing => Ok(()),
+            ValueSeparator::Newline | ValueSeparator::RecordSeparator => {
+                self.writer.write_all(b"\n").map_err(Error::io)
+            }
+        }
+    }
+
+    /// Flush writer bên dưới.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(Error::io)
+    }
+
+    /// Trả về `false` nếu một mảng hoặc đối tượng đã được mở nhưng chưa được
+    /// đóng, tức là việc serialize một giá trị đã thất bại giữa chừng và
+    /// output chỉ chứa một phần của giá trị đó.
+    pub fn is_complete(&self) -> bool {
+        self.depth == 0
+    }
+
+    /// Flush writer và trả nó về.
+    ///
+    /// Nếu giá trị cuối cùng chỉ được ghi một phần (xem
+    /// [`is_complete`](Serializer::is_complete)), trả về lỗi thay vì writer,
+    /// để nơi gọi không vô tình coi output dở dang là một văn bản JSON hợp lệ.
+    ///
+    /// ```
+    /// use serde::ser::{Serialize, SerializeSeq, Serializer as _};
+    ///
+    /// struct Fails;
+    ///
+    /// impl Serialize for Fails {
+    ///     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    ///         let mut seq = serializer.serialize_seq(None)?;
+    ///         seq.serialize_element(&1)?;
+    ///         Err(serde::ser::Error::custom("connection to database lost"))
+    ///     }
+    /// }
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// assert!(Fails.serialize(&mut ser).is_err());
+    /// assert!(!ser.is_complete());
+    /// assert!(ser.finish().is_err());
+    ///
+    /// let mut ser = serde_json::Serializer::new(Vec::new());
+    /// vec![1, 2].serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.finish().unwrap(), b"[1,2]");
+    /// ```
+    pub fn finish(mut self) -> Result<W> {
+        if !self.is_complete() {
+            return Err(ser::Error::custom(
+                "serializer finished with an incomplete value",
+            ));
+        }
+        tri!(self.flush());
+        Ok(self.writer)
+    }
+
+    /// Ghi NaN hoặc vô cực theo `non_finite_policy`.
+    fn write_non_finite(&mut self, value: f64) -> Result<()> {
+        match self.non_finite_policy {
+            NonFinitePolicy::Null => self
+                .formatter
+                .write_null(&mut self.writer)
+                .map_err(Error::io),
+            NonFinitePolicy::Error => Err(Error::syntax(ErrorCode::FloatMustBeFinite, 0, 0)),
+            NonFinitePolicy::StringTokens => format_escaped_str(
+                &mut self.writer,
+                &mut self.formatter,
+                NonFinitePolicy::token(value),
+                self.escape_solidus,
+            )
+            .map_err(Error::io),
+        }
+    }
+
+    fn check_depth(&self) -> Result<()> {
+        if self.depth >= self.max_depth {
+            return Err(Error::syntax(ErrorCode::RecursionLimitExceeded, 0, 0));
+        }
+        Ok(())
+    }
+
+    /// Ghi một chuỗi, áp dụng giới hạn `max_len` theo `length_overflow`.
+    fn write_limited_str(&mut self, value: &str, max_len: usize, code: ErrorCode) -> Result<()> {
+        let mut value = value;
+        if value.len() > max_len {
+            if self.length_overflow == LengthOverflow::Error {
+                return Err(Error::syntax(code, 0, 0));
+            }
+            let mut end = max_len;
+            while !value.is_char_boundary(end) {
+                end -= 1;
+            }
+            value = &value[..end];
+        }
+        format_escaped_str(
+            &mut self.writer,
+            &mut self.formatter,
+            value,
+            self.escape_solidus,
+        )
+        .map_err(Error::io)
+    }
+
+    fn write_key_str(&mut self, key: &str) -> Result<()> {
+        let max_len = self.max_key_len;
+        self.write_limited_str(key, max_len, ErrorCode::KeyTooLong)
+    }
+}
+
+/// Writer dùng cho chế độ ghi có checkpoint: mọi byte được giữ trong bộ đệm
+/// cho đến checkpoint tiếp theo, nên phần output của một giá trị mã hóa thất
+/// bại giữa chừng có thể bị hủy bằng [`Serializer::rollback`] trước khi nó tới
+/// writer thật, ví dụ một socket.
+///
+/// `flush` ghi phần đang chờ xuống writer bên dưới, giống như một checkpoint.
+///
+/// ```
+/// use serde::ser::{Serialize, SerializeSeq, Serializer as _};
+/// use serde_json::ser::{RollbackWriter, ValueSeparator};
+///
+/// struct Row(Option<u32>);
+///
+/// impl Serialize for Row {
+///     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+///         let mut seq = serializer.serialize_seq(None)?;
+///         seq.serialize_element("row")?;
+///         match self.0 {
+///             Some(id) => seq.serialize_element(&id)?,
+///             None => return Err(serde::ser::Error::custom("missing id")),
+///         }
+///         seq.end()
+///     }
+/// }
+///
+/// let mut ser = serde_json::Serializer::new(RollbackWriter::new(Vec::new()));
+/// ser.set_value_separator(ValueSeparator::Newline);
+/// for row in [Row(Some(1)), Row(None), Row(Some(3))] {
+///     let checkpoint = ser.checkpoint().unwrap();
+///     if ser.write_value(&row).is_err() {
+///         ser.rollback(checkpoint).unwrap();
+///     }
+/// }
+/// let out = ser.finish().unwrap().into_inner();
+/// assert_eq!(out, b"[\"row\",1]\n[\"row\",3]\n");
+/// ```
+#[derive(Debug)]
+pub struct RollbackWriter<W> {
+    inner: W,
+    pending: Vec<u8>,
+    // Tăng mỗi khi phần đang chờ được ghi xuống `inner`; một checkpoint cũ
+    // hơn thế không còn rollback được.
+    generation: u64,
+}
+
+impl<W> RollbackWriter<W>
+where
+    W: io::Write,
+{
+    /// Bọc `inner` để dùng với [`Serializer::checkpoint`] và
+    /// [`Serializer::rollback`].
+    pub fn new(inner: W) -> Self {
+        RollbackWriter {
+            inner,
+            pending: Vec::new(),
+            generation: 0,
+        }
+    }
+
+    /// Trả về writer bên dưới.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Trả về writer bên dưới. Các byte chưa được checkpoint hoặc flush bị
+    /// bỏ đi.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn commit(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            tri!(self.inner.write_all(&self.pending));
+            self.pending.clear();
+            self.generation += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<W> io::Write for RollbackWriter<W>
+where
+    W: io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        tri!(self.commit());
+        self.inner.flush()
+    }
+}
+
+/// Trạng thái của một [`Serializer`] tại một checkpoint, được tạo bởi
+/// [`Serializer::checkpoint`].
+#[derive(Clone, Debug)]
+pub struct Checkpoint<F> {
+    generation: u64,
+    depth: usize,
+    wrote_value: bool,
+    formatter: F,
+}
+
+impl<W, F> Serializer<RollbackWriter<W>, F>
+where
+    W: io::Write,
+    F: Formatter + Clone,
+{
+    /// Ghi mọi byte đang chờ xuống writer bên dưới và ghi nhớ trạng thái hiện
+    /// tại, để [`rollback`](Serializer::rollback) có thể quay lại đây.
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu writer bên dưới trả về lỗi.
+    pub fn checkpoint(&mut self) -> Result<Checkpoint<F>> {
+        tri!(self.writer.commit().map_err(Error::io));
+        Ok(Checkpoint {
+            generation: self.writer.generation,
+            depth: self.depth,
+            wrote_value: self.wrote_value,
+            formatter: self.formatter.clone(),
+        })
+    }
+
+    /// Hủy mọi byte được ghi sau `checkpoint` và khôi phục trạng thái của
+    /// serializer, để output vẫn hợp lệ sau khi mã hóa một giá trị thất bại.
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu đã có một checkpoint hoặc `flush` mới hơn, vì khi đó các
+    /// byte đã được ghi xuống writer bên dưới và không thể hủy nữa.
+    pub fn rollback(&mut self, checkpoint: Checkpoint<F>) -> Result<()> {
+        if checkpoint.generation != self.writer.generation {
+            return Err(ser::Error::custom(
+                "cannot roll back to a checkpoint that was already committed",
+            ));
+        }
+        self.writer.pending.clear();
+        self.depth = checkpoint.depth;
+        self.wrote_value = checkpoint.wrote_value;
+        self.formatter = checkpoint.formatter;
+        Ok(())
+    }
 }
 
 impl<'a, W, F> ser::Serializer for &'a mut Serializer<W, F>
@@ -151,24 +748,19 @@ where
     #[inline]
     fn serialize_f32(self, value: f32) -> Result<()> {
         match value.classify() {
-            FpCategory::Nan | FpCategory::Infinite => self
-                .formatter
-                .write_null(&mut self.writer)
-                .map_err(Error::io),
-            _ => self
-                .formatter
-                .write_f32(&mut self.writer, value)
-                .map_err(Error::io),
+            FpCategory::Nan | FpCategory::Infinite => self.write_non_finite(value as f64),
+            _ => match self.f32_policy {
+                F32Policy::Native => self.formatter.write_f32(&mut self.writer, value),
+                F32Policy::Widen => self.formatter.write_f64(&mut self.writer, value as f64),
+            }
+            .map_err(Error::io),
         }
     }
 
     #[inline]
     fn serialize_f64(self, value: f64) -> Result<()> {
         match value.classify() {
-            FpCategory::Nan | FpCategory::Infinite => self
-                .formatter
-                .write_null(&mut self.writer)
-                .map_err(Error::io),
+            FpCategory::Nan | FpCategory::Infinite => self.write_non_finite(value),
             _ => self
                 .formatter
                 .write_f64(&mut self.writer, value)
@@ -185,17 +777,35 @@ where
 
     #[inline]
     fn serialize_str(self, value: &str) -> Result<()> {
-        format_escaped_str(&mut self.writer, &mut self.formatter, value).map_err(Error::io)
+        let max_len = self.max_string_len;
+        self.write_limited_str(value, max_len, ErrorCode::StringTooLong)
     }
 
     #[inline]
     fn serialize_bytes(self, value: &[u8]) -> Result<()> {
-        use serde::ser::SerializeSeq;
-        let mut seq = tri!(self.serialize_seq(Some(value.len())));
-        for byte in value {
-            tri!(seq.serialize_element(byte));
+        match self.bytes_encoding {
+            BytesEncoding::Array => self
+                .formatter
+                .write_byte_array(&mut self.writer, value)
+                .map_err(Error::io),
+            encoding => {
+                let mut encoded = Vec::new();
+                encoding.encode(value, &mut encoded);
+                // Ký tự base64 và hex không cần escape.
+                let encoded = unsafe { str::from_utf8_unchecked(&encoded) };
+                tri!(self
+                    .formatter
+                    .begin_string(&mut self.writer)
+                    .map_err(Error::io));
+                tri!(self
+                    .formatter
+                    .write_string_fragment(&mut self.writer, encoded)
+                    .map_err(Error::io));
+                self.formatter
+                    .end_string(&mut self.writer)
+                    .map_err(Error::io)
+            }
         }
-        seq.end()
     }
 
     #[inline]
@@ -222,11 +832,23 @@ where
 
     /// Mã hóa các kiểu dữ liệu mới, mà không cần 1 đối tượng bao bọc bên ngoài 
     #[inline]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        match name {
+            #[cfg(any(feature = "decimal", feature = "bigint"))]
+            crate::number::DIGITS_TOKEN => {
+                if let Ok(Value::String(digits)) = value.serialize(crate::value::Serializer) {
+                    return self
+                        .formatter
+                        .write_number_str(&mut self.writer, &digits)
+                        .map_err(Error::io);
+                }
+                value.serialize(self)
+            }
+            _ => value.serialize(self),
+        }
     }
 
     #[inline]
@@ -240,6 +862,8 @@ where
     where
         T: ?Sized + Serialize,
     {
+        tri!(self.check_depth());
+        self.depth += 1;
         tri!(self
             .formatter
             .begin_object(&mut self.writer)
@@ -248,7 +872,7 @@ where
             .formatter
             .begin_object_key(&mut self.writer, true)
             .map_err(Error::io));
-        tri!(self.serialize_str(variant));
+        tri!(self.write_key_str(variant));
         tri!(self
             .formatter
             .end_object_key(&mut self.writer)
@@ -262,6 +886,7 @@ where
             .formatter
             .end_object_value(&mut self.writer)
             .map_err(Error::io));
+        self.depth -= 1;
         self.formatter
             .end_object(&mut self.writer)
             .map_err(Error::io)
@@ -282,6 +907,7 @@ where
 
     #[inline]
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        tri!(self.check_depth());
         tri!(self
             .formatter
             .begin_array(&mut self.writer)
@@ -294,11 +920,14 @@ where
             Ok(Compound::Map {
                 ser: self,
                 state: State::Empty,
+                keys: None,
             })
         } else {
+            self.depth += 1;
             Ok(Compound::Map {
                 ser: self,
                 state: State::First,
+                keys: None,
             })
         }
     }
@@ -325,6 +954,8 @@ where
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
+        tri!(self.check_depth());
+        self.depth += 1;
         tri!(self
             .formatter
             .begin_object(&mut self.writer)
@@ -333,7 +964,7 @@ where
             .formatter
             .begin_object_key(&mut self.writer, true)
             .map_err(Error::io));
-        tri!(self.serialize_str(variant));
+        tri!(self.write_key_str(variant));
         tri!(self
             .formatter
             .end_object_key(&mut self.writer)
@@ -347,6 +978,7 @@ where
 
     #[inline]
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        tri!(self.check_depth());
         tri!(self
             .formatter
             .begin_object(&mut self.writer)
@@ -359,11 +991,19 @@ where
             Ok(Compound::Map {
                 ser: self,
                 state: State::Empty,
+                keys: None,
             })
         } else {
+            self.depth += 1;
+            let keys = if self.reject_duplicate_keys {
+                Some(BTreeSet::new())
+            } else {
+                None
+            };
             Ok(Compound::Map {
                 ser: self,
                 state: State::First,
+                keys,
             })
         }
     }
@@ -383,6 +1023,8 @@ where
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStructVariant> {
+        tri!(self.check_depth());
+        self.depth += 1;
         tri!(self
             .formatter
             .begin_object(&mut self.writer)
@@ -391,7 +1033,7 @@ where
             .formatter
             .begin_object_key(&mut self.writer, true)
             .map_err(Error::io));
-        tri!(self.serialize_str(variant));
+        tri!(self.write_key_str(variant));
         tri!(self
             .formatter
             .end_object_key(&mut self.writer)
@@ -412,6 +1054,7 @@ where
         struct Adapter<'ser, W: 'ser, F: 'ser> {
             writer: &'ser mut W,
             formatter: &'ser mut F,
+            escape_solidus: bool,
             error: Option<io::Error>,
         }
 
@@ -422,7 +1065,12 @@ where
         {
             fn write_str(&mut self, s: &str) -> fmt::Result {
                 debug_assert!(self.error.is_none());
-                match format_escaped_str_contents(self.writer, self.formatter, s) {
+                match format_escaped_str_contents(
+                    self.writer,
+                    self.formatter,
+                    s,
+                    self.escape_solidus,
+                ) {
                     Ok(()) => Ok(()),
                     Err(err) => {
                         self.error = Some(err);
@@ -440,6 +1088,7 @@ where
             let mut adapter = Adapter {
                 writer: &mut self.writer,
                 formatter: &mut self.formatter,
+                escape_solidus: self.escape_solidus,
                 error: None,
             };
             match write!(adapter, "{}", value) {
@@ -470,6 +1119,9 @@ pub enum Compound<'a, W: 'a, F: 'a> {
     Map {
         ser: &'a mut Serializer<W, F>,
         state: State,
+        // Các khóa đã ghi trong đối tượng này, dưới dạng JSON, khi bật
+        // `set_reject_duplicate_keys`.
+        keys: Option<BTreeSet<Vec<u8>>>,
     },
 }
 
@@ -487,7 +1139,7 @@ where
         T: ?Sized + Serialize,
     {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 tri!(ser
                     .formatter
                     .begin_array_value(&mut ser.writer, *state == State::First)
@@ -504,9 +1156,12 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => match state {
+            Compound::Map { ser, state, .. } => match state {
                 State::Empty => Ok(()),
-                _ => ser.formatter.end_array(&mut ser.writer).map_err(Error::io),
+                _ => {
+                    ser.depth -= 1;
+                    ser.formatter.end_array(&mut ser.writer).map_err(Error::io)
+                }
             },
         }
     }
@@ -575,15 +1230,19 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 match state {
                     State::Empty => {}
-                    _ => tri!(ser.formatter.end_array(&mut ser.writer).map_err(Error::io)),
+                    _ => {
+                        ser.depth -= 1;
+                        tri!(ser.formatter.end_array(&mut ser.writer).map_err(Error::io));
+                    }
                 }
                 tri!(ser
                     .formatter
                     .end_object_value(&mut ser.writer)
                     .map_err(Error::io));
+                ser.depth -= 1;
                 ser.formatter.end_object(&mut ser.writer).map_err(Error::io)
             }
         }
@@ -604,7 +1263,19 @@ where
         T: ?Sized + Serialize,
     {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, keys } => {
+                if let Some(keys) = keys {
+                    let mut text = Serializer::new(Vec::new());
+                    text.bytes_encoding = ser.bytes_encoding;
+                    text.max_key_len = ser.max_key_len;
+                    text.length_overflow = ser.length_overflow;
+                    text.non_string_keys = ser.non_string_keys;
+                    tri!(key.serialize(MapKeySerializer { ser: &mut text }));
+                    if keys.contains(&text.writer) {
+                        return Err(duplicate_key(&text.writer));
+                    }
+                    keys.insert(text.writer);
+                }
                 tri!(ser
                     .formatter
                     .begin_object_key(&mut ser.writer, *state == State::First)
@@ -642,9 +1313,12 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => match state {
+            Compound::Map { ser, state, .. } => match state {
                 State::Empty => Ok(()),
-                _ => ser.formatter.end_object(&mut ser.writer).map_err(Error::io),
+                _ => {
+                    ser.depth -= 1;
+                    ser.formatter.end_object(&mut ser.writer).map_err(Error::io)
+                }
             },
         }
     }
@@ -697,15 +1371,19 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 match state {
                     State::Empty => {}
-                    _ => tri!(ser.formatter.end_object(&mut ser.writer).map_err(Error::io)),
+                    _ => {
+                        ser.depth -= 1;
+                        tri!(ser.formatter.end_object(&mut ser.writer).map_err(Error::io));
+                    }
                 }
                 tri!(ser
                     .formatter
                     .end_object_value(&mut ser.writer)
                     .map_err(Error::io));
+                ser.depth -= 1;
                 ser.formatter.end_object(&mut ser.writer).map_err(Error::io)
             }
         }
@@ -716,11 +1394,50 @@ struct MapKeySerializer<'a, W: 'a, F: 'a> {
     ser: &'a mut Serializer<W, F>,
 }
 
+impl<'a, W, F> MapKeySerializer<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    /// Ghi một khóa số nguyên bằng `write`, trong dấu nháy hay không tùy
+    /// theo `non_string_keys`.
+    fn write_integer<G>(self, write: G) -> Result<()>
+    where
+        G: FnOnce(&mut F, &mut W) -> io::Result<()>,
+    {
+        let ser = self.ser;
+        let quote = match ser.non_string_keys {
+            NonStringKeys::Quote => true,
+            NonStringKeys::Unquoted => false,
+            NonStringKeys::Reject => return Err(key_must_be_a_string()),
+        };
+        if quote {
+            tri!(ser
+                .formatter
+                .begin_string(&mut ser.writer)
+                .map_err(Error::io));
+        }
+        tri!(write(&mut ser.formatter, &mut ser.writer).map_err(Error::io));
+        if quote {
+            tri!(ser.formatter.end_string(&mut ser.writer).map_err(Error::io));
+        }
+        Ok(())
+    }
+}
+
 
 fn key_must_be_a_string() -> Error {
     Error::syntax(ErrorCode::KeyMustBeAString, 0, 0)
 }
 
+#[cold]
+fn duplicate_key(text: &[u8]) -> Error {
+    ser::Error::custom(format_args!(
+        "duplicate key {}",
+        String::from_utf8_lossy(text)
+    ))
+}
+
 impl<'a, W, F> ser::Serializer for MapKeySerializer<'a, W, F>
 where
     W: io::Write,
@@ -731,7 +1448,7 @@ where
 
     #[inline]
     fn serialize_str(self, value: &str) -> Result<()> {
-        self.ser.serialize_str(value)
+        self.ser.write_key_str(value)
     }
 
     #[inline]
@@ -741,7 +1458,7 @@ where
         _variant_index: u32,
         variant: &'static str,
     ) -> Result<()> {
-        self.ser.serialize_str(variant)
+        self.ser.write_key_str(variant)
     }
 
     #[inline]
@@ -760,178 +1477,51 @@ where
     type SerializeStruct = Impossible<(), Error>;
     type SerializeStructVariant = Impossible<(), Error>;
 
-    fn serialize_bool(self, _value: bool) -> Result<()> {
-        Err(key_must_be_a_string())
+    fn serialize_bool(self, value: bool) -> Result<()> {
+        if self.ser.non_string_keys == NonStringKeys::Reject {
+            return Err(key_must_be_a_string());
+        }
+        self.ser.serialize_str(if value { "true" } else { "false" })
     }
 
     fn serialize_i8(self, value: i8) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_i8(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_i8(writer, value))
     }
 
     fn serialize_i16(self, value: i16) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_i16(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_i16(writer, value))
     }
 
     fn serialize_i32(self, value: i32) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_i32(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_i32(writer, value))
     }
 
     fn serialize_i64(self, value: i64) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_i64(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_i64(writer, value))
     }
 
     fn serialize_i128(self, value: i128) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_i128(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_i128(writer, value))
     }
 
     fn serialize_u8(self, value: u8) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_u8(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_u8(writer, value))
     }
 
     fn serialize_u16(self, value: u16) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_u16(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_u16(writer, value))
     }
 
     fn serialize_u32(self, value: u32) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_u32(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_u32(writer, value))
     }
 
     fn serialize_u64(self, value: u64) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_u64(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_u64(writer, value))
     }
 
     fn serialize_u128(self, value: u128) -> Result<()> {
-        tri!(self
-            .ser
-            .formatter
-            .begin_string(&mut self.ser.writer)
-            .map_err(Error::io));
-        tri!(self
-            .ser
-            .formatter
-            .write_u128(&mut self.ser.writer, value)
-            .map_err(Error::io));
-        self.ser
-            .formatter
-            .end_string(&mut self.ser.writer)
-            .map_err(Error::io)
+        self.write_integer(|formatter, writer| formatter.write_u128(writer, value))
     }
 
     fn serialize_f32(self, _value: f32) -> Result<()> {
@@ -943,6 +1533,9 @@ where
     }
 
     fn serialize_char(self, value: char) -> Result<()> {
+        if self.ser.non_string_keys == NonStringKeys::Reject {
+            return Err(key_must_be_a_string());
+        }
         self.ser.serialize_str(&value.to_string())
     }
 
@@ -1040,7 +1633,8 @@ pub enum CharEscape {
     Quote,
     /// Kí tự \
     ReverseSolidus,
-    /// Kí tự /
+    /// Kí tự /, chỉ được escape khi bật
+    /// [`Serializer::set_escape_solidus`].
     Solidus,
     /// Phím backspace trên bàn phím, thường được kí hiệu là \b ? 
     Backspace,
@@ -1410,6 +2004,26 @@ pub trait Formatter {
         Ok(())
     }
 
+    /// Ghi một chuỗi byte dưới dạng mảng JSON các số, ví dụ `[1,2,3]`.
+    ///
+    /// Mặc định dùng các hàm `begin_array`, `begin_array_value`, `write_u8`,
+    /// ... nên kết quả giống như khi serialize một `Vec<u8>`.
+    #[inline]
+    fn write_byte_array<W>(&mut self, writer: &mut W, value: &[u8]) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        tri!(self.begin_array(writer));
+        let mut first = true;
+        for byte in value {
+            tri!(self.begin_array_value(writer, first));
+            tri!(self.write_u8(writer, *byte));
+            tri!(self.end_array_value(writer));
+            first = false;
+        }
+        self.end_array(writer)
+    }
+
     /// Writes a raw JSON fragment that doesn't need any escaping to the
     /// specified writer.
     #[inline]
@@ -1419,6 +2033,55 @@ pub trait Formatter {
     {
         writer.write_all(fragment.as_bytes())
     }
+
+    /// Gọi trước mỗi giá trị cấp cao nhất được ghi bằng
+    /// [`Serializer::write_value`]; `first` là `true` với giá trị đầu tiên.
+    /// Mặc định không ghi gì.
+    ///
+    /// Cùng với [`end_value`](Formatter::end_value), cho phép một formatter
+    /// tự ghi dấu phân tách giữa các văn bản trong một luồng, ví dụ một mảng
+    /// JSON được ghi từng phần tử một:
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use serde_json::ser::Formatter;
+    /// use std::io;
+    ///
+    /// struct CommaSeparated;
+    ///
+    /// impl Formatter for CommaSeparated {
+    ///     fn begin_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    ///     where
+    ///         W: ?Sized + io::Write,
+    ///     {
+    ///         writer.write_all(if first { b"[" } else { b"," })
+    ///     }
+    /// }
+    ///
+    /// let mut ser = serde_json::Serializer::with_formatter(Vec::new(), CommaSeparated);
+    /// ser.write_value(&json!({"id": 1})).unwrap();
+    /// ser.write_value(&json!({"id": 2})).unwrap();
+    /// let mut out = ser.into_inner();
+    /// out.push(b']');
+    /// assert_eq!(out, br#"[{"id":1},{"id":2}]"#);
+    /// ```
+    #[inline]
+    fn begin_value<W>(&mut self, _writer: &mut W, _first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        Ok(())
+    }
+
+    /// Gọi sau mỗi giá trị cấp cao nhất được ghi bằng
+    /// [`Serializer::write_value`]. Mặc định không ghi gì.
+    #[inline]
+    fn end_value<W>(&mut self, _writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        Ok(())
+    }
 }
 
 /// This structure compacts a JSON value with no extra whitespace.
@@ -1432,7 +2095,7 @@ impl Formatter for CompactFormatter {}
 pub struct PrettyFormatter<'a> {
     current_indent: usize,
     has_value: bool,
-    indent: &'a [u8],
+    indent: Cow<'a, [u8]>,
 }
 
 impl<'a> PrettyFormatter<'a> {
@@ -1447,9 +2110,74 @@ impl<'a> PrettyFormatter<'a> {
         PrettyFormatter {
             current_indent: 0,
             has_value: false,
-            indent,
+            indent: Cow::Borrowed(indent),
         }
     }
+
+    /// Giống [`with_indent`](Self::with_indent) nhưng giữ luôn chuỗi lùi lề,
+    /// nên formatter không mượn gì và có thể được lưu trong struct cấu hình
+    /// hoặc tạo từ thiết lập lúc chạy.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::ser::{PrettyFormatter, Serializer};
+    ///
+    /// struct Settings {
+    ///     formatter: PrettyFormatter<'static>,
+    /// }
+    ///
+    /// let width = 4;
+    /// let settings = Settings {
+    ///     formatter: PrettyFormatter::with_indent_owned(" ".repeat(width)),
+    /// };
+    ///
+    /// let mut out = Vec::new();
+    /// let mut ser = Serializer::with_formatter(&mut out, settings.formatter.clone());
+    /// serde_json::json!([1]).serialize(&mut ser).unwrap();
+    /// assert_eq!(out, b"[\n    1\n]");
+    /// ```
+    pub fn with_indent_owned<I>(indent: I) -> Self
+    where
+        I: Into<Vec<u8>>,
+    {
+        PrettyFormatter {
+            current_indent: 0,
+            has_value: false,
+            indent: Cow::Owned(indent.into()),
+        }
+    }
+
+    /// Bắt đầu căn lề ở mức `level` thay vì 0, để nhúng kết quả vào giữa
+    /// một tài liệu pretty đã viết sẵn.
+    ///
+    /// Dòng đầu tiên được ghi tại vị trí hiện tại của writer; các dòng sau,
+    /// kể cả dấu đóng ngoặc cuối cùng, được lùi thêm `level` lần `indent`.
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_json::ser::{PrettyFormatter, Serializer};
+    ///
+    /// let mut out = b"{\n  \"kind\": \"order\",\n  \"body\": ".to_vec();
+    /// let formatter = PrettyFormatter::new().indent_level(1);
+    /// let mut ser = Serializer::with_formatter(&mut out, formatter);
+    /// serde_json::json!({"id": 7, "tags": ["a"]}).serialize(&mut ser).unwrap();
+    /// out.extend_from_slice(b"\n}");
+    ///
+    /// let expected = r#"{
+    ///   "kind": "order",
+    ///   "body": {
+    ///     "id": 7,
+    ///     "tags": [
+    ///       "a"
+    ///     ]
+    ///   }
+    /// }"#;
+    /// assert_eq!(String::from_utf8(out).unwrap(), expected);
+    /// ```
+    pub fn indent_level(mut self, level: usize) -> Self {
+        self.current_indent = level;
+        self
+    }
 }
 
 impl<'a> Default for PrettyFormatter<'a> {
@@ -1478,7 +2206,7 @@ impl<'a> Formatter for PrettyFormatter<'a> {
 
         if self.has_value {
             tri!(writer.write_all(b"\n"));
-            tri!(indent(writer, self.current_indent, self.indent));
+            tri!(indent(writer, self.current_indent, &self.indent));
         }
 
         writer.write_all(b"]")
@@ -1490,7 +2218,7 @@ impl<'a> Formatter for PrettyFormatter<'a> {
         W: ?Sized + io::Write,
     {
         tri!(writer.write_all(if first { b"\n" } else { b",\n" }));
-        indent(writer, self.current_indent, self.indent)
+        indent(writer, self.current_indent, &self.indent)
     }
 
     #[inline]
@@ -1521,7 +2249,7 @@ impl<'a> Formatter for PrettyFormatter<'a> {
 
         if self.has_value {
             tri!(writer.write_all(b"\n"));
-            tri!(indent(writer, self.current_indent, self.indent));
+            tri!(indent(writer, self.current_indent, &self.indent));
         }
 
         writer.write_all(b"}")
@@ -1533,7 +2261,7 @@ impl<'a> Formatter for PrettyFormatter<'a> {
         W: ?Sized + io::Write,
     {
         tri!(writer.write_all(if first { b"\n" } else { b",\n" }));
-        indent(writer, self.current_indent, self.indent)
+        indent(writer, self.current_indent, &self.indent)
     }
 
     #[inline]
@@ -1554,13 +2282,23 @@ impl<'a> Formatter for PrettyFormatter<'a> {
     }
 }
 
-fn format_escaped_str<W, F>(writer: &mut W, formatter: &mut F, value: &str) -> io::Result<()>
+pub(crate) fn format_escaped_str<W, F>(
+    writer: &mut W,
+    formatter: &mut F,
+    value: &str,
+    escape_solidus: bool,
+) -> io::Result<()>
 where
     W: ?Sized + io::Write,
     F: ?Sized + Formatter,
 {
     tri!(formatter.begin_string(writer));
-    tri!(format_escaped_str_contents(writer, formatter, value));
+    tri!(format_escaped_str_contents(
+        writer,
+        formatter,
+        value,
+        escape_solidus
+    ));
     formatter.end_string(writer)
 }
 
@@ -1568,6 +2306,7 @@ fn format_escaped_str_contents<W, F>(
     writer: &mut W,
     formatter: &mut F,
     value: &str,
+    escape_solidus: bool,
 ) -> io::Result<()>
 where
     W: ?Sized + io::Write,
@@ -1576,21 +2315,40 @@ where
     let bytes = value.as_bytes();
 
     let mut start = 0;
-
-    for (i, &byte) in bytes.iter().enumerate() {
-        let escape = ESCAPE[byte as usize];
-        if escape == 0 {
-            continue;
+    let mut i = 0;
+    let mut next = find_escape(bytes, 0);
+
+    loop {
+        // Giữ lại vị trí `find_escape` đã tìm được trong lúc ghi các dấu `/`
+        // đứng trước nó, để mỗi byte chỉ bị dò một lần.
+        if next < i {
+            next = find_escape(bytes, i);
+        }
+        i = if escape_solidus {
+            match bytes[i..next].iter().position(|&b| b == b'/') {
+                Some(slash) => i + slash,
+                None => next,
+            }
+        } else {
+            next
+        };
+        if i == bytes.len() {
+            break;
         }
 
         if start < i {
             tri!(formatter.write_string_fragment(writer, &value[start..i]));
         }
 
-        let char_escape = CharEscape::from_escape_table(escape, byte);
+        let byte = bytes[i];
+        let char_escape = match byte {
+            b'/' => CharEscape::Solidus,
+            _ => CharEscape::from_escape_table(ESCAPE[byte as usize], byte),
+        };
         tri!(formatter.write_char_escape(writer, char_escape));
 
-        start = i + 1;
+        i += 1;
+        start = i;
     }
 
     if start == bytes.len() {
@@ -1600,6 +2358,76 @@ where
     formatter.write_string_fragment(writer, &value[start..])
 }
 
+// Trả về vị trí của byte đầu tiên từ `from` trở đi cần escape, hoặc
+// `bytes.len()` nếu không còn byte nào.
+#[cfg(not(feature = "simd"))]
+#[inline]
+fn find_escape(bytes: &[u8], from: usize) -> usize {
+    match bytes[from..].iter().position(|&b| ESCAPE[b as usize] != 0) {
+        Some(i) => from + i,
+        None => bytes.len(),
+    }
+}
+
+// Với feature "simd", các đoạn không cần escape được bỏ qua theo khối 16 byte
+// (SSE2 trên x86_64) hoặc 8 byte (SWAR trên các kiến trúc khác); phần đuôi và
+// khối chứa byte cần escape được dò lại bằng bảng `ESCAPE`.
+#[cfg(feature = "simd")]
+#[inline]
+fn find_escape(bytes: &[u8], from: usize) -> usize {
+    let mut i = from;
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        use core::arch::x86_64::*;
+
+        // SSE2 luôn có trên x86_64.
+        unsafe {
+            let below = _mm_set1_epi8(0x1F);
+            let quote = _mm_set1_epi8(b'"' as i8);
+            let backslash = _mm_set1_epi8(b'\\' as i8);
+            while i + 16 <= bytes.len() {
+                let chunk = _mm_loadu_si128(bytes.as_ptr().add(i) as *const __m128i);
+                // max(b, 0x1F) == 0x1F khi và chỉ khi b <= 0x1F (so sánh không dấu).
+                let control = _mm_cmpeq_epi8(_mm_max_epu8(chunk, below), below);
+                let special = _mm_or_si128(
+                    _mm_cmpeq_epi8(chunk, quote),
+                    _mm_cmpeq_epi8(chunk, backslash),
+                );
+                let mask = _mm_movemask_epi8(_mm_or_si128(control, special));
+                if mask != 0 {
+                    return i + mask.trailing_zeros() as usize;
+                }
+                i += 16;
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        const ONES: u64 = 0x0101_0101_0101_0101;
+        const HIGH: u64 = 0x8080_8080_8080_8080;
+        let has_zero = |x: u64| x.wrapping_sub(ONES) & !x & HIGH;
+        while i + 8 <= bytes.len() {
+            let mut word = [0; 8];
+            word.copy_from_slice(&bytes[i..i + 8]);
+            let x = u64::from_ne_bytes(word);
+            let control = x.wrapping_sub(ONES * 0x20) & !x & HIGH;
+            if control | has_zero(x ^ (ONES * b'"' as u64)) | has_zero(x ^ (ONES * b'\\' as u64))
+                != 0
+            {
+                break;
+            }
+            i += 8;
+        }
+    }
+
+    match bytes[i..].iter().position(|&b| ESCAPE[b as usize] != 0) {
+        Some(j) => i + j,
+        None => bytes.len(),
+    }
+}
+
 const BB: u8 = b'b'; // \x08
 const TT: u8 = b't'; // \x09
 const NN: u8 = b'n'; // \x0A
@@ -1667,6 +2495,61 @@ where
     value.serialize(&mut ser)
 }
 
+/// Mã hóa rust data -> json data -> Luồng IO, thông qua một bộ đệm
+///
+/// `to_writer` ghi thẳng từng token vào writer, nên với một writer không có
+/// bộ đệm như `File` hay `TcpStream` mỗi token là một lần gọi hệ thống.
+/// Hàm này bọc writer trong một `io::BufWriter` và flush khi kết thúc.
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// # fn main() -> std::io::Result<()> {
+/// let path = std::env::temp_dir().join("serde_json_to_writer_buffered.json");
+/// let file = std::fs::File::create(&path)?;
+/// serde_json::to_writer_buffered(file, &json!({"id": 1}))?;
+/// assert_eq!(std::fs::read_to_string(&path)?, r#"{"id":1}"#);
+/// # std::fs::remove_file(&path)
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại,
+/// T có 1 map với key không phải là string, hoặc khi flush writer thất bại
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn to_writer_buffered<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    let mut writer = std::io::BufWriter::new(writer);
+    tri!(to_writer(&mut writer, value));
+    io::Write::flush(&mut writer).map_err(Error::io)
+}
+
+/// Mã hóa rust data -> json data -> Luồng IO, dạng pretty và thông qua một
+/// bộ đệm
+///
+/// Xem [`to_writer_buffered`].
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại,
+/// T có 1 map với key không phải là string, hoặc khi flush writer thất bại
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn to_writer_pretty_buffered<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    let mut writer = std::io::BufWriter::new(writer);
+    tri!(to_writer_pretty(&mut writer, value));
+    io::Write::flush(&mut writer).map_err(Error::io)
+}
+
 /// Mã hóa rust data -> json data -> Luồng IO 
 ///
 /// # Errors
@@ -1737,6 +2620,433 @@ where
     Ok(string)
 }
 
+/// Mã hóa rust data -> json data -> `Vec<u8>` dạng pretty, lùi lề mỗi cấp
+/// bằng `indent` thay vì hai khoảng trắng
+///
+/// Xem [`to_string_pretty_with_indent`].
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại
+/// hoặc T có 1 map với key không phải là string
+#[inline]
+pub fn to_vec_pretty_with_indent<T>(value: &T, indent: &str) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+    tri!(value.serialize(&mut Serializer::with_formatter(&mut writer, formatter)));
+    Ok(writer)
+}
+
+/// Mã hóa rust data -> json data -> `String` dạng pretty, lùi lề mỗi cấp
+/// bằng `indent` thay vì hai khoảng trắng
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// let value = json!({"tags": ["a", "b"]});
+/// let tabs = serde_json::to_string_pretty_with_indent(&value, "\t").unwrap();
+/// assert_eq!(tabs, "{\n\t\"tags\": [\n\t\t\"a\",\n\t\t\"b\"\n\t]\n}");
+///
+/// let four = serde_json::to_string_pretty_with_indent(&value, "    ").unwrap();
+/// assert!(four.contains("\n        \"a\""));
+/// ```
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại
+/// hoặc T có 1 map với key không phải là string
+#[inline]
+pub fn to_string_pretty_with_indent<T>(value: &T, indent: &str) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let vec = tri!(to_vec_pretty_with_indent(value, indent));
+    let string = unsafe {
+        // We do not emit invalid UTF-8, and `indent` is a `str`.
+        String::from_utf8_unchecked(vec)
+    };
+    Ok(string)
+}
+
+/// Mã hóa rust data -> json data -> `Vec<u8>`, cấp phát trước `capacity` byte
+///
+/// `to_vec` bắt đầu với 128 byte và nới rộng dần, nên một output vài MB phải
+/// cấp phát và sao chép lại hàng chục lần. Khi đã biết gần đúng kích thước
+/// output, ví dụ từ lần mã hóa trước của cùng loại dữ liệu, hãy truyền nó vào
+/// đây để chỉ cấp phát một lần.
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// let rows = json!([{"id": 1}, {"id": 2}]);
+/// let bytes = serde_json::to_vec_with_capacity(&rows, 4096).unwrap();
+/// assert_eq!(bytes, br#"[{"id":1},{"id":2}]"#);
+/// assert!(bytes.capacity() >= 4096);
+/// ```
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại
+/// hoặc T có 1 map với key không phải là string
+#[inline]
+pub fn to_vec_with_capacity<T>(value: &T, capacity: usize) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = Vec::with_capacity(capacity);
+    tri!(to_writer(&mut writer, value));
+    Ok(writer)
+}
+
+/// Mã hóa rust data -> json data -> `String`, cấp phát trước `capacity` byte
+///
+/// Xem [`to_vec_with_capacity`].
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại
+/// hoặc T có 1 map với key không phải là string
+#[inline]
+pub fn to_string_with_capacity<T>(value: &T, capacity: usize) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let vec = tri!(to_vec_with_capacity(value, capacity));
+    let string = unsafe {
+        // We do not emit invalid UTF-8.
+        String::from_utf8_unchecked(vec)
+    };
+    Ok(string)
+}
+
+/// Mã hóa rust data -> json data -> `String` dạng gọn, dài không quá
+/// `max_len` byte, dành cho việc ghi log an toàn các dữ liệu có thể rất lớn.
+///
+/// Khi kết quả đầy đủ dài hơn `max_len`, những chuỗi dài được cắt bớt thành
+/// `"đầu chuỗi…(+N more)"` với N là số ký tự bị bỏ, những mảng dài chỉ giữ các
+/// phần tử đầu và thêm phần tử `"…(+N more)"`, những đối tượng lớn chỉ giữ các
+/// thành viên đầu và thêm thành viên `"…": "(+N more)"`, giống
+/// [`Value::summarize`]. Giới hạn được giảm dần cho đến khi kết quả vừa
+/// `max_len`, nên cấu trúc vẫn là JSON hợp lệ.
+/// Chỉ khi ngay cả khung cấu trúc cũng không vừa, kết quả mới bị cắt thẳng tại
+/// ranh giới ký tự và kết thúc bằng `…`.
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// let body = json!({"user": "ana", "blob": "x".repeat(1000), "ids": (0..100).collect::<Vec<_>>()});
+/// let logged = serde_json::to_string_truncated(&body, 80).unwrap();
+/// assert!(logged.len() <= 80);
+/// assert!(logged.contains("more)"));
+/// serde_json::from_str::<serde_json::Value>(&logged).unwrap();
+///
+/// assert_eq!(serde_json::to_string_truncated(&json!([1, 2]), 80).unwrap(), "[1,2]");
+/// ```
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại
+/// hoặc T có 1 map với key không phải là string
+pub fn to_string_truncated<T>(value: &T, max_len: usize) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let value = tri!(crate::value::to_value(value));
+    fit_to_len(&value, max_len, to_string)
+}
+
+/// Mã hóa `value` bằng `render`, rút gọn dần như [`to_string_truncated`] cho
+/// đến khi kết quả dài không quá `max_len` byte.
+fn fit_to_len<F>(value: &Value, max_len: usize, render: F) -> Result<String>
+where
+    F: Fn(&Value) -> Result<String>,
+{
+    let render = |limit| render(&value.summarize(limit, limit));
+
+    // Giảm một nửa giới hạn cho đến khi vừa, rồi tìm nhị phân giới hạn lớn
+    // nhất còn vừa giữa hai mốc đó.
+    let mut too_long = max_len.saturating_add(1);
+    let mut limit = max_len;
+    let mut first = None;
+    let mut best = loop {
+        let string = tri!(render(limit));
+        if string.len() <= max_len {
+            break string;
+        }
+        if limit == 0 {
+            // Không giới hạn nào vừa: cắt thẳng bản ít bị rút gọn nhất.
+            return Ok(cut_str(first.unwrap_or(string), max_len));
+        }
+        first.get_or_insert(string);
+        too_long = limit;
+        limit /= 2;
+    };
+    while too_long - limit > 1 {
+        let mid = limit + (too_long - limit) / 2;
+        let string = tri!(render(mid));
+        if string.len() <= max_len {
+            limit = mid;
+            best = string;
+        } else {
+            too_long = mid;
+        }
+    }
+    Ok(best)
+}
+
+/// Các tùy chọn của [`to_log_string`].
+///
+/// Mặc định không che hay lọc khóa nào và giới hạn kết quả ở 4096 byte.
+///
+/// ```
+/// use serde_json::LogOptions;
+///
+/// let options = LogOptions::new()
+///     .redact(&["password", "authorization"])
+///     .max_len(1024);
+/// # let _ = options;
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogOptions {
+    redact: Vec<String>,
+    allow: Option<Vec<String>>,
+    max_len: usize,
+}
+
+impl LogOptions {
+    /// Tạo các tùy chọn mặc định.
+    pub fn new() -> Self {
+        LogOptions {
+            redact: Vec::new(),
+            allow: None,
+            max_len: 4096,
+        }
+    }
+
+    /// Thay giá trị của các thành viên có khóa này, ở mọi độ sâu, bằng
+    /// `"[REDACTED]"`. Khóa được so khớp không phân biệt hoa thường ASCII.
+    /// Gọi lại sẽ thêm vào danh sách.
+    pub fn redact<S>(mut self, keys: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.redact
+            .extend(keys.iter().map(|key| String::from(key.as_ref())));
+        self
+    }
+
+    /// Chỉ giữ lại các thành viên có khóa này, ở mọi độ sâu; mọi thành viên
+    /// khác bị bỏ. Khóa của các đối tượng lồng nhau cũng phải được liệt kê.
+    /// Gọi lại sẽ thêm vào danh sách.
+    pub fn allow<S>(mut self, keys: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.allow
+            .get_or_insert_with(Vec::new)
+            .extend(keys.iter().map(|key| String::from(key.as_ref())));
+        self
+    }
+
+    /// Độ dài tối đa của kết quả, tính bằng byte. Xem
+    /// [`to_string_truncated`] về cách rút gọn.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    fn filter(&self, value: &mut Value) {
+        let matches = |list: &[String], key: &str| list.iter().any(|k| k.eq_ignore_ascii_case(key));
+        match value {
+            Value::Array(list) => {
+                for element in list {
+                    self.filter(element);
+                }
+            }
+            Value::Object(map) => {
+                if let Some(allow) = &self.allow {
+                    map.retain(|key, _| matches(allow, key));
+                }
+                for (key, member) in map.iter_mut() {
+                    if matches(&self.redact, key) {
+                        *member = Value::String(String::from("[REDACTED]"));
+                    } else {
+                        self.filter(member);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        LogOptions::new()
+    }
+}
+
+/// Mã hóa rust data -> json data -> `String` để ghi vào log: luôn là JSON dạng
+/// gọn trên đúng một dòng, đã che và lọc khóa theo `options`, và dài không
+/// quá giới hạn trong `options`.
+///
+/// Ngoài `\n` và `\r` vốn luôn được escape, các ký tự U+2028 và U+2029 mà một
+/// số công cụ đọc log coi là xuống dòng cũng được escape thành `\u2028` và
+/// `\u2029`.
+///
+/// ```
+/// # use serde_json::json;
+/// use serde_json::LogOptions;
+///
+/// let request = json!({
+///     "user": "ana",
+///     "password": "hunter2",
+///     "note": "line one\nline two\u{2028}",
+/// });
+/// let options = LogOptions::new().redact(&["Password"]);
+/// assert_eq!(
+///     serde_json::to_log_string(&request, &options).unwrap(),
+///     r#"{"note":"line one\nline two\u2028","password":"[REDACTED]","user":"ana"}"#,
+/// );
+/// ```
+///
+/// # Errors
+///
+/// việc mã hóa có thể thất bại nếu như quá trình triển khai mã hóa của T thất bại
+/// hoặc T có 1 map với key không phải là string
+pub fn to_log_string<T>(value: &T, options: &LogOptions) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let mut value = tri!(crate::value::to_value(value));
+    options.filter(&mut value);
+    fit_to_len(&value, options.max_len, |value| {
+        let string = tri!(to_string(value));
+        // Hai ký tự này chỉ có thể xuất hiện bên trong chuỗi JSON, nơi escape
+        // `\uXXXX` luôn hợp lệ.
+        if string.contains(&['\u{2028}', '\u{2029}'][..]) {
+            Ok(string
+                .replace('\u{2028}', "\\u2028")
+                .replace('\u{2029}', "\\u2029"))
+        } else {
+            Ok(string)
+        }
+    })
+}
+
+/// Thay các giá trị tại những JSON Pointer trong `edits` bên trong văn bản
+/// JSON `source`, giữ nguyên từng byte còn lại: khoảng trắng, thụt lề, thứ
+/// tự khoá và cách viết số của các phần không bị sửa.
+///
+/// Nhẹ hơn việc dựng cả tài liệu thành `Value` rồi mã hóa lại, vốn làm mất
+/// định dạng gốc, cho những công cụ kiểu "tăng trường version". Giá trị mới
+/// được viết gọn nếu giá trị cũ nằm trên một dòng; nếu không, nó được in đẹp
+/// với thụt lề theo dòng chứa giá trị cũ.
+///
+/// ```
+/// # use serde_json::json;
+/// #
+/// let source = r#"{
+///     "name": "app",
+///     "version": "1.2.3",
+///     "deps": { "a": "^1" }
+/// }"#;
+/// let patched = serde_json::patch_str(source, &[
+///     ("/version", json!("1.3.0")),
+///     ("/deps/a", json!({"version": "^2", "optional": true})),
+/// ]).unwrap();
+/// assert_eq!(patched, r#"{
+///     "name": "app",
+///     "version": "1.3.0",
+///     "deps": { "a": {"optional":true,"version":"^2"} }
+/// }"#);
+/// ```
+///
+/// # Errors
+///
+/// Trả về lỗi nếu `source` không phải JSON hợp lệ, nếu một pointer không trỏ
+/// tới giá trị nào, hoặc nếu hai pointer trùng nhau hay lồng vào nhau.
+pub fn patch_str<S>(source: &str, edits: &[(S, Value)]) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let (_, spans) = tri!(crate::de::from_str_with_spans(source));
+    let mut replacements = Vec::with_capacity(edits.len());
+    for (pointer, value) in edits {
+        let pointer = pointer.as_ref();
+        match spans.get(pointer) {
+            Some(range) => replacements.push((range, pointer, value)),
+            None => {
+                return Err(ser::Error::custom(format_args!(
+                    "no value at pointer `{}`",
+                    pointer
+                )))
+            }
+        }
+    }
+    replacements.sort_by_key(|(range, _, _)| range.start);
+    for pair in replacements.windows(2) {
+        if pair[1].0.start < pair[0].0.end {
+            return Err(ser::Error::custom(format_args!(
+                "edits at `{}` and `{}` overlap",
+                pair[0].1, pair[1].1
+            )));
+        }
+    }
+
+    let mut patched = String::with_capacity(source.len());
+    let mut copied = 0;
+    for (range, _, value) in replacements {
+        patched.push_str(&source[copied..range.start]);
+        patched.push_str(&tri!(render_in_place(source, range.clone(), value)));
+        copied = range.end;
+    }
+    patched.push_str(&source[copied..]);
+    Ok(patched)
+}
+
+/// Mã hóa `value` để thay cho đoạn `range` của `source`, theo cách
+/// [`patch_str`] mô tả.
+fn render_in_place(source: &str, range: Range<usize>, value: &Value) -> Result<String> {
+    let old = &source[range.clone()];
+    if !old.contains('\n') {
+        return to_string(value);
+    }
+    let line_start = source[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let base = leading_whitespace(&source[line_start..]);
+    // Thụt lề của một cấp được lấy từ dòng thứ hai của giá trị cũ.
+    let unit = old
+        .split('\n')
+        .nth(1)
+        .and_then(|line| leading_whitespace(line).strip_prefix(base))
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or("  ");
+    let pretty = tri!(to_string_pretty_with_indent(value, unit));
+    Ok(pretty.replace('\n', &alloc::format!("\n{}", base)))
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    let len = line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+    &line[..len]
+}
+
+/// Cắt `string` tại ranh giới ký tự sao cho cả dấu `…` cuối vẫn vừa `max_len`.
+fn cut_str(mut string: String, max_len: usize) -> String {
+    let ellipsis = "\u{2026}";
+    let mut end = max_len.saturating_sub(ellipsis.len());
+    while !string.is_char_boundary(end) {
+        end -= 1;
+    }
+    string.truncate(end);
+    if ellipsis.len() <= max_len {
+        string.push_str(ellipsis);
+    }
+    string
+}
+
 fn indent<W>(wr: &mut W, n: usize, s: &[u8]) -> io::Result<()>
 where
     W: ?Sized + io::Write,
@@ -0,0 +1,183 @@
+//! Giá trị kèm vị trí của nó trong tài liệu JSON nguồn.
+
+use crate::read::Position;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Range;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Tên struct mà `Deserializer` của crate này nhận ra để ghi lại vị trí.
+pub(crate) const NAME: &str = "$serde_json::private::Spanned";
+/// Khóa mang giá trị bên trong.
+pub(crate) const VALUE: &str = "$serde_json::private::Spanned::value";
+/// Khóa mang vị trí đầu và cuối, dưới dạng sáu số
+/// `[line, column, byte_offset]` của từng vị trí.
+pub(crate) const SPAN: &str = "$serde_json::private::Spanned::span";
+
+const FIELDS: &[&str] = &[VALUE, SPAN];
+
+/// Một giá trị cùng với vị trí bắt đầu và kết thúc của nó trong đầu vào.
+///
+/// Dùng làm kiểu của một trường để biết giá trị của trường đó nằm ở đâu
+/// trong tài liệu, chẳng hạn để báo lỗi kiểm tra cấu hình đúng dòng. Vị trí
+/// chỉ được ghi lại khi giải mã bằng [`Deserializer`](crate::Deserializer)
+/// của crate này; các deserializer khác, kể cả `Value`, trả về lỗi. Cũng vì
+/// thế `Spanned` không dùng được bên trong `#[serde(flatten)]` hay enum
+/// `untagged`, vốn giải mã qua bộ đệm trung gian.
+///
+/// `Spanned` được mã hóa y như giá trị bên trong, và phép so sánh, hash chỉ
+/// xét giá trị, không xét vị trí.
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::de::Position;
+/// use serde_json::Spanned;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: Spanned<u16>,
+/// }
+///
+/// let data = "{\n  \"name\": \"web\",\n  \"port\": 8080\n}";
+/// let config: Config = serde_json::from_str(data).unwrap();
+///
+/// assert_eq!(*config.port.get_ref(), 8080);
+/// assert_eq!(&data[config.port.span()], "8080");
+/// assert_eq!(config.port.start(), Position { line: 3, column: 10, byte_offset: 29 });
+/// assert_eq!(config.port.end(), Position { line: 3, column: 14, byte_offset: 33 });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Spanned<T> {
+    start: Position,
+    end: Position,
+    value: T,
+}
+
+impl<T> Spanned<T> {
+    /// Tạo một `Spanned` từ giá trị và hai vị trí cho trước.
+    pub fn new(value: T, start: Position, end: Position) -> Self {
+        Spanned { start, end, value }
+    }
+
+    /// Vị trí ngay trước byte đầu tiên của giá trị.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Vị trí ngay sau byte cuối cùng của giá trị.
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Khoảng byte của giá trị trong đầu vào, dùng được để cắt lại văn bản
+    /// gốc.
+    pub fn span(&self) -> Range<usize> {
+        self.start.byte_offset..self.end.byte_offset
+    }
+
+    /// Tham chiếu đến giá trị bên trong.
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Tham chiếu khả biến đến giá trị bên trong.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Bỏ vị trí và trả về giá trị bên trong.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Spanned<T> {}
+
+impl<T: PartialOrd> PartialOrd for Spanned<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for Spanned<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Hash> Hash for Spanned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T: Serialize> Serialize for Spanned<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Spanned<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(NAME, FIELDS, SpannedVisitor(PhantomData))
+    }
+}
+
+struct SpannedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SpannedVisitor<T> {
+    type Value = Spanned<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a value deserialized by serde_json::Deserializer")
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<Spanned<T>, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let unsupported =
+            || de::Error::custom("Spanned<T> can only be deserialized by serde_json::Deserializer");
+        let key: Option<String> = tri!(visitor.next_key());
+        if key.as_deref() != Some(VALUE) {
+            return Err(unsupported());
+        }
+        let value = tri!(visitor.next_value());
+        let key: Option<String> = tri!(visitor.next_key());
+        if key.as_deref() != Some(SPAN) {
+            return Err(unsupported());
+        }
+        let [line, column, byte_offset, end_line, end_column, end_byte_offset]: [usize; 6] =
+            tri!(visitor.next_value());
+        Ok(Spanned {
+            start: Position {
+                line,
+                column,
+                byte_offset,
+            },
+            end: Position {
+                line: end_line,
+                column: end_column,
+                byte_offset: end_byte_offset,
+            },
+            value,
+        })
+    }
+}
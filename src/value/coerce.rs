@@ -0,0 +1,212 @@
+use super::Value;
+use crate::number::{is_integral, Number};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A lightweight description of the shape [`Value::coerce_to`] normalizes a
+/// value towards.
+///
+/// ```
+/// use serde_json::value::Shape;
+///
+/// let order = Shape::object()
+///     .field("id", Shape::Integer)
+///     .field("paid", Shape::Bool)
+///     .field("tags", Shape::array(Shape::String))
+///     .field("note", Shape::nullable(Shape::String));
+/// # let _ = order;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    /// Any value; nothing is coerced.
+    Any,
+    /// A boolean. The strings `"true"` and `"false"` are converted.
+    Bool,
+    /// An integer. Strings holding an integer, and floats without a
+    /// fractional part, are converted.
+    Integer,
+    /// Any number. Strings holding a JSON number are converted.
+    Number,
+    /// A string. Numbers and booleans are converted to their JSON text.
+    String,
+    /// An array whose elements have the given shape. Any other value except
+    /// `null` is wrapped in a one-element array.
+    Array(Box<Shape>),
+    /// An object whose listed members have the given shapes. Members that are
+    /// not listed are left alone.
+    Object(Vec<(String, Shape)>),
+    /// An object whose members all have the given shape.
+    Map(Box<Shape>),
+    /// Either `null` or the given shape.
+    Nullable(Box<Shape>),
+}
+
+impl Shape {
+    /// An array whose elements have the shape `element`.
+    pub fn array(element: Shape) -> Self {
+        Shape::Array(Box::new(element))
+    }
+
+    /// An object with no listed members yet; add them with
+    /// [`field`](Shape::field).
+    pub fn object() -> Self {
+        Shape::Object(Vec::new())
+    }
+
+    /// An object whose members all have the shape `member`.
+    pub fn map(member: Shape) -> Self {
+        Shape::Map(Box::new(member))
+    }
+
+    /// Either `null` or `shape`.
+    pub fn nullable(shape: Shape) -> Self {
+        Shape::Nullable(Box::new(shape))
+    }
+
+    /// Adds a member to an object shape, replacing an earlier member with
+    /// the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this shape is not [`Shape::Object`].
+    pub fn field(mut self, name: &str, shape: Shape) -> Self {
+        match &mut self {
+            Shape::Object(fields) => {
+                fields.retain(|(field, _)| field != name);
+                fields.push((name.to_owned(), shape));
+            }
+            _ => panic!("Shape::field called on a shape that is not an object"),
+        }
+        self
+    }
+}
+
+/// A change made by [`Value::coerce_to`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Coercion {
+    /// JSON Pointer to the changed value, as it is after coercion.
+    pub pointer: String,
+    /// The value before it was changed.
+    pub original: Value,
+}
+
+impl Value {
+    /// Normalizes this value in place towards `shape`, converting compatible
+    /// values and returning the changes made.
+    ///
+    /// Coercion only converts values that represent the expected data in
+    /// another form: `"42"` becomes `42` where an integer is expected, and a
+    /// lone value becomes a one-element array where an array is expected.
+    /// Values that cannot be converted are left as they are, so a later
+    /// deserialization still reports them.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// use serde_json::value::Shape;
+    ///
+    /// let shape = Shape::array(
+    ///     Shape::object()
+    ///         .field("id", Shape::Integer)
+    ///         .field("price", Shape::Number)
+    ///         .field("tags", Shape::array(Shape::String)),
+    /// );
+    ///
+    /// let mut feed = json!([
+    ///     {"id": "17", "price": "9.5", "tags": "new"},
+    ///     {"id": 18, "price": 3, "tags": ["sale", 2024]},
+    /// ]);
+    /// let changes = feed.coerce_to(&shape);
+    ///
+    /// assert_eq!(feed, json!([
+    ///     {"id": 17, "price": 9.5, "tags": ["new"]},
+    ///     {"id": 18, "price": 3, "tags": ["sale", "2024"]},
+    /// ]));
+    /// let pointers: Vec<_> = changes.iter().map(|c| c.pointer.as_str()).collect();
+    /// assert_eq!(pointers, ["/0/id", "/0/price", "/0/tags", "/1/tags/1"]);
+    /// assert_eq!(changes[0].original, json!("17"));
+    /// ```
+    pub fn coerce_to(&mut self, shape: &Shape) -> Vec<Coercion> {
+        let mut changes = Vec::new();
+        coerce(self, shape, &mut String::new(), &mut changes);
+        changes
+    }
+}
+
+fn coerce(value: &mut Value, shape: &Shape, pointer: &mut String, changes: &mut Vec<Coercion>) {
+    let converted = match (shape, &*value) {
+        (Shape::Nullable(_), Value::Null) => None,
+        (Shape::Nullable(shape), _) => return coerce(value, shape, pointer, changes),
+        (Shape::Bool, Value::String(s)) => match s.as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        (Shape::Integer, Value::String(s)) => {
+            let s = s.trim();
+            if let Ok(u) = s.parse::<u64>() {
+                Some(Value::Number(u.into()))
+            } else if let Ok(i) = s.parse::<i64>() {
+                Some(Value::Number(i.into()))
+            } else {
+                None
+            }
+        }
+        (Shape::Integer, Value::Number(n)) if !n.is_i64() && !n.is_u64() => match n.as_f64() {
+            // i64::MIN is exact as a float, -i64::MIN is just past i64::MAX.
+            Some(f) if is_integral(f) && f >= i64::MIN as f64 && f < -(i64::MIN as f64) => {
+                Some(Value::Number((f as i64).into()))
+            }
+            _ => None,
+        },
+        (Shape::Number, Value::String(s)) => s.trim().parse::<Number>().ok().map(Value::Number),
+        (Shape::String, Value::Number(n)) => Some(Value::String(n.to_string())),
+        (Shape::String, Value::Bool(b)) => Some(Value::String(b.to_string())),
+        (Shape::Array(_), Value::Array(_) | Value::Null) => None,
+        (Shape::Array(_), _) => Some(Value::Array(alloc::vec![value.clone()])),
+        _ => None,
+    };
+    if let Some(converted) = converted {
+        let original = core::mem::replace(value, converted);
+        changes.push(Coercion {
+            pointer: pointer.clone(),
+            original,
+        });
+    }
+
+    let len = pointer.len();
+    match (shape, value) {
+        (Shape::Array(element), Value::Array(list)) => {
+            for (index, element_value) in list.iter_mut().enumerate() {
+                pointer.push('/');
+                pointer.push_str(&index.to_string());
+                coerce(element_value, element, pointer, changes);
+                pointer.truncate(len);
+            }
+        }
+        (Shape::Object(fields), Value::Object(map)) => {
+            for (key, member) in map.iter_mut() {
+                if let Some((_, shape)) = fields.iter().find(|(field, _)| field == key) {
+                    push_key(pointer, key);
+                    coerce(member, shape, pointer, changes);
+                    pointer.truncate(len);
+                }
+            }
+        }
+        (Shape::Map(shape), Value::Object(map)) => {
+            for (key, member) in map.iter_mut() {
+                push_key(pointer, key);
+                coerce(member, shape, pointer, changes);
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push_key(pointer: &mut String, key: &str) {
+    pointer.push('/');
+    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+}
@@ -0,0 +1,125 @@
+use super::{parse_index, Value};
+use crate::error::Error;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::de::DeserializeOwned;
+
+/// Deserializes `T` from `value`, and on failure keeps deserializing a copy
+/// from which the failing values have been removed, collecting one error per
+/// pass.
+///
+/// Returns the value from the first pass that succeeds, if any, together
+/// with the errors. Errors that only follow from an earlier removal, such as
+/// a missing field for a member that was removed because it had the wrong
+/// type, are left out.
+pub(super) fn from_value_pruning<T>(value: Value) -> (Option<T>, Vec<Error>)
+where
+    T: DeserializeOwned,
+{
+    let mut err = match T::deserialize(&value) {
+        Ok(t) => return (Some(t), Vec::new()),
+        Err(err) => err,
+    };
+
+    let mut working = value.clone();
+    let mut errors = Vec::new();
+    // Pointers into `value` of everything removed from `working`.
+    let mut removed: Vec<String> = Vec::new();
+    loop {
+        let working_path = err.path().unwrap_or("").to_string();
+        let path = original_pointer(&value, &removed, &working_path);
+        if !follows_from_removal(&err, &path, &removed) {
+            errors.push(err.with_path(&path));
+        }
+        if path.is_empty() || !remove(&mut working, &working_path) {
+            return (None, errors);
+        }
+        removed.push(path);
+        err = match T::deserialize(&working) {
+            Ok(t) => return (Some(t), errors),
+            Err(err) => err,
+        };
+    }
+}
+
+fn follows_from_removal(err: &Error, path: &str, removed: &[String]) -> bool {
+    match err.missing_field() {
+        Some(field) => {
+            let member = alloc::format!("{}/{}", path, escape(field));
+            removed.contains(&member)
+        }
+        // For example an invalid length for an array that lost an element.
+        None => removed.iter().any(|pointer| {
+            pointer.len() > path.len()
+                && pointer.starts_with(path)
+                && pointer.as_bytes()[path.len()] == b'/'
+        }),
+    }
+}
+
+/// Translates a pointer into the working copy to the same place in the
+/// original value, counting the array elements that were removed.
+fn original_pointer(original: &Value, removed: &[String], pointer: &str) -> String {
+    let mut path = String::new();
+    let mut node = Some(original);
+    for token in pointer.split('/').skip(1) {
+        match (node, parse_index(token)) {
+            (Some(Value::Array(list)), Some(index)) => {
+                let position = (0..list.len())
+                    .filter(|i| !removed.contains(&alloc::format!("{}/{}", path, i)))
+                    .nth(index);
+                match position {
+                    Some(position) => {
+                        path.push('/');
+                        path.push_str(&position.to_string());
+                        node = list.get(position);
+                    }
+                    None => {
+                        path.push('/');
+                        path.push_str(token);
+                        node = None;
+                    }
+                }
+            }
+            (Some(Value::Object(map)), _) => {
+                node = map.get(&unescape(token));
+                path.push('/');
+                path.push_str(token);
+            }
+            _ => {
+                node = None;
+                path.push('/');
+                path.push_str(token);
+            }
+        }
+    }
+    path
+}
+
+/// Removes the value at `pointer` from its parent and returns whether there
+/// was one.
+fn remove(value: &mut Value, pointer: &str) -> bool {
+    let (parent, token) = match pointer.rfind('/') {
+        Some(slash) => (&pointer[..slash], unescape(&pointer[slash + 1..])),
+        None => return false,
+    };
+    match value.pointer_mut(parent) {
+        Some(Value::Object(map)) => map.remove(&token).is_some(),
+        Some(Value::Array(list)) => match parse_index(&token) {
+            Some(index) if index < list.len() => {
+                list.remove(index);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
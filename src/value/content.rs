@@ -0,0 +1,832 @@
+//! Bộ đệm cho một giá trị JSON đã phân tích, để giải mã lại nhiều lần mà
+//! không phải phân tích lại đầu vào.
+
+use crate::error::Error;
+use crate::map::Map;
+use crate::number::Number;
+use crate::value::Value;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice;
+use serde::de::{
+    self, Deserialize, DeserializeSeed, EnumAccess, Expected, IntoDeserializer, MapAccess,
+    SeqAccess, Unexpected, VariantAccess, Visitor,
+};
+
+use super::MapKeyDeserializer;
+
+/// Một giá trị JSON được giữ lại ở dạng gần với token của nó.
+///
+/// Khác với `Value`, chuỗi và khóa được mượn thẳng từ đầu vào khi có thể,
+/// và thành viên của object được giữ theo đúng thứ tự xuất hiện trong một
+/// `Vec` thay vì dựng thành `Map`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Content<'de> {
+    Null,
+    Bool(bool),
+    Number(Number),
+    Str(&'de str),
+    String(String),
+    Seq(Vec<Content<'de>>),
+    Map(Vec<(Cow<'de, str>, Content<'de>)>),
+}
+
+impl<'de> Content<'de> {
+    #[cold]
+    fn invalid_type<E>(&self, exp: &dyn Expected) -> E
+    where
+        E: de::Error,
+    {
+        de::Error::invalid_type(self.unexpected(), exp)
+    }
+
+    #[cold]
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Content::Null => Unexpected::Unit,
+            Content::Bool(b) => Unexpected::Bool(*b),
+            Content::Number(n) => n.unexpected(),
+            Content::Str(s) => Unexpected::Str(s),
+            Content::String(s) => Unexpected::Str(s),
+            Content::Seq(_) => Unexpected::Seq,
+            Content::Map(_) => Unexpected::Map,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Content<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Content<'de>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    #[inline]
+    fn visit_bool<E>(self, value: bool) -> Result<Content<'de>, E> {
+        Ok(Content::Bool(value))
+    }
+
+    #[inline]
+    fn visit_i64<E>(self, value: i64) -> Result<Content<'de>, E> {
+        Ok(Content::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_u64<E>(self, value: u64) -> Result<Content<'de>, E> {
+        Ok(Content::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, value: f64) -> Result<Content<'de>, E> {
+        Ok(Number::from_f64(value).map_or(Content::Null, Content::Number))
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Content<'de>, E> {
+        Ok(Content::Str(value))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<Content<'de>, E> {
+        Ok(Content::String(value.to_owned()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, value: String) -> Result<Content<'de>, E> {
+        Ok(Content::String(value))
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> Result<Content<'de>, E> {
+        Ok(Content::Null)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Content<'de>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Content<'de>, E> {
+        Ok(Content::Null)
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Content<'de>, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut vec = Vec::new();
+        while let Some(elem) = tri!(visitor.next_element()) {
+            vec.push(elem);
+        }
+        Ok(Content::Seq(vec))
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<Content<'de>, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(key) = tri!(visitor.next_key_seed(KeySeed)) {
+            // Số chính xác đi qua serde dưới dạng một map có khóa riêng; giữ
+            // nó lại thành số để giải mã lại được vào `f64` hay `u64`.
+            #[cfg(any(feature = "decimal", feature = "bigint"))]
+            if entries.is_empty() && key == crate::number::DIGITS_TOKEN {
+                let digits: String = tri!(visitor.next_value());
+                return crate::number::number_from_digits(&digits).map(Content::Number);
+            }
+            entries.push((key, tri!(visitor.next_value())));
+        }
+        Ok(Content::Map(entries))
+    }
+}
+
+/// Một giá trị JSON bất kỳ được đệm lại rẻ nhất có thể, để giải mã thành
+/// kiểu cụ thể sau, có khi nhiều lần.
+///
+/// Dùng cho việc giải mã hai pha: đọc một trường phân loại như `"type"`
+/// trước, rồi mới chọn kiểu cho phần còn lại. Khác với `Value`, chuỗi và
+/// khóa được mượn từ đầu vào khi có thể, và object không được dựng thành
+/// `Map`, nên đệm một thông điệp gần như chỉ tốn một lần phân tích.
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::value::ContentValue;
+///
+/// #[derive(Deserialize)]
+/// struct Resize<'a> {
+///     target: &'a str,
+///     width: u32,
+/// }
+///
+/// let input = r#"{"type": "resize", "target": "logo.png", "width": 64}"#;
+/// let message: ContentValue = serde_json::from_str(input).unwrap();
+///
+/// match message.get_str("type") {
+///     Some("resize") => {
+///         let resize: Resize = message.deserialize_into().unwrap();
+///         assert_eq!(resize.target, "logo.png");
+///         assert_eq!(resize.width, 64);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentValue<'de> {
+    content: Content<'de>,
+}
+
+impl<'de> ContentValue<'de> {
+    /// Trả về deserializer đọc từ giá trị này. Nó là `Copy`, nên có thể thử
+    /// nhiều kiểu lần lượt.
+    pub fn deserializer(&self) -> BufferedDeserializer<'_, 'de> {
+        BufferedDeserializer::new(&self.content)
+    }
+
+    /// Giải mã giá trị này thành `T`. Giá trị vẫn được giữ lại, nên có thể
+    /// gọi lại với kiểu khác.
+    ///
+    /// # Errors
+    ///
+    /// Trả về lỗi nếu giá trị không khớp với `T`.
+    pub fn deserialize_into<T>(&self) -> Result<T, Error>
+    where
+        T: Deserialize<'de>,
+    {
+        T::deserialize(self.deserializer())
+    }
+
+    /// Trả về deserializer cho thành viên `key` nếu đây là object có khóa
+    /// đó, và `None` nếu không.
+    ///
+    /// Nếu khóa xuất hiện nhiều lần, thành viên cuối cùng được chọn, như khi
+    /// phân tích thành `Value`.
+    pub fn get(&self, key: &str) -> Option<BufferedDeserializer<'_, 'de>> {
+        match &self.content {
+            Content::Map(entries) => entries
+                .iter()
+                .rev()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| BufferedDeserializer::new(value)),
+            _ => None,
+        }
+    }
+
+    /// Trả về thành viên `key` nếu đây là object có khóa đó và giá trị của
+    /// nó là chuỗi. Tiện cho việc đọc trường phân loại.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)?.content {
+            Content::Str(s) => Some(s),
+            Content::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Chuyển giá trị này thành một `Value` thông thường.
+    pub fn to_value(&self) -> Value {
+        self.content.to_value()
+    }
+
+    /// Chép mọi chuỗi đang mượn từ đầu vào, để giá trị sống lâu hơn đầu vào.
+    pub fn into_owned(self) -> ContentValue<'static> {
+        ContentValue {
+            content: self.content.into_owned(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentValue<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<ContentValue<'de>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Content::deserialize(deserializer).map(|content| ContentValue { content })
+    }
+}
+
+impl<'de> Content<'de> {
+    fn to_value(&self) -> Value {
+        match self {
+            Content::Null => Value::Null,
+            Content::Bool(b) => Value::Bool(*b),
+            Content::Number(n) => Value::Number(n.clone()),
+            Content::Str(s) => Value::String((*s).to_owned()),
+            Content::String(s) => Value::String(s.clone()),
+            Content::Seq(elements) => {
+                Value::Array(elements.iter().map(Content::to_value).collect())
+            }
+            Content::Map(entries) => {
+                let mut map = Map::new();
+                for (key, value) in entries {
+                    map.insert(key.as_ref().to_owned(), value.to_value());
+                }
+                Value::Object(map)
+            }
+        }
+    }
+
+    fn into_owned(self) -> Content<'static> {
+        match self {
+            Content::Null => Content::Null,
+            Content::Bool(b) => Content::Bool(b),
+            Content::Number(n) => Content::Number(n),
+            Content::Str(s) => Content::String(s.to_owned()),
+            Content::String(s) => Content::String(s),
+            Content::Seq(elements) => {
+                Content::Seq(elements.into_iter().map(Content::into_owned).collect())
+            }
+            Content::Map(entries) => Content::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Cow::Owned(key.into_owned()), value.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct KeySeed;
+
+impl<'de> DeserializeSeed<'de> for KeySeed {
+    type Value = Cow<'de, str>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string key")
+    }
+
+    fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(s))
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(s.to_owned()))
+    }
+
+    fn visit_string<E>(self, s: String) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(s))
+    }
+}
+
+/// Deserializer đọc từ một giá trị đã được đệm bởi
+/// [`Deserializer::deserialize_buffered`](crate::Deserializer::deserialize_buffered)
+/// hoặc giữ trong một [`ContentValue`].
+///
+/// Kiểu này là `Copy`, nên cùng một giá trị đã đệm có thể được đưa lần lượt
+/// cho nhiều impl `Deserialize`, chẳng hạn mỗi biến thể của một enum
+/// untagged một lần, mà không phải phân tích lại đầu vào. Chuỗi mượn từ đầu
+/// vào vẫn được mượn.
+#[derive(Clone, Copy)]
+pub struct BufferedDeserializer<'a, 'de> {
+    content: &'a Content<'de>,
+}
+
+impl<'a, 'de> BufferedDeserializer<'a, 'de> {
+    pub(crate) fn new(content: &'a Content<'de>) -> Self {
+        BufferedDeserializer { content }
+    }
+}
+
+impl<'a, 'de> fmt::Debug for BufferedDeserializer<'a, 'de> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("BufferedDeserializer")
+    }
+}
+
+fn visit_seq_ref<'a, 'de, V>(seq: &'a [Content<'de>], visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = seq.len();
+    let mut deserializer = SeqRefDeserializer {
+        iter: seq.iter(),
+        index: 0,
+    };
+    let seq = tri!(visitor.visit_seq(&mut deserializer));
+    if deserializer.iter.len() == 0 {
+        Ok(seq)
+    } else {
+        Err(de::Error::invalid_length(len, &"fewer elements in array"))
+    }
+}
+
+fn visit_map_ref<'a, 'de, V>(
+    entries: &'a [(Cow<'de, str>, Content<'de>)],
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = entries.len();
+    let mut deserializer = MapRefDeserializer {
+        iter: entries.iter(),
+        key: "",
+        value: None,
+    };
+    let map = tri!(visitor.visit_map(&mut deserializer));
+    if deserializer.iter.len() == 0 {
+        Ok(map)
+    } else {
+        Err(de::Error::invalid_length(len, &"fewer elements in map"))
+    }
+}
+
+fn visit_str_ref<'a, 'de, V>(content: &'a Content<'de>, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    match content {
+        Content::Str(s) => visitor.visit_borrowed_str(s),
+        Content::String(s) => visitor.visit_str(s),
+        _ => Err(content.invalid_type(&visitor)),
+    }
+}
+
+macro_rules! deserialize_content_number {
+    ($method:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            match self.content {
+                Content::Number(n) => n.$method(visitor),
+                _ => Err(self.content.invalid_type(&visitor)),
+            }
+        }
+    };
+}
+
+impl<'a, 'de> serde::Deserializer<'de> for BufferedDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Null => visitor.visit_unit(),
+            Content::Bool(v) => visitor.visit_bool(*v),
+            Content::Number(n) => n.deserialize_any(visitor),
+            Content::Str(s) => visitor.visit_borrowed_str(s),
+            Content::String(s) => visitor.visit_str(s),
+            Content::Seq(v) => visit_seq_ref(v, visitor),
+            Content::Map(v) => visit_map_ref(v, visitor),
+        }
+    }
+
+    deserialize_content_number!(deserialize_i8);
+    deserialize_content_number!(deserialize_i16);
+    deserialize_content_number!(deserialize_i32);
+    deserialize_content_number!(deserialize_i64);
+    deserialize_content_number!(deserialize_i128);
+    deserialize_content_number!(deserialize_u8);
+    deserialize_content_number!(deserialize_u16);
+    deserialize_content_number!(deserialize_u32);
+    deserialize_content_number!(deserialize_u64);
+    deserialize_content_number!(deserialize_u128);
+    deserialize_content_number!(deserialize_f32);
+    deserialize_content_number!(deserialize_f64);
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (variant, value) = match self.content {
+            // enums are encoded in json as maps with a single key:value pair
+            Content::Map(entries) => match entries.as_slice() {
+                [(variant, value)] => (variant.as_ref(), Some(value)),
+                _ => {
+                    return Err(de::Error::invalid_value(
+                        Unexpected::Map,
+                        &"map with a single key",
+                    ));
+                }
+            },
+            Content::Str(variant) => (*variant, None),
+            Content::String(variant) => (variant.as_str(), None),
+            other => {
+                return Err(de::Error::invalid_type(
+                    other.unexpected(),
+                    &"string or map",
+                ));
+            }
+        };
+
+        visitor.visit_enum(EnumRefDeserializer { variant, value })
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Bool(v) => visitor.visit_bool(*v),
+            _ => Err(self.content.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visit_str_ref(self.content, visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Seq(v) => visit_seq_ref(v, visitor),
+            _ => visit_str_ref(self.content, visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Null => visitor.visit_unit(),
+            _ => Err(self.content.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Seq(v) => visit_seq_ref(v, visitor),
+            _ => Err(self.content.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Map(v) => visit_map_ref(v, visitor),
+            _ => Err(self.content.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Seq(v) => visit_seq_ref(v, visitor),
+            Content::Map(v) => visit_map_ref(v, visitor),
+            _ => Err(self.content.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+impl<'a, 'de> IntoDeserializer<'de, Error> for BufferedDeserializer<'a, 'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// Deserializer cho một khóa đã đệm, để khóa số nguyên hay bool được đọc
+/// giống như khi giải mã thẳng từ đầu vào.
+fn key_deserializer<'de>(key: &Cow<'de, str>) -> MapKeyDeserializer<'de> {
+    let key = match key {
+        Cow::Borrowed(key) => Cow::Borrowed(*key),
+        // `MapKeyDeserializer` cần chuỗi sống đủ lâu như đầu vào, nên khóa có
+        // escape, hay mọi khóa khi đọc từ `io::Read`, phải chép lại ở mỗi
+        // lần giải mã.
+        Cow::Owned(key) => Cow::Owned(key.clone()),
+    };
+    MapKeyDeserializer { key }
+}
+
+struct EnumRefDeserializer<'a, 'de> {
+    variant: &'a str,
+    value: Option<&'a Content<'de>>,
+}
+
+impl<'a, 'de> EnumAccess<'de> for EnumRefDeserializer<'a, 'de> {
+    type Error = Error;
+    type Variant = VariantRefDeserializer<'a, 'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = de::value::StrDeserializer::<Error>::new(self.variant);
+        let visitor = VariantRefDeserializer { value: self.value };
+        seed.deserialize(variant).map(|v| (v, visitor))
+    }
+}
+
+struct VariantRefDeserializer<'a, 'de> {
+    value: Option<&'a Content<'de>>,
+}
+
+impl<'a, 'de> VariantAccess<'de> for VariantRefDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            Some(value) => Deserialize::deserialize(BufferedDeserializer::new(value)),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(BufferedDeserializer::new(value)),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Content::Seq(v)) => {
+                if v.is_empty() {
+                    visitor.visit_unit()
+                } else {
+                    visit_seq_ref(v, visitor)
+                }
+            }
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"tuple variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Content::Map(v)) => visit_map_ref(v, visitor),
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+struct SeqRefDeserializer<'a, 'de> {
+    iter: slice::Iter<'a, Content<'de>>,
+    index: usize,
+}
+
+impl<'a, 'de> SeqAccess<'de> for SeqRefDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => {
+                let index = self.index;
+                self.index += 1;
+                match seed.deserialize(BufferedDeserializer::new(value)) {
+                    Ok(element) => Ok(Some(element)),
+                    Err(err) => Err(err.prepend_path(&index.to_string())),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapRefDeserializer<'a, 'de> {
+    iter: slice::Iter<'a, (Cow<'de, str>, Content<'de>)>,
+    key: &'a str,
+    value: Option<&'a Content<'de>>,
+}
+
+impl<'a, 'de> MapAccess<'de> for MapRefDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.key = key;
+                self.value = Some(value);
+                match seed.deserialize(key_deserializer(key)) {
+                    Ok(key) => Ok(Some(key)),
+                    Err(err) => Err(err.prepend_path(self.key)),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => match seed.deserialize(BufferedDeserializer::new(value)) {
+                Ok(value) => Ok(value),
+                Err(err) => Err(err.prepend_path(self.key)),
+            },
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
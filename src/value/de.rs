@@ -3,7 +3,7 @@ use crate::map::Map;
 use crate::number::Number;
 use crate::value::Value;
 use alloc::borrow::{Cow, ToOwned};
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::{self, Vec};
 use core::fmt;
 use core::slice;
@@ -108,6 +108,11 @@ impl<'de> Deserialize<'de> for Value {
                         let number: NumberFromString = visitor.next_value()?;
                         Ok(Value::Number(number.value))
                     }
+                    #[cfg(any(feature = "decimal", feature = "bigint"))]
+                    Some(KeyClass::Digits) => {
+                        let digits: String = tri!(visitor.next_value());
+                        crate::number::number_from_digits(&digits).map(Value::Number)
+                    }
                     #[cfg(feature = "raw_value")]
                     Some(KeyClass::RawValue) => {
                         let value = visitor.next_value_seed(crate::raw::BoxedFromString)?;
@@ -147,7 +152,7 @@ macro_rules! deserialize_number {
             V: Visitor<'de>,
         {
             match self {
-                Value::Number(n) => n.deserialize_any(visitor),
+                Value::Number(n) => n.$method(visitor),
                 _ => Err(self.invalid_type(&visitor)),
             }
         }
@@ -552,12 +557,14 @@ impl<'de> VariantAccess<'de> for VariantDeserializer {
 
 struct SeqDeserializer {
     iter: vec::IntoIter<Value>,
+    index: usize,
 }
 
 impl SeqDeserializer {
     fn new(vec: Vec<Value>) -> Self {
         SeqDeserializer {
             iter: vec.into_iter(),
+            index: 0,
         }
     }
 }
@@ -570,7 +577,14 @@ impl<'de> SeqAccess<'de> for SeqDeserializer {
         T: DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(value) => seed.deserialize(value).map(Some),
+            Some(value) => {
+                let index = self.index;
+                self.index += 1;
+                match seed.deserialize(value) {
+                    Ok(element) => Ok(Some(element)),
+                    Err(err) => Err(err.prepend_path(&index.to_string())),
+                }
+            }
             None => Ok(None),
         }
     }
@@ -585,6 +599,8 @@ impl<'de> SeqAccess<'de> for SeqDeserializer {
 
 struct MapDeserializer {
     iter: <Map<String, Value> as IntoIterator>::IntoIter,
+    // Khóa của `value`, giữ lại để ghi vào đường dẫn của lỗi.
+    key: String,
     value: Option<Value>,
 }
 
@@ -592,6 +608,7 @@ impl MapDeserializer {
     fn new(map: Map<String, Value>) -> Self {
         MapDeserializer {
             iter: map.into_iter(),
+            key: String::new(),
             value: None,
         }
     }
@@ -606,11 +623,13 @@ impl<'de> MapAccess<'de> for MapDeserializer {
     {
         match self.iter.next() {
             Some((key, value)) => {
+                self.key = key;
                 self.value = Some(value);
-                let key_de = MapKeyDeserializer {
-                    key: Cow::Owned(key),
-                };
-                seed.deserialize(key_de).map(Some)
+                let key_de = MapKeyRefDeserializer { key: &self.key };
+                match seed.deserialize(key_de) {
+                    Ok(key) => Ok(Some(key)),
+                    Err(err) => Err(err.prepend_path(&self.key)),
+                }
             }
             None => Ok(None),
         }
@@ -621,7 +640,10 @@ impl<'de> MapAccess<'de> for MapDeserializer {
         T: DeserializeSeed<'de>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(value),
+            Some(value) => match seed.deserialize(value) {
+                Ok(value) => Ok(value),
+                Err(err) => Err(err.prepend_path(&self.key)),
+            },
             None => Err(serde::de::Error::custom("value is missing")),
         }
     }
@@ -642,7 +664,7 @@ macro_rules! deserialize_value_ref_number {
             V: Visitor<'de>,
         {
             match self {
-                Value::Number(n) => n.deserialize_any(visitor),
+                Value::Number(n) => n.$method(visitor),
                 _ => Err(self.invalid_type(&visitor)),
             }
         }
@@ -696,6 +718,28 @@ where
     }
 }
 
+/// Deserializer mượn dữ liệu từ một `Value` mà không tiêu thụ nó.
+///
+/// Chỉ là tên khác của `&Value`, vốn đã là một `Deserializer` trả chuỗi dưới
+/// dạng `&'de str`. Xem [`from_value_ref`](crate::from_value_ref).
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use serde_json::value::ValueRefDeserializer;
+///
+/// #[derive(Deserialize)]
+/// struct User<'a> {
+///     name: &'a str,
+/// }
+///
+/// let cached = json!({"name": "ann"});
+/// let de: ValueRefDeserializer = &cached;
+/// let user = User::deserialize(de).unwrap();
+/// assert_eq!(user.name, "ann");
+/// ```
+pub type ValueRefDeserializer<'a> = &'a Value;
+
 impl<'de> serde::Deserializer<'de> for &'de Value {
     type Error = Error;
 
@@ -939,6 +983,14 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
     }
 }
 
+impl<'de> IntoDeserializer<'de, Error> for &'de Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
 struct EnumRefDeserializer<'de> {
     variant: &'de str,
     value: Option<&'de Value>,
@@ -1032,11 +1084,15 @@ impl<'de> VariantAccess<'de> for VariantRefDeserializer<'de> {
 
 struct SeqRefDeserializer<'de> {
     iter: slice::Iter<'de, Value>,
+    index: usize,
 }
 
 impl<'de> SeqRefDeserializer<'de> {
     fn new(slice: &'de [Value]) -> Self {
-        SeqRefDeserializer { iter: slice.iter() }
+        SeqRefDeserializer {
+            iter: slice.iter(),
+            index: 0,
+        }
     }
 }
 
@@ -1048,7 +1104,14 @@ impl<'de> SeqAccess<'de> for SeqRefDeserializer<'de> {
         T: DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(value) => seed.deserialize(value).map(Some),
+            Some(value) => {
+                let index = self.index;
+                self.index += 1;
+                match seed.deserialize(value) {
+                    Ok(element) => Ok(Some(element)),
+                    Err(err) => Err(err.prepend_path(&index.to_string())),
+                }
+            }
             None => Ok(None),
         }
     }
@@ -1063,6 +1126,7 @@ impl<'de> SeqAccess<'de> for SeqRefDeserializer<'de> {
 
 struct MapRefDeserializer<'de> {
     iter: <&'de Map<String, Value> as IntoIterator>::IntoIter,
+    key: &'de str,
     value: Option<&'de Value>,
 }
 
@@ -1070,6 +1134,7 @@ impl<'de> MapRefDeserializer<'de> {
     fn new(map: &'de Map<String, Value>) -> Self {
         MapRefDeserializer {
             iter: map.into_iter(),
+            key: "",
             value: None,
         }
     }
@@ -1084,11 +1149,15 @@ impl<'de> MapAccess<'de> for MapRefDeserializer<'de> {
     {
         match self.iter.next() {
             Some((key, value)) => {
+                self.key = key;
                 self.value = Some(value);
                 let key_de = MapKeyDeserializer {
                     key: Cow::Borrowed(&**key),
                 };
-                seed.deserialize(key_de).map(Some)
+                match seed.deserialize(key_de) {
+                    Ok(key) => Ok(Some(key)),
+                    Err(err) => Err(err.prepend_path(self.key)),
+                }
             }
             None => Ok(None),
         }
@@ -1099,7 +1168,10 @@ impl<'de> MapAccess<'de> for MapRefDeserializer<'de> {
         T: DeserializeSeed<'de>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(value),
+            Some(value) => match seed.deserialize(value) {
+                Ok(value) => Ok(value),
+                Err(err) => Err(err.prepend_path(self.key)),
+            },
             None => Err(serde::de::Error::custom("value is missing")),
         }
     }
@@ -1112,8 +1184,10 @@ impl<'de> MapAccess<'de> for MapRefDeserializer<'de> {
     }
 }
 
-struct MapKeyDeserializer<'de> {
-    key: Cow<'de, str>,
+/// Deserializes a map key from its text, parsing it as an integer when an
+/// integer is requested.
+pub(crate) struct MapKeyDeserializer<'de> {
+    pub(crate) key: Cow<'de, str>,
 }
 
 macro_rules! deserialize_integer_key {
@@ -1130,6 +1204,17 @@ macro_rules! deserialize_integer_key {
             }
         }
     };
+    (ref $method:ident => $visit:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            match self.key.parse() {
+                Ok(integer) => visitor.$visit(integer),
+                Err(_) => visitor.visit_str(self.key),
+            }
+        }
+    };
 }
 
 impl<'de> serde::Deserializer<'de> for MapKeyDeserializer<'de> {
@@ -1153,6 +1238,96 @@ impl<'de> serde::Deserializer<'de> for MapKeyDeserializer<'de> {
     deserialize_integer_key!(deserialize_u64 => visit_u64);
     deserialize_integer_key!(deserialize_u128 => visit_u128);
 
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &*self.key {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Map keys cannot be null.
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.key
+            .into_deserializer()
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Like `MapKeyDeserializer`, but for a key that lives shorter than `'de`,
+/// such as one the map deserializer keeps for error paths.
+struct MapKeyRefDeserializer<'a> {
+    key: &'a str,
+}
+
+impl<'de, 'a> serde::Deserializer<'de> for MapKeyRefDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.key)
+    }
+
+    deserialize_integer_key!(ref deserialize_i8 => visit_i8);
+    deserialize_integer_key!(ref deserialize_i16 => visit_i16);
+    deserialize_integer_key!(ref deserialize_i32 => visit_i32);
+    deserialize_integer_key!(ref deserialize_i64 => visit_i64);
+    deserialize_integer_key!(ref deserialize_i128 => visit_i128);
+    deserialize_integer_key!(ref deserialize_u8 => visit_u8);
+    deserialize_integer_key!(ref deserialize_u16 => visit_u16);
+    deserialize_integer_key!(ref deserialize_u32 => visit_u32);
+    deserialize_integer_key!(ref deserialize_u64 => visit_u64);
+    deserialize_integer_key!(ref deserialize_u128 => visit_u128);
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.key {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
     #[inline]
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
     where
@@ -1189,7 +1364,7 @@ impl<'de> serde::Deserializer<'de> for MapKeyDeserializer<'de> {
     }
 
     forward_to_deserialize_any! {
-        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
         tuple_struct map struct identifier ignored_any
     }
 }
@@ -1200,6 +1375,8 @@ enum KeyClass {
     Map(String),
     #[cfg(feature = "arbitrary_precision")]
     Number,
+    #[cfg(any(feature = "decimal", feature = "bigint"))]
+    Digits,
     #[cfg(feature = "raw_value")]
     RawValue,
 }
@@ -1229,6 +1406,8 @@ impl<'de> Visitor<'de> for KeyClassifier {
         match s {
             #[cfg(feature = "arbitrary_precision")]
             crate::number::TOKEN => Ok(KeyClass::Number),
+            #[cfg(any(feature = "decimal", feature = "bigint"))]
+            crate::number::DIGITS_TOKEN => Ok(KeyClass::Digits),
             #[cfg(feature = "raw_value")]
             crate::raw::TOKEN => Ok(KeyClass::RawValue),
             _ => Ok(KeyClass::Map(s.to_owned())),
@@ -1243,6 +1422,8 @@ impl<'de> Visitor<'de> for KeyClassifier {
         match s.as_str() {
             #[cfg(feature = "arbitrary_precision")]
             crate::number::TOKEN => Ok(KeyClass::Number),
+            #[cfg(any(feature = "decimal", feature = "bigint"))]
+            crate::number::DIGITS_TOKEN => Ok(KeyClass::Digits),
             #[cfg(feature = "raw_value")]
             crate::raw::TOKEN => Ok(KeyClass::RawValue),
             _ => Ok(KeyClass::Map(s)),
@@ -0,0 +1,167 @@
+use super::Value;
+use crate::error::{Error, ErrorCode, Result};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+/// A partial-response field selection such as `"a,b(c,d),e.*"`.
+///
+/// The syntax is a comma-separated list of fields:
+///
+/// - `name` selects an object member, or an array element if it is an
+///   index. `*` selects every member or element.
+/// - `a.b` selects `b` inside `a`. Dots are applied through arrays only by
+///   naming the index or `*`, as in `items.*.id`.
+/// - `a(b,c.d)` selects `b` and `c.d` inside `a`; groups can be nested.
+///
+/// Names cannot contain `,`, `.`, `(` or `)`, and whitespace around them is
+/// ignored. An empty mask selects the whole value.
+///
+/// Applying a mask keeps the selected subtrees and removes everything else,
+/// as [`Value::retain_paths`] does.
+///
+/// ```
+/// # use serde_json::json;
+/// use serde_json::value::FieldMask;
+///
+/// let mask: FieldMask = "id,author(name),comments.*.text".parse().unwrap();
+///
+/// let mut post = json!({
+///     "id": 7,
+///     "body": "…",
+///     "author": {"name": "ann", "email": "ann@example.com"},
+///     "comments": [{"text": "hi", "by": 3}, {"text": "yo", "by": 4}],
+/// });
+/// mask.apply(&mut post);
+///
+/// assert_eq!(post, json!({
+///     "id": 7,
+///     "author": {"name": "ann"},
+///     "comments": [{"text": "hi"}, {"text": "yo"}],
+/// }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldMask {
+    pointers: Vec<String>,
+}
+
+impl FieldMask {
+    /// Parses a mask. Syntax errors report the column, counted from 1, at
+    /// which they were found.
+    pub fn parse(mask: &str) -> Result<FieldMask> {
+        if mask.trim().is_empty() {
+            return Ok(FieldMask {
+                pointers: alloc::vec![String::new()],
+            });
+        }
+        let mut parser = Parser {
+            input: mask,
+            pos: 0,
+        };
+        let pointers = tri!(parser.list());
+        match parser.peek() {
+            None => Ok(FieldMask { pointers }),
+            Some(_) => Err(parser.error("unexpected character")),
+        }
+    }
+
+    /// The selected paths as JSON Pointers with `*` wildcards, in the order
+    /// they appear in the mask.
+    ///
+    /// ```
+    /// use serde_json::value::FieldMask;
+    ///
+    /// let mask = FieldMask::parse("a(b,c.d),e/f").unwrap();
+    /// assert_eq!(mask.pointers(), ["/a/b", "/a/c/d", "/e~1f"]);
+    /// ```
+    pub fn pointers(&self) -> &[String] {
+        &self.pointers
+    }
+
+    /// Removes everything from `value` that the mask does not select.
+    pub fn apply(&self, value: &mut Value) {
+        value.retain_paths(&self.pointers);
+    }
+}
+
+impl FromStr for FieldMask {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<FieldMask> {
+        FieldMask::parse(s)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.as_bytes().get(self.pos) {
+            self.pos += 1;
+        }
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::syntax(
+            ErrorCode::Message(message.to_owned().into_boxed_str()),
+            1,
+            self.pos + 1,
+        )
+    }
+
+    /// `item (',' item)*`, expanded to pointers.
+    fn list(&mut self) -> Result<Vec<String>> {
+        let mut pointers = tri!(self.item());
+        while self.peek() == Some(b',') {
+            self.pos += 1;
+            pointers.extend(tri!(self.item()));
+        }
+        Ok(pointers)
+    }
+
+    /// `name ('.' name)* ['(' list ')']`, expanded to pointers.
+    fn item(&mut self) -> Result<Vec<String>> {
+        let mut prefix = tri!(self.name());
+        while self.peek() == Some(b'.') {
+            self.pos += 1;
+            prefix.push_str(&tri!(self.name()));
+        }
+        if self.peek() != Some(b'(') {
+            return Ok(alloc::vec![prefix]);
+        }
+        self.pos += 1;
+        let nested = tri!(self.list());
+        if self.peek() != Some(b')') {
+            return Err(self.error("expected `)`"));
+        }
+        self.pos += 1;
+        Ok(nested
+            .into_iter()
+            .map(|pointer| alloc::format!("{}{}", prefix, pointer))
+            .collect())
+    }
+
+    /// A single field name, returned as an escaped pointer token with its
+    /// leading `/`.
+    fn name(&mut self) -> Result<String> {
+        self.peek();
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c| matches!(c, ',' | '.' | '(' | ')'))
+            .unwrap_or(rest.len());
+        let name = rest[..len].trim_end();
+        if name.is_empty() {
+            return Err(self.error("expected field name"));
+        }
+        self.pos += len;
+        Ok(alloc::format!(
+            "/{}",
+            name.replace('~', "~0").replace('/', "~1")
+        ))
+    }
+}
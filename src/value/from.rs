@@ -207,6 +207,14 @@ impl<T: Into<Value>> FromIterator<T> for Value {
     ///
     /// let v = std::iter::repeat(42).take(5);
     /// let x: Value = v.collect();
+    /// assert_eq!(x, serde_json::json!([42, 42, 42, 42, 42]));
+    /// ```
+    ///
+    /// ```
+    /// use serde_json::Value;
+    ///
+    /// let x: Value = (0..3).map(Value::from).collect();
+    /// assert_eq!(x, serde_json::json!([0, 1, 2]));
     /// ```
     ///
     /// ```
@@ -228,7 +236,9 @@ impl<T: Into<Value>> FromIterator<T> for Value {
 }
 
 impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for Value {
-
+    /// Collect key/value pairs into `Value::Object`. Later pairs overwrite
+    /// earlier ones with the same key.
+    ///
     /// # Examples
     ///
     /// ```
@@ -236,6 +246,7 @@ impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for Value {
     ///
     /// let v: Vec<_> = vec![("lorem", 40), ("ipsum", 2)];
     /// let x: Value = v.into_iter().collect();
+    /// assert_eq!(x, serde_json::json!({"lorem": 40, "ipsum": 2}));
     /// ```
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
         Value::Object(
@@ -273,3 +284,66 @@ where
         }
     }
 }
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl<Tz> From<chrono::DateTime<Tz>> for Value
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: core::fmt::Display,
+{
+    /// Convert a `DateTime` to an RFC 3339 string `Value`, using `Z` for UTC
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use serde_json::Value;
+    ///
+    /// let t = Utc.with_ymd_and_hms(2023, 4, 5, 6, 7, 8).unwrap();
+    /// let x: Value = t.into();
+    /// assert_eq!(x, "2023-04-05T06:07:08Z");
+    /// ```
+    fn from(datetime: chrono::DateTime<Tz>) -> Self {
+        Value::String(datetime.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+    }
+}
+
+#[cfg(feature = "decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "decimal")))]
+impl From<rust_decimal::Decimal> for Value {
+    /// Chuyển một `Decimal` thành số JSON chính xác
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_decimal::Decimal;
+    /// use serde_json::Value;
+    ///
+    /// let x: Value = Decimal::new(1999, 2).into();
+    /// assert_eq!(x.to_string(), "19.99");
+    /// ```
+    fn from(d: rust_decimal::Decimal) -> Self {
+        Value::Number(d.into())
+    }
+}
+
+#[cfg(feature = "bigint")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+impl From<num_bigint::BigInt> for Value {
+    /// Chuyển một `BigInt` thành số nguyên JSON chính xác
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    /// use serde_json::Value;
+    ///
+    /// let big = BigInt::from(u64::MAX) * 10u8;
+    /// let x: Value = big.into();
+    /// assert_eq!(x.to_string(), "184467440737095516150");
+    /// ```
+    fn from(b: num_bigint::BigInt) -> Self {
+        Value::Number(b.into())
+    }
+}
@@ -0,0 +1,96 @@
+use super::Value;
+use core::hash::{Hash, Hasher};
+
+impl Value {
+    /// Feeds the content of this value into `state`.
+    ///
+    /// Two values that are `==` always produce the same hash, regardless of
+    /// the order in which object members were inserted, so the hash can be
+    /// used to key caches or to deduplicate documents without serializing
+    /// them first. The bytes written to `state` depend only on the content,
+    /// not on the platform, so a fixed hasher gives results that are stable
+    /// across processes and machines.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// fn digest(v: &serde_json::Value) -> u64 {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     v.content_hash(&mut hasher);
+    ///     hasher.finish()
+    /// }
+    ///
+    /// let a = json!({"id": 1, "tags": ["x", "y"]});
+    /// let b = serde_json::from_str(r#"{"tags":["x","y"],"id":1}"#).unwrap();
+    /// assert_eq!(digest(&a), digest(&b));
+    /// assert_ne!(digest(&a), digest(&json!({"id": 1, "tags": ["y", "x"]})));
+    /// ```
+    pub fn content_hash<H: Hasher>(&self, state: &mut H) {
+        // Every value starts with a type tag and every container with its
+        // length, so that for example `[[], []]` and `[[[]]]` differ.
+        match self {
+            Value::Null => state.write_u8(0),
+            Value::Bool(b) => {
+                state.write_u8(1);
+                state.write_u8(*b as u8);
+            }
+            Value::Number(n) => {
+                state.write_u8(2);
+                n.content_hash(state);
+            }
+            Value::String(s) => {
+                state.write_u8(3);
+                write_str(state, s);
+            }
+            Value::Array(list) => {
+                state.write_u8(4);
+                state.write_u64(list.len() as u64);
+                for element in list {
+                    element.content_hash(state);
+                }
+            }
+            Value::Object(map) => {
+                state.write_u8(5);
+                state.write_u64(map.len() as u64);
+                // Equal maps must hash equally whatever their backend, so
+                // entries of an insertion-ordered map are hashed sorted.
+                if map.preserves_order() {
+                    let mut entries: alloc::vec::Vec<_> = map.iter().collect();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                    for (key, value) in entries {
+                        write_str(state, key);
+                        value.content_hash(state);
+                    }
+                } else {
+                    for (key, value) in map {
+                        write_str(state, key);
+                        value.content_hash(state);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Hashes by content; see [`Value::content_hash`].
+///
+/// ```
+/// # use serde_json::json;
+/// use std::collections::HashSet;
+///
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(json!({"a": 1, "b": [true]})));
+/// assert!(!seen.insert(json!({"b": [true], "a": 1})));
+/// ```
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.content_hash(state);
+    }
+}
+
+fn write_str<H: Hasher>(state: &mut H, s: &str) {
+    state.write_u64(s.len() as u64);
+    state.write(s.as_bytes());
+}
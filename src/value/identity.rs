@@ -0,0 +1,70 @@
+use super::{Map, Value};
+use crate::number::Number;
+use alloc::string::String;
+use core::any::TypeId;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ptr;
+
+/// Converts `value` to a `Value` without serializing it if it already is a
+/// `Value`, `Map<String, Value>` or `Number`, or a shared reference to one.
+/// Owned values are moved and references are cloned. Any other value is
+/// handed back.
+pub(super) fn to_value_fast<T>(value: T) -> Result<Value, T> {
+    let id = type_id::<T>();
+    // SAFETY: each `cast` is to the type that `id` was just compared with.
+    unsafe {
+        if id == TypeId::of::<Value>() {
+            Ok(cast::<T, Value>(value))
+        } else if id == TypeId::of::<Map<String, Value>>() {
+            Ok(Value::Object(cast(value)))
+        } else if id == TypeId::of::<Number>() {
+            Ok(Value::Number(cast(value)))
+        } else if id == TypeId::of::<&Value>() {
+            Ok(cast::<T, &Value>(value).clone())
+        } else if id == TypeId::of::<&Map<String, Value>>() {
+            Ok(Value::Object(cast::<T, &Map<String, Value>>(value).clone()))
+        } else if id == TypeId::of::<&Number>() {
+            Ok(Value::Number(cast::<T, &Number>(value).clone()))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// `TypeId::of::<T>()` for any `T`, with the lifetimes in `T` erased.
+fn type_id<T: ?Sized>() -> TypeId {
+    trait NonStaticAny {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom = PhantomData::<T>;
+    // SAFETY: only the lifetime bound of the trait object changes, and
+    // `get_type_id` never looks at the value.
+    let any =
+        unsafe { mem::transmute::<&dyn NonStaticAny, &(dyn NonStaticAny + 'static)>(&phantom) };
+    any.get_type_id()
+}
+
+/// Moves `value` into a `U`.
+///
+/// # Safety
+///
+/// `T` and `U` must be the same type except for lifetimes. A reference
+/// returned with a longer lifetime than it had must not outlive the
+/// original.
+unsafe fn cast<T, U>(value: T) -> U {
+    let value = ManuallyDrop::new(value);
+    ptr::read(&*value as *const T as *const U)
+}
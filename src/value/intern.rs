@@ -0,0 +1,181 @@
+use super::Value;
+use crate::number::Number;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::result;
+use serde::ser::{Serialize, Serializer};
+use std::collections::HashMap;
+
+/// A read-only JSON value in which strings, arrays and objects are reference
+/// counted, so that identical strings and identical sub-trees are stored only
+/// once. Created by [`Value::intern`].
+///
+/// Object members are kept in the order in which the original `Map` iterates
+/// them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SharedValue {
+    /// A JSON null.
+    Null,
+    /// A JSON boolean.
+    Bool(bool),
+    /// A JSON number.
+    Number(Number),
+    /// A JSON string, shared with every equal string in the document.
+    String(Arc<str>),
+    /// A JSON array, shared with every equal array in the document.
+    Array(Arc<[SharedValue]>),
+    /// A JSON object, shared with every equal object in the document.
+    Object(Arc<[(Arc<str>, SharedValue)]>),
+}
+
+impl SharedValue {
+    /// Returns the string if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SharedValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is an array.
+    pub fn as_array(&self) -> Option<&[SharedValue]> {
+        match self {
+            SharedValue::Array(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Looks up a member of an object. Returns `None` if this is not an
+    /// object or the key is missing.
+    pub fn get(&self, key: &str) -> Option<&SharedValue> {
+        match self {
+            SharedValue::Object(entries) => entries
+                .iter()
+                .find(|(k, _)| **k == *key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns true if `self` and `other` are the same shared allocation,
+    /// rather than merely equal. Always false for null, booleans and numbers.
+    pub fn ptr_eq(&self, other: &SharedValue) -> bool {
+        match (self, other) {
+            (SharedValue::String(a), SharedValue::String(b)) => Arc::ptr_eq(a, b),
+            (SharedValue::Array(a), SharedValue::Array(b)) => Arc::ptr_eq(a, b),
+            (SharedValue::Object(a), SharedValue::Object(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Copies this value back into an ordinary `Value`.
+    pub fn to_value(&self) -> Value {
+        match self {
+            SharedValue::Null => Value::Null,
+            SharedValue::Bool(b) => Value::Bool(*b),
+            SharedValue::Number(n) => Value::Number(n.clone()),
+            SharedValue::String(s) => Value::String(String::from(&**s)),
+            SharedValue::Array(list) => Value::Array(list.iter().map(Self::to_value).collect()),
+            SharedValue::Object(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (String::from(&**key), value.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl Serialize for SharedValue {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            SharedValue::Null => serializer.serialize_unit(),
+            SharedValue::Bool(b) => serializer.serialize_bool(*b),
+            SharedValue::Number(n) => n.serialize(serializer),
+            SharedValue::String(s) => serializer.serialize_str(s),
+            SharedValue::Array(list) => serializer.collect_seq(list.iter()),
+            SharedValue::Object(entries) => {
+                serializer.collect_map(entries.iter().map(|(key, value)| (&**key, value)))
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Builds a [`SharedValue`] in which identical strings, including object
+    /// keys, and identical arrays and objects are stored once and shared.
+    ///
+    /// Documents with many repeated enum-like strings or repeated records can
+    /// shrink several-fold. The pass hashes every array and object, so it
+    /// costs time proportional to the document size times its depth.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let v = json!([
+    ///     {"status": "active", "tags": ["a", "b"]},
+    ///     {"status": "active", "tags": ["a", "b"]},
+    /// ]);
+    ///
+    /// let shared = v.intern();
+    /// let rows = shared.as_array().unwrap();
+    /// assert!(rows[0].ptr_eq(&rows[1]));
+    /// assert_eq!(shared.to_value(), v);
+    /// assert_eq!(serde_json::to_string(&shared).unwrap(), v.to_string());
+    /// ```
+    pub fn intern(&self) -> SharedValue {
+        Interner {
+            strings: HashMap::new(),
+            trees: HashMap::new(),
+        }
+        .intern(self)
+    }
+}
+
+struct Interner<'a> {
+    strings: HashMap<&'a str, Arc<str>>,
+    trees: HashMap<&'a Value, SharedValue>,
+}
+
+impl<'a> Interner<'a> {
+    fn string(&mut self, s: &'a str) -> Arc<str> {
+        self.strings
+            .entry(s)
+            .or_insert_with(|| Arc::from(s))
+            .clone()
+    }
+
+    fn intern(&mut self, value: &'a Value) -> SharedValue {
+        match value {
+            Value::Null => SharedValue::Null,
+            Value::Bool(b) => SharedValue::Bool(*b),
+            Value::Number(n) => SharedValue::Number(n.clone()),
+            Value::String(s) => SharedValue::String(self.string(s)),
+            Value::Array(list) => {
+                if let Some(shared) = self.trees.get(value) {
+                    return shared.clone();
+                }
+                let elements: Vec<_> = list.iter().map(|v| self.intern(v)).collect();
+                let shared = SharedValue::Array(Arc::from(elements));
+                self.trees.insert(value, shared.clone());
+                shared
+            }
+            Value::Object(map) => {
+                if let Some(shared) = self.trees.get(value) {
+                    return shared.clone();
+                }
+                let entries: Vec<_> = map
+                    .iter()
+                    .map(|(key, v)| (self.string(key), self.intern(v)))
+                    .collect();
+                let shared = SharedValue::Object(Arc::from(entries));
+                self.trees.insert(value, shared.clone());
+                shared
+            }
+        }
+    }
+}
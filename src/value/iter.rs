@@ -0,0 +1,110 @@
+use super::Value;
+use crate::map;
+use alloc::string::String;
+use alloc::vec;
+use core::iter::FusedIterator;
+
+/// An item yielded by the owning iterator over a [`Value`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Item {
+    /// An element of an array, or the value itself if it was not an array or
+    /// an object.
+    Value(Value),
+    /// A `(key, value)` entry of an object.
+    Entry(String, Value),
+}
+
+impl Item {
+    /// Drops the key, if any, and returns the value.
+    pub fn into_value(self) -> Value {
+        match self {
+            Item::Value(value) | Item::Entry(_, value) => value,
+        }
+    }
+}
+
+impl IntoIterator for Value {
+    type Item = Item;
+    type IntoIter = IntoIter;
+
+    /// Drains any shape of value.
+    ///
+    /// Arrays yield their elements and objects yield their entries. Any other
+    /// value, including `null`, yields exactly one item: itself.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use serde_json::value::Item;
+    ///
+    /// let items: Vec<Item> = json!({"a": 1}).into_iter().collect();
+    /// assert_eq!(items, [Item::Entry("a".to_owned(), json!(1))]);
+    ///
+    /// let values: Vec<_> = json!([1, 2]).into_iter().map(Item::into_value).collect();
+    /// assert_eq!(values, [1, 2]);
+    ///
+    /// assert_eq!(json!("x").into_iter().count(), 1);
+    /// ```
+    fn into_iter(self) -> IntoIter {
+        IntoIter {
+            iter: match self {
+                Value::Array(array) => IntoIterImpl::Array(array.into_iter()),
+                Value::Object(object) => IntoIterImpl::Object(object.into_iter()),
+                scalar => IntoIterImpl::Scalar(Some(scalar)),
+            },
+        }
+    }
+}
+
+/// An owning iterator over a [`Value`], created by `Value::into_iter`.
+pub struct IntoIter {
+    iter: IntoIterImpl,
+}
+
+enum IntoIterImpl {
+    Array(vec::IntoIter<Value>),
+    Object(map::IntoIter),
+    Scalar(Option<Value>),
+}
+
+impl Iterator for IntoIter {
+    type Item = Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Item> {
+        match &mut self.iter {
+            IntoIterImpl::Array(iter) => iter.next().map(Item::Value),
+            IntoIterImpl::Object(iter) => iter.next().map(|(k, v)| Item::Entry(k, v)),
+            IntoIterImpl::Scalar(value) => value.take().map(Item::Value),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Item> {
+        match &mut self.iter {
+            IntoIterImpl::Array(iter) => iter.next_back().map(Item::Value),
+            IntoIterImpl::Object(iter) => iter.next_back().map(|(k, v)| Item::Entry(k, v)),
+            IntoIterImpl::Scalar(value) => value.take().map(Item::Value),
+        }
+    }
+}
+
+impl ExactSizeIterator for IntoIter {
+    #[inline]
+    fn len(&self) -> usize {
+        match &self.iter {
+            IntoIterImpl::Array(iter) => iter.len(),
+            IntoIterImpl::Object(iter) => iter.len(),
+            IntoIterImpl::Scalar(value) => value.is_some() as usize,
+        }
+    }
+}
+
+impl FusedIterator for IntoIter {}
@@ -0,0 +1,140 @@
+use super::Value;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::result;
+use serde::ser::{Serialize, Serializer};
+
+/// Controls the order in which object members are written, without
+/// reordering the `Map` itself.
+///
+/// Keys named with [`first`](KeyOrder::first) are written before all others,
+/// in the order given. The remaining keys are sorted by the comparator set
+/// with [`then_by`](KeyOrder::then_by), or alphabetically by default. The
+/// same order applies to objects at every depth.
+///
+/// ```
+/// # use serde_json::json;
+/// use serde_json::value::KeyOrder;
+///
+/// let order = KeyOrder::new().first(&["id", "type"]);
+/// let v = json!({"name": "x", "type": "user", "attrs": {"b": 1, "id": 2}, "id": 7});
+///
+/// assert_eq!(
+///     serde_json::to_string(&v.with_key_order(&order)).unwrap(),
+///     r#"{"id":7,"type":"user","attrs":{"id":2,"b":1},"name":"x"}"#,
+/// );
+/// ```
+#[derive(Default)]
+pub struct KeyOrder {
+    first: Vec<String>,
+    cmp: Option<Box<KeyCmp>>,
+}
+
+type KeyCmp = dyn Fn(&str, &str) -> Ordering + Send + Sync;
+
+impl KeyOrder {
+    /// Creates an order that sorts keys alphabetically.
+    pub fn new() -> Self {
+        KeyOrder::default()
+    }
+
+    /// Writes these keys before all others, in the given order. Calling this
+    /// again appends to the list.
+    pub fn first<S>(mut self, keys: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.first
+            .extend(keys.iter().map(|key| String::from(key.as_ref())));
+        self
+    }
+
+    /// Sorts the keys not named by [`first`](KeyOrder::first) with `cmp`
+    /// instead of alphabetically.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// use serde_json::value::KeyOrder;
+    ///
+    /// let by_length = KeyOrder::new().then_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+    /// let v = json!({"ccc": 3, "a": 1, "bb": 2});
+    /// assert_eq!(
+    ///     serde_json::to_string(&v.with_key_order(&by_length)).unwrap(),
+    ///     r#"{"a":1,"bb":2,"ccc":3}"#,
+    /// );
+    /// ```
+    pub fn then_by<F>(mut self, cmp: F) -> Self
+    where
+        F: Fn(&str, &str) -> Ordering + Send + Sync + 'static,
+    {
+        self.cmp = Some(Box::new(cmp));
+        self
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        let rank = |key: &str| self.first.iter().position(|first| first == key);
+        match (rank(a), rank(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => match &self.cmp {
+                Some(cmp) => cmp(a, b),
+                None => a.cmp(b),
+            },
+        }
+    }
+}
+
+impl Debug for KeyOrder {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("KeyOrder")
+            .field("first", &self.first)
+            .field("custom", &self.cmp.is_some())
+            .finish()
+    }
+}
+
+/// A `Value` that serializes its objects in a [`KeyOrder`]. Created by
+/// [`Value::with_key_order`].
+#[derive(Debug)]
+pub struct WithKeyOrder<'a> {
+    value: &'a Value,
+    order: &'a KeyOrder,
+}
+
+impl Value {
+    /// Wraps this value so that serializing it writes object members in the
+    /// given order. Pass the result to any serializer, for example
+    /// `serde_json::to_string_pretty`.
+    pub fn with_key_order<'a>(&'a self, order: &'a KeyOrder) -> WithKeyOrder<'a> {
+        WithKeyOrder { value: self, order }
+    }
+}
+
+impl<'a> Serialize for WithKeyOrder<'a> {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let order = self.order;
+        match self.value {
+            Value::Array(v) => {
+                serializer.collect_seq(v.iter().map(|value| value.with_key_order(order)))
+            }
+            Value::Object(m) => {
+                let mut entries: Vec<_> = m.iter().collect();
+                entries.sort_by(|a, b| order.compare(a.0, b.0));
+                serializer.collect_map(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key, value.with_key_order(order))),
+                )
+            }
+            other => other.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::{parse_index, Map, Value};
+use crate::de::Deserializer;
+use crate::error::Result;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A partially parsed JSON document, in which arrays and objects nested
+/// deeper than a chosen depth are kept as raw text and only parsed when they
+/// are accessed.
+///
+/// Parsing a large document in which only a few fields are read is then
+/// close to the cost of validating its syntax. Created by
+/// [`LazyValue::from_str`].
+///
+/// ```
+/// use serde_json::json;
+/// use serde_json::value::LazyValue;
+///
+/// let input = r#"{"id": 7, "payload": {"rows": [[1, 2], [3, 4]]}}"#;
+/// let mut doc = LazyValue::from_str(input, 1).unwrap();
+///
+/// assert_eq!(doc.get("id").unwrap().unwrap().to_value().unwrap(), json!(7));
+///
+/// // Not parsed yet.
+/// let payload = doc.get("payload").unwrap().unwrap();
+/// assert_eq!(payload.as_raw(), Some(r#"{"rows": [[1, 2], [3, 4]]}"#));
+///
+/// let row = doc.pointer("/payload/rows/1").unwrap().unwrap();
+/// assert_eq!(row.to_value().unwrap(), json!([3, 4]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum LazyValue<'a> {
+    /// A null, boolean, number or string.
+    Scalar(Value),
+    /// An array whose elements have been parsed lazily.
+    Array(Vec<LazyValue<'a>>),
+    /// An object whose members have been parsed lazily, in input order.
+    Object(Vec<(String, LazyValue<'a>)>),
+    /// The text of an array or object that has not been parsed yet. Its
+    /// syntax has already been checked.
+    Raw(&'a str),
+}
+
+impl<'a> LazyValue<'a> {
+    /// Parses `s`, keeping every array and object that is `depth` or more
+    /// levels deep as raw text. With a depth of 0 the whole document is kept
+    /// raw, if it is an array or object.
+    ///
+    /// The whole input is still checked to be valid JSON.
+    pub fn from_str(s: &'a str, depth: usize) -> Result<Self> {
+        let mut de = Deserializer::from_str(s);
+        let value = tri!(de.parse_lazy(depth));
+        tri!(de.end());
+        Ok(value)
+    }
+
+    /// Returns the raw text if this array or object has not been parsed yet.
+    pub fn as_raw(&self) -> Option<&'a str> {
+        match self {
+            LazyValue::Raw(raw) => Some(raw),
+            _ => None,
+        }
+    }
+
+    /// Parses one more level of this value if it is still raw. Its nested
+    /// arrays and objects stay raw.
+    pub fn force(&mut self) -> Result<()> {
+        if let LazyValue::Raw(raw) = *self {
+            *self = tri!(LazyValue::from_str(raw, 1));
+        }
+        Ok(())
+    }
+
+    /// Looks up a member of an object, parsing this value first if needed.
+    /// Returns `None` if this is not an object or the key is missing.
+    ///
+    /// If the key appears more than once, the last member wins, as when
+    /// parsing into a `Value`.
+    pub fn get(&mut self, key: &str) -> Result<Option<&mut LazyValue<'a>>> {
+        tri!(self.force());
+        Ok(match self {
+            LazyValue::Object(members) => members
+                .iter_mut()
+                .rev()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        })
+    }
+
+    /// Looks up an element of an array, parsing this value first if needed.
+    /// Returns `None` if this is not an array or the index is out of bounds.
+    pub fn get_index(&mut self, index: usize) -> Result<Option<&mut LazyValue<'a>>> {
+        tri!(self.force());
+        Ok(match self {
+            LazyValue::Array(elements) => elements.get_mut(index),
+            _ => None,
+        })
+    }
+
+    /// Looks up a value by JSON Pointer, parsing only the raw subtrees along
+    /// the path. See [`Value::pointer`] for the syntax.
+    pub fn pointer(&mut self, pointer: &str) -> Result<Option<&mut LazyValue<'a>>> {
+        if pointer.is_empty() {
+            return Ok(Some(self));
+        }
+        if !pointer.starts_with('/') {
+            return Ok(None);
+        }
+        let mut target = self;
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            tri!(target.force());
+            let next = match target {
+                LazyValue::Object(_) => tri!(target.get(&token)),
+                LazyValue::Array(_) => match parse_index(&token) {
+                    Some(index) => tri!(target.get_index(index)),
+                    None => None,
+                },
+                _ => None,
+            };
+            target = match next {
+                Some(next) => next,
+                None => return Ok(None),
+            };
+        }
+        Ok(Some(target))
+    }
+
+    /// Fully parses this value into an ordinary `Value`.
+    pub fn to_value(&self) -> Result<Value> {
+        Ok(match self {
+            LazyValue::Scalar(value) => value.clone(),
+            LazyValue::Array(elements) => {
+                let mut list = Vec::with_capacity(elements.len());
+                for element in elements {
+                    list.push(tri!(element.to_value()));
+                }
+                Value::Array(list)
+            }
+            LazyValue::Object(members) => {
+                let mut map = Map::new();
+                for (key, value) in members {
+                    map.insert(key.clone(), tri!(value.to_value()));
+                }
+                Value::Object(map)
+            }
+            LazyValue::Raw(raw) => tri!(crate::from_str(raw)),
+        })
+    }
+}
@@ -0,0 +1,167 @@
+use super::Value;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// How [`Value::merge_with`] combines an existing value with an incoming one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Merge objects key by key, recursing into members present on both
+    /// sides. Any other incoming value replaces the existing one. This is the
+    /// default.
+    Deep,
+    /// Replace the existing value with the incoming one, even if both are
+    /// objects.
+    Replace,
+    /// Like `Deep`, but when both values are arrays the incoming elements are
+    /// appended to the existing array.
+    Concat,
+    /// Like `Concat`, but skip incoming elements that are already present.
+    ConcatDedup,
+    /// Keep the existing value and ignore the incoming one.
+    Keep,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy::Deep
+    }
+}
+
+/// Per-path policies for [`Value::merge_with`].
+///
+/// Paths are JSON Pointers in which a `*` token matches any single key. Rules
+/// are tried in the order they were added and the first match wins, so add
+/// specific paths before broader wildcards. Paths with no matching rule use
+/// the default policy.
+///
+/// ```
+/// # use serde_json::json;
+/// use serde_json::value::{MergePolicies, MergePolicy};
+///
+/// let policies = MergePolicies::new()
+///     .path("/tags", MergePolicy::ConcatDedup)
+///     .path("/env/*", MergePolicy::Replace)
+///     .path("/name", MergePolicy::Keep);
+///
+/// let mut config = json!({
+///     "name": "base",
+///     "tags": ["a", "b"],
+///     "env": {"prod": {"replicas": 3, "debug": false}},
+///     "limits": {"cpu": 1}
+/// });
+/// config.merge_with(json!({
+///     "name": "override",
+///     "tags": ["b", "c"],
+///     "env": {"prod": {"replicas": 5}},
+///     "limits": {"memory": 512}
+/// }), &policies);
+///
+/// assert_eq!(config, json!({
+///     "name": "base",
+///     "tags": ["a", "b", "c"],
+///     "env": {"prod": {"replicas": 5}},
+///     "limits": {"cpu": 1, "memory": 512}
+/// }));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MergePolicies {
+    default: MergePolicy,
+    rules: Vec<(Vec<String>, MergePolicy)>,
+}
+
+impl MergePolicies {
+    /// Creates an empty set of rules with [`MergePolicy::Deep`] as the
+    /// default.
+    pub fn new() -> Self {
+        MergePolicies::default()
+    }
+
+    /// Sets the policy used where no rule matches.
+    pub fn default_policy(mut self, policy: MergePolicy) -> Self {
+        self.default = policy;
+        self
+    }
+
+    /// Adds a rule for the values at `pointer`. An empty pointer refers to
+    /// the root value.
+    pub fn path(mut self, pointer: &str, policy: MergePolicy) -> Self {
+        let tokens = pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        self.rules.push((tokens, policy));
+        self
+    }
+
+    fn policy_at(&self, path: &[String]) -> MergePolicy {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| {
+                pattern.len() == path.len()
+                    && pattern
+                        .iter()
+                        .zip(path)
+                        .all(|(pattern, token)| pattern == "*" || pattern == token)
+            })
+            .map_or(self.default, |(_, policy)| *policy)
+    }
+}
+
+impl Value {
+    /// Deep-merges `other` into `self`.
+    ///
+    /// Objects are merged key by key; any other value from `other`, including
+    /// arrays and `null`, replaces the existing one. Use
+    /// [`merge_with`](Value::merge_with) to choose a different behavior per
+    /// path.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut v = json!({"a": {"x": 1}, "list": [1]});
+    /// v.merge(json!({"a": {"y": 2}, "list": [2]}));
+    /// assert_eq!(v, json!({"a": {"x": 1, "y": 2}, "list": [2]}));
+    /// ```
+    pub fn merge(&mut self, other: Value) {
+        self.merge_with(other, &MergePolicies::new());
+    }
+
+    /// Deep-merges `other` into `self`, using `policies` to decide how each
+    /// path is combined.
+    pub fn merge_with(&mut self, other: Value, policies: &MergePolicies) {
+        merge_at(self, other, policies, &mut Vec::new());
+    }
+}
+
+fn merge_at(target: &mut Value, other: Value, policies: &MergePolicies, path: &mut Vec<String>) {
+    match (policies.policy_at(path), target, other) {
+        (MergePolicy::Keep, _, _) => {}
+        (MergePolicy::Replace, target, other) => *target = other,
+        (_, Value::Object(target), Value::Object(other)) => {
+            for (key, value) in other {
+                match target.get_mut(&key) {
+                    Some(existing) => {
+                        path.push(key);
+                        merge_at(existing, value, policies, path);
+                        path.pop();
+                    }
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (MergePolicy::Concat, Value::Array(target), Value::Array(other)) => {
+            target.extend(other);
+        }
+        (MergePolicy::ConcatDedup, Value::Array(target), Value::Array(other)) => {
+            for value in other {
+                if !target.contains(&value) {
+                    target.push(value);
+                }
+            }
+        }
+        (_, target, other) => *target = other,
+    }
+}
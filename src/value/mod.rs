@@ -94,13 +94,27 @@ use alloc::vec::Vec;
 use core::fmt::{self, Debug, Display};
 use core::mem;
 use core::str;
-use serde::de::DeserializeOwned;
+use serde::de::{Deserialize, DeserializeOwned};
 use serde::ser::Serialize;
 
+pub use self::coerce::{Coercion, Shape};
+pub use self::content::{BufferedDeserializer, ContentValue};
+pub use self::de::ValueRefDeserializer;
+pub(crate) use self::content::Content;
+pub(crate) use self::de::MapKeyDeserializer;
+pub use self::field_mask::FieldMask;
 pub use self::index::Index;
-pub use self::ser::Serializer;
+#[cfg(feature = "std")]
+pub use self::intern::SharedValue;
+pub use self::iter::{IntoIter, Item};
+pub use self::key_order::{KeyOrder, WithKeyOrder};
+pub use self::lazy::LazyValue;
+pub use self::merge::{MergePolicies, MergePolicy};
+pub use self::ser::{F32Policy, NonFinitePolicy, Serializer, ToValueOptions};
+pub use self::stats::Stats;
+pub use self::transform::{Case, Transform};
 pub use crate::map::Map;
-pub use crate::number::Number;
+pub use crate::number::{FromJsonNumber, Number};
 
 #[cfg(feature = "raw_value")]
 pub use crate::raw::{to_raw_value, RawValue};
@@ -250,7 +264,7 @@ impl Display for Value {
     }
 }
 
-fn parse_index(s: &str) -> Option<usize> {
+pub(crate) fn parse_index(s: &str) -> Option<usize> {
     if s.starts_with('+') || (s.starts_with('0') && s.len() != 1) {
         return None;
     }
@@ -485,6 +499,25 @@ impl Value {
         }
     }
 
+    /// If the `Value` is a String containing an RFC 3339 timestamp, returns
+    /// the parsed `DateTime`. Returns None otherwise.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let v = json!({ "at": "2023-04-05T06:07:08+02:00", "b": "yesterday" });
+    ///
+    /// let at = v["at"].as_datetime().unwrap();
+    /// assert_eq!(at.timestamp(), 1680667628);
+    ///
+    /// assert_eq!(v["b"].as_datetime(), None);
+    /// ```
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn as_datetime(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        chrono::DateTime::parse_from_rfc3339(self.as_str()?).ok()
+    }
+
     /// Returns true if the `Value` is a Number. Returns false otherwise.
     ///
     /// ```
@@ -504,6 +537,92 @@ impl Value {
         }
     }
 
+    /// If the `Value` is a Number, returns the associated [`Number`]. Returns
+    /// None otherwise.
+    ///
+    /// ```
+    /// # use serde_json::{json, Number};
+    /// #
+    /// let v = json!({ "a": 1, "b": 2.2, "c": -3, "d": "4" });
+    ///
+    /// assert_eq!(v["a"].as_number(), Some(&Number::from(1u64)));
+    /// assert_eq!(v["b"].as_number(), Some(&Number::from_f64(2.2).unwrap()));
+    /// assert_eq!(v["c"].as_number(), Some(&Number::from(-3i64)));
+    ///
+    /// // The string `"4"` is not a number.
+    /// assert_eq!(v["d"].as_number(), None);
+    /// ```
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Value::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is a Number, returns the associated mutable [`Number`].
+    /// Returns None otherwise.
+    ///
+    /// ```
+    /// # use serde_json::{json, Number};
+    /// #
+    /// let mut v = json!({ "price": 10 });
+    ///
+    /// if let Some(price) = v["price"].as_number_mut() {
+    ///     *price = &*price * &Number::from(2);
+    /// }
+    /// assert_eq!(v, json!({ "price": 20 }));
+    /// ```
+    pub fn as_number_mut(&mut self) -> Option<&mut Number> {
+        match self {
+            Value::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the `Value` is a Number whose value is a mathematical
+    /// integer, even if it is stored as a float such as `2.0`.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let v = json!({ "a": 64, "b": 2.0, "c": 2.5, "d": "2" });
+    ///
+    /// assert!(v["a"].is_integer());
+    /// assert!(v["b"].is_integer());
+    /// assert!(!v["c"].is_integer());
+    /// assert!(!v["d"].is_integer());
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_integer(),
+            _ => false,
+        }
+    }
+
+    /// If the `Value` is a Number that can be represented as `T`, returns it.
+    /// Returns None otherwise.
+    ///
+    /// Integer types only accept integers within their range; floats such as
+    /// `2.0` are rejected. See [`FromJsonNumber`] for the exact rules.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let v = json!({ "port": 8080, "ratio": 0.5, "big": 70000, "name": "x" });
+    ///
+    /// assert_eq!(v["port"].to_num::<u16>(), Some(8080));
+    /// assert_eq!(v["big"].to_num::<u16>(), None);
+    /// assert_eq!(v["ratio"].to_num::<f32>(), Some(0.5));
+    /// assert_eq!(v["ratio"].to_num::<u8>(), None);
+    /// assert_eq!(v["name"].to_num::<u8>(), None);
+    /// ```
+    pub fn to_num<T>(&self) -> Option<T>
+    where
+        T: FromJsonNumber,
+    {
+        self.as_number()?.to_num()
+    }
+
     /// Returns true if the `Value` is an integer between `i64::MIN` and
     /// `i64::MAX`.
     ///
@@ -807,6 +926,128 @@ impl Value {
     pub fn take(&mut self) -> Value {
         mem::replace(self, Value::Null)
     }
+
+    /// Parses a `Value` from an IO stream of JSON.
+    ///
+    /// Shorthand for `serde_json::from_reader::<_, Value>(reader)`.
+    ///
+    /// ```
+    /// # use serde_json::{json, Value};
+    /// #
+    /// let reader = br#"{"id": 7}"#.as_ref();
+    /// assert_eq!(Value::from_reader(reader).unwrap(), json!({"id": 7}));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn from_reader<R>(reader: R) -> Result<Value, Error>
+    where
+        R: io::Read,
+    {
+        crate::de::from_reader(reader)
+    }
+
+    /// Reads and parses the JSON file at `path`.
+    ///
+    /// The file is read through a buffer. Failing to open or read the file is
+    /// reported as an IO error.
+    ///
+    /// ```
+    /// # use serde_json::{json, Value};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = std::env::temp_dir().join("serde_json_value_from_file.json");
+    /// std::fs::write(&path, r#"[1, 2, 3]"#)?;
+    ///
+    /// assert_eq!(Value::from_file(&path)?, json!([1, 2, 3]));
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn from_file<P>(path: P) -> Result<Value, Error>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let file = tri!(std::fs::File::open(path).map_err(Error::io));
+        Value::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Serializes this value as compact JSON into the IO stream.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut out = Vec::new();
+    /// json!({"a": [1, null]}).to_writer(&mut out).unwrap();
+    /// assert_eq!(out, br#"{"a":[1,null]}"#);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_writer<W>(&self, writer: W) -> Result<(), Error>
+    where
+        W: io::Write,
+    {
+        crate::ser::to_writer(writer, self)
+    }
+
+    /// Serializes this value into the IO stream using a caller-supplied
+    /// [`Formatter`](crate::ser::Formatter) to control the output style.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// use serde_json::ser::PrettyFormatter;
+    ///
+    /// let mut out = Vec::new();
+    /// let formatter = PrettyFormatter::with_indent(b"    ");
+    /// json!({"a": 1}).write_with_formatter(&mut out, formatter).unwrap();
+    /// assert_eq!(out, b"{\n    \"a\": 1\n}");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_with_formatter<W, F>(&self, writer: W, formatter: F) -> Result<(), Error>
+    where
+        W: io::Write,
+        F: crate::ser::Formatter,
+    {
+        let mut ser = crate::ser::Serializer::with_formatter(writer, formatter);
+        self.serialize(&mut ser)
+    }
+
+    /// Serializes this value as pretty-printed JSON.
+    ///
+    /// Unlike `serde_json::to_string_pretty`, this cannot fail: every `Value`
+    /// is representable as JSON. The compact form is available through
+    /// `to_string()`.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let pretty = json!({"a": [1]}).to_string_pretty();
+    /// assert_eq!(pretty, "{\n  \"a\": [\n    1\n  ]\n}");
+    /// ```
+    pub fn to_string_pretty(&self) -> String {
+        alloc::format!("{:#}", self)
+    }
+
+    /// Serializes this value as compact JSON, leaving out every object member
+    /// whose value is `null`, at any depth. The value itself is not modified.
+    ///
+    /// `null` elements of arrays are kept so that positions do not shift.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let v = json!({"id": 1, "email": null, "tags": [null, "a"], "meta": {"x": null}});
+    /// assert_eq!(v.to_string_skip_nulls(), r#"{"id":1,"meta":{},"tags":[null,"a"]}"#);
+    /// assert!(v["email"].is_null());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_string_skip_nulls(&self) -> String {
+        // Serializing a `Value` cannot fail.
+        crate::ser::to_string(&ser::SkipNulls(self)).unwrap()
+    }
 }
 
 /// The default value is `Value::Null`.
@@ -844,11 +1085,31 @@ impl Default for Value {
     }
 }
 
+mod coerce;
+mod collect;
+mod content;
 mod de;
+mod field_mask;
 mod from;
+mod hash;
+mod identity;
 mod index;
+#[cfg(feature = "std")]
+mod intern;
+mod iter;
+mod key_order;
+mod lazy;
+mod merge;
+mod order;
 mod partial_eq;
+mod paths;
+mod query;
 mod ser;
+mod stats;
+mod summary;
+mod transform;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 /// Convert a `T` into `serde_json::Value` là 1 enum đại diện cho mọi json data hợp lệ
 ///
@@ -903,13 +1164,79 @@ mod ser;
 ///     println!("{}", serde_json::to_value(map).unwrap_err());
 /// }
 /// ```
+///
+/// Nếu `T` là `Value`, `Map<String, Value>` hoặc `Number`, hay tham chiếu tới
+/// một trong các kiểu đó, giá trị được chuyển hoặc clone thẳng mà không đi
+/// qua serialize. Các giá trị lồng bên trong kiểu khác, như `Vec<Value>`, vẫn
+/// được serialize.
 // Taking by value is more friendly to iterator adapters, option and result
 // consumers, etc. See https://github.com/serde-rs/json/pull/149.
 pub fn to_value<T>(value: T) -> Result<Value, Error>
 where
     T: Serialize,
 {
-    value.serialize(Serializer)
+    match identity::to_value_fast(value) {
+        Ok(value) => Ok(value),
+        Err(value) => value.serialize(Serializer),
+    }
+}
+
+/// Chuyển `T` thành `serde_json::Value` như [`to_value`], với các tùy chọn
+/// trong `options`.
+///
+/// ```
+/// use serde_json::json;
+/// use serde_json::value::{to_value_with, F32Policy, ToValueOptions};
+///
+/// let options = ToValueOptions::new().f32_policy(F32Policy::Native);
+/// let v = to_value_with([0.5f32, 0.1f32], &options).unwrap();
+/// assert_eq!(v, json!([0.5, 0.1]));
+/// ```
+pub fn to_value_with<T>(value: T, options: &ToValueOptions) -> Result<Value, Error>
+where
+    T: Serialize,
+{
+    match identity::to_value_fast(value) {
+        Ok(value) => Ok(value),
+        Err(value) => value.serialize(ser::ValueSerializer { options: *options }),
+    }
+}
+
+/// Serialize `value` thẳng vào một `Map` có sẵn thay vì tạo một `Value` mới.
+///
+/// `value` phải được serialize thành một map hoặc struct; các khóa của nó
+/// được thêm vào `map`, ghi đè giá trị cũ của khóa trùng. Dùng cho kiểu
+/// phong bì, trong đó dữ liệu có kiểu được nhúng vào một object động.
+///
+/// ```
+/// use serde::Serialize;
+/// use serde_json::json;
+/// use serde_json::value::to_value_into;
+///
+/// #[derive(Serialize)]
+/// struct Payload {
+///     id: u32,
+///     status: &'static str,
+/// }
+///
+/// let mut envelope = json!({"version": 2, "status": "pending"});
+/// let map = envelope.as_object_mut().unwrap();
+/// to_value_into(Payload { id: 7, status: "ok" }, map).unwrap();
+/// assert_eq!(envelope, json!({"version": 2, "id": 7, "status": "ok"}));
+/// ```
+///
+/// # Errors
+///
+/// Thất bại nếu `value` không phải map hay struct, hoặc trong các trường hợp
+/// như [`to_value`]. Khi đó `map` có thể đã nhận một phần các khóa.
+pub fn to_value_into<T>(value: T, map: &mut Map<String, Value>) -> Result<(), Error>
+where
+    T: Serialize,
+{
+    value.serialize(ser::MapInserter {
+        map,
+        options: ToValueOptions::default(),
+    })
 }
 
 /// Hiểu một serde_json::Value như một thể hiện của kiểu T
@@ -953,3 +1280,147 @@ where
 {
     T::deserialize(value)
 }
+
+/// Hiểu một `&serde_json::Value` như một thể hiện của kiểu T, trong đó T được
+/// phép mượn chuỗi từ chính `Value` thay vì sao chép.
+///
+/// Khác với [`from_value`], `Value` không bị tiêu thụ, nên có thể giữ một tài
+/// liệu lâu dài trong bộ nhớ đệm và trích xuất nhiều kiểu khác nhau từ nó mà
+/// không cấp phát lại các chuỗi.
+///
+/// Hàm này chỉ là cách viết gọn của `T::deserialize(value)`: bản thân
+/// `&Value` đã là một `Deserializer` trả chuỗi dưới dạng `&'de str`, nên các
+/// trường `&'a str` và `Cow<'a, str>` có `#[serde(borrow)]` sẽ nhận
+/// `Cow::Borrowed` trỏ vào `value`.
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+///
+/// #[derive(Deserialize)]
+/// struct Event<'a> {
+///     kind: &'a str,
+///     tags: Vec<&'a str>,
+/// }
+///
+/// let cached = json!({"kind": "click", "tags": ["ui", "nav"]});
+/// let event: Event = serde_json::from_value_ref(&cached).unwrap();
+/// assert_eq!(event.kind, "click");
+/// assert_eq!(event.tags, ["ui", "nav"]);
+/// ```
+///
+/// # Errors
+///
+/// Giống [`from_value`].
+pub fn from_value_ref<'a, T>(value: &'a Value) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    T::deserialize(value)
+}
+
+/// Giống [`from_value`], nhưng khi thất bại trả về mọi lỗi tìm thấy thay vì
+/// chỉ lỗi đầu tiên, mỗi lỗi kèm [`Error::path`] tới giá trị gây lỗi.
+///
+/// Hữu ích khi kiểm tra file cấu hình: người dùng thấy tất cả các trường sai
+/// kiểu hoặc bị thiếu trong một lần. Sau mỗi lỗi, giá trị gây lỗi được bỏ đi
+/// và việc giải mã được thử lại, nên lỗi chỉ là hệ quả của việc bỏ đi đó
+/// (chẳng hạn thiếu chính trường vừa bị bỏ) không được báo. Một struct chỉ
+/// báo một trường bắt buộc bị thiếu.
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use serde_json::value::from_value_collect;
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Config {
+///     name: String,
+///     servers: Vec<Server>,
+/// }
+///
+/// let config = json!({
+///     "name": 7,
+///     "servers": [
+///         {"host": "a", "port": 80},
+///         {"host": "b", "port": 70000},
+///         {"port": 443},
+///     ],
+/// });
+/// let errors = from_value_collect::<Config>(config).unwrap_err();
+/// let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+/// assert_eq!(messages, [
+///     "invalid type: integer `7`, expected a string at /name",
+///     "invalid value: integer `70000`, expected u16 at /servers/1/port",
+///     "missing field `host` at /servers/2",
+/// ]);
+/// ```
+///
+/// # Errors
+///
+/// Danh sách không rỗng các lỗi, theo thứ tự được tìm thấy.
+pub fn from_value_collect<T>(value: Value) -> Result<T, Vec<Error>>
+where
+    T: DeserializeOwned,
+{
+    match collect::from_value_pruning(value) {
+        (Some(t), errors) if errors.is_empty() => Ok(t),
+        (_, errors) => Err(errors),
+    }
+}
+
+/// Giống [`from_value`], nhưng giá trị không giải mã được sẽ bị bỏ qua thay
+/// vì làm hỏng cả kết quả; các lỗi bị bỏ qua được trả về cùng kết quả.
+///
+/// Mỗi giá trị gây lỗi được bỏ khỏi input rồi việc giải mã được thử lại, nên
+/// trường có `#[serde(default)]` nhận `Default::default()`, trường
+/// `Option` nhận `None`, và phần tử mảng hoặc mục của map bị bỏ đi. Nếu một
+/// trường bắt buộc gây lỗi thì cả struct chứa nó bị bỏ theo cách đó. Các lỗi
+/// được báo như [`from_value_collect`], mỗi lỗi kèm [`Error::path`].
+///
+/// Dùng cho các pipeline như telemetry, nơi một bản ghi hỏng một phần vẫn
+/// nên giữ lại các trường tốt.
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use serde_json::value::from_value_lenient;
+///
+/// #[derive(Deserialize, Default, Debug, PartialEq)]
+/// #[serde(default)]
+/// struct Sample {
+///     host: String,
+///     cpu: f64,
+///     tags: Vec<String>,
+/// }
+///
+/// let record = json!({"host": "web-1", "cpu": "high", "tags": ["a", 2]});
+/// let (sample, errors) = from_value_lenient::<Sample>(record).unwrap();
+/// assert_eq!(sample, Sample {
+///     host: "web-1".to_owned(),
+///     cpu: 0.0,
+///     tags: vec!["a".to_owned()],
+/// });
+/// let paths: Vec<_> = errors.iter().map(|err| err.path().unwrap()).collect();
+/// assert_eq!(paths, ["/cpu", "/tags/1"]);
+/// ```
+///
+/// # Errors
+///
+/// Trả về các lỗi tìm được nếu ngay cả giá trị gốc cũng không thể giải mã
+/// thành `T`, ví dụ khi nó có kiểu sai hoặc thiếu một trường bắt buộc.
+pub fn from_value_lenient<T>(value: Value) -> Result<(T, Vec<Error>), Vec<Error>>
+where
+    T: DeserializeOwned,
+{
+    match collect::from_value_pruning(value) {
+        (Some(t), errors) => Ok((t, errors)),
+        (None, errors) => Err(errors),
+    }
+}
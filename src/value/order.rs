@@ -0,0 +1,153 @@
+use super::Value;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+impl Value {
+    /// A total ordering over all JSON values.
+    ///
+    /// Values of different types are ordered `null < bool < number < string <
+    /// array < object`. Numbers compare by numeric value, with an integer
+    /// ordered before a float of the same value (`1 < 1.0`). Strings compare
+    /// by their UTF-8 bytes. Arrays compare element by element. Objects
+    /// compare their entries sorted by key, as if they were arrays of
+    /// `[key, value]` pairs.
+    ///
+    /// Two values compare equal exactly when they are `==`.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!(json!(null).total_cmp(&json!(false)), Ordering::Less);
+    /// assert_eq!(json!(2).total_cmp(&json!(10.5)), Ordering::Less);
+    /// assert_eq!(json!("b").total_cmp(&json!("ab")), Ordering::Greater);
+    /// assert_eq!(json!([1, 2]).total_cmp(&json!([1])), Ordering::Greater);
+    /// assert_eq!(json!({"a": 1}).total_cmp(&json!({"a": 1})), Ordering::Equal);
+    /// ```
+    pub fn total_cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Number(a), Value::Number(b)) => a.total_cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Array(a), Value::Array(b)) => {
+                cmp_seq(a.iter(), b.iter(), |x, y| x.total_cmp(y))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+                a.sort_by(|x, y| x.0.cmp(y.0));
+                b.sort_by(|x, y| x.0.cmp(y.0));
+                cmp_seq(a.into_iter(), b.into_iter(), |x, y| {
+                    x.0.cmp(y.0).then_with(|| x.1.total_cmp(y.1))
+                })
+            }
+            _ => type_rank(self).cmp(&type_rank(other)),
+        }
+    }
+
+    /// Sorts the elements of an array in place by [`total_cmp`]. Does nothing
+    /// if this is not an array.
+    ///
+    /// [`total_cmp`]: Value::total_cmp
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut v = json!([3, "a", null, 1.5, [0]]);
+    /// v.sort_array();
+    /// assert_eq!(v, json!([null, 1.5, 3, "a", [0]]));
+    /// ```
+    pub fn sort_array(&mut self) {
+        if let Value::Array(list) = self {
+            list.sort_by(Value::total_cmp);
+        }
+    }
+
+    /// Stably sorts the elements of an array in place by the value each one
+    /// has at the JSON Pointer `key`. Elements where `key` does not resolve
+    /// come first. Does nothing if this is not an array.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut v = json!([{"id": 3}, {"id": 1, "x": 0}, {}, {"id": 1}]);
+    /// v.sort_array_by_key("/id");
+    /// assert_eq!(v, json!([{}, {"id": 1, "x": 0}, {"id": 1}, {"id": 3}]));
+    /// ```
+    pub fn sort_array_by_key(&mut self, key: &str) {
+        if let Value::Array(list) = self {
+            list.sort_by(|a, b| match (a.pointer(key), b.pointer(key)) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            });
+        }
+    }
+
+    /// Removes repeated elements from an array, keeping the first occurrence
+    /// of each value and the original order. Does nothing if this is not an
+    /// array.
+    ///
+    /// Unlike `Vec::dedup`, duplicates do not need to be adjacent.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut v = json!([1, "a", 1, {"k": 1}, "a", 1.0, {"k": 1}]);
+    /// v.dedup_array();
+    /// assert_eq!(v, json!([1, "a", {"k": 1}, 1.0]));
+    /// ```
+    pub fn dedup_array(&mut self) {
+        let list = match self {
+            Value::Array(list) => list,
+            _ => return,
+        };
+
+        // A stable sort keeps equal values in their original order, so the
+        // first of each run is the first occurrence.
+        let mut order: Vec<usize> = (0..list.len()).collect();
+        order.sort_by(|&a, &b| list[a].total_cmp(&list[b]));
+        let mut keep = Vec::new();
+        keep.resize(list.len(), true);
+        for pair in order.windows(2) {
+            if list[pair[0]] == list[pair[1]] {
+                keep[pair[1]] = false;
+            }
+        }
+
+        let mut index = 0;
+        list.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+    }
+}
+
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+fn cmp_seq<I, F>(mut a: I, mut b: I, mut cmp: F) -> Ordering
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match cmp(&x, &y) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::{parse_index, Value};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+impl Value {
+    /// Keeps only the parts of this value selected by `patterns`, removing
+    /// everything else.
+    ///
+    /// Each pattern is a JSON Pointer in which a `*` token matches any object
+    /// key or array index. The whole subtree at a matching path is kept,
+    /// together with the objects and arrays leading to it. Objects and arrays
+    /// on the way to a pattern that matches nothing are dropped, so selecting
+    /// a missing field does not leave an empty parent behind. Kept array
+    /// elements move down to fill the gaps. The empty pointer `""` selects
+    /// the whole value.
+    ///
+    /// If nothing matches, objects and arrays are left empty and any other
+    /// value becomes `null`.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut user = json!({
+    ///     "id": 7,
+    ///     "name": "ann",
+    ///     "address": {"city": "Hanoi", "zip": "100000"},
+    ///     "orders": [{"id": 1, "total": 30}, {"id": 2, "total": 12}],
+    /// });
+    /// user.retain_paths(&["/name", "/address/city", "/orders/*/id", "/missing/x"]);
+    ///
+    /// assert_eq!(user, json!({
+    ///     "name": "ann",
+    ///     "address": {"city": "Hanoi"},
+    ///     "orders": [{"id": 1}, {"id": 2}],
+    /// }));
+    /// ```
+    pub fn retain_paths<S: AsRef<str>>(&mut self, patterns: &[S]) {
+        let patterns = parse_patterns(patterns);
+        let patterns: Vec<&[String]> = patterns.iter().map(Vec::as_slice).collect();
+        if !retain_at(self, &patterns) {
+            match self {
+                Value::Object(map) => map.clear(),
+                Value::Array(list) => list.clear(),
+                _ => *self = Value::Null,
+            }
+        }
+    }
+
+    /// Removes the parts of this value selected by `patterns`, the inverse of
+    /// [`retain_paths`](Value::retain_paths).
+    ///
+    /// Patterns use the same syntax. Paths that match nothing are ignored and
+    /// removing array elements shifts the following elements down. Matching
+    /// the whole value with `""` replaces it with `null`.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let mut user = json!({
+    ///     "name": "ann",
+    ///     "password": "hunter2",
+    ///     "sessions": [{"token": "a", "ip": "10.0.0.1"}, {"token": "b"}],
+    /// });
+    /// user.remove_paths(&["/password", "/sessions/*/token"]);
+    ///
+    /// assert_eq!(user, json!({
+    ///     "name": "ann",
+    ///     "sessions": [{"ip": "10.0.0.1"}, {}],
+    /// }));
+    /// ```
+    pub fn remove_paths<S: AsRef<str>>(&mut self, patterns: &[S]) {
+        let patterns = parse_patterns(patterns);
+        let patterns: Vec<&[String]> = patterns.iter().map(Vec::as_slice).collect();
+        if patterns.iter().any(|pattern| pattern.is_empty()) {
+            *self = Value::Null;
+        } else {
+            remove_at(self, &patterns);
+        }
+    }
+}
+
+fn parse_patterns<S: AsRef<str>>(patterns: &[S]) -> Vec<Vec<String>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            pattern
+                .as_ref()
+                .split('/')
+                .skip(1)
+                .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the remaining tokens of every pattern whose next token matches
+/// the child `key`, or `index` for array elements.
+fn child_patterns<'a>(
+    patterns: &[&'a [String]],
+    key: &str,
+    index: Option<usize>,
+) -> Vec<&'a [String]> {
+    patterns
+        .iter()
+        .filter(|pattern| {
+            let token = &pattern[0];
+            token == "*"
+                || match index {
+                    Some(index) => parse_index(token) == Some(index),
+                    None => token == key,
+                }
+        })
+        .map(|pattern| &pattern[1..])
+        .collect()
+}
+
+/// Prunes `value` to what `patterns` select and returns whether anything was
+/// selected.
+fn retain_at(value: &mut Value, patterns: &[&[String]]) -> bool {
+    if patterns.iter().any(|pattern| pattern.is_empty()) {
+        return true;
+    }
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, child| {
+                let patterns = child_patterns(patterns, key, None);
+                !patterns.is_empty() && retain_at(child, &patterns)
+            });
+            !map.is_empty()
+        }
+        Value::Array(list) => {
+            retain_elements(list, |index, child| {
+                let patterns = child_patterns(patterns, "", Some(index));
+                !patterns.is_empty() && retain_at(child, &patterns)
+            });
+            !list.is_empty()
+        }
+        _ => false,
+    }
+}
+
+fn remove_at(value: &mut Value, patterns: &[&[String]]) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, child| remove_child(child, &child_patterns(patterns, key, None)))
+        }
+        Value::Array(list) => retain_elements(list, |index, child| {
+            remove_child(child, &child_patterns(patterns, "", Some(index)))
+        }),
+        _ => {}
+    }
+}
+
+/// Applies `patterns` below `child` and returns whether `child` itself stays.
+fn remove_child(child: &mut Value, patterns: &[&[String]]) -> bool {
+    if patterns.iter().any(|pattern| pattern.is_empty()) {
+        return false;
+    }
+    if !patterns.is_empty() {
+        remove_at(child, patterns);
+    }
+    true
+}
+
+/// `Vec::retain` with mutable access to the element and its original index.
+fn retain_elements<F>(list: &mut Vec<Value>, mut f: F)
+where
+    F: FnMut(usize, &mut Value) -> bool,
+{
+    let mut kept = Vec::with_capacity(list.len());
+    for (index, mut element) in list.drain(..).enumerate() {
+        if f(index, &mut element) {
+            kept.push(element);
+        }
+    }
+    *list = kept;
+}
@@ -0,0 +1,338 @@
+use super::{parse_index, Value};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+impl Value {
+    /// Looks up a value using a compact, gjson-style path.
+    ///
+    /// This is a lighter-weight alternative to JSON Pointer for quick data
+    /// extraction in scripts and tests. A path is a series of components
+    /// separated by `.`:
+    ///
+    /// - `name` selects an object member, or an array element if it is an
+    ///   index. Use `\.`, `\*` and `\?` for literal characters.
+    /// - `na*e` or `n?me` selects the first object member whose key matches
+    ///   the wildcard pattern.
+    /// - `#` as the last component returns the length of an array. Followed
+    ///   by more components, the rest of the path is applied to every element
+    ///   and the results are collected into an array.
+    /// - `#(cond)` selects the first array element matching the condition,
+    ///   and `#(cond)#` selects all of them. A condition is a path relative
+    ///   to the element, optionally followed by one of `==`, `!=`, `<`, `<=`,
+    ///   `>`, `>=`, `%` (wildcard match) or `!%` and a JSON literal. Without
+    ///   an operator the condition checks that the path exists.
+    ///
+    /// Since some results are computed rather than found in the document,
+    /// the result is returned by value. Returns `None` if nothing matches or
+    /// if the path is malformed.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let data = json!({
+    ///     "name": {"first": "Tom", "last": "Anderson"},
+    ///     "friends": [
+    ///         {"first": "Dale", "age": 44, "nets": ["ig", "fb", "tw"]},
+    ///         {"first": "Roger", "age": 68, "nets": ["fb", "tw"]},
+    ///         {"first": "Jane", "age": 47, "nets": ["ig", "tw"]}
+    ///     ]
+    /// });
+    ///
+    /// assert_eq!(data.query("name.last"), Some(json!("Anderson")));
+    /// assert_eq!(data.query("name.f*"), Some(json!("Tom")));
+    /// assert_eq!(data.query("friends.#"), Some(json!(3)));
+    /// assert_eq!(data.query("friends.1.first"), Some(json!("Roger")));
+    /// assert_eq!(data.query("friends.#.age"), Some(json!([44, 68, 47])));
+    /// assert_eq!(data.query("friends.#(age>45).first"), Some(json!("Roger")));
+    /// assert_eq!(
+    ///     data.query("friends.#(nets.#(==\"ig\"))#.first"),
+    ///     Some(json!(["Dale", "Jane"])),
+    /// );
+    /// assert_eq!(data.query("friends.#(first%\"J*\").age"), Some(json!(47)));
+    /// assert_eq!(data.query("friends.5"), None);
+    /// ```
+    pub fn query(&self, path: &str) -> Option<Value> {
+        let parts = parse_path(path)?;
+        eval(self, &parts)
+    }
+}
+
+enum Part {
+    Key(String),
+    Pattern(Vec<Token>),
+    Count,
+    Filter { condition: Condition, all: bool },
+}
+
+struct Condition {
+    path: Vec<Part>,
+    comparison: Option<(Op, Value)>,
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Like,
+    NotLike,
+}
+
+// Two-character operators come first so that `<=` is not read as `<`.
+const OPS: [(&str, Op); 8] = [
+    ("==", Op::Eq),
+    ("!=", Op::Ne),
+    ("<=", Op::Le),
+    (">=", Op::Ge),
+    ("!%", Op::NotLike),
+    ("<", Op::Lt),
+    (">", Op::Gt),
+    ("%", Op::Like),
+];
+
+#[derive(PartialEq)]
+enum Token {
+    Char(char),
+    AnyString,
+    AnyChar,
+}
+
+fn eval(value: &Value, parts: &[Part]) -> Option<Value> {
+    let (part, rest) = match parts.split_first() {
+        Some(split) => split,
+        None => return Some(value.clone()),
+    };
+
+    match part {
+        Part::Key(key) => {
+            let child = match value {
+                Value::Object(map) => map.get(key),
+                Value::Array(list) => parse_index(key).and_then(|i| list.get(i)),
+                _ => None,
+            };
+            eval(child?, rest)
+        }
+        Part::Pattern(pattern) => value
+            .as_object()?
+            .iter()
+            .filter(|(key, _)| glob_match(pattern, key))
+            .find_map(|(_, child)| eval(child, rest)),
+        Part::Count => {
+            let list = value.as_array()?;
+            Some(if rest.is_empty() {
+                Value::from(list.len())
+            } else {
+                list.iter().filter_map(|child| eval(child, rest)).collect()
+            })
+        }
+        Part::Filter { condition, all } => {
+            let mut found = value
+                .as_array()?
+                .iter()
+                .filter(|child| condition.test(child));
+            if *all {
+                Some(found.filter_map(|child| eval(child, rest)).collect())
+            } else {
+                found.find_map(|child| eval(child, rest))
+            }
+        }
+    }
+}
+
+impl Condition {
+    fn test(&self, value: &Value) -> bool {
+        let lhs = match eval(value, &self.path) {
+            Some(lhs) => lhs,
+            None => return false,
+        };
+        let (op, rhs) = match &self.comparison {
+            Some(comparison) => comparison,
+            None => return true,
+        };
+
+        match op {
+            Op::Eq => lhs == *rhs,
+            Op::Ne => lhs != *rhs,
+            Op::Like | Op::NotLike => match (lhs.as_str(), rhs.as_str()) {
+                (Some(text), Some(pattern)) => {
+                    glob_match(&tokenize(pattern), text) == matches!(op, Op::Like)
+                }
+                _ => false,
+            },
+            Op::Lt | Op::Le | Op::Gt | Op::Ge => {
+                let ordering = match (&lhs, rhs) {
+                    (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+                    (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+                    _ => None,
+                };
+                match ordering {
+                    Some(ordering) => match op {
+                        Op::Lt => ordering.is_lt(),
+                        Op::Le => ordering.is_le(),
+                        Op::Gt => ordering.is_gt(),
+                        _ => ordering.is_ge(),
+                    },
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+fn parse_path(path: &str) -> Option<Vec<Part>> {
+    if path.is_empty() {
+        return Some(Vec::new());
+    }
+    split_top_level(path)?.into_iter().map(parse_part).collect()
+}
+
+/// Splits at every `.` that is not escaped, quoted, or inside parentheses.
+fn split_top_level(path: &str) -> Option<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, b) in path.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' => escaped = true,
+            b'"' if depth > 0 => quoted = !quoted,
+            b'(' if !quoted => depth += 1,
+            b')' if !quoted => depth = depth.checked_sub(1)?,
+            b'.' if depth == 0 => {
+                parts.push(&path[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || quoted {
+        return None;
+    }
+    parts.push(&path[start..]);
+    Some(parts)
+}
+
+fn parse_part(raw: &str) -> Option<Part> {
+    if raw == "#" {
+        return Some(Part::Count);
+    }
+
+    if let Some(filter) = raw.strip_prefix("#(") {
+        let (inner, all) = match filter.strip_suffix(")#") {
+            Some(inner) => (inner, true),
+            None => (filter.strip_suffix(')')?, false),
+        };
+        let condition = parse_condition(inner)?;
+        return Some(Part::Filter { condition, all });
+    }
+
+    let tokens = tokenize(raw);
+    if tokens.iter().all(|token| matches!(token, Token::Char(_))) {
+        let key = tokens
+            .into_iter()
+            .map(|token| match token {
+                Token::Char(ch) => ch,
+                _ => unreachable!(),
+            })
+            .collect();
+        Some(Part::Key(key))
+    } else {
+        Some(Part::Pattern(tokens))
+    }
+}
+
+fn parse_condition(inner: &str) -> Option<Condition> {
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, b) in inner.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' => escaped = true,
+            b'"' => quoted = !quoted,
+            b'(' if !quoted => depth += 1,
+            b')' if !quoted => depth = depth.checked_sub(1)?,
+            _ if depth == 0 && !quoted => {
+                let rest = &inner[i..];
+                if let Some(&(symbol, op)) = OPS.iter().find(|(symbol, _)| rest.starts_with(symbol))
+                {
+                    let rhs = crate::from_str(rest[symbol.len()..].trim()).ok()?;
+                    return Some(Condition {
+                        path: parse_path(inner[..i].trim())?,
+                        comparison: Some((op, rhs)),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(Condition {
+        path: parse_path(inner.trim())?,
+        comparison: None,
+    })
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        tokens.push(match ch {
+            '\\' => Token::Char(chars.next().unwrap_or('\\')),
+            '*' => Token::AnyString,
+            '?' => Token::AnyChar,
+            ch => Token::Char(ch),
+        });
+    }
+    tokens
+}
+
+/// Wildcard matching with backtracking to the most recent `*` only, which
+/// keeps it linear in practice.
+fn glob_match(pattern: &[Token], text: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let mut p = 0;
+    let mut t = 0;
+    let mut star = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(Token::AnyString) => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(Token::AnyChar) => {
+                p += 1;
+                t += 1;
+            }
+            Some(Token::Char(ch)) if *ch == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|token| *token == Token::AnyString)
+}
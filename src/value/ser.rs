@@ -1,7 +1,7 @@
 use crate::error::{Error, ErrorCode, Result};
 use crate::map::Map;
 use crate::number::Number;
-use crate::value::{to_value, Value};
+use crate::value::Value;
 use alloc::borrow::ToOwned;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
@@ -34,6 +34,159 @@ impl Serialize for Value {
     }
 }
 
+/// Serializes a `Value` while leaving out object members whose value is
+/// `null`. Nulls inside arrays are kept so that positions do not shift.
+pub(crate) struct SkipNulls<'a>(pub &'a Value);
+
+impl<'a> Serialize for SkipNulls<'a> {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        match self.0 {
+            Value::Array(v) => serializer.collect_seq(v.iter().map(SkipNulls)),
+            Value::Object(m) => serializer.collect_map(
+                m.iter()
+                    .filter(|(_, v)| !v.is_null())
+                    .map(|(k, v)| (k, SkipNulls(v))),
+            ),
+            other => other.serialize(serializer),
+        }
+    }
+}
+
+/// How an `f32` is converted to a JSON number, used by both
+/// [`to_value_with`](crate::value::to_value_with) and
+/// [`Serializer::set_f32_policy`](crate::Serializer::set_f32_policy).
+///
+/// ```
+/// use serde_json::value::{F32Policy, ToValueOptions};
+///
+/// let x = 0.1f32;
+/// assert_eq!(serde_json::to_string(&x).unwrap(), "0.1");
+/// assert_eq!(serde_json::to_value(x).unwrap().to_string(), "0.10000000149011612");
+///
+/// let native = ToValueOptions::new().f32_policy(F32Policy::Native);
+/// let v = serde_json::value::to_value_with(x, &native).unwrap();
+/// assert_eq!(v.to_string(), "0.1");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum F32Policy {
+    /// Use the shortest decimal that reads back as the same `f32`, so `0.1f32`
+    /// becomes `0.1`. In a `Value` the number is stored as the `f64` closest
+    /// to that decimal. This is the default for `Serializer`.
+    Native,
+    /// Convert to `f64` exactly and use the shortest decimal for that `f64`,
+    /// so `0.1f32` becomes `0.10000000149011612`. This is the default for
+    /// [`to_value`](crate::to_value), which has always widened `f32`.
+    Widen,
+}
+
+impl F32Policy {
+    pub(crate) fn to_f64(self, value: f32) -> f64 {
+        match self {
+            F32Policy::Native if value.is_finite() => {
+                let mut buffer = ryu::Buffer::new();
+                // The shortest representation always parses.
+                buffer.format_finite(value).parse().unwrap_or(value as f64)
+            }
+            _ => value as f64,
+        }
+    }
+}
+
+/// How NaN and infinite floats are handled, used by both
+/// [`to_value_with`](crate::value::to_value_with) and
+/// [`Serializer::set_non_finite_policy`](crate::Serializer::set_non_finite_policy).
+///
+/// JSON has no representation for these values.
+///
+/// ```
+/// use serde_json::json;
+/// use serde_json::value::{to_value_with, NonFinitePolicy, ToValueOptions};
+///
+/// let values = [1.5, f64::NAN, f64::NEG_INFINITY];
+/// assert_eq!(serde_json::to_string(&values).unwrap(), "[1.5,null,null]");
+///
+/// let tokens = ToValueOptions::new().non_finite_policy(NonFinitePolicy::StringTokens);
+/// let v = to_value_with(values, &tokens).unwrap();
+/// assert_eq!(v, json!([1.5, "NaN", "-Infinity"]));
+///
+/// let strict = ToValueOptions::new().non_finite_policy(NonFinitePolicy::Error);
+/// assert!(to_value_with(values, &strict).is_err());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NonFinitePolicy {
+    /// Write `null`. This is the default.
+    Null,
+    /// Fail with an error.
+    Error,
+    /// Write the strings `"NaN"`, `"Infinity"` and `"-Infinity"`, as
+    /// JavaScript's `String(x)` and many other JSON libraries do.
+    StringTokens,
+}
+
+impl Default for NonFinitePolicy {
+    fn default() -> Self {
+        NonFinitePolicy::Null
+    }
+}
+
+impl NonFinitePolicy {
+    /// The string written for `value` under `StringTokens`.
+    pub(crate) fn token(value: f64) -> &'static str {
+        if value.is_nan() {
+            "NaN"
+        } else if value > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        }
+    }
+}
+
+/// Options for [`to_value_with`](crate::value::to_value_with).
+///
+/// ```
+/// use serde_json::value::{F32Policy, ToValueOptions};
+///
+/// let options = ToValueOptions::new().f32_policy(F32Policy::Native);
+/// # let _ = options;
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ToValueOptions {
+    f32_policy: F32Policy,
+    non_finite_policy: NonFinitePolicy,
+}
+
+impl Default for ToValueOptions {
+    fn default() -> Self {
+        ToValueOptions {
+            f32_policy: F32Policy::Widen,
+            non_finite_policy: NonFinitePolicy::default(),
+        }
+    }
+}
+
+impl ToValueOptions {
+    /// The options used by [`to_value`](crate::to_value).
+    pub fn new() -> Self {
+        ToValueOptions::default()
+    }
+
+    /// Sets how `f32` values are converted. Defaults to [`F32Policy::Widen`].
+    pub fn f32_policy(mut self, policy: F32Policy) -> Self {
+        self.f32_policy = policy;
+        self
+    }
+
+    /// Sets how NaN and infinite floats are handled.
+    pub fn non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite_policy = policy;
+        self
+    }
+}
+
 /// Viết tắt: Serializer trả về kết quả là một Value.
 /// Nó là serializer cho hàm serde_json::to_value. 
 /// Nó chuyển từ giá trị T sang serde_json::Value, 
@@ -52,6 +205,19 @@ impl Serialize for Value {
 /// ```
 pub struct Serializer;
 
+// `Serializer` uses the default options; every method goes to a
+// `ValueSerializer` carrying them.
+macro_rules! forward_to_value_serializer {
+    ($($method:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            #[inline]
+            fn $method(self, $($arg: $ty),*) -> Result<$ret> {
+                ValueSerializer::default().$method($($arg),*)
+            }
+        )*
+    };
+}
+
 impl serde::Serializer for Serializer {
     type Ok = Value;
     type Error = Error;
@@ -64,6 +230,101 @@ impl serde::Serializer for Serializer {
     type SerializeStruct = SerializeMap;
     type SerializeStructVariant = SerializeStructVariant;
 
+    forward_to_value_serializer! {
+        serialize_bool(value: bool) -> Value;
+        serialize_i8(value: i8) -> Value;
+        serialize_i16(value: i16) -> Value;
+        serialize_i32(value: i32) -> Value;
+        serialize_i64(value: i64) -> Value;
+        serialize_u8(value: u8) -> Value;
+        serialize_u16(value: u16) -> Value;
+        serialize_u32(value: u32) -> Value;
+        serialize_u64(value: u64) -> Value;
+        serialize_f32(value: f32) -> Value;
+        serialize_f64(value: f64) -> Value;
+        serialize_char(value: char) -> Value;
+        serialize_str(value: &str) -> Value;
+        serialize_bytes(value: &[u8]) -> Value;
+        serialize_none() -> Value;
+        serialize_unit() -> Value;
+        serialize_unit_struct(name: &'static str) -> Value;
+        serialize_unit_variant(name: &'static str, variant_index: u32, variant: &'static str) -> Value;
+        serialize_seq(len: Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(len: usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(name: &'static str, len: usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(name: &'static str, variant_index: u32, variant: &'static str, len: usize) -> Self::SerializeTupleVariant;
+        serialize_map(len: Option<usize>) -> Self::SerializeMap;
+        serialize_struct(name: &'static str, len: usize) -> Self::SerializeStruct;
+        serialize_struct_variant(name: &'static str, variant_index: u32, variant: &'static str, len: usize) -> Self::SerializeStructVariant;
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    fn serialize_i128(self, value: i128) -> Result<Value> {
+        ValueSerializer::default().serialize_i128(value)
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    fn serialize_u128(self, value: u128) -> Result<Value> {
+        ValueSerializer::default().serialize_u128(value)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        ValueSerializer::default().serialize_newtype_struct(name, value)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        ValueSerializer::default().serialize_newtype_variant(name, variant_index, variant, value)
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        ValueSerializer::default().serialize_some(value)
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Display,
+    {
+        ValueSerializer::default().collect_str(value)
+    }
+}
+
+/// The serializer behind [`Serializer`] and
+/// [`to_value_with`](crate::value::to_value_with).
+#[derive(Copy, Clone, Default)]
+pub(crate) struct ValueSerializer {
+    pub(crate) options: ToValueOptions,
+}
+
+impl serde::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
     #[inline]
     fn serialize_bool(self, value: bool) -> Result<Value> {
         Ok(Value::Bool(value))
@@ -120,12 +381,21 @@ impl serde::Serializer for Serializer {
 
     #[inline]
     fn serialize_f32(self, value: f32) -> Result<Value> {
-        self.serialize_f64(value as f64)
+        self.serialize_f64(self.options.f32_policy.to_f64(value))
     }
 
     #[inline]
     fn serialize_f64(self, value: f64) -> Result<Value> {
-        Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+        match Number::from_f64(value) {
+            Some(n) => Ok(Value::Number(n)),
+            None => match self.options.non_finite_policy {
+                NonFinitePolicy::Null => Ok(Value::Null),
+                NonFinitePolicy::Error => Err(Error::syntax(ErrorCode::FloatMustBeFinite, 0, 0)),
+                NonFinitePolicy::StringTokens => {
+                    Ok(Value::String(NonFinitePolicy::token(value).to_owned()))
+                }
+            },
+        }
     }
 
     #[inline]
@@ -166,11 +436,20 @@ impl serde::Serializer for Serializer {
     }
 
     #[inline]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        match name {
+            #[cfg(any(feature = "decimal", feature = "bigint"))]
+            crate::number::DIGITS_TOKEN => match tri!(value.serialize(self)) {
+                Value::String(digits) => {
+                    crate::number::number_from_digits(&digits).map(Value::Number)
+                }
+                other => Ok(other),
+            },
+            _ => value.serialize(self),
+        }
     }
 
     fn serialize_newtype_variant<T>(
@@ -184,7 +463,7 @@ impl serde::Serializer for Serializer {
         T: ?Sized + Serialize,
     {
         let mut values = Map::new();
-        values.insert(String::from(variant), tri!(to_value(value)));
+        values.insert(String::from(variant), tri!(value.serialize(self)));
         Ok(Value::Object(values))
     }
 
@@ -204,6 +483,7 @@ impl serde::Serializer for Serializer {
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
         Ok(SerializeVec {
             vec: Vec::with_capacity(len.unwrap_or(0)),
+            options: self.options,
         })
     }
 
@@ -229,6 +509,7 @@ impl serde::Serializer for Serializer {
         Ok(SerializeTupleVariant {
             name: String::from(variant),
             vec: Vec::with_capacity(len),
+            options: self.options,
         })
     }
 
@@ -236,6 +517,7 @@ impl serde::Serializer for Serializer {
         Ok(SerializeMap::Map {
             map: Map::new(),
             next_key: None,
+            options: self.options,
         })
     }
 
@@ -259,6 +541,7 @@ impl serde::Serializer for Serializer {
         Ok(SerializeStructVariant {
             name: String::from(variant),
             map: Map::new(),
+            options: self.options,
         })
     }
 
@@ -272,17 +555,20 @@ impl serde::Serializer for Serializer {
 
 pub struct SerializeVec {
     vec: Vec<Value>,
+    options: ToValueOptions,
 }
 
 pub struct SerializeTupleVariant {
     name: String,
     vec: Vec<Value>,
+    options: ToValueOptions,
 }
 
 pub enum SerializeMap {
     Map {
         map: Map<String, Value>,
         next_key: Option<String>,
+        options: ToValueOptions,
     },
     #[cfg(feature = "arbitrary_precision")]
     Number { out_value: Option<Value> },
@@ -293,6 +579,7 @@ pub enum SerializeMap {
 pub struct SerializeStructVariant {
     name: String,
     map: Map<String, Value>,
+    options: ToValueOptions,
 }
 
 impl serde::ser::SerializeSeq for SerializeVec {
@@ -303,7 +590,9 @@ impl serde::ser::SerializeSeq for SerializeVec {
     where
         T: ?Sized + Serialize,
     {
-        self.vec.push(tri!(to_value(value)));
+        self.vec.push(tri!(value.serialize(ValueSerializer {
+            options: self.options
+        })));
         Ok(())
     }
 
@@ -352,7 +641,9 @@ impl serde::ser::SerializeTupleVariant for SerializeTupleVariant {
     where
         T: ?Sized + Serialize,
     {
-        self.vec.push(tri!(to_value(value)));
+        self.vec.push(tri!(value.serialize(ValueSerializer {
+            options: self.options
+        })));
         Ok(())
     }
 
@@ -390,12 +681,17 @@ impl serde::ser::SerializeMap for SerializeMap {
         T: ?Sized + Serialize,
     {
         match self {
-            SerializeMap::Map { map, next_key } => {
+            SerializeMap::Map {
+                map,
+                next_key,
+                options,
+            } => {
                 let key = next_key.take();
                 // Panic xảy ra vì đây là dấu hiệu của lỗi trong chương trình, 
                 // không phải là một sự cố dự kiến..
                 let key = key.expect("serialize_value called before serialize_key");
-                map.insert(key, tri!(to_value(value)));
+                let value = tri!(value.serialize(ValueSerializer { options: *options }));
+                map.insert(key, value);
                 Ok(())
             }
             #[cfg(feature = "arbitrary_precision")]
@@ -452,8 +748,8 @@ impl serde::Serializer for MapKeySerializer {
         value.serialize(self)
     }
 
-    fn serialize_bool(self, _value: bool) -> Result<String> {
-        Err(key_must_be_a_string())
+    fn serialize_bool(self, value: bool) -> Result<String> {
+        Ok(value.to_string())
     }
 
     fn serialize_i8(self, value: i8) -> Result<String> {
@@ -652,7 +948,10 @@ impl serde::ser::SerializeStructVariant for SerializeStructVariant {
     where
         T: ?Sized + Serialize,
     {
-        self.map.insert(String::from(key), tri!(to_value(value)));
+        let value = tri!(value.serialize(ValueSerializer {
+            options: self.options
+        }));
+        self.map.insert(String::from(key), value);
         Ok(())
     }
 
@@ -665,6 +964,239 @@ impl serde::ser::SerializeStructVariant for SerializeStructVariant {
     }
 }
 
+/// Serializes a map or struct by inserting its entries into an existing map.
+pub(crate) struct MapInserter<'a> {
+    pub(crate) map: &'a mut Map<String, Value>,
+    pub(crate) options: ToValueOptions,
+}
+
+pub(crate) struct SerializeInto<'a> {
+    map: &'a mut Map<String, Value>,
+    next_key: Option<String>,
+    options: ToValueOptions,
+}
+
+fn expected_object() -> Error {
+    Error::syntax(ErrorCode::ExpectedObject, 0, 0)
+}
+
+impl<'a> serde::Serializer for MapInserter<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = SerializeInto<'a>;
+    type SerializeStruct = SerializeInto<'a>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_str(self, _value: &str) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        Err(expected_object())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(expected_object())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(expected_object())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(expected_object())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(expected_object())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(expected_object())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(SerializeInto {
+            map: self.map,
+            next_key: None,
+            options: self.options,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        match name {
+            #[cfg(feature = "arbitrary_precision")]
+            crate::number::TOKEN => Err(expected_object()),
+            #[cfg(feature = "raw_value")]
+            crate::raw::TOKEN => Err(expected_object()),
+            _ => self.serialize_map(Some(len)),
+        }
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(expected_object())
+    }
+}
+
+impl<'a> serde::ser::SerializeMap for SerializeInto<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.next_key = Some(tri!(key.serialize(MapKeySerializer)));
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.next_key.take();
+        let key = key.expect("serialize_value called before serialize_key");
+        let value = tri!(value.serialize(ValueSerializer {
+            options: self.options
+        }));
+        self.map.insert(key, value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> serde::ser::SerializeStruct for SerializeInto<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        serde::ser::SerializeMap::serialize_entry(self, key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(feature = "arbitrary_precision")]
 struct NumberValueEmitter;
 
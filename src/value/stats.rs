@@ -0,0 +1,108 @@
+use super::Value;
+use alloc::vec::Vec;
+
+/// Shape statistics of a `Value`, returned by [`Value::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Maximum nesting depth; see [`Value::depth`].
+    pub depth: usize,
+    /// Total number of values, including the root and every container.
+    pub nodes: usize,
+    /// Number of `null` values.
+    pub nulls: usize,
+    /// Number of booleans.
+    pub bools: usize,
+    /// Number of numbers.
+    pub numbers: usize,
+    /// Number of strings, not counting object keys.
+    pub strings: usize,
+    /// Number of arrays.
+    pub arrays: usize,
+    /// Number of objects.
+    pub objects: usize,
+    /// Total number of object members.
+    pub keys: usize,
+    /// Total length in bytes of all strings and object keys.
+    pub string_bytes: usize,
+}
+
+impl Value {
+    /// Returns the maximum nesting depth of this value. Scalars have depth 0,
+    /// and each enclosing array or object adds 1.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// assert_eq!(json!(1).depth(), 0);
+    /// assert_eq!(json!([]).depth(), 1);
+    /// assert_eq!(json!({"a": [1, {"b": null}], "c": 2}).depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.stats().depth
+    }
+
+    /// Returns the total number of values in this document, including the
+    /// root and every array and object.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// assert_eq!(json!(null).node_count(), 1);
+    /// assert_eq!(json!({"a": [1, 2], "b": {}}).node_count(), 5);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        self.stats().nodes
+    }
+
+    /// Walks the whole document once and reports its depth, size and number
+    /// of values of each type.
+    ///
+    /// The walk uses an explicit stack rather than recursion, so it is safe
+    /// to call on arbitrarily deep documents.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let stats = json!({"id": 7, "tags": ["a", "bc"], "owner": null}).stats();
+    /// assert_eq!(stats.depth, 2);
+    /// assert_eq!(stats.nodes, 6);
+    /// assert_eq!(stats.strings, 2);
+    /// assert_eq!(stats.keys, 3);
+    /// assert_eq!(stats.string_bytes, 3 + 11);
+    /// ```
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        let mut stack = Vec::new();
+        stack.push((self, 0));
+
+        while let Some((value, depth)) = stack.pop() {
+            stats.nodes += 1;
+            match value {
+                Value::Null => stats.nulls += 1,
+                Value::Bool(_) => stats.bools += 1,
+                Value::Number(_) => stats.numbers += 1,
+                Value::String(s) => {
+                    stats.strings += 1;
+                    stats.string_bytes += s.len();
+                }
+                Value::Array(list) => {
+                    stats.arrays += 1;
+                    stats.depth = stats.depth.max(depth + 1);
+                    stack.extend(list.iter().map(|child| (child, depth + 1)));
+                }
+                Value::Object(map) => {
+                    stats.objects += 1;
+                    stats.keys += map.len();
+                    stats.depth = stats.depth.max(depth + 1);
+                    for (key, child) in map {
+                        stats.string_bytes += key.len();
+                        stack.push((child, depth + 1));
+                    }
+                }
+            }
+        }
+
+        stats
+    }
+}
@@ -0,0 +1,69 @@
+use super::Value;
+use crate::map::Map;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+impl Value {
+    /// Returns a reduced copy of this value for previews in UIs and error
+    /// messages.
+    ///
+    /// At every depth, arrays keep their first `max_items` elements followed
+    /// by a `"…(+N more)"` string, objects keep their first `max_items`
+    /// members followed by a `"…": "(+N more)"` member, and strings keep
+    /// their first `max_string` characters followed by `…(+N more)`, where N
+    /// counts what was left out. Values within the limits are copied as they
+    /// are.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// #
+    /// let doc = json!({
+    ///     "rows": [1, 2, 3, 4, 5],
+    ///     "text": "a rather long description",
+    /// });
+    ///
+    /// assert_eq!(
+    ///     doc.summarize(2, 8),
+    ///     json!({
+    ///         "rows": [1, 2, "…(+3 more)"],
+    ///         "text": "a rather…(+17 more)",
+    ///     }),
+    /// );
+    /// ```
+    pub fn summarize(&self, max_items: usize, max_string: usize) -> Value {
+        let more = |n: usize| alloc::format!("\u{2026}(+{} more)", n);
+        match self {
+            Value::String(s) => match s.char_indices().nth(max_string) {
+                Some((end, _)) => {
+                    let rest = s[end..].chars().count();
+                    Value::String(alloc::format!("{}{}", &s[..end], more(rest)))
+                }
+                None => self.clone(),
+            },
+            Value::Array(list) => {
+                let mut summary: Vec<Value> = list
+                    .iter()
+                    .take(max_items)
+                    .map(|v| v.summarize(max_items, max_string))
+                    .collect();
+                if list.len() > max_items {
+                    summary.push(Value::String(more(list.len() - max_items)));
+                }
+                Value::Array(summary)
+            }
+            Value::Object(map) => {
+                let mut summary: Map<String, Value> = map
+                    .iter()
+                    .take(max_items)
+                    .map(|(k, v)| (k.clone(), v.summarize(max_items, max_string)))
+                    .collect();
+                if map.len() > max_items {
+                    let rest = alloc::format!("(+{} more)", map.len() - max_items);
+                    summary.insert(String::from("\u{2026}"), Value::String(rest));
+                }
+                Value::Object(summary)
+            }
+            _ => self.clone(),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use super::{parse_index, Value};
+use crate::map::Map;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use core::mem;
+
+/// Naming convention applied to object keys by [`Transform::rename_keys`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// `camelCase`
+    Camel,
+    /// `PascalCase`
+    Pascal,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+}
+
+/// A reusable sequence of edits to apply to a `Value`.
+///
+/// Steps run in the order they were added. A `Transform` can be applied to
+/// any number of values.
+///
+/// ```
+/// # use serde_json::json;
+/// use serde_json::value::{Case, Transform};
+///
+/// let sanitize = Transform::new()
+///     .remove("/debug")
+///     .rename_keys(Case::Camel)
+///     .prune_nulls();
+///
+/// let mut v = json!({
+///     "user_name": "ann",
+///     "last_login": null,
+///     "debug": {"trace_id": 7},
+///     "home_address": {"zip_code": "1000", "unit": null}
+/// });
+/// sanitize.apply(&mut v);
+///
+/// assert_eq!(v, json!({
+///     "userName": "ann",
+///     "homeAddress": {"zipCode": "1000"}
+/// }));
+/// ```
+#[derive(Default)]
+pub struct Transform {
+    steps: Vec<Step>,
+}
+
+enum Step {
+    Remove(String),
+    RenameKeys(Case),
+    PruneNulls,
+    Custom(Box<dyn Fn(&mut Value) + Send + Sync>),
+}
+
+impl Transform {
+    /// Creates a transform that does nothing.
+    pub fn new() -> Self {
+        Transform { steps: Vec::new() }
+    }
+
+    /// Removes the value at the given JSON Pointer, if it exists. Removing an
+    /// array element shifts the following elements down.
+    pub fn remove<P>(mut self, pointer: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.steps.push(Step::Remove(pointer.into()));
+        self
+    }
+
+    /// Renames every object key, at any depth, to the given case.
+    ///
+    /// Words are split at `_`, `-`, spaces and lower-to-upper case changes, so
+    /// any of the supported cases can be converted into any other. If two keys
+    /// of one object map to the same name, the later one wins.
+    pub fn rename_keys(mut self, case: Case) -> Self {
+        self.steps.push(Step::RenameKeys(case));
+        self
+    }
+
+    /// Removes object members whose value is `null`, at any depth. Null array
+    /// elements are kept so that positions do not shift.
+    pub fn prune_nulls(mut self) -> Self {
+        self.steps.push(Step::PruneNulls);
+        self
+    }
+
+    /// Adds an arbitrary step.
+    ///
+    /// ```
+    /// # use serde_json::json;
+    /// use serde_json::value::Transform;
+    ///
+    /// let t = Transform::new().then(|v| v["version"] = json!(2));
+    ///
+    /// let mut v = json!({"version": 1});
+    /// t.apply(&mut v);
+    /// assert_eq!(v, json!({"version": 2}));
+    /// ```
+    pub fn then<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Value) + Send + Sync + 'static,
+    {
+        self.steps.push(Step::Custom(Box::new(f)));
+        self
+    }
+
+    /// Runs every step, in order, on `value`.
+    pub fn apply(&self, value: &mut Value) {
+        for step in &self.steps {
+            match step {
+                Step::Remove(pointer) => {
+                    remove_pointer(value, pointer);
+                }
+                Step::RenameKeys(case) => rename_keys(value, *case),
+                Step::PruneNulls => prune_nulls(value),
+                Step::Custom(f) => f(value),
+            }
+        }
+    }
+}
+
+impl Debug for Transform {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = formatter.debug_list();
+        for step in &self.steps {
+            match step {
+                Step::Remove(pointer) => list.entry(&format_args!("Remove({:?})", pointer)),
+                Step::RenameKeys(case) => list.entry(&format_args!("RenameKeys({:?})", case)),
+                Step::PruneNulls => list.entry(&format_args!("PruneNulls")),
+                Step::Custom(_) => list.entry(&format_args!("Custom")),
+            };
+        }
+        list.finish()
+    }
+}
+
+fn remove_pointer(value: &mut Value, pointer: &str) -> Option<Value> {
+    let split = pointer.rfind('/')?;
+    let parent = value.pointer_mut(&pointer[..split])?;
+    let token = pointer[split + 1..].replace("~1", "/").replace("~0", "~");
+    match parent {
+        Value::Object(map) => map.remove(&token),
+        Value::Array(list) => {
+            let index = parse_index(&token).filter(|&index| index < list.len())?;
+            Some(list.remove(index))
+        }
+        _ => None,
+    }
+}
+
+fn rename_keys(value: &mut Value, case: Case) {
+    match value {
+        Value::Object(map) => {
+            let old = mem::replace(map, Map::new());
+            for (key, mut child) in old {
+                rename_keys(&mut child, case);
+                map.insert(convert_case(&key, case), child);
+            }
+        }
+        Value::Array(list) => {
+            for child in list {
+                rename_keys(child, case);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn prune_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, child| !child.is_null());
+            for child in map.values_mut() {
+                prune_nulls(child);
+            }
+        }
+        Value::Array(list) => {
+            for child in list {
+                prune_nulls(child);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn split_words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == '_' || ch == '-' || ch.is_whitespace() {
+            if !word.is_empty() {
+                words.push(mem::take(&mut word));
+            }
+            continue;
+        }
+        if ch.is_uppercase() && !word.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |c| c.is_lowercase());
+            // "fooBar" -> foo|Bar, "HTTPServer" -> HTTP|Server
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(mem::take(&mut word));
+            }
+        }
+        word.push(ch);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn convert_case(key: &str, case: Case) -> String {
+    let words = split_words(key);
+    let mut out = String::with_capacity(key.len());
+    for (i, word) in words.iter().enumerate() {
+        let separator = match case {
+            Case::Snake | Case::ScreamingSnake => Some('_'),
+            Case::Kebab => Some('-'),
+            Case::Camel | Case::Pascal => None,
+        };
+        if let (Some(separator), true) = (separator, i > 0) {
+            out.push(separator);
+        }
+        let capitalize = match case {
+            Case::Camel => i > 0,
+            Case::Pascal => true,
+            _ => false,
+        };
+        if case == Case::ScreamingSnake {
+            out.extend(word.chars().flat_map(char::to_uppercase));
+        } else if capitalize {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                out.extend(first.to_uppercase());
+                out.extend(chars.flat_map(char::to_lowercase));
+            }
+        } else {
+            out.extend(word.chars().flat_map(char::to_lowercase));
+        }
+    }
+    out
+}
@@ -0,0 +1,178 @@
+use super::{Map, Value};
+use crate::error::Error;
+use crate::number::Number;
+use alloc::string::String;
+use alloc::vec::Vec;
+use js_sys::{Array, BigInt, Object};
+use serde::de::Error as _;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Integers with a larger magnitude than this cannot be represented exactly by
+/// a JavaScript number and are converted to `BigInt` instead.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Same limit as the parser applies to JSON text.
+const RECURSION_LIMIT: usize = 128;
+
+impl Value {
+    /// Converts this value into a JavaScript value directly, without going
+    /// through `JSON.stringify` and `JSON.parse`.
+    ///
+    /// Objects become plain JavaScript objects and arrays become `Array`s.
+    /// Integers that a JavaScript number cannot hold exactly become `BigInt`s.
+    ///
+    /// ```no_run
+    /// # use serde_json::json;
+    /// #
+    /// let js = json!({"id": 7, "tags": ["a", "b"]}).to_js();
+    /// assert!(js.is_object());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    pub fn to_js(&self) -> JsValue {
+        match self {
+            Value::Null => JsValue::NULL,
+            Value::Bool(b) => JsValue::from_bool(*b),
+            Value::Number(n) => number_to_js(n),
+            Value::String(s) => JsValue::from_str(s),
+            Value::Array(list) => list.iter().map(Value::to_js).collect::<Array>().into(),
+            Value::Object(map) => {
+                let entries: Array = map
+                    .iter()
+                    .map(|(key, value)| Array::of2(&JsValue::from_str(key), &value.to_js()))
+                    .collect();
+                // Unlike assigning properties one by one, `fromEntries` treats
+                // a "__proto__" key as an ordinary property.
+                match Object::from_entries(&entries) {
+                    Ok(object) => object.into(),
+                    Err(err) => wasm_bindgen::throw_val(err),
+                }
+            }
+        }
+    }
+
+    /// Converts a JavaScript value into a `Value` directly, without going
+    /// through `JSON.stringify` and `JSON.parse`.
+    ///
+    /// The result matches what `JSON.parse(JSON.stringify(value))` would give:
+    /// `undefined`, `NaN` and infinities become null, whole numbers become
+    /// integers, and objects contribute their own enumerable string-keyed
+    /// properties. `BigInt`s are accepted if they fit in an `i64` or `u64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for functions, symbols, out-of-range `BigInt`s, and
+    /// values nested more than 128 levels deep, which includes cyclic
+    /// objects.
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    pub fn from_js(value: &JsValue) -> Result<Value, Error> {
+        from_js(value, RECURSION_LIMIT)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+impl From<Value> for JsValue {
+    fn from(value: Value) -> Self {
+        value.to_js()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+impl From<&Value> for JsValue {
+    fn from(value: &Value) -> Self {
+        value.to_js()
+    }
+}
+
+fn number_to_js(n: &Number) -> JsValue {
+    if let Some(u) = n.as_u64() {
+        if u <= MAX_SAFE_INTEGER {
+            JsValue::from_f64(u as f64)
+        } else {
+            BigInt::from(u).into()
+        }
+    } else if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() <= MAX_SAFE_INTEGER {
+            JsValue::from_f64(i as f64)
+        } else {
+            BigInt::from(i).into()
+        }
+    } else {
+        JsValue::from_f64(n.as_f64().unwrap_or(f64::NAN))
+    }
+}
+
+fn from_js(value: &JsValue, remaining_depth: usize) -> Result<Value, Error> {
+    if value.is_null() || value.is_undefined() {
+        return Ok(Value::Null);
+    }
+    if let Some(b) = value.as_bool() {
+        return Ok(Value::Bool(b));
+    }
+    if let Some(f) = value.as_f64() {
+        return Ok(number_from_f64(f));
+    }
+    if let Some(s) = value.as_string() {
+        return Ok(Value::String(s));
+    }
+    if let Some(big) = value.dyn_ref::<BigInt>() {
+        return number_from_bigint(big);
+    }
+    if value.is_function() || value.is_symbol() || !value.is_object() {
+        return Err(Error::custom(
+            "JavaScript value cannot be represented as JSON",
+        ));
+    }
+
+    if remaining_depth == 0 {
+        return Err(Error::custom("recursion limit exceeded"));
+    }
+    if Array::is_array(value) {
+        let array: &Array = value.unchecked_ref();
+        let mut list = Vec::with_capacity(array.length() as usize);
+        for element in array.iter() {
+            list.push(tri!(from_js(&element, remaining_depth - 1)));
+        }
+        return Ok(Value::Array(list));
+    }
+    let mut map = Map::new();
+    for entry in Object::entries(value.unchecked_ref()).iter() {
+        let entry: Array = entry.unchecked_into();
+        let key = entry.get(0).as_string().unwrap_or_default();
+        let member = entry.get(1);
+        // JSON.stringify leaves out members it cannot represent.
+        if member.is_undefined() || member.is_function() || member.is_symbol() {
+            continue;
+        }
+        map.insert(key, tri!(from_js(&member, remaining_depth - 1)));
+    }
+    Ok(Value::Object(map))
+}
+
+fn number_from_f64(f: f64) -> Value {
+    if f.fract() == 0.0 && f.abs() <= MAX_SAFE_INTEGER as f64 {
+        if f < 0.0 {
+            Value::Number(Number::from(f as i64))
+        } else {
+            Value::Number(Number::from(f as u64))
+        }
+    } else {
+        Number::from_f64(f).map_or(Value::Null, Value::Number)
+    }
+}
+
+fn number_from_bigint(big: &BigInt) -> Result<Value, Error> {
+    let digits: String = match big.to_string(10) {
+        Ok(digits) => digits.into(),
+        Err(_) => return Err(Error::custom("invalid BigInt")),
+    };
+    if let Ok(u) = digits.parse::<u64>() {
+        Ok(Value::Number(Number::from(u)))
+    } else if let Ok(i) = digits.parse::<i64>() {
+        Ok(Value::Number(Number::from(i)))
+    } else {
+        Err(Error::custom(format_args!(
+            "BigInt {} does not fit in a 64-bit integer",
+            digits
+        )))
+    }
+}
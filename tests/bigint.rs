@@ -0,0 +1,80 @@
+#![cfg(feature = "bigint")]
+
+use num_bigint::BigInt;
+use serde::Deserialize;
+use serde_json::{from_str, from_value, json, to_string, Number, Value};
+
+#[test]
+fn test_bigint_round_trip() {
+    let json = r#"{"id":123456789012345678901234567890,"neg":-99999999999999999999,"small":5}"#;
+    let value: Value = from_str(json).unwrap();
+    assert_eq!(to_string(&value).unwrap(), json);
+    assert_eq!(value["small"], json!(5));
+
+    let id = value["id"].as_u64();
+    assert_eq!(id, None);
+    let expected: BigInt = "123456789012345678901234567890".parse().unwrap();
+    assert_eq!(value["id"], Value::from(expected.clone()));
+    assert_eq!(value["id"].as_number().unwrap().as_bigint(), Some(expected));
+
+    // Số nằm trong phạm vi của u64 không thành BigInt.
+    assert_eq!(Number::from(BigInt::from(u64::MAX)), Number::from(u64::MAX));
+    assert_eq!(Number::from(BigInt::from(-1)), Number::from(-1));
+
+    // Số có phần thập phân vẫn là f64.
+    let value: Value = from_str("123456789012345678901234567890.5").unwrap();
+    assert!(value.is_f64());
+}
+
+#[test]
+fn test_bigint_into_primitives() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Account {
+        balance: u128,
+        debt: i128,
+    }
+
+    let value: Value = from_str(
+        r#"{"balance":340282366920938463463374607431768211455,"debt":-18446744073709551616}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        from_value::<Account>(value.clone()).unwrap(),
+        Account {
+            balance: u128::MAX,
+            debt: -(1i128 << 64),
+        }
+    );
+    assert_eq!(
+        Account::deserialize(&value).unwrap(),
+        from_value::<Account>(value.clone()).unwrap(),
+    );
+    assert_eq!(
+        from_value::<f64>(value["debt"].clone()).unwrap(),
+        -18446744073709551616.0
+    );
+    assert!(from_value::<u64>(value["balance"].clone()).is_err());
+}
+
+#[test]
+fn test_bigint_from_str() {
+    let digits = "1234567890123456789012345678901234567890";
+    let n: Number = digits.parse().unwrap();
+    assert_eq!(n.to_string(), digits);
+    assert_eq!(n.as_bigint(), digits.parse().ok());
+}
+
+#[test]
+fn test_bigint_to_num() {
+    let v: Value = from_str(
+        "[340282366920938463463374607431768211455, 340282366920938463463374607431768211456]",
+    )
+    .unwrap();
+    assert_eq!(v[0].to_num::<u128>(), Some(u128::MAX));
+    assert_eq!(v[0].to_num::<i128>(), None);
+    assert_eq!(v[1].to_num::<u128>(), None);
+    assert_eq!(
+        v[1].to_num::<f64>(),
+        Some(340282366920938463463374607431768211456.0)
+    );
+}
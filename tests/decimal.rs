@@ -0,0 +1,140 @@
+#![cfg(feature = "decimal")]
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::{from_str, from_value, json, to_string, to_value, Number, Value};
+
+#[test]
+fn test_decimal_round_trip() {
+    let cases = [
+        ("0.1", "0.1"),
+        ("19.990", "19.990"),
+        ("-0.000001", "-0.000001"),
+        ("12345678901234567890.5", "12345678901234567890.5"),
+        ("1.5e3", "1500.0"),
+        ("2E+2", "200.0"),
+        ("-1.0", "-1.0"),
+    ];
+    for (json, expected) in cases {
+        let value: Value = from_str(json).unwrap();
+        assert_eq!(to_string(&value).unwrap(), expected);
+    }
+
+    let value: Value = from_str(r#"{"price":19.990,"ratio":0.1,"qty":3}"#).unwrap();
+    assert_eq!(
+        to_string(&value).unwrap(),
+        r#"{"price":19.990,"qty":3,"ratio":0.1}"#
+    );
+    assert_eq!(value["price"], Value::from(Decimal::new(19990, 3)));
+    assert_eq!(value["qty"], json!(3));
+
+    let number: Number = from_str("0.30").unwrap();
+    assert_eq!(number.to_string(), "0.30");
+    assert_eq!(number.as_f64(), Some(0.3));
+    assert!(number.is_f64());
+    assert_eq!(to_value(&number).unwrap(), Value::Number(number.clone()));
+}
+
+#[test]
+fn test_decimal_into_primitives() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Item {
+        price: f64,
+    }
+
+    let value: Value = from_str(r#"{"price":0.1}"#).unwrap();
+    assert_eq!(
+        from_value::<Item>(value.clone()).unwrap(),
+        Item { price: 0.1 }
+    );
+    assert_eq!(Item::deserialize(&value).unwrap(), Item { price: 0.1 });
+    assert!(from_value::<u64>(value["price"].clone()).is_err());
+}
+
+#[test]
+fn test_decimal_fallback() {
+    // Ngoài phạm vi của Decimal thì vẫn làm tròn về f64.
+    let value: Value = from_str("1e300").unwrap();
+    assert_eq!(value.as_f64(), Some(1e300));
+    assert_eq!(value.as_number().unwrap().as_decimal(), None);
+}
+
+#[test]
+fn test_decimal_eq_float() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(n: &Number) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        n.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let parsed: Number = from_str("1.50").unwrap();
+    let float = Number::from_f64(1.5).unwrap();
+    assert_eq!(parsed, float);
+    assert_eq!(hash(&parsed), hash(&float));
+    assert_ne!(parsed, Number::from_f64(1.25).unwrap());
+
+    let value: Value = from_str(r#"{"x":0.1}"#).unwrap();
+    assert_eq!(value, json!({"x": 0.1}));
+}
+
+#[test]
+fn test_decimal_arithmetic() {
+    let a: Number = from_str("0.1").unwrap();
+    let b: Number = from_str("0.2").unwrap();
+    let sum = &a + &b;
+    assert_eq!(sum.to_string(), "0.3");
+    assert_eq!((&sum * &Number::from(3)).to_string(), "0.9");
+    assert!(a < b);
+    assert!(sum > Number::from_f64(0.29).unwrap());
+
+    // Có số hạng f64 thì tính bằng f64.
+    let float = &a + &Number::from_f64(0.2).unwrap();
+    assert_eq!(float.as_f64(), Some(0.1 + 0.2));
+}
+
+#[test]
+fn test_decimal_from_str() {
+    let n: Number = "1.50".parse().unwrap();
+    assert_eq!(n.to_string(), "1.50");
+    assert_eq!(to_string(&n).unwrap(), "1.50");
+}
+
+#[test]
+fn test_decimal_is_integer() {
+    let n: Number = from_str("2.00").unwrap();
+    assert!(n.is_integer());
+    assert!(n.fits::<u8>());
+    assert_eq!(n.to_num::<u8>(), None);
+    assert!(!from_str::<Number>("2.01").unwrap().is_integer());
+}
+
+#[test]
+fn test_decimal_literal_flag() {
+    for json in ["1.0", "1e3", "0.50", "-7.25E-1"] {
+        let number: Number = from_str(json).unwrap();
+        assert!(number.is_decimal_literal(), "{}", json);
+        let again: Number = from_str(&number.to_string()).unwrap();
+        assert!(again.is_decimal_literal(), "{}", json);
+    }
+    assert!(!from_str::<Number>("10").unwrap().is_decimal_literal());
+    assert!(!Number::from(Decimal::new(5, 0)).is_decimal_literal());
+
+    // Không đủ chỗ cho một chữ số thập phân trong Decimal thì dùng f64.
+    let number: Number = from_str("79228162514264337593543950335e0").unwrap();
+    assert!(number.is_decimal_literal());
+    assert_eq!(number.as_decimal(), None);
+}
+
+#[test]
+fn test_decimal_integer_literals() {
+    let n: Number = from_str("123456789012345678901234567").unwrap();
+    assert!(!n.is_decimal_literal());
+    assert_eq!(n.to_string(), "123456789012345678901234567");
+
+    let value: Value = from_str("[-0, 0, -0.0]").unwrap();
+    assert_eq!(to_string(&value).unwrap(), "[-0,0,-0.0]");
+    assert!(!value[0].as_number().unwrap().is_decimal_literal());
+}
@@ -0,0 +1,233 @@
+use serde_json::{from_str, Error, Value};
+use std::io;
+
+fn parse_error(json: &str) -> Error {
+    from_str::<Value>(json).unwrap_err()
+}
+
+#[test]
+fn test_clone_eq() {
+    let err = parse_error("[1, 2");
+    let clone = err.clone();
+    assert_eq!(err, clone);
+    assert_eq!(clone.to_string(), err.to_string());
+    assert_eq!(clone.classify(), err.classify());
+    assert_eq!((clone.line(), clone.column()), (err.line(), err.column()));
+}
+
+#[test]
+fn test_ne() {
+    assert_ne!(parse_error("[1, 2"), parse_error("[1,\n 2"));
+    assert_ne!(parse_error("[1, 2"), parse_error("[1, 2]]"));
+    assert_ne!(
+        <Error as serde::de::Error>::custom("a"),
+        <Error as serde::de::Error>::custom("b"),
+    );
+}
+
+#[test]
+fn test_clone_io() {
+    let err = Error::io(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+    let clone = err.clone();
+    assert!(clone.is_io());
+    assert_eq!(clone.to_string(), "pipe closed");
+    assert_eq!(
+        io::Error::from(clone.clone()).kind(),
+        io::ErrorKind::BrokenPipe
+    );
+    assert_eq!(err, clone);
+}
+
+#[test]
+fn test_span() {
+    let json = "{\n  \"a\": [1, tru]\n}";
+    let err = parse_error(json);
+    assert_eq!(err.span(), Some(16..17));
+    assert_eq!(&json[16..17], "]");
+
+    let err = serde_json::from_reader::<_, Value>(json.as_bytes()).unwrap_err();
+    assert_eq!(err.span(), Some(16..17));
+
+    let err = parse_error("[1, 2");
+    assert_eq!(err.span(), Some(5..5));
+
+    let json = "[1, \"a\"日本]";
+    let err = parse_error(json);
+    assert_eq!(err.span(), Some(7..10));
+    assert_eq!(&json[7..10], "日");
+
+    let err = serde_json::from_slice::<Value>("[1, é]".as_bytes()).unwrap_err();
+    assert_eq!(err.span(), Some(4..6));
+
+    assert_eq!(<Error as serde::de::Error>::custom("oops").span(), None);
+}
+
+#[test]
+fn test_char_column() {
+    let json = "[\n  \"日本語\", x]";
+    let err = parse_error(json);
+    assert_eq!((err.line(), err.column()), (2, 16));
+    assert_eq!(err.char_column(json, 1), 10);
+
+    let json = "{\n\t\"a\":\t[1, x]\n}";
+    let err = parse_error(json);
+    assert_eq!(err.column(), 11);
+    assert_eq!(err.char_column(json, 1), 11);
+    assert_eq!(err.char_column(json, 4), 17);
+    assert_eq!(err.char_column(json, 8), 21);
+
+    let err = <Error as serde::de::Error>::custom("oops");
+    assert_eq!(err.char_column(json, 4), 0);
+}
+
+#[cfg(feature = "diagnostic")]
+#[test]
+fn test_diagnostic() {
+    use miette::Diagnostic;
+
+    let err = parse_error("[1, x]");
+    assert_eq!(err.code().unwrap().to_string(), "serde_json::syntax");
+    let labels: Vec<_> = err.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].offset(), 4);
+    assert_eq!(labels[0].len(), 1);
+    assert_eq!(labels[0].label(), Some("expected value"));
+
+    let mut de = serde_json::Deserializer::from_str("[1, x]");
+    de.set_document("data/a.json");
+    let err = <Value as serde::Deserialize>::deserialize(&mut de).unwrap_err();
+    let labels: Vec<_> = err.labels().unwrap().collect();
+    assert_eq!(labels[0].label(), Some("data/a.json: expected value"));
+}
+
+#[test]
+fn test_document() {
+    use serde::Deserialize;
+
+    let mut de = serde_json::Deserializer::from_str("{\"a\": [1, x]}");
+    de.set_document("data/a.json");
+    let err = Value::deserialize(&mut de).unwrap_err();
+    assert_eq!(err.document(), Some("data/a.json"));
+    assert_eq!(
+        err.to_string(),
+        "data/a.json: expected value at line 1 column 11",
+    );
+    assert_eq!(
+        format!("{:?}", err),
+        "Error(\"expected value\", line: 1, column: 11, document: \"data/a.json\")",
+    );
+
+    let mut de = serde_json::Deserializer::from_str("\"\\u00zz\"");
+    de.set_document("b.json");
+    let err = String::deserialize(&mut de).unwrap_err();
+    assert_eq!(err.document(), Some("b.json"));
+
+    let mut de = serde_json::Deserializer::from_str("{\"a\": \"x\"}");
+    de.set_document("c.json");
+    let err = <std::collections::BTreeMap<String, u8>>::deserialize(&mut de).unwrap_err();
+    assert_eq!(err.document(), Some("c.json"));
+
+    assert_eq!(parse_error("[").document(), None);
+}
+
+#[test]
+fn test_limit() {
+    let json = "[".repeat(1000);
+    let err = parse_error(&json);
+    assert!(err.is_limit());
+    assert!(!err.is_syntax());
+    assert_eq!(err.classify(), serde_json::error::Category::Limit);
+    assert_eq!(io::Error::from(err).kind(), io::ErrorKind::InvalidData);
+
+    assert!(!parse_error("[1,]").is_limit());
+
+    // Lỗi vượt độ sâu không còn được coi là lỗi cú pháp, kể cả khi đọc vào
+    // `Value`.
+    let err = serde_json::from_str::<serde_json::Value>(&json).unwrap_err();
+    assert_eq!(err.classify(), serde_json::error::Category::Limit);
+    assert!(!err.is_syntax());
+    assert_eq!(
+        err.to_string(),
+        "recursion limit exceeded at line 1 column 128"
+    );
+}
+
+#[test]
+fn test_unsupported() {
+    use serde_json::error::Category;
+
+    for (json, msg) in &[
+        (
+            "[1, // x\n2]",
+            "comments are not enabled at line 1 column 5",
+        ),
+        ("/* x */ 1", "comments are not enabled at line 1 column 1"),
+        (
+            "{'a': 1}",
+            "single-quoted strings are not enabled at line 1 column 2",
+        ),
+        (
+            "['a']",
+            "single-quoted strings are not enabled at line 1 column 2",
+        ),
+        (
+            "[0x1F]",
+            "radix literals are not enabled at line 1 column 3",
+        ),
+        ("-0b1", "radix literals are not enabled at line 1 column 3"),
+        (
+            r#""\x41""#,
+            "non-standard escapes are not enabled at line 1 column 3",
+        ),
+    ] {
+        let err = parse_error(json);
+        assert_eq!(err.classify(), Category::Unsupported, "{}", json);
+        assert!(err.is_unsupported());
+        assert_eq!(err.to_string(), *msg);
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::Unsupported);
+    }
+    let err = serde_json::from_str::<serde::de::IgnoredAny>("[0x1F]").unwrap_err();
+    assert!(err.is_unsupported());
+
+    // Lỗi cú pháp thường vẫn là lỗi cú pháp.
+    assert!(parse_error("[1 2]").is_syntax());
+    assert!(parse_error("0a").is_syntax());
+}
+
+#[test]
+fn test_value_path() {
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Item {
+        price: u32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Order {
+        items: Vec<Item>,
+    }
+
+    let value = json!({"items": [{"price": 1}, {"price": 2}, {"price": 3}, {"price": "x"}]});
+    let err = Order::deserialize(&value).unwrap_err();
+    assert_eq!(err.path(), Some("/items/3/price"));
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"x\", expected u32 at /items/3/price",
+    );
+    assert_eq!(serde_json::from_value::<Order>(value).unwrap_err(), err);
+
+    let err = serde_json::from_value::<Order>(json!({"items": [{}]})).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `price` at /items/0");
+
+    let err = serde_json::from_value::<BTreeMap<u8, u8>>(json!({"a/b~": 1})).unwrap_err();
+    assert_eq!(err.path(), Some("/a~1b~0"));
+
+    let err = serde_json::from_value::<Order>(json!([])).unwrap_err();
+    assert_eq!(err.path(), None);
+    assert_eq!(parse_error("[1, x]").path(), None);
+}
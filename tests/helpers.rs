@@ -0,0 +1,182 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{from_str, json, to_string, to_value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Record {
+    #[serde(with = "serde_json::helpers::u64_as_string")]
+    id: u64,
+    #[serde(with = "serde_json::helpers::f64_as_string")]
+    price: f64,
+    #[serde(with = "serde_json::helpers::base64")]
+    data: Vec<u8>,
+    #[serde(with = "serde_json::helpers::timestamp_millis")]
+    at: SystemTime,
+    #[serde(with = "serde_json::helpers::empty_string_as_none")]
+    note: Option<String>,
+}
+
+#[test]
+fn test_round_trip() {
+    let record = Record {
+        id: 9007199254740993,
+        price: 0.1,
+        data: vec![0, 1, 254, 255],
+        at: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        note: Some("x".to_owned()),
+    };
+    let expected = json!({
+        "id": "9007199254740993",
+        "price": "0.1",
+        "data": "AAH+/w==",
+        "at": 1_700_000_000_123u64,
+        "note": "x",
+    });
+    assert_eq!(to_value(&record).unwrap(), expected);
+    assert_eq!(
+        from_str::<Record>(&to_string(&record).unwrap()).unwrap(),
+        record
+    );
+}
+
+#[test]
+fn test_lenient_input() {
+    let record: Record =
+        from_str(r#"{"id": 7, "price": 2, "data": "AAH+/w", "at": -1000, "note": null}"#).unwrap();
+    assert_eq!(record.id, 7);
+    assert_eq!(record.price, 2.0);
+    assert_eq!(record.data, [0, 1, 254, 255]);
+    assert_eq!(record.at, UNIX_EPOCH - Duration::from_secs(1));
+    assert_eq!(record.note, None);
+
+    let record: Record =
+        from_str(r#"{"id": "1", "price": "NaN", "data": "", "at": 0, "note": ""}"#).unwrap();
+    assert!(record.price.is_nan());
+    assert_eq!(record.note, None);
+    assert_eq!(
+        to_value(&record).unwrap(),
+        json!({"id": "1", "price": "NaN", "data": "", "at": 0, "note": ""}),
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = from_str::<Record>(r#"{"id": "-1"}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"-1\", expected an unsigned integer or a string containing one at line 1 column 11",
+    );
+
+    let err = from_str::<Record>(r#"{"id": 1, "price": 1, "data": "!!"}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"!!\", expected a base64 string at line 1 column 34",
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_rfc3339() {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde_json::Value;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Event {
+        #[serde(with = "serde_json::helpers::rfc3339")]
+        at: DateTime<Utc>,
+    }
+
+    let at = Utc.with_ymd_and_hms(2023, 4, 5, 6, 7, 8).unwrap();
+    let event = Event { at };
+    assert_eq!(
+        to_value(&event).unwrap(),
+        json!({"at": "2023-04-05T06:07:08Z"})
+    );
+    assert_eq!(
+        from_str::<Event>(r#"{"at": "2023-04-05T08:07:08+02:00"}"#).unwrap(),
+        event,
+    );
+    assert!(from_str::<Event>(r#"{"at": "yesterday"}"#).is_err());
+
+    let value = Value::from(at);
+    assert_eq!(value, "2023-04-05T06:07:08Z");
+    assert_eq!(value.as_datetime().unwrap(), at);
+    assert_eq!(json!(1).as_datetime(), None);
+}
+
+#[test]
+fn test_borrowed_keys() {
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        #[serde(borrow, with = "serde_json::helpers::borrowed_keys")]
+        tree: BTreeMap<Cow<'a, str>, i32>,
+        #[serde(borrow, with = "serde_json::helpers::borrowed_keys")]
+        hash: HashMap<Cow<'a, str>, Vec<i32>>,
+    }
+
+    let input = r#"{"tree": {"plain": 1, "esc\"aped": 2}, "hash": {"x": [1, 2]}}"#;
+    let value: Borrowed = from_str(input).unwrap();
+    for key in value.tree.keys() {
+        assert_eq!(matches!(key, Cow::Borrowed(_)), *key == "plain");
+    }
+    assert_eq!(value.tree["esc\"aped"], 2);
+    assert!(matches!(value.hash.keys().next(), Some(Cow::Borrowed("x"))));
+    assert_eq!(value.hash["x"], [1, 2]);
+
+    let value: Borrowed = serde_json::from_slice(input.as_bytes()).unwrap();
+    assert_eq!(value.tree.len(), 2);
+
+    // Plain `&str` keys borrow too, but cannot hold an escaped key.
+    let map: HashMap<&str, i32> = from_str(r#"{"a": 1, "b": 2}"#).unwrap();
+    assert_eq!(map["b"], 2);
+    assert!(from_str::<HashMap<&str, i32>>(r#"{"a\n": 1}"#).is_err());
+
+    assert!(from_str::<Borrowed>(r#"{"tree": [], "hash": {}}"#).is_err());
+}
+
+#[test]
+fn test_optional_field() {
+    use serde_json::helpers::OptionalField;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Patch {
+        #[serde(default, skip_serializing_if = "OptionalField::is_missing")]
+        a: OptionalField<u32>,
+        #[serde(default, skip_serializing_if = "OptionalField::is_missing")]
+        b: OptionalField<Vec<u32>>,
+        #[serde(default)]
+        c: OptionalField<bool>,
+    }
+
+    let patch: Patch = from_str(r#"{"a": null, "b": [1]}"#).unwrap();
+    assert_eq!(patch.a, OptionalField::Null);
+    assert_eq!(patch.b, OptionalField::Value(vec![1]));
+    assert_eq!(patch.c, OptionalField::Missing);
+    assert_eq!(
+        to_value(&patch).unwrap(),
+        json!({"a": null, "b": [1], "c": null})
+    );
+
+    let patch: Patch = serde_json::from_value(json!({})).unwrap();
+    assert!(patch.a.is_missing() && !patch.a.is_present());
+    assert_eq!(to_string(&patch).unwrap(), r#"{"c":null}"#);
+    assert!(from_str::<Patch>(r#"{"a": "x"}"#).is_err());
+
+    let mut target = Some(5);
+    OptionalField::Missing.apply_to(&mut target);
+    assert_eq!(target, Some(5));
+    OptionalField::Value(6).apply_to(&mut target);
+    assert_eq!(target, Some(6));
+    OptionalField::Null.apply_to(&mut target);
+    assert_eq!(target, None);
+
+    assert_eq!(OptionalField::from(Some(None::<u8>)), OptionalField::Null);
+    assert_eq!(
+        OptionalField::Value(2).map(|v| v * 2).into_option(),
+        Some(Some(4))
+    );
+    assert_eq!(OptionalField::Value(3).as_ref().value(), Some(&&3));
+}
@@ -0,0 +1,204 @@
+#![cfg(feature = "jmespath")]
+
+use serde_json::jmespath::Expression;
+use serde_json::{json, Value};
+
+fn search(data: &Value, expr: &str) -> Value {
+    match data.search(expr) {
+        Ok(value) => value,
+        Err(err) => panic!("{}: {}", expr, err),
+    }
+}
+
+#[test]
+fn test_basic_expressions() {
+    let data = json!({"a": {"b": {"c": [0, 1, 2, 3, 4, 5]}}, "x-y": 1});
+
+    assert_eq!(search(&data, "a.b.c[0]"), json!(0));
+    assert_eq!(search(&data, "a.b.c[-1]"), json!(5));
+    assert_eq!(search(&data, "a.b.c[10]"), json!(null));
+    assert_eq!(search(&data, "a.b.c[1:3]"), json!([1, 2]));
+    assert_eq!(search(&data, "a.b.c[::2]"), json!([0, 2, 4]));
+    assert_eq!(search(&data, "a.b.c[::-2]"), json!([5, 3, 1]));
+    assert_eq!(search(&data, "a.b.c[-2:]"), json!([4, 5]));
+    assert_eq!(search(&data, "\"x-y\""), json!(1));
+    assert_eq!(search(&data, "a.missing.c"), json!(null));
+    assert_eq!(search(&data, "@.a.b | keys(@)"), json!(["c"]));
+}
+
+#[test]
+fn test_projections() {
+    let data = json!({
+        "people": [
+            {"first": "James", "last": "d", "age": 30},
+            {"first": "Jacob", "last": "e", "age": 50},
+            {"first": "Jayden", "last": "f"},
+            {"missing": "different"}
+        ],
+        "ops": {
+            "functionA": {"numArgs": 2},
+            "functionB": {"numArgs": 3},
+            "functionC": {"variadic": true}
+        },
+        "nested": [[0, 1], 2, [3], [[4]]]
+    });
+
+    assert_eq!(
+        search(&data, "people[*].first"),
+        json!(["James", "Jacob", "Jayden"])
+    );
+    assert_eq!(search(&data, "people[:2].last"), json!(["d", "e"]));
+    assert_eq!(search(&data, "ops.*.numArgs"), json!([2, 3]));
+    assert_eq!(search(&data, "nested[]"), json!([0, 1, 2, 3, [4]]));
+    assert_eq!(search(&data, "nested[][]"), json!([0, 1, 2, 3, 4]));
+    assert_eq!(search(&data, "people[?age > `40`].first"), json!(["Jacob"]));
+    assert_eq!(
+        search(&data, "people[?!age].first || 'none'"),
+        json!(["Jayden"])
+    );
+    assert_eq!(
+        search(&data, "people[?age && age < `40` || last == 'f'].first"),
+        json!(["James", "Jayden"])
+    );
+    assert_eq!(
+        search(&data, "people[*].[first, age] | [0]"),
+        json!(["James", 30])
+    );
+    assert_eq!(
+        search(&data, "people[0].{name: first, years: age}"),
+        json!({"name": "James", "years": 30})
+    );
+    assert_eq!(search(&data, "people[*].first | [0]"), json!("James"));
+    assert_eq!(search(&data, "people.first"), json!(null));
+
+    // Phép chiếu lồng nhau giữ nguyên cấu trúc cho đến khi được làm phẳng.
+    let groups = json!([{"xs": [1, 2]}, {"xs": [3]}]);
+    assert_eq!(search(&groups, "[*].xs[?@ > `1`]"), json!([[2], [3]]));
+    assert_eq!(search(&groups, "[*].xs[?@ > `1`][]"), json!([2, 3]));
+}
+
+#[test]
+fn test_functions() {
+    let data = json!({
+        "items": [
+            {"name": "b", "price": 2.5, "tags": ["x", "y"]},
+            {"name": "a", "price": 10, "tags": []},
+            {"name": "c", "price": -4, "tags": ["y"]}
+        ],
+        "text": "hello",
+        "empty": []
+    });
+
+    assert_eq!(search(&data, "length(items)"), json!(3));
+    assert_eq!(search(&data, "length(text)"), json!(5));
+    assert_eq!(
+        search(&data, "items[?contains(tags, 'y')].name"),
+        json!(["b", "c"])
+    );
+    assert_eq!(search(&data, "contains(text, 'ell')"), json!(true));
+    assert_eq!(search(&data, "sort(items[*].name)"), json!(["a", "b", "c"]));
+    assert_eq!(
+        search(&data, "sort_by(items, &price)[*].name"),
+        json!(["c", "b", "a"])
+    );
+    assert_eq!(search(&data, "max_by(items, &price).name"), json!("a"));
+    assert_eq!(search(&data, "min_by(items, &price).name"), json!("c"));
+    assert_eq!(search(&data, "max(items[*].price)"), json!(10));
+    assert_eq!(search(&data, "min(empty)"), json!(null));
+    assert_eq!(search(&data, "sum(items[*].price)"), json!(8.5));
+    assert_eq!(search(&data, "sum(empty)"), json!(0));
+    assert_eq!(search(&data, "avg(`[1, 2]`)"), json!(1.5));
+    assert_eq!(search(&data, "abs(items[2].price)"), json!(4));
+    assert_eq!(search(&data, "join(', ', items[*].name)"), json!("b, a, c"));
+    assert_eq!(search(&data, "map(&length(tags), items)"), json!([2, 0, 1]));
+    assert_eq!(
+        search(&data, "not_null(missing, `null`, text)"),
+        json!("hello")
+    );
+    assert_eq!(search(&data, "reverse(text)"), json!("olleh"));
+    assert_eq!(search(&data, "starts_with(text, 'he')"), json!(true));
+    assert_eq!(search(&data, "ends_with(text, 'x')"), json!(false));
+    assert_eq!(search(&data, "to_number('12')"), json!(12));
+    assert_eq!(
+        search(&data, "to_string(items[0].tags)"),
+        json!(r#"["x","y"]"#)
+    );
+    assert_eq!(search(&data, "type(items[0].price)"), json!("number"));
+    assert_eq!(search(&data, "values({a: `1`})"), json!([1]));
+}
+
+#[test]
+fn test_errors() {
+    let data = json!({"a": [1, "x"]});
+
+    for (expr, err) in &[
+        ("a.", "unexpected end of expression at line 1 column 3"),
+        ("a[", "unexpected end of expression at line 1 column 3"),
+        ("a[?b", "unexpected end of expression at line 1 column 5"),
+        ("a = b", "expected `==` at line 1 column 3"),
+        ("'abc", "unterminated `'` at line 1 column 1"),
+        ("`{`", "invalid JSON literal at line 1 column 1"),
+        ("a[::0]", "unexpected token at line 1 column 6"),
+        ("foo bar", "unexpected token at line 1 column 5"),
+        ("\"f\"(a)", "unexpected token at line 1 column 4"),
+    ] {
+        let actual = Expression::compile(expr).unwrap_err().to_string();
+        assert_eq!(actual, *err, "{}", expr);
+    }
+
+    for (expr, err) in &[
+        ("nope(a)", "unknown function: nope()"),
+        ("length(a, a)", "length() takes 1 argument(s), found 2"),
+        (
+            "length(`1`)",
+            "invalid type for argument 1 of length(): expected string, array or object, found number",
+        ),
+        ("sort(a)", "sort() requires all numbers or all strings"),
+        (
+            "sum(a)",
+            "invalid type for argument 1 of sum(): expected array of numbers, found string",
+        ),
+        ("&a", "expression reference used outside of a function"),
+    ] {
+        let actual = data.search(expr).unwrap_err().to_string();
+        assert_eq!(actual, *err, "{}", expr);
+    }
+}
+
+#[test]
+fn test_reuse_compiled() {
+    let expr: Expression = "items[?id == `2`] | [0].name".parse().unwrap();
+    let a = json!({"items": [{"id": 1, "name": "x"}, {"id": 2, "name": "y"}]});
+    let b = json!({"items": []});
+    assert_eq!(expr.search(&a).unwrap(), json!("y"));
+    assert_eq!(expr.search(&b).unwrap(), json!(null));
+}
+
+#[test]
+fn test_nesting_limit() {
+    let data = json!({"a": 1});
+
+    for expr in &["(".repeat(5000) + "a", "!".repeat(20000) + "a"] {
+        let err = data.search(expr).unwrap_err();
+        assert!(err.to_string().starts_with("expression nested too deeply"));
+    }
+
+    let nested = format!("{}a{}", "(".repeat(100), ")".repeat(100));
+    assert_eq!(data.search(&nested).unwrap(), json!(1));
+    let chain = vec!["a"; 100].join(".");
+    assert_eq!(data.search(&chain).unwrap(), json!(null));
+    assert!(data.search(&vec!["a"; 5000].join(".")).is_err());
+}
+
+#[test]
+fn test_numeric_equality() {
+    let data = json!({"a": [1, 2.0, "1", [1], {"k": 1}]});
+    assert_eq!(data.search("a[?@ == `1.0`]").unwrap(), json!([1]));
+    assert_eq!(data.search("a[?@ == `2`]").unwrap(), json!([2.0]));
+    assert_eq!(data.search("a[?@ == `[1.0]`]").unwrap(), json!([[1]]));
+    assert_eq!(data.search("a[?@ == `{\"k\": 1.0}`]").unwrap(), json!([{"k": 1}]));
+    assert_eq!(
+        data.search("a[?@ != `1.0`]").unwrap(),
+        json!([2.0, "1", [1], {"k": 1}])
+    );
+}
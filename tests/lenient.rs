@@ -0,0 +1,362 @@
+use serde::de::{Deserialize, IgnoredAny};
+use serde_derive::Deserialize;
+use serde_json::de::{DeserializerBuilder, Profile, StrRead};
+use serde_json::{json, Deserializer, Value};
+use std::collections::BTreeMap;
+
+fn from_lenient_escapes<'a, T: Deserialize<'a>>(j: &'a str) -> serde_json::Result<T> {
+    let mut de = Deserializer::from_str(j);
+    de.set_lenient_escapes(true);
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[test]
+fn test_lenient_escapes() {
+    assert_eq!(
+        from_lenient_escapes::<String>(r#""\x41\x7e\xE9""#).unwrap(),
+        "A~\u{e9}"
+    );
+    assert_eq!(
+        from_lenient_escapes::<String>(r#""\u{1F600}\u{0}\u{41}B""#).unwrap(),
+        "\u{1F600}\u{0}AB"
+    );
+    assert_eq!(
+        from_lenient_escapes::<Value>(r#"{"\x6b": ["\u{10FFFF}"]}"#).unwrap(),
+        json!({"k": ["\u{10FFFF}"]}),
+    );
+    from_lenient_escapes::<IgnoredAny>(r#"["\x41", "\u{1F600}"]"#).unwrap();
+
+    let mut de = Deserializer::from_reader(&br#""\x41\u{1F600}""#[..]);
+    de.set_lenient_escapes(true);
+    assert_eq!(String::deserialize(&mut de).unwrap(), "A\u{1F600}");
+
+    for (j, err) in &[
+        (r#""\x4""#, "invalid escape at line 1 column 5"),
+        (r#""\xG1""#, "invalid escape at line 1 column 4"),
+        (r#""\u{}""#, "invalid escape at line 1 column 5"),
+        (r#""\u{1234567}""#, "invalid escape at line 1 column 11"),
+        (r#""\u{1F600""#, "invalid escape at line 1 column 10"),
+        (
+            r#""\u{D800}""#,
+            "invalid unicode code point at line 1 column 9",
+        ),
+        (
+            r#""\u{110000}""#,
+            "invalid unicode code point at line 1 column 11",
+        ),
+    ] {
+        assert_eq!(
+            from_lenient_escapes::<String>(j).unwrap_err().to_string(),
+            *err
+        );
+    }
+
+    // Tắt mặc định.
+    assert!(serde_json::from_str::<String>(r#""\x41""#).is_err());
+    assert!(serde_json::from_str::<String>(r#""\u{41}""#).is_err());
+}
+
+fn from_control_chars<'a, T: Deserialize<'a>>(j: &'a str) -> serde_json::Result<T> {
+    let mut de = Deserializer::from_str(j);
+    de.set_control_chars_in_strings(true);
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[test]
+fn test_control_chars_in_strings() {
+    assert_eq!(
+        from_control_chars::<String>("\"a\nb\tc\u{0}\"").unwrap(),
+        "a\nb\tc\u{0}"
+    );
+    assert_eq!(
+        from_control_chars::<Value>("{\"k\ney\": [\"x\r\ny\", \"\\n\"]}").unwrap(),
+        json!({"k\ney": ["x\r\ny", "\n"]}),
+    );
+    from_control_chars::<IgnoredAny>("[\"a\nb\", {\"c\td\": 1}]").unwrap();
+
+    let mut de = Deserializer::from_reader(&b"\"a\nb\""[..]);
+    de.set_control_chars_in_strings(true);
+    assert_eq!(String::deserialize(&mut de).unwrap(), "a\nb");
+
+    // Vị trí lỗi vẫn tính dòng mới bên trong chuỗi.
+    assert_eq!(
+        from_control_chars::<Value>("[\"a\nb\" 1]")
+            .unwrap_err()
+            .to_string(),
+        "expected `,` or `]` at line 2 column 4",
+    );
+
+    // Tắt mặc định.
+    assert!(serde_json::from_str::<String>("\"a\nb\"").is_err());
+    assert!(serde_json::from_str::<IgnoredAny>("\"a\nb\"").is_err());
+}
+
+fn from_single_quotes<'a, T: Deserialize<'a>>(j: &'a str) -> serde_json::Result<T> {
+    let mut de = Deserializer::from_str(j);
+    de.set_single_quotes(true);
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Config {
+    name: String,
+    mode: Mode,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Mode {
+    Fast,
+}
+
+#[test]
+fn test_single_quotes() {
+    assert_eq!(
+        from_single_quotes::<String>(r#"'it\'s "quoted"'"#).unwrap(),
+        r#"it's "quoted""#
+    );
+    assert_eq!(from_single_quotes::<String>(r#""it's""#).unwrap(), "it's");
+    assert_eq!(
+        from_single_quotes::<Value>(r#"{'a': ['', 'x\ny', "z"], "b": 'c'}"#).unwrap(),
+        json!({"a": ["", "x\ny", "z"], "b": "c"}),
+    );
+    assert_eq!(
+        from_single_quotes::<Config>("{'name': 'n', 'mode': 'Fast'}").unwrap(),
+        Config {
+            name: "n".to_owned(),
+            mode: Mode::Fast,
+        },
+    );
+    from_single_quotes::<IgnoredAny>(r#"{'a': ['b\'', "c'"]}"#).unwrap();
+
+    let mut de = Deserializer::from_reader(&br#"['a"', "b'"]"#[..]);
+    de.set_single_quotes(true);
+    assert_eq!(Vec::<String>::deserialize(&mut de).unwrap(), ["a\"", "b'"]);
+
+    for (j, err) in &[
+        ("'abc", "EOF while parsing a string at line 1 column 4"),
+        (r#""a\'""#, "invalid escape at line 1 column 4"),
+        ("['a' 'b']", "expected `,` or `]` at line 1 column 6"),
+    ] {
+        assert_eq!(
+            from_single_quotes::<Value>(j).unwrap_err().to_string(),
+            *err
+        );
+    }
+
+    // Dấu nháy còn lại chỉ là một ký tự thường, nên chuỗi vẫn được mượn.
+    assert_eq!(
+        from_single_quotes::<&str>(r#"'say "hi"'"#).unwrap(),
+        r#"say "hi""#
+    );
+    assert_eq!(from_single_quotes::<&str>(r#""it's""#).unwrap(), "it's");
+    let reader = std::io::BufReader::with_capacity(3, &br#"['a"b', "c'd"]"#[..]);
+    let mut de = Deserializer::from_buf_read(reader);
+    de.set_single_quotes(true);
+    assert_eq!(
+        Vec::<String>::deserialize(&mut de).unwrap(),
+        ["a\"b", "c'd"]
+    );
+
+    // Tắt mặc định.
+    assert!(serde_json::from_str::<String>("'a'").is_err());
+    assert_eq!(serde_json::from_str::<String>(r#""it's""#).unwrap(), "it's");
+    assert_eq!(serde_json::from_str::<&str>(r#""it's""#).unwrap(), "it's");
+}
+
+fn from_unquoted_keys<'a, T: Deserialize<'a>>(j: &'a str) -> serde_json::Result<T> {
+    let mut de = Deserializer::from_str(j);
+    de.set_unquoted_keys(true);
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[test]
+fn test_unquoted_keys() {
+    assert_eq!(
+        from_unquoted_keys::<Value>(r#"{a: 1, _b2: {$c: [true]}, "d e": null, ký: 2}"#).unwrap(),
+        json!({"a": 1, "_b2": {"$c": [true]}, "d e": null, "ký": 2}),
+    );
+    assert_eq!(
+        from_unquoted_keys::<Config>(r#"{ name : "n",mode:"Fast" }"#).unwrap(),
+        Config {
+            name: "n".to_owned(),
+            mode: Mode::Fast,
+        },
+    );
+    let map = from_unquoted_keys::<BTreeMap<Mode, u8>>("{Fast: 1}").unwrap();
+    assert_eq!(map[&Mode::Fast], 1);
+    from_unquoted_keys::<IgnoredAny>(r#"{a: {b: [{c: 1}]}, "d": 2}"#).unwrap();
+
+    let mut de = Deserializer::from_reader(&b"{key: 1}"[..]);
+    de.set_unquoted_keys(true);
+    assert_eq!(Value::deserialize(&mut de).unwrap(), json!({"key": 1}));
+
+    // Kết hợp với chuỗi nháy đơn.
+    let mut de = Deserializer::from_str("{a: 'x', 'b': 'y'}");
+    de.set_unquoted_keys(true);
+    de.set_single_quotes(true);
+    assert_eq!(
+        Value::deserialize(&mut de).unwrap(),
+        json!({"a": "x", "b": "y"}),
+    );
+
+    for (j, err) in &[
+        ("{1a: 1}", "key must be a string at line 1 column 2"),
+        ("{a-b: 1}", "expected `:` at line 1 column 3"),
+        ("{a: 1, : 2}", "key must be a string at line 1 column 8"),
+    ] {
+        assert_eq!(
+            from_unquoted_keys::<Value>(j).unwrap_err().to_string(),
+            *err
+        );
+    }
+    assert_eq!(
+        from_unquoted_keys::<IgnoredAny>("{-: 1}")
+            .unwrap_err()
+            .to_string(),
+        "key must be a string at line 1 column 2",
+    );
+
+    // Tắt mặc định; giá trị không có nháy vẫn bị từ chối.
+    assert!(serde_json::from_str::<Value>("{a: 1}").is_err());
+    assert!(from_unquoted_keys::<Value>("{a: b}").is_err());
+}
+
+fn from_builder<'a, T: Deserialize<'a>>(
+    builder: DeserializerBuilder,
+    j: &'a str,
+) -> serde_json::Result<T> {
+    let mut de = builder.build(StrRead::new(j));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[test]
+fn test_comments() {
+    let builder = DeserializerBuilder::new().comments(true);
+    assert_eq!(
+        from_builder::<Value>(
+            builder,
+            "// đầu\n[1, /* a */ 2 // b\n, {\"k\" /**/: /***/ 3}] /* cuối */",
+        )
+        .unwrap(),
+        json!([1, 2, {"k": 3}]),
+    );
+    from_builder::<IgnoredAny>(builder, "[1 /* x */, {\"a\": [] // y\n}]").unwrap();
+    assert_eq!(
+        from_builder::<u8>(builder, "1 // không có xuống dòng").unwrap(),
+        1
+    );
+    // Dấu `/` trong chuỗi không mở chú thích.
+    assert_eq!(
+        from_builder::<String>(builder, r#""a//b""#).unwrap(),
+        "a//b"
+    );
+
+    for (j, err) in &[
+        ("[1 /* x", "EOF while parsing a value at line 1 column 7"),
+        ("[1 /x]", "expected value at line 1 column 5"),
+    ] {
+        assert_eq!(
+            from_builder::<Value>(builder, j).unwrap_err().to_string(),
+            *err
+        );
+    }
+
+    // Tắt mặc định.
+    assert!(serde_json::from_str::<Value>("1 // x").is_err());
+}
+
+#[test]
+fn test_trailing_commas() {
+    let builder = DeserializerBuilder::new().trailing_commas(true);
+    assert_eq!(
+        from_builder::<Value>(builder, r#"{"a": [1, 2, ], "b": {"c": null,},}"#).unwrap(),
+        json!({"a": [1, 2], "b": {"c": null}}),
+    );
+    assert_eq!(
+        from_builder::<Config>(builder, r#"{"name": "n", "mode": "Fast",}"#).unwrap(),
+        Config {
+            name: "n".to_owned(),
+            mode: Mode::Fast,
+        },
+    );
+    assert_eq!(
+        from_builder::<(u8, u8)>(builder, "[1, 2,]").unwrap(),
+        (1, 2)
+    );
+    from_builder::<IgnoredAny>(builder, r#"[[1,], {"a": {},},]"#).unwrap();
+
+    for (j, err) in &[
+        ("[,]", "expected value at line 1 column 2"),
+        ("[1,,]", "expected value at line 1 column 4"),
+        ("{,}", "key must be a string at line 1 column 2"),
+    ] {
+        assert_eq!(
+            from_builder::<Value>(builder, j).unwrap_err().to_string(),
+            *err
+        );
+    }
+    assert!(from_builder::<IgnoredAny>(builder, "[1,,]").is_err());
+
+    // Tắt mặc định.
+    assert!(serde_json::from_str::<Value>("[1,]").is_err());
+}
+
+#[test]
+fn test_profiles() {
+    let j = "{name: 'ann', /* id */ id: 0x10, tags: ['a\\x41',],}";
+    assert_eq!(
+        from_builder::<Value>(DeserializerBuilder::new().profile(Profile::Json5), j).unwrap(),
+        json!({"name": "ann", "id": 16, "tags": ["aA"]}),
+    );
+    assert!(from_builder::<Value>(DeserializerBuilder::new().profile(Profile::Jsonc), j).is_err());
+
+    // JSON5 cho phép tiền tố hệ 16 viết hoa.
+    let builder = DeserializerBuilder::new().profile(Profile::Json5);
+    assert_eq!(
+        from_builder::<Value>(builder, "[0X1F, -0Xa]").unwrap(),
+        json!([31, -10]),
+    );
+
+    let j = "[1, 2, // x\n]";
+    for (profile, ok) in &[
+        (Profile::Strict, false),
+        (Profile::Jsonc, true),
+        (Profile::Json5, true),
+        (Profile::Logs, false),
+    ] {
+        let builder = DeserializerBuilder::new().profile(*profile);
+        assert_eq!(
+            from_builder::<Value>(builder, j).is_ok(),
+            *ok,
+            "{:?}",
+            profile
+        );
+    }
+
+    let j = "{\"msg\": \"line 1\nline 2\", \"code\": \"\\x1b[0m\"}";
+    assert_eq!(
+        from_builder::<Value>(DeserializerBuilder::new().profile(Profile::Logs), j).unwrap(),
+        json!({"msg": "line 1\nline 2", "code": "\u{1b}[0m"}),
+    );
+
+    // Profile ghi đè các cờ đã chọn trước đó; các cờ sau đó được giữ.
+    let builder = DeserializerBuilder::new()
+        .single_quotes(true)
+        .profile(Profile::Strict)
+        .comments(true);
+    assert_eq!(builder, DeserializerBuilder::new().comments(true));
+    assert_eq!(
+        DeserializerBuilder::new(),
+        DeserializerBuilder::new().profile(Profile::default())
+    );
+}
@@ -45,3 +45,68 @@ fn test_retain() {
     let keys: Vec<_> = val.keys().collect();
     assert_eq!(keys, &["a", "c"]);
 }
+
+#[test]
+fn test_new_ordered() {
+    let mut map = Map::new_ordered();
+    assert!(map.preserves_order());
+    assert!(!Map::new_sorted().preserves_order());
+    map.insert("c".to_owned(), Value::from(1));
+    map.insert("a".to_owned(), Value::from(2));
+    map.entry("b").or_insert(Value::from(3));
+    assert_eq!(
+        map.insert("c".to_owned(), Value::from(4)),
+        Some(Value::from(1))
+    );
+
+    let keys: Vec<_> = map.keys().collect();
+    assert_eq!(keys, &["c", "a", "b"]);
+    let keys: Vec<_> = map.keys().rev().collect();
+    assert_eq!(keys, &["b", "a", "c"]);
+    assert_eq!(map["a"], 2);
+    assert_eq!(
+        Value::Object(map.clone()).to_string(),
+        r#"{"c":4,"a":2,"b":3}"#
+    );
+
+    assert_eq!(map.remove("c"), Some(Value::from(4)));
+    map.insert("c".to_owned(), Value::from(5));
+    if let serde_json::map::Entry::Occupied(entry) = map.entry("a") {
+        assert_eq!(entry.remove(), 2);
+    }
+    map.retain(|_, v| *v != 3);
+    let entries: Vec<_> = map.clone().into_iter().collect();
+    assert_eq!(entries, vec![("c".to_owned(), Value::from(5))]);
+    assert_eq!(map.get("c"), Some(&Value::from(5)));
+    assert!(map.get("b").is_none());
+}
+
+#[test]
+fn test_ordered_eq_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(map: &Map<String, Value>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        Value::Object(map.clone()).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mut ordered = Map::new_ordered();
+    ordered.insert("b".to_owned(), Value::Null);
+    ordered.insert("a".to_owned(), Value::Null);
+    let mut sorted = Map::new();
+    sorted.insert("a".to_owned(), Value::Null);
+    sorted.insert("b".to_owned(), Value::Null);
+
+    assert_eq!(ordered, sorted);
+    assert_eq!(sorted, ordered);
+    assert_eq!(hash(&ordered), hash(&sorted));
+    assert_eq!(format!("{:?}", ordered), r#"{"b": Null, "a": Null}"#);
+
+    sorted.append(&mut ordered);
+    assert!(ordered.is_empty() && ordered.preserves_order());
+    ordered.append(&mut sorted);
+    let keys: Vec<_> = ordered.keys().collect();
+    assert_eq!(keys, &["a", "b"]);
+}
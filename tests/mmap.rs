@@ -0,0 +1,35 @@
+#![cfg(feature = "mmap")]
+
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("serde_json_mmap_{}_{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_from_path_mmap() {
+    let path = temp_file("ok.json", b" {\"a\": [1, \"two\", null]}\n");
+    let value: Value = serde_json::from_path_mmap(&path).unwrap();
+    assert_eq!(value, json!({"a": [1, "two", null]}));
+    fs::remove_file(&path).unwrap();
+
+    let path = temp_file("empty.json", b"");
+    let err = serde_json::from_path_mmap::<Value, _>(&path).unwrap_err();
+    assert!(err.is_eof());
+    fs::remove_file(&path).unwrap();
+
+    let path = temp_file("trailing.json", b"[1] [2]");
+    let err = serde_json::from_path_mmap::<Value, _>(&path).unwrap_err();
+    assert!(err.is_syntax());
+    assert_eq!((err.line(), err.column()), (1, 5));
+    fs::remove_file(&path).unwrap();
+
+    let missing = std::env::temp_dir().join("serde_json_mmap_does_not_exist.json");
+    let err = serde_json::from_path_mmap::<Value, _>(missing).unwrap_err();
+    assert!(err.is_io());
+}
@@ -0,0 +1,227 @@
+use serde::de::{Deserialize, IgnoredAny};
+use serde_json::{json, Deserializer, Number, Value};
+
+fn f(x: f64) -> Number {
+    Number::from_f64(x).unwrap()
+}
+
+#[test]
+fn test_arithmetic() {
+    assert_eq!(Number::from(2) + Number::from(-5), Number::from(-3));
+    assert_eq!(
+        Number::from(u64::MAX) - Number::from(1),
+        Number::from(u64::MAX - 1)
+    );
+    assert_eq!(
+        Number::from(i64::MIN) * Number::from(1),
+        Number::from(i64::MIN)
+    );
+    assert_eq!(Number::from(-9) / Number::from(3), Number::from(-3));
+    assert_eq!(Number::from(1) + f(0.5), f(1.5));
+    assert_eq!(f(0.25) * f(2.0), f(0.5));
+
+    // Số nguyên bị tràn được tính lại bằng f64.
+    let overflow = Number::from(u64::MAX) + Number::from(1);
+    assert_eq!(overflow.as_f64(), Some(18446744073709551616.0));
+
+    let values = json!([1, 2, 3.5]);
+    let sum = values
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(serde_json::Value::as_number)
+        .fold(Number::from(0), |acc, n| &acc + n);
+    assert_eq!(sum, f(6.5));
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    assert_eq!(
+        Number::from(i64::MAX).checked_add(&Number::from(i64::MAX)),
+        Some(Number::from(u64::MAX - 1))
+    );
+    assert_eq!(Number::from(1).checked_div(&Number::from(0)), None);
+    assert_eq!(f(1.0).checked_div(&Number::from(0)), None);
+    assert_eq!(f(f64::MAX).checked_mul(&f(2.0)), None);
+    assert_eq!(Number::from(1).checked_div(&Number::from(4)), Some(f(0.25)));
+}
+
+// Với feature `bigint`, số nguyên bị tràn trở thành `BigInt`.
+#[cfg(not(feature = "bigint"))]
+#[test]
+fn test_checked_integer_overflow() {
+    assert_eq!(Number::from(i64::MIN).checked_add(&Number::from(-1)), None);
+    assert_eq!(Number::from(0).checked_sub(&Number::from(u64::MAX)), None);
+    assert_eq!(Number::from(u64::MAX).checked_mul(&Number::from(2)), None);
+}
+
+#[test]
+#[should_panic(expected = "result of div is not a JSON number")]
+fn test_divide_by_zero() {
+    let _ = Number::from(1) / Number::from(0);
+}
+
+#[test]
+fn test_ordering() {
+    let mut numbers = vec![
+        f(2.5),
+        Number::from(-3),
+        Number::from(u64::MAX),
+        f(-3.5),
+        Number::from(0),
+    ];
+    numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(
+        numbers,
+        [
+            f(-3.5),
+            Number::from(-3),
+            Number::from(0),
+            f(2.5),
+            Number::from(u64::MAX)
+        ]
+    );
+
+    assert!(Number::from(u64::MAX) < f(18446744073709551616.0));
+    assert!(Number::from(i64::MIN) > f(-1e300));
+    assert!(Number::from(3) > f(2.999));
+    assert!(Number::from(-3) < f(-2.999));
+    assert_eq!(Number::from(1).partial_cmp(&f(1.0)), None);
+    assert!(Number::from(1) <= Number::from(1));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("0".parse::<Number>().unwrap(), Number::from(0));
+    assert_eq!(
+        "18446744073709551615".parse::<Number>().unwrap(),
+        Number::from(u64::MAX)
+    );
+    assert_eq!(
+        "-9223372036854775808".parse::<Number>().unwrap(),
+        Number::from(i64::MIN)
+    );
+    assert_eq!("1E2".parse::<Number>().unwrap(), f(100.0));
+    assert_eq!("-0.5".parse::<Number>().unwrap(), f(-0.5));
+
+    for bad in [
+        "", "-", "+1", "1.", ".5", "1e", "0x10", "1 ", "NaN", "1.0.0",
+    ] {
+        let err = bad.parse::<Number>().unwrap_err();
+        assert!(err.is_syntax() || err.is_eof(), "{:?}: {}", bad, err);
+    }
+}
+
+#[test]
+fn test_to_num() {
+    let v = json!([255, 256, -1, 1.5, 1e300, "1"]);
+
+    assert_eq!(v[0].to_num::<u8>(), Some(255));
+    assert_eq!(v[1].to_num::<u8>(), None);
+    assert_eq!(v[1].to_num::<i16>(), Some(256));
+    assert_eq!(v[2].to_num::<u64>(), None);
+    assert_eq!(v[2].to_num::<i8>(), Some(-1));
+    assert_eq!(v[2].to_num::<i128>(), Some(-1));
+    assert_eq!(v[3].to_num::<i64>(), None);
+    assert_eq!(v[3].to_num::<f32>(), Some(1.5));
+    assert_eq!(v[4].to_num::<f64>(), Some(1e300));
+    assert_eq!(v[4].to_num::<f32>(), None);
+    assert_eq!(v[5].to_num::<u8>(), None);
+    assert_eq!(
+        Number::from(u64::MAX).to_num::<u128>(),
+        Some(u64::MAX as u128)
+    );
+}
+
+#[test]
+fn test_from_json_number_custom() {
+    use serde_json::FromJsonNumber;
+
+    #[derive(PartialEq, Debug)]
+    struct Percent(u8);
+
+    impl FromJsonNumber for Percent {
+        fn from_json_number(number: &Number) -> Option<Self> {
+            number.to_num::<u8>().filter(|&p| p <= 100).map(Percent)
+        }
+    }
+
+    assert_eq!(json!(42).to_num::<Percent>(), Some(Percent(42)));
+    assert_eq!(json!(101).to_num::<Percent>(), None);
+}
+
+#[test]
+fn test_is_integer_and_fits() {
+    let v = json!([0, -7, 3.0, 3.25, 1e20, -0.0, u64::MAX]);
+
+    let integers: Vec<bool> = v
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.is_integer())
+        .collect();
+    assert_eq!(integers, [true, true, true, false, true, true, true]);
+
+    assert!(v[0].as_number().unwrap().fits::<u8>());
+    assert!(v[1].as_number().unwrap().fits::<i8>());
+    assert!(!v[1].as_number().unwrap().fits::<u32>());
+    assert!(v[2].as_number().unwrap().fits::<u16>());
+    assert!(!v[3].as_number().unwrap().fits::<i64>());
+    assert!(v[3].as_number().unwrap().fits::<f32>());
+    assert!(!v[4].as_number().unwrap().fits::<u64>());
+    assert!(v[4].as_number().unwrap().fits::<u128>());
+    assert!(v[5].as_number().unwrap().fits::<u8>());
+    assert!(v[6].as_number().unwrap().fits::<u64>());
+    assert!(!v[6].as_number().unwrap().fits::<i64>());
+    assert!(!json!("1").is_integer());
+}
+
+fn from_radix_str<'a, T: Deserialize<'a>>(j: &'a str) -> serde_json::Result<T> {
+    let mut de = Deserializer::from_str(j);
+    de.set_radix_literals(true);
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[test]
+fn test_radix_literals() {
+    assert_eq!(from_radix_str::<u8>("0xFF").unwrap(), 255);
+    assert_eq!(from_radix_str::<u32>("0o755").unwrap(), 0o755);
+    assert_eq!(from_radix_str::<i8>("-0b1010").unwrap(), -10);
+    assert_eq!(
+        from_radix_str::<i64>("-0x8000000000000000").unwrap(),
+        i64::MIN
+    );
+    assert_eq!(
+        from_radix_str::<Value>("[0xdeadBEEF, 0, 0.5, -0x0]").unwrap(),
+        json!([0xdead_beef_u32, 0, 0.5, 0]),
+    );
+    from_radix_str::<IgnoredAny>(r#"{"a": [0x1F, -0b1]}"#).unwrap();
+    assert_eq!(
+        from_radix_str::<Value>("[0X1F, 0O17, -0B11]").unwrap(),
+        json!([31, 15, -3]),
+    );
+    assert_eq!(from_radix_str::<u16>("0XfF").unwrap(), 255);
+    from_radix_str::<IgnoredAny>("[0XA, 0O7, 0B1]").unwrap();
+
+    for (j, err) in &[
+        ("0x", "EOF while parsing a value at line 1 column 2"),
+        ("0xG", "invalid number at line 1 column 3"),
+        ("0b102", "trailing characters at line 1 column 5"),
+        ("0x1.5", "trailing characters at line 1 column 4"),
+        (
+            "0x10000000000000000",
+            "number out of range at line 1 column 19",
+        ),
+        (
+            "-0x8000000000000001",
+            "number out of range at line 1 column 19",
+        ),
+    ] {
+        assert_eq!(from_radix_str::<Value>(j).unwrap_err().to_string(), *err);
+    }
+
+    // Tắt mặc định.
+    assert!(serde_json::from_str::<Value>("0xFF").is_err());
+}
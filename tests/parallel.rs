@@ -0,0 +1,92 @@
+#![cfg(feature = "parallel")]
+
+use serde_json::parallel::{from_ndjson_slice, to_vec_par, to_writer_par, ParLineDeserializer};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read};
+
+#[test]
+fn test_from_ndjson_slice() {
+    let input = b"{\"a\": 1}\r\n\n  \n[true]\n{\"a\": }\n\"last\"";
+    let results = from_ndjson_slice::<Value>(input);
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap(), &json!({"a": 1}));
+    assert_eq!(results[1].as_ref().unwrap(), &json!([true]));
+    let err = results[2].as_ref().unwrap_err();
+    assert!(err.is_syntax());
+    assert_eq!((err.line(), err.column()), (5, 7));
+    assert_eq!(results[3].as_ref().unwrap(), &json!("last"));
+
+    let borrowed = from_ndjson_slice::<&str>(b"\"x\"\n\"y\"\n");
+    let borrowed: Vec<&str> = borrowed.into_iter().map(Result::unwrap).collect();
+    assert_eq!(borrowed, ["x", "y"]);
+
+    assert!(from_ndjson_slice::<Value>(b"").is_empty());
+}
+
+#[test]
+fn test_par_line_deserializer_order() {
+    let input: String = (0..10_000).map(|i| format!("{{\"i\": {}}}\n", i)).collect();
+    let values = ParLineDeserializer::<_, Value>::new(input.as_bytes()).batch_lines(97);
+    let mut count = 0;
+    for (i, value) in values.enumerate() {
+        assert_eq!(value.unwrap(), json!({ "i": i }));
+        count += 1;
+    }
+    assert_eq!(count, 10_000);
+}
+
+#[test]
+fn test_par_line_deserializer_errors() {
+    let input = b"1\n\n[\n3\n";
+    let mut values = ParLineDeserializer::<_, Value>::new(&input[..]).batch_lines(2);
+    assert_eq!(values.next().unwrap().unwrap(), json!(1));
+    let err = values.next().unwrap().unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.line(), 3);
+    assert_eq!(values.next().unwrap().unwrap(), json!(3));
+    assert!(values.next().is_none());
+    assert!(values.next().is_none());
+}
+
+struct FailAfter<R>(R);
+
+impl<R: Read> Read for FailAfter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.read(buf)? {
+            0 => Err(io::Error::new(io::ErrorKind::Other, "boom")),
+            n => Ok(n),
+        }
+    }
+}
+
+#[test]
+fn test_par_line_deserializer_io_error() {
+    let reader: Box<dyn BufRead> = Box::new(BufReader::new(FailAfter(&b"1\n2\n"[..])));
+    let mut values = ParLineDeserializer::<_, u32>::new(reader);
+    assert_eq!(values.next().unwrap().unwrap(), 1);
+    assert_eq!(values.next().unwrap().unwrap(), 2);
+    assert!(values.next().unwrap().unwrap_err().is_io());
+    assert!(values.next().is_none());
+}
+
+#[test]
+fn test_to_vec_par() {
+    let rows: Vec<Value> = (0..5_000)
+        .map(|i| json!({"id": i, "name": format!("row {}", i), "tags": [i % 3]}))
+        .collect();
+    let expected = serde_json::to_vec(&rows).unwrap();
+    assert_eq!(to_vec_par(&rows).unwrap(), expected);
+
+    let value = Value::Array(rows);
+    let mut out = Vec::new();
+    to_writer_par(&mut out, value.as_array().unwrap()).unwrap();
+    assert_eq!(out, expected);
+
+    assert_eq!(to_vec_par::<u8>(&[]).unwrap(), b"[]");
+    assert_eq!(to_vec_par(&[1]).unwrap(), b"[1]");
+
+    let mut bad = vec![BTreeMap::new(); 100];
+    bad[57].insert(vec![1], 2);
+    assert!(to_vec_par(&bad).is_err());
+}
@@ -0,0 +1,858 @@
+use serde_json::json;
+use std::io::{self, Write};
+
+struct CountingWriter {
+    bytes: Vec<u8>,
+    writes: usize,
+    flushes: usize,
+}
+
+impl CountingWriter {
+    fn new() -> Self {
+        CountingWriter {
+            bytes: Vec::new(),
+            writes: 0,
+            flushes: 0,
+        }
+    }
+}
+
+impl Write for &mut CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_to_writer_buffered() {
+    let value = json!({"a": [1, 2, 3], "b": "x"});
+
+    let mut writer = CountingWriter::new();
+    serde_json::to_writer_buffered(&mut writer, &value).unwrap();
+    assert_eq!(writer.bytes, serde_json::to_vec(&value).unwrap());
+    assert_eq!(writer.writes, 1);
+    assert_eq!(writer.flushes, 1);
+
+    let mut writer = CountingWriter::new();
+    serde_json::to_writer_pretty_buffered(&mut writer, &value).unwrap();
+    assert_eq!(writer.bytes, serde_json::to_vec_pretty(&value).unwrap());
+    assert_eq!(writer.writes, 1);
+}
+
+#[test]
+fn test_finish() {
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    enum E {
+        Newtype(Vec<u8>),
+        Tuple(u8, u8),
+        Struct { a: u8 },
+        Empty(),
+    }
+
+    let mut ser = serde_json::Serializer::new(Vec::new());
+    let values = (
+        E::Newtype(vec![1]),
+        E::Tuple(1, 2),
+        E::Struct { a: 1 },
+        E::Empty(),
+    );
+    values.serialize(&mut ser).unwrap();
+    assert!(ser.is_complete());
+    let out = ser.finish().unwrap();
+    assert_eq!(
+        out,
+        br#"[{"Newtype":[1]},{"Tuple":[1,2]},{"Struct":{"a":1}},{"Empty":[]}]"#
+    );
+
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(vec![1], 1);
+    let mut ser = serde_json::Serializer::new(Vec::new());
+    map.serialize(&mut ser).unwrap_err();
+    assert!(!ser.is_complete());
+    let err = ser.finish().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "serializer finished with an incomplete value"
+    );
+}
+
+#[test]
+fn test_bytes_encoding() {
+    use serde::{Deserialize, Serialize};
+    use serde_json::ser::BytesEncoding;
+
+    fn encode(bytes: &[u8], encoding: BytesEncoding) -> String {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_bytes_encoding(encoding);
+        serde_bytes::Bytes::new(bytes).serialize(&mut ser).unwrap();
+        String::from_utf8(ser.into_inner()).unwrap()
+    }
+
+    fn decode(json: &str, encoding: BytesEncoding) -> serde_json::Result<Vec<u8>> {
+        let mut de = serde_json::Deserializer::from_str(json);
+        de.set_bytes_encoding(encoding);
+        serde_bytes::ByteBuf::deserialize(&mut de).map(serde_bytes::ByteBuf::into_vec)
+    }
+
+    let cases: &[(&[u8], &str, &str)] = &[
+        (b"", r#""""#, r#""""#),
+        (b"f", r#""Zg==""#, r#""66""#),
+        (b"fo", r#""Zm8=""#, r#""666f""#),
+        (b"foo", r#""Zm9v""#, r#""666f6f""#),
+        (b"foob", r#""Zm9vYg==""#, r#""666f6f62""#),
+        (&[0, 255, 16], r#""AP8Q""#, r#""00ff10""#),
+    ];
+    for &(bytes, base64, hex) in cases {
+        assert_eq!(encode(bytes, BytesEncoding::Base64), base64);
+        assert_eq!(encode(bytes, BytesEncoding::Hex), hex);
+        assert_eq!(decode(base64, BytesEncoding::Base64).unwrap(), bytes);
+        assert_eq!(decode(hex, BytesEncoding::Hex).unwrap(), bytes);
+    }
+
+    assert_eq!(encode(b"hi", BytesEncoding::Array), "[104,105]");
+    assert_eq!(decode(r#""hi""#, BytesEncoding::Array).unwrap(), b"hi");
+    assert_eq!(decode("[104,105]", BytesEncoding::Base64).unwrap(), b"hi");
+    assert_eq!(
+        decode(r#""Zm9vYg""#, BytesEncoding::Base64).unwrap(),
+        b"foob"
+    );
+    assert_eq!(decode(r#""00FF""#, BytesEncoding::Hex).unwrap(), [0, 255]);
+
+    let err = decode(r#""Zm9v!""#, BytesEncoding::Base64).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"Zm9v!\", expected a base64 string at line 1 column 7",
+    );
+    assert!(decode(r#""abc""#, BytesEncoding::Hex).is_err());
+}
+
+#[test]
+fn test_write_byte_array_hook() {
+    use serde::Serialize;
+    use serde_json::ser::{CompactFormatter, Formatter};
+
+    struct Quoted;
+
+    impl Formatter for Quoted {
+        fn write_byte_array<W>(&mut self, writer: &mut W, value: &[u8]) -> io::Result<()>
+        where
+            W: ?Sized + Write,
+        {
+            write!(writer, "\"{} bytes\"", value.len())
+        }
+    }
+
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), Quoted);
+    serde_bytes::Bytes::new(b"abc").serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_inner(), br#""3 bytes""#);
+
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), CompactFormatter);
+    serde_bytes::Bytes::new(b"abc").serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_inner(), b"[97,98,99]");
+
+    let pretty = serde_json::to_string_pretty(&serde_bytes::Bytes::new(b"ab")).unwrap();
+    assert_eq!(pretty, "[\n  97,\n  98\n]");
+}
+
+#[test]
+fn test_max_depth() {
+    use serde::Serialize;
+    use serde_json::Value;
+
+    #[derive(Serialize)]
+    enum E {
+        Newtype(u8),
+        Tuple(u8, u8),
+        Struct { a: u8 },
+    }
+
+    fn serialize<T: Serialize>(value: &T, max_depth: usize) -> serde_json::Result<Vec<u8>> {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_max_depth(max_depth);
+        value.serialize(&mut ser)?;
+        ser.finish()
+    }
+
+    let value = json!({"a": [1, {"b": []}]});
+    assert_eq!(value.depth(), 4);
+    assert!(serialize(&value, 4).is_ok());
+    let err = serialize(&value, 3).unwrap_err();
+    assert!(err.is_limit());
+    assert_eq!(err.to_string(), "recursion limit exceeded");
+
+    assert_eq!(serialize(&1, 0).unwrap(), b"1");
+    assert!(serialize(&json!([]), 0).unwrap_err().is_limit());
+
+    assert_eq!(serialize(&E::Newtype(1), 1).unwrap(), br#"{"Newtype":1}"#);
+    assert!(serialize(&[E::Newtype(1)], 1).is_err());
+    assert!(serialize(&E::Tuple(1, 2), 2).is_ok());
+    assert!(serialize(&E::Tuple(1, 2), 1).is_err());
+    assert!(serialize(&E::Struct { a: 1 }, 2).is_ok());
+    assert!(serialize(&E::Struct { a: 1 }, 1).is_err());
+
+    let mut deep = Value::Null;
+    for _ in 0..100_000 {
+        deep = Value::Array(vec![deep]);
+    }
+    assert!(serialize(&deep, 128).unwrap_err().is_limit());
+    while let Value::Array(mut list) = deep {
+        deep = list.pop().unwrap();
+    }
+}
+
+#[test]
+fn test_reject_duplicate_keys() {
+    use serde::ser::{Serialize, SerializeMap};
+    use std::collections::BTreeMap;
+
+    struct Pairs(Vec<(serde_json::Value, u8)>);
+
+    impl Serialize for Pairs {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(None)?;
+            for (k, v) in &self.0 {
+                map.serialize_entry(k, v)?;
+            }
+            map.end()
+        }
+    }
+
+    fn serialize<T: Serialize>(value: &T) -> serde_json::Result<Vec<u8>> {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_reject_duplicate_keys(true);
+        value.serialize(&mut ser)?;
+        ser.finish()
+    }
+
+    let pairs = Pairs(vec![(json!("a"), 1), (json!("b"), 2), (json!("a"), 3)]);
+    assert_eq!(
+        serde_json::to_vec(&pairs).unwrap(),
+        br#"{"a":1,"b":2,"a":3}"#
+    );
+    let err = serialize(&pairs).unwrap_err();
+    assert_eq!(err.to_string(), r#"duplicate key "a""#);
+
+    // Keys are compared as written, so an integer key clashes with the
+    // equivalent string key.
+    let mut ints = BTreeMap::new();
+    ints.insert(1, 0);
+    assert!(serialize(&ints).is_ok());
+    let mixed = Pairs(vec![(json!(1), 1), (json!("1"), 2)]);
+    assert!(serialize(&mixed).is_err());
+
+    // Each object tracks its own keys.
+    let nested = json!({"a": {"a": {"a": 1}}, "b": [{"a": 1}, {"a": 2}]});
+    assert_eq!(
+        serialize(&nested).unwrap(),
+        serde_json::to_vec(&nested).unwrap()
+    );
+    let nested = vec![
+        Pairs(vec![(json!("x"), 1)]),
+        Pairs(vec![(json!("x"), 1), (json!("y"), 2), (json!("x"), 3)]),
+    ];
+    assert!(serialize(&nested).is_err());
+}
+
+#[test]
+fn test_escape_at_every_offset() {
+    fn expected(s: &str) -> String {
+        let mut out = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\u{1}' => out.push_str("\\u0001"),
+                '\u{1f}' => out.push_str("\\u001f"),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    // Place each special character at every position of strings long enough
+    // to cross several 8- and 16-byte blocks, next to non-ASCII bytes and
+    // the bytes just outside the escaped ranges.
+    let filler: Vec<char> = "a é ~\u{7f}ü!".chars().collect();
+    for special in ['"', '\\', '\n', '\u{1}', '\u{1f}'] {
+        for len in 1..40 {
+            for pos in 0..len {
+                let s: String = (0..len)
+                    .map(|i| filler[i % filler.len()])
+                    .enumerate()
+                    .flat_map(|(i, c)| if i == pos { vec![special, c] } else { vec![c] })
+                    .collect();
+                assert_eq!(serde_json::to_string(&s).unwrap(), expected(&s));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_with_capacity() {
+    let value = json!({"name": "x", "list": [1, 2, 3]});
+    let expected = serde_json::to_string(&value).unwrap();
+
+    let s = serde_json::to_string_with_capacity(&value, 1000).unwrap();
+    assert_eq!(s, expected);
+    assert!(s.capacity() >= 1000);
+
+    // A hint that is too small only costs reallocations.
+    let v = serde_json::to_vec_with_capacity(&value, 0).unwrap();
+    assert_eq!(v, expected.as_bytes());
+}
+
+#[test]
+fn test_write_value() {
+    use serde_json::ser::ValueSeparator;
+
+    let write_all = |separator| {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_value_separator(separator);
+        ser.write_value(&json!([1])).unwrap();
+        ser.write_value("a").unwrap();
+        ser.write_value(&json!({"b": null})).unwrap();
+        String::from_utf8(ser.finish().unwrap()).unwrap()
+    };
+    assert_eq!(write_all(ValueSeparator::Nothing), r#"[1]"a"{"b":null}"#);
+    assert_eq!(
+        write_all(ValueSeparator::Newline),
+        "[1]\n\"a\"\n{\"b\":null}\n"
+    );
+    assert_eq!(
+        write_all(ValueSeparator::RecordSeparator),
+        "\x1e[1]\n\x1e\"a\"\n\x1e{\"b\":null}\n"
+    );
+
+    // Output written with newlines reads back as a stream.
+    let mut ser = serde_json::Serializer::pretty(Vec::new());
+    ser.set_value_separator(ValueSeparator::Newline);
+    for i in 0..3 {
+        ser.write_value(&json!({ "i": i })).unwrap();
+    }
+    let bytes = ser.into_inner();
+    let values: Vec<serde_json::Value> = serde_json::Deserializer::from_slice(&bytes)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(values, [json!({"i": 0}), json!({"i": 1}), json!({"i": 2})]);
+
+    // A failed value poisons the stream.
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(vec![1], 1);
+    let mut ser = serde_json::Serializer::new(Vec::new());
+    assert!(ser.write_value(&vec![map]).is_err());
+    assert!(ser.write_value(&1).is_err());
+}
+
+#[test]
+fn test_checkpoint_rollback() {
+    use serde_json::ser::RollbackWriter;
+
+    struct FailsInside;
+
+    impl serde::Serialize for FailsInside {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            use serde::ser::{Error, SerializeMap};
+            let mut map = serializer.serialize_map(None)?;
+            map.serialize_entry("nested", &[[1]])?;
+            Err(S::Error::custom("broken"))
+        }
+    }
+
+    let mut out = CountingWriter::new();
+    let mut ser = serde_json::Serializer::pretty(RollbackWriter::new(&mut out));
+    let checkpoint = ser.checkpoint().unwrap();
+    assert!(ser.write_value(&FailsInside).is_err());
+    assert!(!ser.is_complete());
+    ser.rollback(checkpoint).unwrap();
+    assert!(ser.is_complete());
+
+    // The formatter's indentation was restored along with the bytes.
+    ser.write_value(&json!({"a": [1]})).unwrap();
+    ser.finish().unwrap();
+    assert_eq!(out.bytes, b"{\n  \"a\": [\n    1\n  ]\n}");
+    assert_eq!(out.flushes, 1);
+
+    // Nothing reaches the inner writer before a checkpoint or flush, and
+    // committed bytes cannot be rolled back.
+    let mut ser = serde_json::Serializer::new(RollbackWriter::new(Vec::new()));
+    let first = ser.checkpoint().unwrap();
+    ser.write_value(&1).unwrap();
+    let second = ser.checkpoint().unwrap();
+    ser.write_value(&2).unwrap();
+    assert!(ser.rollback(first).is_err());
+    ser.rollback(second).unwrap();
+    ser.write_value(&3).unwrap();
+    assert_eq!(ser.into_inner().into_inner(), b"1");
+}
+
+#[test]
+fn test_formatter_value_hooks() {
+    use serde_json::ser::{Formatter, ValueSeparator};
+
+    struct Tagged {
+        count: usize,
+    }
+
+    impl Formatter for Tagged {
+        fn begin_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+        where
+            W: ?Sized + io::Write,
+        {
+            self.count += 1;
+            write!(writer, "{}{}:", if first { "" } else { " " }, self.count)
+        }
+
+        fn end_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+        where
+            W: ?Sized + io::Write,
+        {
+            writer.write_all(b";")
+        }
+    }
+
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), Tagged { count: 0 });
+    ser.write_value(&[1, 2]).unwrap();
+    ser.write_value("x").unwrap();
+    assert_eq!(ser.into_inner(), br#"1:[1,2]; 2:"x";"#);
+
+    // The hooks run inside the separator chosen on the serializer.
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), Tagged { count: 0 });
+    ser.set_value_separator(ValueSeparator::RecordSeparator);
+    ser.write_value(&true).unwrap();
+    assert_eq!(ser.into_inner(), b"\x1e1:true;\n");
+
+    // Plain `serialize` does not call them.
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), Tagged { count: 0 });
+    serde::Serialize::serialize(&1, &mut ser).unwrap();
+    assert_eq!(ser.into_inner(), b"1");
+}
+
+#[test]
+fn test_to_string_truncated() {
+    use serde_json::{to_string_truncated, Value};
+
+    let value = json!({"name": "abcdefghij", "ids": [1, 2, 3, 4, 5, 6, 7, 8]});
+    let full = serde_json::to_string(&value).unwrap();
+    assert_eq!(to_string_truncated(&value, full.len()).unwrap(), full);
+
+    let value = json!({"name": "abcdefghijklmnopqrstuvwxyz", "ids": (0..40).collect::<Vec<_>>()});
+    let short = to_string_truncated(&value, 80).unwrap();
+    assert!(short.len() <= 80);
+    assert_eq!(
+        serde_json::from_str::<Value>(&short).unwrap(),
+        json!({
+            "ids": [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, "\u{2026}(+30 more)"],
+            "name": "abcdefghij\u{2026}(+16 more)",
+        }),
+    );
+
+    let wide: Value = ('a'..='j').map(|c| (c.to_string(), json!(1))).collect();
+    let short = to_string_truncated(&wide, 40).unwrap();
+    assert_eq!(
+        short,
+        "{\"a\":1,\"b\":1,\"c\":1,\"\u{2026}\":\"(+7 more)\"}"
+    );
+
+    // Multi-byte characters are never split.
+    let short = to_string_truncated(&json!("\u{e9}\u{e9}\u{e9}\u{e9}"), 8).unwrap();
+    assert!(short.len() <= 8);
+    assert_eq!(short, "\"\u{e9}\u{e9}\u{2026}");
+
+    assert_eq!(to_string_truncated(&json!([[[[]]]]), 2).unwrap(), "");
+}
+
+#[test]
+fn test_to_log_string() {
+    use serde_json::{to_log_string, LogOptions, Value};
+
+    let event = json!({
+        "user": {"name": "ana", "Token": "abc", "prefs": {"lang": "pt"}},
+        "items": [{"name": "x", "token": "def"}],
+        "debug": "a\nb\u{2029}c",
+    });
+
+    let options = LogOptions::new().redact(&["token"]);
+    let line = to_log_string(&event, &options).unwrap();
+    assert!(!line.contains('\n') && !line.contains('\u{2029}'));
+    assert_eq!(
+        serde_json::from_str::<Value>(&line).unwrap(),
+        json!({
+            "user": {"name": "ana", "Token": "[REDACTED]", "prefs": {"lang": "pt"}},
+            "items": [{"name": "x", "token": "[REDACTED]"}],
+            "debug": "a\nb\u{2029}c",
+        }),
+    );
+
+    let options = LogOptions::new()
+        .allow(&["user", "name", "items"])
+        .redact(&["name"]);
+    assert_eq!(
+        to_log_string(&event, &options).unwrap(),
+        r#"{"items":[{"name":"[REDACTED]"}],"user":{"name":"[REDACTED]"}}"#,
+    );
+
+    let options = LogOptions::new().max_len(30);
+    let line = to_log_string(&json!({"blob": "x".repeat(100)}), &options).unwrap();
+    assert!(line.len() <= 30);
+    assert!(line.ends_with("more)\"}"));
+}
+
+#[test]
+fn test_string_and_key_limits() {
+    use serde::Serialize;
+    use serde_json::ser::LengthOverflow;
+    use serde_json::Serializer;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    enum Kind {
+        LongVariantName(u8),
+    }
+
+    let value = json!({"name": "\u{e9}t\u{e9}", "description": "ok"});
+
+    let mut ser = Serializer::new(Vec::new());
+    ser.set_max_key_len(8);
+    let err = value.serialize(&mut ser).unwrap_err();
+    assert!(err.is_limit());
+    assert_eq!(err.to_string(), "key length limit exceeded");
+
+    let mut ser = Serializer::new(Vec::new());
+    ser.set_max_string_len(4);
+    let err = value.serialize(&mut ser).unwrap_err();
+    assert_eq!(err.to_string(), "string length limit exceeded");
+
+    // Keys are only subject to the key limit.
+    let mut ser = Serializer::new(Vec::new());
+    ser.set_max_string_len(5);
+    value.serialize(&mut ser).unwrap();
+
+    // Truncation never splits a character.
+    let mut ser = Serializer::new(Vec::new());
+    ser.set_max_string_len(4);
+    ser.set_max_key_len(4);
+    ser.set_length_overflow(LengthOverflow::Truncate);
+    value.serialize(&mut ser).unwrap();
+    assert_eq!(
+        ser.into_inner(),
+        "{\"desc\":\"ok\",\"name\":\"\u{e9}t\"}".as_bytes()
+    );
+
+    let mut ser = Serializer::new(Vec::new());
+    ser.set_max_key_len(4);
+    assert!(Kind::LongVariantName(1)
+        .serialize(&mut ser)
+        .unwrap_err()
+        .is_limit());
+
+    // Keys that collide after truncation are still caught.
+    let mut map = BTreeMap::new();
+    map.insert("abcd1", 1);
+    map.insert("abcd2", 2);
+    let mut ser = Serializer::new(Vec::new());
+    ser.set_max_key_len(4);
+    ser.set_length_overflow(LengthOverflow::Truncate);
+    ser.set_reject_duplicate_keys(true);
+    assert_eq!(
+        map.serialize(&mut ser).unwrap_err().to_string(),
+        r#"duplicate key "abcd""#,
+    );
+}
+
+#[test]
+fn test_pretty_indent_level() {
+    use serde::Serialize;
+    use serde_json::ser::{PrettyFormatter, Serializer};
+
+    fn pretty(value: &serde_json::Value, level: usize) -> String {
+        let mut out = Vec::new();
+        let formatter = PrettyFormatter::with_indent(b"\t").indent_level(level);
+        value
+            .serialize(&mut Serializer::with_formatter(&mut out, formatter))
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    let value = json!({"a": [1, {}], "b": []});
+    assert_eq!(
+        pretty(&value, 2),
+        "{\n\t\t\t\"a\": [\n\t\t\t\t1,\n\t\t\t\t{}\n\t\t\t],\n\t\t\t\"b\": []\n\t\t}",
+    );
+    assert_eq!(
+        pretty(&value, 0),
+        serde_json::to_string_pretty(&value)
+            .unwrap()
+            .replace("  ", "\t")
+    );
+    assert_eq!(pretty(&json!([]), 3), "[]");
+    assert_eq!(pretty(&json!("x"), 3), "\"x\"");
+}
+
+#[test]
+fn test_pretty_with_indent() {
+    let value = json!({"a": [1, {"b": null}], "c": {}});
+    for indent in ["  ", "\t", "    ", ""] {
+        let expected = serde_json::to_string_pretty(&value)
+            .unwrap()
+            .replace("  ", indent);
+        let string = serde_json::to_string_pretty_with_indent(&value, indent).unwrap();
+        assert_eq!(string, expected);
+        let vec = serde_json::to_vec_pretty_with_indent(&value, indent).unwrap();
+        assert_eq!(vec, expected.as_bytes());
+    }
+}
+
+#[test]
+fn test_pretty_owned_indent() {
+    use serde::Serialize;
+    use serde_json::ser::{PrettyFormatter, Serializer};
+
+    fn formatter(indent: &str) -> PrettyFormatter<'static> {
+        PrettyFormatter::with_indent_owned(indent.to_owned()).indent_level(1)
+    }
+
+    let formatter = formatter("\t");
+    let value = json!({"a": [true]});
+    for _ in 0..2 {
+        let mut out = Vec::new();
+        let mut ser = Serializer::with_formatter(&mut out, formatter.clone());
+        value.serialize(&mut ser).unwrap();
+        assert_eq!(out, b"{\n\t\t\"a\": [\n\t\t\ttrue\n\t\t]\n\t}");
+    }
+}
+
+#[test]
+fn test_patch_str() {
+    let source = "{\n\t\"id\": 7,\n\t\"tags\": [\n\t\t\"a\",\n\t\t\"b\"\n\t],\n\t\"n\": 1.50\n}\n";
+    let patched = serde_json::patch_str(
+        source,
+        &[("/tags", json!(["x", {"k": 1}])), ("/id", json!(8))],
+    )
+    .unwrap();
+    assert_eq!(
+        patched,
+        "{\n\t\"id\": 8,\n\t\"tags\": [\n\t\t\"x\",\n\t\t{\n\t\t\t\"k\": 1\n\t\t}\n\t],\n\t\"n\": 1.50\n}\n"
+    );
+
+    // Không có sửa đổi nào: văn bản giữ nguyên từng byte.
+    let no_edits: &[(&str, serde_json::Value)] = &[];
+    assert_eq!(serde_json::patch_str(source, no_edits).unwrap(), source);
+
+    // Thay cả tài liệu.
+    assert_eq!(
+        serde_json::patch_str(" [1] ", &[("", json!(null))]).unwrap(),
+        " null "
+    );
+
+    let err = serde_json::patch_str(source, &[("/missing", json!(1))]).unwrap_err();
+    assert_eq!(err.to_string(), "no value at pointer `/missing`");
+    let err =
+        serde_json::patch_str(source, &[("/tags/0", json!(1)), ("/tags", json!(2))]).unwrap_err();
+    assert_eq!(err.to_string(), "edits at `/tags` and `/tags/0` overlap");
+    assert!(serde_json::patch_str("{", &[("", json!(1))])
+        .unwrap_err()
+        .is_eof());
+}
+
+#[test]
+fn test_escape_solidus() {
+    use serde::Serialize;
+    use serde_json::ser::{CharEscape, CompactFormatter, Formatter};
+
+    let value = json!({"a/b": ["/", "x/y/z", "\"/\n"]});
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"{"a/b":["/","x/y/z","\"/\n"]}"#
+    );
+
+    let mut ser = serde_json::Serializer::new(Vec::new());
+    ser.set_escape_solidus(true);
+    value.serialize(&mut ser).unwrap();
+    assert_eq!(
+        String::from_utf8(ser.into_inner()).unwrap(),
+        r#"{"a\/b":["\/","x\/y\/z","\"\/\n"]}"#
+    );
+
+    // collect_str đi qua cùng đường escape.
+    let mut ser = serde_json::Serializer::new(Vec::new());
+    ser.set_escape_solidus(true);
+    serde::Serializer::collect_str(&mut ser, &format_args!("{}/{}", 1, 2)).unwrap();
+    assert_eq!(ser.into_inner(), br#""1\/2""#);
+
+    // Formatter tự viết quyết định cách viết escape.
+    struct UnicodeSolidus;
+
+    impl Formatter for UnicodeSolidus {
+        fn write_char_escape<W>(
+            &mut self,
+            writer: &mut W,
+            char_escape: CharEscape,
+        ) -> io::Result<()>
+        where
+            W: ?Sized + io::Write,
+        {
+            match char_escape {
+                CharEscape::Solidus => writer.write_all(b"\\u002f"),
+                other => CompactFormatter.write_char_escape(writer, other),
+            }
+        }
+    }
+
+    let mut ser = serde_json::Serializer::with_formatter(Vec::new(), UnicodeSolidus);
+    ser.set_escape_solidus(true);
+    "</p>\t".serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_inner(), br#""<\u002fp>\t""#);
+
+    // Chuỗi dài toàn `/` phải được escape trong thời gian tuyến tính.
+    let long = "/".repeat(200_000) + "\"" + &"a/".repeat(100_000);
+    let mut ser = serde_json::Serializer::new(Vec::new());
+    ser.set_escape_solidus(true);
+    long.serialize(&mut ser).unwrap();
+    let expected = format!("\"{}\\\"{}\"", "\\/".repeat(200_000), "a\\/".repeat(100_000));
+    assert_eq!(String::from_utf8(ser.into_inner()).unwrap(), expected);
+}
+
+#[test]
+fn test_non_string_keys() {
+    use serde::Serialize;
+    use serde_json::ser::NonStringKeys;
+    use std::collections::BTreeMap;
+
+    fn to_string<T: Serialize>(value: &T, keys: NonStringKeys) -> serde_json::Result<String> {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_non_string_keys(keys);
+        value.serialize(&mut ser)?;
+        Ok(String::from_utf8(ser.into_inner()).unwrap())
+    }
+
+    let ints: BTreeMap<i64, u8> = [(-1, 1), (2, 2)].into_iter().collect();
+    let wide: BTreeMap<u128, u8> = [(u128::MAX, 0)].into_iter().collect();
+    let chars: BTreeMap<char, u8> = [('x', 0)].into_iter().collect();
+    let strings: BTreeMap<&str, u8> = [("k", 0)].into_iter().collect();
+
+    assert_eq!(
+        to_string(&ints, NonStringKeys::Quote).unwrap(),
+        r#"{"-1":1,"2":2}"#
+    );
+    assert_eq!(
+        to_string(&ints, NonStringKeys::Unquoted).unwrap(),
+        "{-1:1,2:2}"
+    );
+    assert_eq!(
+        to_string(&wide, NonStringKeys::Unquoted).unwrap(),
+        "{340282366920938463463374607431768211455:0}"
+    );
+    assert_eq!(
+        to_string(&chars, NonStringKeys::Unquoted).unwrap(),
+        r#"{"x":0}"#
+    );
+
+    let errors = [
+        to_string(&ints, NonStringKeys::Reject).unwrap_err(),
+        to_string(&wide, NonStringKeys::Reject).unwrap_err(),
+        to_string(&chars, NonStringKeys::Reject).unwrap_err(),
+    ];
+    for err in errors {
+        assert_eq!(err.to_string(), "key must be a string");
+    }
+    assert_eq!(
+        to_string(&strings, NonStringKeys::Reject).unwrap(),
+        r#"{"k":0}"#
+    );
+}
+
+#[test]
+fn test_non_string_keys_round_trip() {
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+
+    fn round_trip<T>(value: &T, expected: &str)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let text = serde_json::to_string(value).unwrap();
+        assert_eq!(text, expected);
+        assert_eq!(serde_json::from_str::<T>(&text).unwrap(), *value);
+        assert_eq!(
+            serde_json::from_reader::<_, T>(text.as_bytes()).unwrap(),
+            *value
+        );
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(serde_json::from_value::<T>(json).unwrap(), *value);
+    }
+
+    let unsigned: BTreeMap<u64, u8> = [(0, 0), (u64::MAX, 1)].into_iter().collect();
+    round_trip(&unsigned, r#"{"0":0,"18446744073709551615":1}"#);
+    let signed: BTreeMap<i8, u8> = [(-128, 0), (127, 1)].into_iter().collect();
+    round_trip(&signed, r#"{"-128":0,"127":1}"#);
+    let bools: BTreeMap<bool, u8> = [(false, 0), (true, 1)].into_iter().collect();
+    round_trip(&bools, r#"{"false":0,"true":1}"#);
+    let chars: BTreeMap<char, u8> = [('a', 0), ('é', 1)].into_iter().collect();
+    round_trip(&chars, r#"{"a":0,"é":1}"#);
+
+    let err = serde_json::from_str::<BTreeMap<u8, u8>>(r#"{"256":0}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"256\", expected u8 at line 1 column 6"
+    );
+    let err = serde_json::from_str::<BTreeMap<bool, u8>>(r#"{"yes":0}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"yes\", expected a boolean at line 1 column 6"
+    );
+}
+
+#[test]
+fn test_fixed_buffer_writer() {
+    // Chỉ dùng `serde_json::io`, nên cũng biên dịch được khi không có std.
+    struct FixedBuf {
+        buf: [u8; 8],
+        len: usize,
+    }
+
+    impl serde_json::io::Write for FixedBuf {
+        fn write(&mut self, buf: &[u8]) -> serde_json::io::Result<usize> {
+            let n = buf.len().min(self.buf.len() - self.len);
+            if n == 0 && !buf.is_empty() {
+                return Err(serde_json::io::Error::new(
+                    serde_json::io::ErrorKind::WriteZero,
+                    "buffer full",
+                ));
+            }
+            self.buf[self.len..self.len + n].copy_from_slice(&buf[..n]);
+            self.len += n;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> serde_json::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut out = FixedBuf { buf: [0; 8], len: 0 };
+    serde_json::to_writer(&mut out, &[1, 2]).unwrap();
+    assert_eq!(&out.buf[..out.len], b"[1,2]");
+
+    let mut out = FixedBuf { buf: [0; 8], len: 0 };
+    let err = serde_json::to_writer(&mut out, &[1, 2, 3, 4]).unwrap_err();
+    assert!(err.is_io());
+    assert_eq!(err.to_string(), "buffer full");
+    assert_eq!(&out.buf[..out.len], b"[1,2,3,4");
+}
@@ -26,6 +26,14 @@ macro_rules! test_stream {
             assert_eq!($stream.byte_offset(), 0);
             $test
         }
+        {
+            // A tiny buffer so that values straddle refills.
+            let reader = std::io::BufReader::with_capacity(3, $data.as_bytes());
+            let de = Deserializer::from_buf_read(reader);
+            let mut $stream = de.into_iter::<$ty>();
+            assert_eq!($stream.byte_offset(), 0);
+            $test
+        }
     };
 }
 
@@ -181,3 +189,711 @@ fn test_error() {
         assert!(stream.next().is_none());
     });
 }
+
+#[test]
+fn test_deserializer_byte_offset() {
+    use serde::Deserialize;
+
+    let data = "12 [true] \"x\"";
+
+    macro_rules! check {
+        ($de:expr) => {{
+            let mut de = $de;
+            assert_eq!(de.byte_offset(), 0);
+            assert_eq!(u8::deserialize(&mut de).unwrap(), 12);
+            assert_eq!(de.byte_offset(), 2);
+            assert_eq!(Value::deserialize(&mut de).unwrap(), json!([true]));
+            assert_eq!(de.byte_offset(), 9);
+            assert_eq!(String::deserialize(&mut de).unwrap(), "x");
+            assert_eq!(de.byte_offset(), data.len());
+        }};
+    }
+
+    check!(Deserializer::from_str(data));
+    check!(Deserializer::from_slice(data.as_bytes()));
+    check!(Deserializer::from_reader(data.as_bytes()));
+}
+
+#[test]
+fn test_json_stream_ranges() {
+    let data = " {\"x\":1}\n2 \"three\"[4] ";
+
+    test_stream!(data, Value, |stream| {
+        let mut ranges = Vec::new();
+        while let Some(result) = stream.next_with_range() {
+            let (value, range) = result.unwrap();
+            let raw = &data[range.clone()];
+            assert_eq!(value, serde_json::from_str::<Value>(raw).unwrap());
+            ranges.push(range);
+        }
+        assert_eq!(ranges, [1..8, 9..10, 11..18, 18..21]);
+    });
+}
+
+#[test]
+fn test_json_stream_remaining_input() {
+    let data = b"{\"a\":1} [2] 3 {\"bad\" 4";
+
+    let mut stream = Deserializer::from_slice(data).into_iter::<Value>();
+    assert_eq!(stream.remaining_input(), Some(&data[..]));
+    assert_eq!(stream.size_hint(), (0, Some(data.len())));
+
+    assert_eq!(stream.next().unwrap().unwrap(), json!({"a": 1}));
+    assert_eq!(stream.remaining_input(), Some(&data[7..]));
+    assert_eq!(stream.next().unwrap().unwrap(), json!([2]));
+    assert_eq!(stream.next().unwrap().unwrap(), json!(3));
+    assert_eq!(stream.remaining_input(), Some(&b" {\"bad\" 4"[..]));
+
+    // A failed value is not consumed, so ingestion can resume from the same
+    // point once the input is fixed.
+    assert!(stream.next().unwrap().is_err());
+    assert_eq!(stream.remaining_input(), Some(&b"{\"bad\" 4"[..]));
+    assert!(stream.next().is_none());
+
+    let mut stream = Deserializer::from_str("1 2").into_iter::<Value>();
+    stream.next();
+    assert_eq!(stream.remaining_input(), Some(&b" 2"[..]));
+    assert_eq!(stream.size_hint(), (0, Some(2)));
+
+    let mut reader = &b"1 2"[..];
+    let mut stream = Deserializer::from_reader(&mut reader).into_iter::<Value>();
+    stream.next();
+    assert_eq!(stream.remaining_input(), None);
+    assert_eq!(stream.size_hint(), (0, None));
+    stream.next();
+    assert_eq!(stream.size_hint(), (0, None));
+}
+
+#[test]
+fn test_from_buf_read() {
+    use std::io::BufReader;
+
+    let data =
+        "{\"key with spaces\": \"esc\\\"aped \\u00e9 \\ud83d\\ude00 tail\",\n \"n\": [1, 2.5, -3]}";
+    let expected: Value = serde_json::from_str(data).unwrap();
+    for capacity in 1..8 {
+        let reader = BufReader::with_capacity(capacity, data.as_bytes());
+        let value: Value = serde_json::from_buf_read(reader).unwrap();
+        assert_eq!(value, expected);
+
+        let reader = BufReader::with_capacity(capacity, data.as_bytes());
+        serde_json::from_buf_read::<_, serde::de::IgnoredAny>(reader).unwrap();
+    }
+
+    let bad = "[\"ok\",\n  \"bad \u{1}\"]";
+    let from_str = serde_json::from_str::<Value>(bad).unwrap_err();
+    let from_reader = serde_json::from_reader::<_, Value>(bad.as_bytes()).unwrap_err();
+    let from_buf_read =
+        serde_json::from_buf_read::<_, Value>(BufReader::with_capacity(2, bad.as_bytes()))
+            .unwrap_err();
+    assert_eq!(from_buf_read.to_string(), from_reader.to_string());
+    assert_eq!(from_buf_read.line(), from_str.line());
+    assert_eq!(from_buf_read.column(), from_str.column());
+}
+
+#[test]
+fn test_line_deserializer() {
+    use serde_json::de::LineDeserializer;
+
+    let data = b"{\"a\": 1}\r\n\n  \n[1,\n2]\n\"x\" \"y\"\n{\"a\":\n{\"a\": 3}";
+    let mut lines = LineDeserializer::<_, Value>::new(&data[..]);
+
+    assert_eq!(lines.next().unwrap().unwrap(), json!({"a": 1}));
+    assert_eq!(lines.byte_offset(), 10);
+
+    // A value split across lines is an error on each of its lines.
+    let err = lines.next().unwrap().unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.line(), 4);
+    assert!(lines.next().unwrap().is_err());
+
+    let err = lines.next().unwrap().unwrap_err();
+    assert!(err.is_syntax());
+    assert_eq!((err.line(), err.column()), (6, 5));
+
+    assert!(lines.next().unwrap().is_err());
+    assert_eq!(lines.next().unwrap().unwrap(), json!({"a": 3}));
+    assert!(lines.next().is_none());
+    assert_eq!(lines.byte_offset(), data.len());
+}
+
+#[test]
+fn test_line_deserializer_options() {
+    use serde_json::de::{DeserializerBuilder, DuplicateKeys, Profile};
+
+    let data = b"// header\n{a: 1,}\n{\"a\": 1, \"a\": 2}\n[[[1]]]\n";
+    let mut lines = DeserializerBuilder::new()
+        .profile(Profile::Jsonc)
+        .unquoted_keys(true)
+        .duplicate_keys(DuplicateKeys::Reject)
+        .recursion_limit(2)
+        .build_lines::<_, Value>(&data[..]);
+    lines.set_document("events.ndjson");
+
+    // A line holding only a comment has no value.
+    assert!(lines.next().unwrap().unwrap_err().is_eof());
+    assert_eq!(lines.next().unwrap().unwrap(), json!({"a": 1}));
+
+    let err = lines.next().unwrap().unwrap_err();
+    assert_eq!(err.line(), 3);
+    assert_eq!(err.document(), Some("events.ndjson"));
+
+    let err = lines.next().unwrap().unwrap_err();
+    assert!(err.is_limit());
+    assert_eq!(err.line(), 4);
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn test_skip_value() {
+    use serde::Deserialize;
+
+    let data = "  null [1, {\"a\": [\"b\\\"]\"]}] \"s\\u00e9\" -1.5e3 {} true";
+    let mut de = Deserializer::from_str(data);
+    for _ in 0..5 {
+        de.skip_value().unwrap();
+    }
+    assert!(bool::deserialize(&mut de).unwrap());
+    de.end().unwrap();
+    assert!(de.skip_value().unwrap_err().is_eof());
+
+    let mut de = Deserializer::from_reader(&b"[1, 2} 3"[..]);
+    let err = de.skip_value().unwrap_err();
+    assert!(err.is_syntax());
+    assert_eq!((err.line(), err.column()), (1, 6));
+
+    // The same works through `io::Read`.
+    let mut de = Deserializer::from_reader(&b"{\"skip\": [1]} {\"keep\": 2}"[..]);
+    de.skip_value().unwrap();
+    assert_eq!(Value::deserialize(&mut de).unwrap(), json!({"keep": 2}));
+}
+
+#[test]
+fn test_from_slice_in() {
+    let mut scratch = [0; 64];
+    let value: Value = serde_json::from_slice_in(br#"{"k\n": "v\u00e9"}"#, &mut scratch).unwrap();
+    assert_eq!(value, json!({"k\n": "vé"}));
+
+    let s: &str = serde_json::from_slice_in(br#""plain""#, &mut scratch).unwrap();
+    assert_eq!(s, "plain");
+
+    let err = serde_json::from_slice_in::<Value>(b"[1] 2", &mut scratch).unwrap_err();
+    assert!(err.is_syntax());
+}
+
+#[test]
+fn test_from_slice_in_overflow() {
+    let mut scratch = [0; 4];
+    let value: Value = serde_json::from_slice_in(br#"["a\tb"]"#, &mut scratch).unwrap();
+    assert_eq!(value, json!(["a\tb"]));
+
+    let err = serde_json::from_slice_in::<Value>(br#"["abc\tde"]"#, &mut scratch).unwrap_err();
+    assert!(err.is_limit());
+    assert_eq!(
+        err.to_string(),
+        "scratch buffer too small at line 1 column 11"
+    );
+}
+
+#[test]
+fn test_next_value_position() {
+    use serde::Deserialize;
+    use serde_json::de::Position;
+
+    let data = "{\"a\": 1}\n\t[true,\n  \"x\"]   ";
+    let expected = [
+        Position {
+            line: 1,
+            column: 0,
+            byte_offset: 0,
+        },
+        Position {
+            line: 2,
+            column: 1,
+            byte_offset: 10,
+        },
+    ];
+
+    let mut de = Deserializer::from_str(data);
+    for position in &expected {
+        assert_eq!(de.next_value_position().unwrap(), *position);
+        Value::deserialize(&mut de).unwrap();
+    }
+    assert!(de.next_value_position().unwrap_err().is_eof());
+
+    let mut de = Deserializer::from_reader(data.as_bytes());
+    for position in &expected {
+        assert_eq!(de.next_value_position().unwrap(), *position);
+        Value::deserialize(&mut de).unwrap();
+    }
+    assert!(de.next_value_position().unwrap_err().is_eof());
+}
+
+#[test]
+fn test_end_trailing_ok() {
+    use serde::Deserialize;
+
+    let data = "[1, 2] \n x";
+    let mut de = Deserializer::from_reader(data.as_bytes());
+    Value::deserialize(&mut de).unwrap();
+    let rest = de.end_trailing_ok().unwrap().unwrap();
+    assert_eq!((rest.line, rest.column, rest.byte_offset), (2, 1, 9));
+    let err = de.end_strict().unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 2));
+
+    let mut de = Deserializer::from_str("true \n ");
+    bool::deserialize(&mut de).unwrap();
+    assert_eq!(de.end_trailing_ok().unwrap(), None);
+    de.end_strict().unwrap();
+}
+
+#[test]
+fn test_next_str() {
+    use serde_json::de::Reference;
+
+    let data = r#" "plain" "esc\u00e9" "😀" "\udc00" 1"#;
+    let mut de = Deserializer::from_str(data);
+    assert!(matches!(
+        de.next_str().unwrap(),
+        Reference::Borrowed("plain")
+    ));
+    assert!(matches!(
+        de.next_str().unwrap(),
+        Reference::Copied("esc\u{e9}")
+    ));
+    assert!(matches!(
+        de.next_str().unwrap(),
+        Reference::Borrowed("\u{1f600}")
+    ));
+    assert_eq!(&*de.next_bytes_raw().unwrap(), b"\xED\xB0\x80");
+    assert!(de.next_bytes_raw().unwrap_err().is_data());
+    assert!(de.next_str().unwrap_err().is_eof());
+
+    let err = Deserializer::from_str(r#""\udc00""#)
+        .next_str()
+        .unwrap_err();
+    assert!(err.is_syntax());
+
+    // Invalid UTF-8 is only accepted as bytes.
+    let data = b"\"a\xff\"";
+    assert!(Deserializer::from_slice(data).next_str().is_err());
+    let mut de = Deserializer::from_slice(data);
+    assert_eq!(&*de.next_bytes_raw().unwrap(), b"a\xff");
+
+    // The same works through `io::Read`, where nothing can be borrowed.
+    let mut de = Deserializer::from_reader(&b"\"abc\" \"d\""[..]);
+    assert!(matches!(de.next_str().unwrap(), Reference::Copied("abc")));
+    assert!(matches!(
+        de.next_bytes_raw().unwrap(),
+        Reference::Copied(b"d")
+    ));
+}
+
+#[test]
+fn test_deserializer_builder() {
+    use serde::Deserialize;
+    use serde_json::de::{DeserializerBuilder, DuplicateKeys, Profile, Utf8Policy};
+
+    assert_eq!(Deserializer::builder(), DeserializerBuilder::new());
+    let builder = DeserializerBuilder::new()
+        .profile(Profile::Jsonc)
+        .duplicate_keys(DuplicateKeys::KeepFirst)
+        .utf8_policy(Utf8Policy::Replace);
+    let input: &[u8] = b"{\"a\": \"caf\xE9\", // x\n \"a\": 2, \"b\": [1,],}";
+    let expected = json!({"a": "caf\u{FFFD}", "b": [1]});
+
+    let mut de = builder.build_slice(input);
+    assert_eq!(Value::deserialize(&mut de).unwrap(), expected);
+    let mut de = builder.build_reader(input);
+    assert_eq!(Value::deserialize(&mut de).unwrap(), expected);
+    let mut de = builder.build_buf_read(std::io::BufReader::with_capacity(3, input));
+    assert_eq!(Value::deserialize(&mut de).unwrap(), expected);
+    let mut de = builder.build_str("{\"a\": 1, \"a\": 2}");
+    assert_eq!(Value::deserialize(&mut de).unwrap(), json!({"a": 1}));
+
+    // Profile chỉ đổi các cờ phương ngữ.
+    let builder = DeserializerBuilder::new()
+        .recursion_limit(2)
+        .profile(Profile::Json5);
+    let mut de = builder.build_str("[[1]]");
+    assert!(Value::deserialize(&mut de).unwrap_err().is_limit());
+}
+
+#[test]
+fn test_duplicate_keys() {
+    use serde::Deserialize;
+    use serde_json::de::DuplicateKeys;
+    use std::collections::BTreeMap;
+
+    #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+    struct S {
+        a: u8,
+        b: u8,
+    }
+
+    let j = r#"{"a": 1, "b": {"x": [1, {"a": 0}]}, "a": 2, "b": 3}"#;
+    let mut de = Deserializer::from_str(j);
+    de.set_duplicate_keys(DuplicateKeys::KeepFirst);
+    assert_eq!(
+        Value::deserialize(&mut de).unwrap(),
+        json!({"a": 1, "b": {"x": [1, {"a": 0}]}}),
+    );
+
+    let j = r#"{"b": 2, "a": 1, "b": 3}"#;
+    let mut de = Deserializer::from_str(j);
+    de.set_duplicate_keys(DuplicateKeys::KeepFirst);
+    assert_eq!(S::deserialize(&mut de).unwrap(), S { a: 1, b: 2 });
+
+    // Mặc định struct báo trường lặp, còn map giữ giá trị sau cùng.
+    assert!(serde_json::from_str::<S>(j).is_err());
+    let map: BTreeMap<String, u8> = serde_json::from_str(j).unwrap();
+    assert_eq!(map["b"], 3);
+
+    // Khoá số nguyên vẫn được đọc từ chuỗi.
+    let mut de = Deserializer::from_str(r#"{"1": "x", "2": "y", "1": "z"}"#);
+    de.set_duplicate_keys(DuplicateKeys::Reject);
+    assert_eq!(
+        BTreeMap::<u8, String>::deserialize(&mut de)
+            .unwrap_err()
+            .to_string(),
+        "duplicate key `1` at line 1 column 24",
+    );
+
+    // Khoá giống nhau ở các object khác nhau không phải là lặp.
+    let mut de = Deserializer::from_str(r#"[{"a": 1}, {"a": {"a": 2}}]"#);
+    de.set_duplicate_keys(DuplicateKeys::Reject);
+    Value::deserialize(&mut de).unwrap();
+}
+
+#[test]
+fn test_max_string_len() {
+    use serde::Deserialize;
+    use serde_json::de::DeserializerBuilder;
+
+    let j = r#"{"key": "value", "k": "\n\n\n\n\n\n"}"#;
+    for len in 0..8 {
+        let builder = DeserializerBuilder::new().max_string_len(len);
+        let ok = len >= 6;
+        let mut de = builder.build_str(j);
+        assert_eq!(Value::deserialize(&mut de).is_ok(), ok, "{}", len);
+        let mut de = builder.build_slice(j.as_bytes());
+        assert_eq!(Value::deserialize(&mut de).is_ok(), ok, "{}", len);
+        let mut de = builder.build_reader(j.as_bytes());
+        assert_eq!(Value::deserialize(&mut de).is_ok(), ok, "{}", len);
+        let mut de = builder.build_buf_read(j.as_bytes());
+        assert_eq!(Value::deserialize(&mut de).is_ok(), ok, "{}", len);
+    }
+
+    let mut de = Deserializer::from_str(r#"["abcd", "abcde"]"#);
+    de.set_max_string_len(4);
+    let err = Value::deserialize(&mut de).unwrap_err();
+    assert!(err.is_limit());
+    assert_eq!(
+        err.to_string(),
+        "string length limit exceeded at line 1 column 16"
+    );
+
+    // Chuỗi bị bỏ qua không bị giới hạn.
+    let mut de = Deserializer::from_str(r#"["abcdefgh"]"#);
+    de.set_max_string_len(4);
+    serde::de::IgnoredAny::deserialize(&mut de).unwrap();
+}
+
+#[test]
+fn test_spanned() {
+    use serde::Deserialize;
+    use serde_json::de::Position;
+    use serde_json::Spanned;
+
+    #[derive(Deserialize)]
+    struct Server {
+        host: Spanned<String>,
+        ports: Spanned<Vec<Spanned<u16>>>,
+        weight: Spanned<f64>,
+    }
+
+    fn check(server: &Server, j: &str) {
+        assert_eq!(&j[server.host.span()], r#""a\u0062c""#);
+        assert_eq!(server.host.get_ref(), "abc");
+        assert_eq!(
+            server.host.start(),
+            Position {
+                line: 2,
+                column: 10,
+                byte_offset: 12,
+            }
+        );
+        assert_eq!(&j[server.ports.span()], "[\n    80,\n    443\n  ]");
+        let ports = server.ports.get_ref();
+        assert_eq!(*ports[0].get_ref(), 80);
+        assert_eq!(&j[ports[0].span()], "80");
+        assert_eq!(
+            ports[0].end(),
+            Position {
+                line: 4,
+                column: 6,
+                byte_offset: 43,
+            }
+        );
+        assert_eq!(
+            ports[1].end(),
+            Position {
+                line: 5,
+                column: 7,
+                byte_offset: 52,
+            }
+        );
+        assert_eq!(
+            server.ports.end(),
+            Position {
+                line: 6,
+                column: 3,
+                byte_offset: 56,
+            }
+        );
+        assert_eq!(&j[server.weight.span()], "1.5");
+        assert_eq!(server.weight.end().column, 15);
+    }
+
+    let j =
+        "{\n  \"host\": \"a\\u0062c\",\n  \"ports\": [\n    80,\n    443\n  ],\n  \"weight\": 1.5}";
+    check(&serde_json::from_str(j).unwrap(), j);
+    check(&serde_json::from_slice(j.as_bytes()).unwrap(), j);
+    check(&serde_json::from_reader(j.as_bytes()).unwrap(), j);
+    check(&serde_json::from_buf_read(j.as_bytes()).unwrap(), j);
+
+    // Giá trị ở cuối đầu vào.
+    let n: Spanned<u8> = serde_json::from_reader(&b" 7"[..]).unwrap();
+    assert_eq!(n.span(), 1..2);
+
+    // Vị trí không tham gia so sánh và không xuất hiện khi mã hóa.
+    let a: Spanned<u8> = serde_json::from_str("7").unwrap();
+    let b: Spanned<u8> = serde_json::from_str("  7").unwrap();
+    assert_eq!(a, b);
+    assert_eq!(serde_json::to_string(&b).unwrap(), "7");
+
+    let err = serde_json::from_value::<Spanned<u8>>(json!(7)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `7`, expected a value deserialized by serde_json::Deserializer"
+    );
+    let err = serde_json::from_value::<Spanned<u8>>(json!({"a": 7})).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Spanned<T> can only be deserialized by serde_json::Deserializer"
+    );
+}
+
+#[test]
+fn test_value_with_spans() {
+    let j = "{\n  \"a/b\": [1, {\"x\": null}],\n  \"s\": \"q\\\"\" }";
+    let (value, spans) = serde_json::from_str_with_spans(j).unwrap();
+    assert_eq!(value, json!({"a/b": [1, {"x": null}], "s": "q\""}));
+    let got: Vec<(&str, &str)> = spans
+        .iter()
+        .map(|(pointer, range)| (pointer, &j[range]))
+        .collect();
+    assert_eq!(
+        got,
+        [
+            ("", j),
+            ("/a~1b", r#"[1, {"x": null}]"#),
+            ("/a~1b/0", "1"),
+            ("/a~1b/1", r#"{"x": null}"#),
+            ("/a~1b/1/x", "null"),
+            ("/s", r#""q\"""#),
+        ]
+    );
+    assert_eq!(spans.len(), 6);
+    assert_eq!(spans.get("/nope"), None);
+
+    let (_, from_slice) = serde_json::from_slice_with_spans(j.as_bytes()).unwrap();
+    assert_eq!(from_slice, spans);
+    let mut de = Deserializer::from_reader(j.as_bytes());
+    let (_, from_reader) = de.parse_value_with_spans().unwrap();
+    de.end().unwrap();
+    assert_eq!(from_reader, spans);
+
+    // Khoá lặp: giá trị sau thay thế giá trị trước, kể cả trong bảng.
+    let j = r#"{"k": [1, 2], "k": 3}"#;
+    let (value, spans) = serde_json::from_str_with_spans(j).unwrap();
+    assert_eq!(value, json!({"k": 3}));
+    assert_eq!(spans.get("/k"), Some(19..20));
+    assert_eq!(spans.get("/k/0"), None);
+    assert_eq!(spans.len(), 2);
+
+    assert!(serde_json::from_str_with_spans("[1,]").is_err());
+    assert!(serde_json::from_str_with_spans("[1] 2").is_err());
+}
+
+#[test]
+fn test_reparse_with_spans() {
+    // Mỗi bước: (khoảng byte bị thay, văn bản thay vào, pointer được phân
+    // tích lại).
+    let edits: &[(std::ops::Range<usize>, &str, &str)] = &[
+        (7..8, "2", "/a/0"),
+        (16..20, "\"x\"", "/a/1/b"),
+        (20..20, ", 3", "/a"),
+        (8..8, " ", "/a/0"),
+        (2..3, "A", ""),
+        (0..0, "  ", ""),
+        (34..38, "false", "/c"),
+        (13..23, "[]", "/A/1"),
+    ];
+    let mut text = String::from(r#"{"a": [1, {"b": null}], "c": true}"#);
+    let (mut value, mut spans) = serde_json::from_str_with_spans(&text).unwrap();
+    for (range, replacement, expected) in edits {
+        text.replace_range(range.clone(), replacement);
+        let pointer = spans
+            .reparse(&mut value, &text, range.clone(), replacement.len())
+            .unwrap();
+        assert_eq!(pointer, *expected, "{}", text);
+        let (full_value, full_spans) = serde_json::from_str_with_spans(&text).unwrap();
+        assert_eq!(value, full_value, "{}", text);
+        assert_eq!(spans, full_spans, "{}", text);
+    }
+    assert_eq!(text, r#"  {"A": [2 , [], 3], "c": false}"#);
+
+    // Văn bản không hợp lệ: lỗi và không có gì thay đổi.
+    let before = (value.clone(), spans.clone());
+    let broken = r#"  {"A": [2 , [], 3], "c": fals}"#;
+    assert!(spans.reparse(&mut value, broken, 34..35, 0).is_err());
+    assert_eq!((value, spans), before);
+
+    // Các nút nằm ngoài cây con được phân tích lại giữ nguyên khóa cũ, chỉ
+    // có khoảng byte bị dịch.
+    let keys = |spans: &serde_json::de::Spans| -> Vec<(String, *const u8)> {
+        spans.iter().map(|(p, _)| (p.to_owned(), p.as_ptr())).collect()
+    };
+    let mut text = String::from(r#"{"a": [1, 2, 3], "b": {"c": [4, 5]}, "d": 6}"#);
+    let (mut value, mut spans) = serde_json::from_str_with_spans(&text).unwrap();
+    let before = keys(&spans);
+    text.replace_range(7..8, "[10, 11]");
+    let pointer = spans.reparse(&mut value, &text, 7..8, 8).unwrap();
+    assert_eq!(pointer, "/a/0");
+    assert_eq!(spans.get("/a/0/1"), Some(12..14));
+    assert_eq!(spans.get("/d"), Some(49..50));
+    let after = keys(&spans);
+    for (pointer, key) in &before {
+        if pointer != "/a/0" {
+            assert!(after.contains(&(pointer.clone(), *key)), "{}", pointer);
+        }
+    }
+    let (full_value, full_spans) = serde_json::from_str_with_spans(&text).unwrap();
+    assert_eq!((value, spans), (full_value, full_spans));
+}
+
+#[test]
+fn test_reparse_keeps_recursion_limit() {
+    let text = format!("{}0{}", "[".repeat(127), "]".repeat(127));
+    let (mut value, mut spans) = serde_json::from_str_with_spans(&text).unwrap();
+
+    let edited = format!("{}1{}", "[".repeat(127), "]".repeat(127));
+    let pointer = spans.reparse(&mut value, &edited, 127..128, 1).unwrap();
+    assert_eq!(pointer.len(), 2 * 127);
+
+    // Lồng thêm một mảng vượt giới hạn của cả tài liệu, dù nút được sửa
+    // đứng riêng thì rất nông.
+    let deeper = format!("{}[1]{}", "[".repeat(127), "]".repeat(127));
+    let before = (value.clone(), spans.clone());
+    let err = spans.reparse(&mut value, &deeper, 127..128, 3).unwrap_err();
+    assert!(err.is_limit());
+    assert_eq!((value, spans), before);
+}
+
+#[test]
+fn test_minify_stream() {
+    let minify = |input: &str| {
+        let mut out = Vec::new();
+        serde_json::minify_stream(input.as_bytes(), &mut out)
+            .map(|()| String::from_utf8(out).unwrap())
+    };
+
+    let input = r#" { "a" : [ 1 , -0.5e+10 , 12345678901234567890123 ] ,
+        "b\"A" : { "c" : null , "d" : true , "e" : false } , "f" : "😀\n" } "#;
+    let out = minify(input).unwrap();
+    assert_eq!(
+        out,
+        r#"{"a":[1,-0.5e+10,12345678901234567890123],"b\"A":{"c":null,"d":true,"e":false},"f":"😀\n"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Value>(&out).unwrap(),
+        serde_json::from_str::<Value>(input).unwrap()
+    );
+
+    assert_eq!(minify(" 1 [] \n {} \"x\" ").unwrap(), "1\n[]\n{}\n\"x\"");
+    assert_eq!(minify("  ").unwrap(), "");
+    assert_eq!(minify("[[[[]]]]").unwrap(), "[[[[]]]]");
+
+    for bad in ["[1,]", "{\"a\" 1}", "[01]", "// x\n1", "nul", "[1"] {
+        assert!(minify(bad).is_err(), "{}", bad);
+    }
+
+    // Cú pháp mở rộng chỉ được nhận khi bật trên Deserializer.
+    let mut de = Deserializer::from_str("/* c */ {a: 'x', 'b': [1,],} // end");
+    de.set_comments(true);
+    de.set_trailing_commas(true);
+    de.set_single_quotes(true);
+    de.set_unquoted_keys(true);
+    let mut out = Vec::new();
+    de.minify_to(&mut out).unwrap();
+    assert_eq!(out, br#"{"a":"x","b":[1]}"#);
+}
+
+#[test]
+fn test_deserialize_buffered() {
+    use serde::Deserialize;
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Ping<'a> {
+        id: u64,
+        #[serde(borrow)]
+        from: Cow<'a, str>,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Kind {
+        Up,
+        Down(u8),
+    }
+
+    let input = r#"{"id": 7, "from": "a\"b"} {"id": 1, "from": "x"} [1, 2.5] {"3": true} {"Down": 2}"#;
+    let mut de = Deserializer::from_str(input);
+
+    let ping = de
+        .deserialize_buffered(|buffered| {
+            assert!(Vec::<u64>::deserialize(buffered).is_err());
+            assert!(u64::deserialize(buffered).is_err());
+            Ping::deserialize(buffered)
+        })
+        .unwrap();
+    assert_eq!(ping.id, 7);
+    assert_eq!(ping.from, "a\"b");
+
+    let ping = de.deserialize_buffered(|buffered| Ping::deserialize(buffered)).unwrap();
+    assert!(matches!(ping.from, Cow::Borrowed("x")));
+
+    let pair: (u8, f64) = de.deserialize_buffered(|buffered| Deserialize::deserialize(buffered)).unwrap();
+    assert_eq!(pair, (1, 2.5));
+
+    let map: BTreeMap<u32, bool> = de.deserialize_buffered(|buffered| Deserialize::deserialize(buffered)).unwrap();
+    assert_eq!(map, BTreeMap::from([(3, true)]));
+
+    let kind: Kind = de.deserialize_buffered(|buffered| Deserialize::deserialize(buffered)).unwrap();
+    assert_eq!(kind, Kind::Down(2));
+    de.end().unwrap();
+
+    let mut de = Deserializer::from_str(r#"{"id": 1, "from": 2}"#);
+    let err = de.deserialize_buffered(|buffered| Ping::deserialize(buffered)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `2`, expected a string at line 1 column 20",
+    );
+    assert_eq!(err.path(), Some("/from"));
+
+    let mut de = Deserializer::from_reader(br#"{"up": "Up"}"#.as_slice());
+    let map: BTreeMap<String, Kind> = de.deserialize_buffered(|buffered| Deserialize::deserialize(buffered)).unwrap();
+    assert_eq!(map["up"], Kind::Up);
+}
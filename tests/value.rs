@@ -0,0 +1,1189 @@
+use serde_json::{json, Map, Value};
+
+#[test]
+fn test_collect() {
+    let array: Value = (0..3).map(|i| i * 10).collect();
+    assert_eq!(array, json!([0, 10, 20]));
+
+    let empty: Value = Vec::<Value>::new().into_iter().collect();
+    assert_eq!(empty, json!([]));
+
+    let object: Value = vec![("a", json!(1)), ("b", json!([true])), ("a", json!(3))]
+        .into_iter()
+        .collect();
+    assert_eq!(object, json!({"a": 3, "b": [true]}));
+
+    let map: Map<String, Value> = object.as_object().unwrap().clone();
+    let roundtrip: Value = map.into_iter().collect();
+    assert_eq!(roundtrip, object);
+}
+
+#[test]
+fn test_into_iter() {
+    use serde_json::value::Item;
+
+    let items: Vec<Item> = json!([1, "a", null]).into_iter().collect();
+    assert_eq!(
+        items,
+        [
+            Item::Value(json!(1)),
+            Item::Value(json!("a")),
+            Item::Value(Value::Null),
+        ]
+    );
+
+    let mut iter = json!({"x": 1, "y": [2]}).into_iter();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.next_back(),
+        Some(Item::Entry("y".to_owned(), json!([2])))
+    );
+    assert_eq!(iter.next(), Some(Item::Entry("x".to_owned(), json!(1))));
+    assert_eq!(iter.next(), None);
+
+    for scalar in [Value::Null, json!(true), json!(1.5), json!("s")] {
+        let mut iter = scalar.clone().into_iter();
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next().map(Item::into_value), Some(scalar));
+        assert_eq!(iter.next(), None);
+    }
+
+    assert_eq!(json!([]).into_iter().count(), 0);
+    assert_eq!(json!({}).into_iter().count(), 0);
+}
+
+#[test]
+fn test_io_methods() {
+    let value = json!({"name": "x", "tags": ["a", 1], "empty": {}});
+
+    let mut out = Vec::new();
+    value.to_writer(&mut out).unwrap();
+    assert_eq!(out, value.to_string().into_bytes());
+    assert_eq!(Value::from_reader(out.as_slice()).unwrap(), value);
+
+    let pretty = value.to_string_pretty();
+    assert_eq!(pretty, serde_json::to_string_pretty(&value).unwrap());
+    assert_eq!(Value::from_reader(pretty.as_bytes()).unwrap(), value);
+
+    let err = Value::from_file("/nonexistent/serde_json/value.json").unwrap_err();
+    assert!(err.is_io());
+
+    let err = Value::from_reader(&b"[1,"[..]).unwrap_err();
+    assert!(err.is_eof());
+}
+
+#[test]
+fn test_write_with_formatter() {
+    use serde_json::ser::{CompactFormatter, Formatter};
+    use std::io;
+
+    // Viết hoa mọi chuỗi khi ghi ra.
+    struct Shout;
+
+    impl Formatter for Shout {
+        fn write_string_fragment<W>(&mut self, writer: &mut W, fragment: &str) -> io::Result<()>
+        where
+            W: ?Sized + io::Write,
+        {
+            writer.write_all(fragment.to_uppercase().as_bytes())
+        }
+    }
+
+    let value = json!({"k": ["ab", 1]});
+
+    let mut out = Vec::new();
+    value.write_with_formatter(&mut out, Shout).unwrap();
+    assert_eq!(out, br#"{"K":["AB",1]}"#);
+
+    let mut out = Vec::new();
+    value
+        .write_with_formatter(&mut out, CompactFormatter)
+        .unwrap();
+    assert_eq!(out, value.to_string().into_bytes());
+}
+
+#[test]
+fn test_query() {
+    let data = json!({
+        "a.b": {"c": 1},
+        "items": [
+            {"id": 1, "price": 9.5, "tags": ["x"]},
+            {"id": 2, "price": 20, "tags": []},
+            {"id": 3, "price": 15, "sku": "AB-7"}
+        ],
+        "empty": []
+    });
+
+    assert_eq!(data.query(r"a\.b.c"), Some(json!(1)));
+    assert_eq!(data.query("a?b.c"), Some(json!(1)));
+    assert_eq!(data.query("it*.0.id"), Some(json!(1)));
+    assert_eq!(data.query("empty.#"), Some(json!(0)));
+    assert_eq!(data.query("items.#.tags.#"), Some(json!([1, 0])));
+    assert_eq!(data.query("items.#(price>=15)#.id"), Some(json!([2, 3])));
+    assert_eq!(data.query("items.#(price<10).id"), Some(json!(1)));
+    assert_eq!(data.query("items.#(id!=1)#.id"), Some(json!([2, 3])));
+    assert_eq!(data.query("items.#(sku).id"), Some(json!(3)));
+    assert_eq!(data.query(r#"items.#(sku%"AB-?").id"#), Some(json!(3)));
+    assert_eq!(data.query(r#"items.#(sku!%"A*").id"#), None);
+    assert_eq!(data.query("items.#(price>100)#"), Some(json!([])));
+    assert_eq!(data.query(""), Some(data.clone()));
+
+    // Không tồn tại hoặc cú pháp sai.
+    assert_eq!(data.query("missing"), None);
+    assert_eq!(data.query("items.#(id==1"), None);
+    assert_eq!(data.query("items.#(id==oops)"), None);
+    assert_eq!(data.query("items.x"), None);
+    assert_eq!(data.query("a\\.b.#"), None);
+}
+
+#[test]
+fn test_transform() {
+    use serde_json::value::{Case, Transform};
+
+    let mut v = json!({
+        "HTTPServer": {"max_conn": 3, "tls": null},
+        "items": [{"item-id": 1, "note": null}, null, {"item-id": 2}],
+        "a/b": 1,
+        "debug": true
+    });
+    Transform::new()
+        .remove("/debug")
+        .remove("/a~1b")
+        .remove("/items/1")
+        .remove("/items/9")
+        .remove("/missing/x")
+        .prune_nulls()
+        .rename_keys(Case::Snake)
+        .apply(&mut v);
+    assert_eq!(
+        v,
+        json!({
+            "http_server": {"max_conn": 3},
+            "items": [{"item_id": 1}, {"item_id": 2}]
+        })
+    );
+
+    let cases = [
+        (Case::Camel, "userIdValue"),
+        (Case::Pascal, "UserIdValue"),
+        (Case::Snake, "user_id_value"),
+        (Case::ScreamingSnake, "USER_ID_VALUE"),
+        (Case::Kebab, "user-id-value"),
+    ];
+    for &(case, expected) in &cases {
+        for input in &[
+            "user_id_value",
+            "userIdValue",
+            "UserIDValue",
+            "user-id value",
+        ] {
+            let mut v = json!({ *input: 0 });
+            Transform::new().rename_keys(case).apply(&mut v);
+            assert_eq!(v, json!({ expected: 0 }), "{} {:?}", input, case);
+        }
+    }
+
+    // Nulls inside arrays are kept.
+    let mut v = json!([null, {"a": null}]);
+    Transform::new().prune_nulls().apply(&mut v);
+    assert_eq!(v, json!([null, {}]));
+
+    let t = Transform::new().remove("/x").prune_nulls();
+    assert_eq!(format!("{:?}", t), r#"[Remove("/x"), PruneNulls]"#);
+}
+
+#[test]
+fn test_merge_policies() {
+    use serde_json::value::{MergePolicies, MergePolicy};
+
+    let mut v = json!({"a": {"b": [1]}, "c": 1});
+    v.merge(json!({"a": {"b": [2], "d": null}, "c": {"x": 1}}));
+    assert_eq!(v, json!({"a": {"b": [2], "d": null}, "c": {"x": 1}}));
+
+    let policies = MergePolicies::new()
+        .path("/services/api/ports", MergePolicy::Replace)
+        .path("/services/*/ports", MergePolicy::Concat)
+        .path("/services/*/labels", MergePolicy::ConcatDedup)
+        .path("/version", MergePolicy::Keep)
+        .path("/meta", MergePolicy::Replace);
+
+    let mut base = json!({
+        "version": 1,
+        "meta": {"owner": "a", "team": "x"},
+        "services": {
+            "api": {"ports": [80], "labels": ["web"]},
+            "db": {"ports": [5432], "labels": ["data", "sql"]}
+        }
+    });
+    base.merge_with(
+        json!({
+            "version": 2,
+            "meta": {"owner": "b"},
+            "services": {
+                "api": {"ports": [443], "labels": ["web", "public"]},
+                "db": {"ports": [5433], "labels": ["sql"]},
+                "cache": {"ports": [6379]}
+            }
+        }),
+        &policies,
+    );
+    assert_eq!(
+        base,
+        json!({
+            "version": 1,
+            "meta": {"owner": "b"},
+            "services": {
+                "api": {"ports": [443], "labels": ["web", "public"]},
+                "db": {"ports": [5432, 5433], "labels": ["data", "sql"]},
+                "cache": {"ports": [6379]}
+            }
+        })
+    );
+
+    let mut v = json!({"a": [1], "b": {"c": [1]}});
+    let concat_all = MergePolicies::new().default_policy(MergePolicy::Concat);
+    v.merge_with(json!({"a": [2], "b": {"d": 1}}), &concat_all);
+    assert_eq!(v, json!({"a": [1, 2], "b": {"c": [1], "d": 1}}));
+
+    let mut v = json!({"a": [1], "b": {"c": [1]}});
+    let replace_all = MergePolicies::new().default_policy(MergePolicy::Replace);
+    v.merge_with(json!({"b": {"d": 1}}), &replace_all);
+    assert_eq!(v, json!({"b": {"d": 1}}));
+
+    let mut v = json!({"x": 1});
+    let deep_then_concat = MergePolicies::new().path("/a", MergePolicy::Concat);
+    v.merge_with(json!({"a": [1]}), &deep_then_concat);
+    assert_eq!(v, json!({"x": 1, "a": [1]}));
+}
+
+#[test]
+fn test_sort_and_dedup() {
+    let mut v = json!([{"b": 1}, [1, 2], "b", 2.5, true, [1], -3, null, "a", {"a": 2}, false]);
+    v.sort_array();
+    assert_eq!(
+        v,
+        json!([null, false, true, -3, 2.5, "a", "b", [1], [1, 2], {"a": 2}, {"b": 1}])
+    );
+
+    let mut v = json!([
+        {"id": 2, "n": "x"},
+        {"id": "1"},
+        {"id": 1, "n": "y"},
+        {"name": "none"},
+        {"id": 1, "n": "z"}
+    ]);
+    v.sort_array_by_key("/id");
+    assert_eq!(
+        v,
+        json!([
+            {"name": "none"},
+            {"id": 1, "n": "y"},
+            {"id": 1, "n": "z"},
+            {"id": 2, "n": "x"},
+            {"id": "1"}
+        ])
+    );
+
+    let mut v = json!([3, [1], 3, {"a": [1]}, 3.0, [1], {"a": [1]}, null, null]);
+    v.dedup_array();
+    assert_eq!(v, json!([3, [1], {"a": [1]}, 3.0, null]));
+
+    let mut scalar = json!("not an array");
+    scalar.sort_array();
+    scalar.dedup_array();
+    assert_eq!(scalar, json!("not an array"));
+}
+
+#[test]
+fn test_content_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::Hasher;
+
+    fn digest(v: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        v.content_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let a: Value = serde_json::from_str(r#"{"b": {"y": 2, "x": 1}, "a": [1, 2.5, null]}"#).unwrap();
+    let b = json!({"a": [1, 2.5, null], "b": {"x": 1, "y": 2}});
+    assert_eq!(digest(&a), digest(&b));
+    assert_eq!(digest(&json!(0.0)), digest(&json!(-0.0)));
+
+    let distinct = [
+        json!(null),
+        json!(false),
+        json!(0),
+        json!(0.0),
+        json!(1),
+        json!(1.0),
+        json!(""),
+        json!("a"),
+        json!([]),
+        json!([[]]),
+        json!([[], []]),
+        json!([[[]]]),
+        json!(["ab"]),
+        json!(["a", "b"]),
+        json!({}),
+        json!({"a": "b"}),
+        json!({"ab": ""}),
+    ];
+    let digests: HashSet<u64> = distinct.iter().map(digest).collect();
+    assert_eq!(digests.len(), distinct.len());
+
+    let set: HashSet<Value> = vec![a, b, json!(1), json!(1.0)].into_iter().collect();
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn test_stats() {
+    let v = json!({
+        "name": "doc",
+        "items": [
+            {"id": 1, "ok": true, "note": null},
+            {"id": 2.5, "ok": false, "nested": [[[]]]}
+        ]
+    });
+    assert_eq!(v.depth(), 6);
+    assert_eq!(v.node_count(), 13);
+
+    let stats = v.stats();
+    assert_eq!(stats.nulls, 1);
+    assert_eq!(stats.bools, 2);
+    assert_eq!(stats.numbers, 2);
+    assert_eq!(stats.strings, 1);
+    assert_eq!(stats.arrays, 4);
+    assert_eq!(stats.objects, 3);
+    assert_eq!(stats.keys, 8);
+    assert_eq!(
+        stats.string_bytes,
+        "doc".len() + "nameitemsidoknoteidoknested".len()
+    );
+
+    let mut deep = json!(null);
+    for _ in 0..100_000 {
+        deep = Value::Array(vec![deep]);
+    }
+    assert_eq!(deep.depth(), 100_000);
+    assert_eq!(deep.node_count(), 100_001);
+    // Dropping is recursive; unwind the nesting by hand.
+    while let Value::Array(mut list) = deep {
+        deep = list.pop().unwrap();
+    }
+}
+
+#[test]
+fn test_to_string_skip_nulls() {
+    let v = json!({
+        "a": null,
+        "b": [null, {"c": null, "d": 1}],
+        "e": {"f": {"g": null}},
+        "h": false
+    });
+    assert_eq!(
+        v.to_string_skip_nulls(),
+        r#"{"b":[null,{"d":1}],"e":{"f":{}},"h":false}"#
+    );
+    assert_eq!(v["a"], Value::Null);
+
+    assert_eq!(json!(null).to_string_skip_nulls(), "null");
+    assert_eq!(json!([null]).to_string_skip_nulls(), "[null]");
+    assert_eq!(json!({"a": null}).to_string_skip_nulls(), "{}");
+}
+
+#[test]
+fn test_key_order() {
+    use serde_json::value::KeyOrder;
+
+    let v = json!({
+        "zeta": 1,
+        "type": "t",
+        "alpha": [{"b": 1, "id": 2, "a": 3}],
+        "id": 0
+    });
+    let order = KeyOrder::new().first(&["id", "type"]);
+    assert_eq!(
+        serde_json::to_string(&v.with_key_order(&order)).unwrap(),
+        r#"{"id":0,"type":"t","alpha":[{"id":2,"a":3,"b":1}],"zeta":1}"#,
+    );
+    assert_eq!(
+        serde_json::to_string_pretty(&v.with_key_order(&order)).unwrap(),
+        "{\n  \"id\": 0,\n  \"type\": \"t\",\n  \"alpha\": [\n    {\n      \"id\": 2,\n      \"a\": 3,\n      \"b\": 1\n    }\n  ],\n  \"zeta\": 1\n}",
+    );
+    // The map itself keeps its order.
+    assert_eq!(
+        v.to_string(),
+        r#"{"alpha":[{"a":3,"b":1,"id":2}],"id":0,"type":"t","zeta":1}"#
+    );
+
+    let reverse = KeyOrder::new().first(&["type"]).then_by(|a, b| b.cmp(a));
+    assert_eq!(
+        serde_json::to_string(&v.with_key_order(&reverse)).unwrap(),
+        r#"{"type":"t","zeta":1,"id":0,"alpha":[{"id":2,"b":1,"a":3}]}"#,
+    );
+
+    let as_value = serde_json::to_value(v.with_key_order(&order)).unwrap();
+    assert_eq!(as_value, v);
+}
+
+#[test]
+fn test_json_array() {
+    use serde_json::json_array;
+
+    assert_eq!(json_array!(Vec::<u8>::new()), json!([]));
+    assert_eq!(json_array!(vec![Some(1), None]), json!([1, null]));
+    assert_eq!(json_array!(["x", "y"].iter().rev(),), json!(["y", "x"]));
+
+    let rows = vec![vec![1, 2], vec![3]];
+    let value = json!([for row in &rows => [for x in row => x * 10]]);
+    assert_eq!(value, json!([[10, 20], [30]]));
+
+    let value = json!({
+        "empty": [for x in 0..0 => x],
+        "objects": [for i in 0..2 => {"i": i, "even": i % 2 == 0}],
+        "nested": {"keys": [for (k, _) in json!({"a": 1, "b": 2}).as_object().unwrap() => k]},
+    });
+    assert_eq!(
+        value,
+        json!({
+            "empty": [],
+            "objects": [{"i": 0, "even": true}, {"i": 1, "even": false}],
+            "nested": {"keys": ["a", "b"]},
+        })
+    );
+}
+
+#[test]
+fn test_json_ident_keys() {
+    struct User {
+        name: &'static str,
+        tags: Vec<&'static str>,
+    }
+    let user = User {
+        name: "ann",
+        tags: vec!["a"],
+    };
+    let key = "computed";
+
+    let value = json!({
+        @id: 1,
+        @name: user.name,
+        @nested: { @tags: user.tags, @empty: {} },
+        "quoted": null,
+        (key): true,
+    });
+    assert_eq!(
+        value,
+        json!({
+            "id": 1,
+            "name": "ann",
+            "nested": {"tags": ["a"], "empty": {}},
+            "quoted": null,
+            "computed": true,
+        })
+    );
+
+    // Identifier không có `@` vẫn là biểu thức, như trước khi có `@tên`.
+    let id = String::from("x");
+    assert_eq!(json!({ id: 1 }), json!({ "x": 1 }));
+}
+
+#[test]
+fn test_json_static() {
+    use serde_json::json_static;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+    fn config() -> &'static Value {
+        json_static!({
+            "builds": BUILDS.fetch_add(1, Ordering::SeqCst),
+            "limits": [1, 2, 3],
+        })
+    }
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| std::thread::spawn(|| config() as *const Value as usize))
+        .collect();
+    let addresses: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert!(addresses
+        .iter()
+        .all(|&a| a == config() as *const Value as usize));
+    assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+    assert_eq!(config(), &json!({"builds": 0, "limits": [1, 2, 3]}));
+
+    // Each invocation site has its own value.
+    let a: &'static Value = json_static!([1]);
+    let b: &'static Value = json_static!([1]);
+    assert_eq!(a, b);
+    assert!(!std::ptr::eq(a, b));
+}
+
+#[test]
+fn test_intern() {
+    let v = json!({
+        "rows": [
+            {"kind": "user", "flags": [1, 2]},
+            {"kind": "user", "flags": [1, 2]},
+            {"kind": "admin", "flags": [1, 2]},
+        ],
+        "kind": "user",
+        "empty": [[], {}],
+        "n": 1.5,
+    });
+    let shared = v.intern();
+    assert_eq!(shared.to_value(), v);
+    assert_eq!(serde_json::to_string(&shared).unwrap(), v.to_string());
+
+    let rows = shared.get("rows").unwrap().as_array().unwrap();
+    assert!(rows[0].ptr_eq(&rows[1]));
+    assert!(!rows[0].ptr_eq(&rows[2]));
+    assert_eq!(rows[0], rows[0].clone());
+    // Equal sub-trees inside different parents are shared too.
+    assert!(rows[0]
+        .get("flags")
+        .unwrap()
+        .ptr_eq(rows[2].get("flags").unwrap()));
+    // Equal strings are shared, different ones are not.
+    assert!(!shared
+        .get("kind")
+        .unwrap()
+        .ptr_eq(rows[2].get("kind").unwrap()));
+    assert!(shared
+        .get("kind")
+        .unwrap()
+        .ptr_eq(rows[0].get("kind").unwrap()));
+
+    assert_eq!(shared.get("missing"), None);
+    assert_eq!(json!(1).intern().get("a"), None);
+    assert!(!json!(null).intern().ptr_eq(&json!(null).intern()));
+}
+
+#[test]
+fn test_lazy_value() {
+    use serde_json::value::LazyValue;
+
+    let input = r#" {"a": [1, {"b": [true, null]}], "s": "x\ny", "a": {"dup": 1}, "e": []} "#;
+    let expected: Value = serde_json::from_str(input).unwrap();
+    for depth in 0..5 {
+        let doc = LazyValue::from_str(input, depth).unwrap();
+        assert_eq!(doc.to_value().unwrap(), expected, "depth {}", depth);
+    }
+
+    let mut doc = LazyValue::from_str(input, 0).unwrap();
+    assert!(doc.as_raw().unwrap().starts_with("{\"a\""));
+    // The last duplicate wins, as with Value.
+    let dup = doc.pointer("/a/dup").unwrap().unwrap();
+    assert_eq!(dup, &LazyValue::Scalar(json!(1)));
+    assert!(doc.as_raw().is_none());
+    assert_eq!(doc.get("e").unwrap().unwrap().as_raw(), Some("[]"));
+    assert!(doc.get("missing").unwrap().is_none());
+    assert!(doc.pointer("/s/0").unwrap().is_none());
+    assert!(doc.pointer("a").unwrap().is_none());
+    assert!(doc.get_index(0).unwrap().is_none());
+
+    let mut list = LazyValue::from_str(r#"[{"a/b": {"~": [5]}}]"#, 1).unwrap();
+    let five = list.pointer("/0/a~1b/~0/0").unwrap().unwrap();
+    assert_eq!(five.to_value().unwrap(), json!(5));
+    assert!(list.pointer("/01").unwrap().is_none());
+
+    // Syntax errors anywhere are reported up front, with their position.
+    for (input, line, column) in &[
+        ("[1, [2, {\"a\" 3}]]", 1, 14),
+        ("[1, [2]", 1, 7),
+        ("[1,]", 1, 4),
+        ("{\"a\": [1]} x", 1, 12),
+    ] {
+        let err = LazyValue::from_str(input, 1).unwrap_err();
+        assert_eq!((err.line(), err.column()), (*line, *column), "{}", input);
+    }
+}
+
+#[test]
+fn test_from_slice_projected() {
+    use serde_json::from_slice_projected;
+
+    let input = br#"{
+        "id": 7,
+        "a/b": {"~": 1},
+        "list": [10, {"x": [true]}, 30],
+        "skip": {"deep": [[[[{"s": "\u00e9\"}"}]]]]},
+        "id": 8
+    }"#;
+    let pointers = [
+        "/id",
+        "/list/1/x/0",
+        "/list/1",
+        "/list/3",
+        "/list/01",
+        "/a~1b/~0",
+        "",
+        "/skip/deep/0/0/0/0/s",
+        "/missing/x",
+        "no-slash",
+    ];
+    let found = from_slice_projected(input, &pointers).unwrap();
+    let full: Value = serde_json::from_slice(input).unwrap();
+    for (pointer, found) in pointers.iter().zip(&found) {
+        let expected = if pointer.is_empty() || pointer.starts_with('/') {
+            full.pointer(pointer).cloned()
+        } else {
+            None
+        };
+        assert_eq!(*found, expected, "{}", pointer);
+    }
+    assert_eq!(found[0], Some(json!(8)));
+
+    let none: [&str; 0] = [];
+    assert_eq!(from_slice_projected(b"[1, 2]", &none).unwrap(), []);
+
+    // The skipped parts are still checked.
+    let err = from_slice_projected(br#"{"a": 1, "b": [1 2]}"#, &["/a"]).unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 18));
+    let err = from_slice_projected(br#"{"a": 1,}"#, &["/a"]).unwrap_err();
+    assert!(err.is_syntax());
+    let err = from_slice_projected(b"[1] [2]", &["/0"]).unwrap_err();
+    assert!(err.is_syntax());
+}
+
+#[test]
+fn test_from_value_ref() {
+    use serde::Deserialize;
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize)]
+    struct Doc<'a> {
+        #[serde(borrow)]
+        title: Cow<'a, str>,
+        #[serde(borrow)]
+        labels: BTreeMap<&'a str, &'a str>,
+        count: u32,
+        missing: Option<&'a str>,
+    }
+
+    let cached = json!({
+        "title": "report",
+        "labels": {"env": "prod", "team": "core"},
+        "count": 3,
+        "missing": null,
+    });
+    let doc: Doc = serde_json::from_value_ref(&cached).unwrap();
+    assert!(matches!(doc.title, Cow::Borrowed("report")));
+    assert_eq!(doc.labels["env"], "prod");
+    assert_eq!(doc.count, 3);
+    assert_eq!(doc.missing, None);
+    // Borrowed strings point into the cached document.
+    let env = cached["labels"]["env"].as_str().unwrap();
+    assert_eq!(doc.labels["env"].as_ptr(), env.as_ptr());
+
+    let count = &cached["count"];
+    assert_eq!(u8::deserialize(count).unwrap(), 3);
+    assert!(<&str>::deserialize(count).is_err());
+
+    assert!(serde_json::from_value_ref::<Doc>(&json!([1])).is_err());
+}
+
+#[test]
+fn test_summarize() {
+    let doc = json!({
+        "name": "caf\u{e9} au lait",
+        "list": [[1, 2, 3], {"a": 1, "b": 2, "c": 3}],
+        "n": 7,
+    });
+    assert_eq!(
+        doc.summarize(2, 4),
+        json!({
+            "list": [[1, 2, "\u{2026}(+1 more)"], {"a": 1, "b": 2, "\u{2026}": "(+1 more)"}],
+            "n": 7,
+            "\u{2026}": "(+1 more)",
+        }),
+    );
+    assert_eq!(doc.summarize(3, 4)["name"], "caf\u{e9}\u{2026}(+8 more)");
+    assert_eq!(doc.summarize(10, 100), doc);
+    assert_eq!(json!([1]).summarize(0, 0), json!(["\u{2026}(+1 more)"]));
+}
+
+#[test]
+fn test_retain_and_remove_paths() {
+    let doc = json!({
+        "a": 1,
+        "b": {"c": 2, "d": 3},
+        "list": [{"x": 1, "y": 2}, {"x": 3}, 5],
+        "a/b": true,
+    });
+
+    let mut v = doc.clone();
+    v.retain_paths(&["/b/d", "/list/1", "/a~1b", "/list/*/y", "/b/missing/deep"]);
+    assert_eq!(
+        v,
+        json!({"b": {"d": 3}, "list": [{"y": 2}, {"x": 3}], "a/b": true})
+    );
+
+    let mut v = doc.clone();
+    v.retain_paths(&[""]);
+    assert_eq!(v, doc);
+
+    let mut v = doc.clone();
+    v.retain_paths(&["/nope"]);
+    assert_eq!(v, json!({}));
+
+    let mut v = json!("text");
+    v.retain_paths(&["/a"]);
+    assert_eq!(v, Value::Null);
+
+    let mut v = doc.clone();
+    v.remove_paths(&[
+        String::from("/b/c"),
+        String::from("/list/*/x"),
+        String::from("/list/2"),
+    ]);
+    assert_eq!(
+        v,
+        json!({"a": 1, "b": {"d": 3}, "list": [{"y": 2}, {}], "a/b": true})
+    );
+
+    let mut v = doc.clone();
+    v.remove_paths(&["/a/deeper", "/zzz"]);
+    assert_eq!(v, doc);
+
+    v.remove_paths(&[""]);
+    assert_eq!(v, Value::Null);
+}
+
+#[test]
+fn test_field_mask() {
+    use serde_json::value::FieldMask;
+
+    let mask = FieldMask::parse(" a , b( c , d(e) ), list.*.x, 0 ").unwrap();
+    assert_eq!(mask.pointers(), ["/a", "/b/c", "/b/d/e", "/list/*/x", "/0"]);
+
+    let mut v = json!({
+        "a": 1,
+        "b": {"c": 2, "d": {"e": 3, "f": 4}, "g": 5},
+        "list": [{"x": 1, "y": 2}],
+        "0": "zero",
+        "h": 6,
+    });
+    mask.apply(&mut v);
+    assert_eq!(
+        v,
+        json!({
+            "a": 1,
+            "b": {"c": 2, "d": {"e": 3}},
+            "list": [{"x": 1}],
+            "0": "zero",
+        })
+    );
+
+    let mut v = json!({"a": 1});
+    FieldMask::parse("").unwrap().apply(&mut v);
+    assert_eq!(v, json!({"a": 1}));
+
+    for (mask, column) in [("a,,b", 3), ("a(b", 4), ("a.", 3), ("a)b", 2), ("()", 1)] {
+        let err = mask.parse::<FieldMask>().unwrap_err();
+        assert_eq!(err.column(), column, "{}", mask);
+    }
+}
+
+#[test]
+fn test_coerce_to() {
+    use serde_json::value::Shape;
+
+    let shape = Shape::object()
+        .field("flag", Shape::Bool)
+        .field("count", Shape::Integer)
+        .field("ratio", Shape::Number)
+        .field("label", Shape::String)
+        .field("maybe", Shape::nullable(Shape::array(Shape::Integer)))
+        .field("nums", Shape::array(Shape::Integer))
+        .field("env", Shape::map(Shape::String))
+        .field("raw", Shape::Any);
+    let mut v = json!({
+        "flag": "true",
+        "count": 3.0,
+        "ratio": " -1.5e2 ",
+        "label": false,
+        "maybe": null,
+        "nums": "7",
+        "env": {"a/b": 1, "c": "x"},
+        "raw": "12",
+        "extra": "5",
+    });
+    let changes = v.coerce_to(&shape);
+    assert_eq!(
+        v,
+        json!({
+            "flag": true,
+            "count": 3,
+            "ratio": -150.0,
+            "label": "false",
+            "maybe": null,
+            "nums": [7],
+            "env": {"a/b": "1", "c": "x"},
+            "raw": "12",
+            "extra": "5",
+        })
+    );
+    let mut pointers: Vec<_> = changes.iter().map(|c| c.pointer.as_str()).collect();
+    pointers.sort_unstable();
+    assert_eq!(
+        pointers,
+        [
+            "/count",
+            "/env/a~1b",
+            "/flag",
+            "/label",
+            "/nums",
+            "/nums/0",
+            "/ratio"
+        ]
+    );
+
+    let mut v = json!({"flag": "yes", "count": "1.5", "nums": [1, "x"], "maybe": "4"});
+    let changes = v.coerce_to(&shape);
+    assert_eq!(
+        v,
+        json!({"flag": "yes", "count": "1.5", "nums": [1, "x"], "maybe": [4]})
+    );
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].original, json!("4"));
+    assert_eq!(changes[1].pointer, "/maybe/0");
+
+    let mut v = json!(null);
+    assert!(v.coerce_to(&Shape::array(Shape::Any)).is_empty());
+
+    // Số thực chỉ thành số nguyên khi không có phần lẻ và nằm trong i64.
+    let mut v = json!([2.5, -4.0, -9223372036854775808.0, 9223372036854775808.0]);
+    v.coerce_to(&Shape::array(Shape::Integer));
+    assert_eq!(v, json!([2.5, -4, i64::MIN, 9223372036854775808.0]));
+}
+
+#[test]
+fn test_f32_policy() {
+    use serde::Serialize;
+    use serde_json::value::{to_value_with, F32Policy, ToValueOptions};
+
+    let reading = (
+        vec![0.1f32, 1.0, f32::MAX, -f32::MIN_POSITIVE, 16777216.0],
+        Some(0.3f32),
+    );
+
+    for policy in [F32Policy::Native, F32Policy::Widen] {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_f32_policy(policy);
+        reading.serialize(&mut ser).unwrap();
+        let direct = String::from_utf8(ser.into_inner()).unwrap();
+
+        let options = ToValueOptions::new().f32_policy(policy);
+        let via_value = to_value_with(&reading, &options).unwrap().to_string();
+        assert_eq!(direct, via_value);
+    }
+
+    let direct = serde_json::to_string(&reading).unwrap();
+    assert_eq!(
+        direct,
+        "[[0.1,1.0,3.4028235e38,-1.1754944e-38,16777216.0],0.3]"
+    );
+    let native = ToValueOptions::new().f32_policy(F32Policy::Native);
+    assert_eq!(
+        to_value_with(&reading, &native).unwrap().to_string(),
+        direct
+    );
+
+    // `to_value` keeps widening `f32` as it always has.
+    assert_eq!(serde_json::to_value(0.1f32).unwrap(), json!(0.10000000149011612));
+    assert_eq!(
+        serde_json::to_value(0.3f32).unwrap(),
+        to_value_with(0.3f32, &ToValueOptions::new().f32_policy(F32Policy::Widen)).unwrap()
+    );
+}
+
+#[test]
+fn test_non_finite_policy() {
+    use serde::Serialize;
+    use serde_json::value::{to_value_with, NonFinitePolicy, ToValueOptions};
+
+    let values = (f64::NAN, f64::INFINITY, f32::NEG_INFINITY, 0.5f32);
+
+    for (policy, expected) in [
+        (NonFinitePolicy::Null, Some("[null,null,null,0.5]")),
+        (
+            NonFinitePolicy::StringTokens,
+            Some(r#"["NaN","Infinity","-Infinity",0.5]"#),
+        ),
+        (NonFinitePolicy::Error, None),
+    ] {
+        let mut ser = serde_json::Serializer::new(Vec::new());
+        ser.set_non_finite_policy(policy);
+        let direct = values
+            .serialize(&mut ser)
+            .map(|()| String::from_utf8(ser.into_inner()).unwrap());
+
+        let options = ToValueOptions::new().non_finite_policy(policy);
+        let via_value = to_value_with(values, &options).map(|v| v.to_string());
+
+        match expected {
+            Some(expected) => {
+                assert_eq!(direct.unwrap(), expected);
+                assert_eq!(via_value.unwrap(), expected);
+            }
+            None => {
+                let err = direct.unwrap_err();
+                assert_eq!(err.to_string(), "float must be finite");
+                assert!(err.is_data());
+                assert_eq!(via_value.unwrap_err().to_string(), "float must be finite");
+            }
+        }
+    }
+
+    assert_eq!(serde_json::to_value(f64::NAN).unwrap(), Value::Null);
+}
+
+#[test]
+fn test_from_value_collect() {
+    use serde::Deserialize;
+    use serde_json::value::from_value_collect;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Shape {
+        name: String,
+        points: Vec<Point>,
+        color: (u8, u8, u8),
+        label: Option<String>,
+    }
+
+    fn paths(value: Value) -> Vec<String> {
+        from_value_collect::<Shape>(value)
+            .unwrap_err()
+            .iter()
+            .map(|err| err.path().unwrap_or("").to_owned())
+            .collect()
+    }
+
+    let shape = json!({
+        "name": "tri",
+        "points": [{"x": 0, "y": 0}, {"x": 1, "y": 2}],
+        "color": [1, 2, 3],
+    });
+    let expected = Shape {
+        name: "tri".to_owned(),
+        points: vec![Point { x: 0, y: 0 }, Point { x: 1, y: 2 }],
+        color: (1, 2, 3),
+        label: None,
+    };
+    assert_eq!(from_value_collect::<Shape>(shape).unwrap(), expected);
+
+    // Indices refer to the original array after earlier elements are removed.
+    let shape = json!({
+        "name": "tri",
+        "points": [{"x": "0", "y": 0}, {"x": 1, "y": 2}, {"x": 1, "y": 2, "z": 3}],
+        "color": [1, 2, 300],
+        "label": 5,
+    });
+    assert_eq!(
+        paths(shape),
+        ["/color/2", "/label", "/points/0/x", "/points/2/z"],
+    );
+
+    // A field missing from the input is reported, one removed for having the
+    // wrong type is not reported again.
+    let errors = from_value_collect::<Shape>(json!({"points": [], "color": [0, 0, 0]}));
+    let errors = errors.unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "missing field `name`");
+    assert_eq!(
+        paths(json!({"name": 1, "points": [], "color": [0, 0, 0]})),
+        ["/name"]
+    );
+
+    assert_eq!(paths(json!([])), [""]);
+}
+
+#[test]
+fn test_from_value_lenient() {
+    use serde::Deserialize;
+    use serde_json::value::from_value_lenient;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Reading {
+        sensor: String,
+        #[serde(default)]
+        value: f64,
+        unit: Option<String>,
+    }
+
+    let readings = json!([
+        {"sensor": "a", "value": 1.5, "unit": "C"},
+        {"sensor": "b", "value": "NaN", "unit": 7},
+        {"sensor": 3, "value": 2.0},
+        {"sensor": "d", "value": 4.0},
+    ]);
+    let (readings, errors) = from_value_lenient::<Vec<Reading>>(readings).unwrap();
+    let reading = |sensor: &str, value, unit: Option<&str>| Reading {
+        sensor: sensor.to_owned(),
+        value,
+        unit: unit.map(str::to_owned),
+    };
+    assert_eq!(
+        readings,
+        [
+            reading("a", 1.5, Some("C")),
+            reading("b", 0.0, None),
+            reading("d", 4.0, None),
+        ],
+    );
+    let paths: Vec<_> = errors.iter().map(|err| err.path().unwrap()).collect();
+    assert_eq!(paths, ["/1/unit", "/1/value", "/2/sensor"]);
+
+    let (reading, errors) =
+        from_value_lenient::<Reading>(json!({"sensor": "e", "value": 1})).unwrap();
+    assert_eq!(reading.value, 1.0);
+    assert!(errors.is_empty());
+
+    let errors = from_value_lenient::<Reading>(json!({"value": 1})).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "missing field `sensor`");
+}
+
+#[test]
+fn test_to_value_into() {
+    use serde_json::value::to_value_into;
+    use std::collections::BTreeMap;
+
+    let mut map = Map::new_ordered();
+    map.insert("kind".to_owned(), json!("event"));
+    map.insert("id".to_owned(), json!(0));
+
+    let mut payload = BTreeMap::new();
+    payload.insert("id", vec![1, 2]);
+    payload.insert("at", vec![3]);
+    to_value_into(Some(&payload), &mut map).unwrap();
+    let keys: Vec<_> = map.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["kind", "id", "at"]);
+    assert_eq!(map["id"], json!([1, 2]));
+
+    let err = to_value_into([1, 2], &mut map).unwrap_err();
+    assert_eq!(err.to_string(), "value must serialize to a map or struct");
+    assert!(err.is_data());
+    assert!(to_value_into(None::<Value>, &mut map).is_err());
+    assert!(to_value_into(json!("x"), &mut map).is_err());
+    assert_eq!(map.len(), 3);
+
+    let mut map = Map::new();
+    to_value_into(json!({"a": {"b": null}}), &mut map).unwrap();
+    assert_eq!(Value::Object(map), json!({"a": {"b": null}}));
+}
+
+#[test]
+fn test_to_value_identity() {
+    use serde_json::{to_value, Number};
+
+    let mut map = Map::new_ordered();
+    map.insert("b".to_owned(), json!(1));
+    map.insert("a".to_owned(), json!([2.5, null]));
+    let value = Value::Object(map.clone());
+
+    // Converted without serializing, so the insertion order is kept.
+    for converted in [
+        to_value(&value).unwrap(),
+        to_value(value.clone()).unwrap(),
+        to_value(&map).unwrap(),
+        to_value(map).unwrap(),
+    ] {
+        assert_eq!(converted, value);
+        let keys: Vec<_> = converted.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, ["b", "a"]);
+    }
+
+    let n = Number::from(7u8);
+    assert_eq!(to_value(&n).unwrap(), json!(7));
+    assert_eq!(to_value(n).unwrap(), json!(7));
+
+    let list = vec![value.clone(), Value::Null];
+    assert_eq!(
+        to_value(&list).unwrap(),
+        json!([{"a": [2.5, null], "b": 1}, null])
+    );
+    let borrowed: &str = &String::from("s");
+    assert_eq!(to_value(borrowed).unwrap(), json!("s"));
+}
+
+#[test]
+fn test_content_value() {
+    use serde::Deserialize;
+    use serde_json::value::ContentValue;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(tag = "op", rename_all = "lowercase")]
+    enum Op<'a> {
+        Move { x: i32, y: i32 },
+        Say { text: &'a str },
+    }
+
+    let input = r#"{"op": "say", "text": "hi", "op": "move", "x": -1, "y": 2}"#;
+    let value: ContentValue = serde_json::from_str(input).unwrap();
+
+    assert_eq!(value.get_str("op"), Some("move"));
+    assert_eq!(value.get_str("x"), None);
+    assert_eq!(i32::deserialize(value.get("x").unwrap()).unwrap(), -1);
+    assert!(value.get("z").is_none());
+    assert_eq!(
+        value.to_value(),
+        json!({"op": "move", "text": "hi", "x": -1, "y": 2})
+    );
+
+    let input = r#"{"op": "say", "text": "hi"}"#;
+    let value: ContentValue = serde_json::from_str(input).unwrap();
+    assert_eq!(value.deserialize_into::<Op>().unwrap(), Op::Say { text: "hi" });
+    assert!(value.deserialize_into::<Vec<u8>>().is_err());
+    assert_eq!(
+        Map::<String, Value>::deserialize(value.deserializer()).unwrap()["text"],
+        "hi"
+    );
+
+    let owned = {
+        let input = String::from(r#"[1, "a\nb", {"k": null}]"#);
+        let value: ContentValue = serde_json::from_str(&input).unwrap();
+        value.into_owned()
+    };
+    assert_eq!(owned.to_value(), json!([1, "a\nb", {"k": null}]));
+    assert!(owned.get("k").is_none());
+
+    let source = json!({"n": 2.5});
+    let value = ContentValue::deserialize(&source).unwrap();
+    assert_eq!(f64::deserialize(value.get("n").unwrap()).unwrap(), 2.5);
+}
+
+#[test]
+fn test_from_value_map_keys() {
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Side {
+        Left,
+        Right,
+    }
+
+    let ints: BTreeMap<i32, u8> = serde_json::from_value(json!({"-1": 1, "7": 2})).unwrap();
+    assert_eq!(ints, BTreeMap::from([(-1, 1), (7, 2)]));
+    let bools: BTreeMap<bool, u8> = serde_json::from_value(json!({"true": 1})).unwrap();
+    assert_eq!(bools, BTreeMap::from([(true, 1)]));
+    let sides: BTreeMap<Side, u8> = serde_json::from_value(json!({"Left": 1, "Right": 2})).unwrap();
+    assert_eq!(sides, BTreeMap::from([(Side::Left, 1), (Side::Right, 2)]));
+    let strings: BTreeMap<String, u8> = serde_json::from_value(json!({"k": 1})).unwrap();
+    assert_eq!(strings, BTreeMap::from([("k".to_owned(), 1)]));
+
+    let err = serde_json::from_value::<BTreeMap<Side, u8>>(json!({"Up": 1})).unwrap_err();
+    assert_eq!(err.path(), Some("/Up"));
+}